@@ -0,0 +1,101 @@
+//! Baselines for the hot paths of a frame: one integrator step, stepping a
+//! whole ensemble of trajectories, and pushing points through a full trail.
+//!
+//! Run with `cargo bench`; pass a filter such as `cargo bench -- trail` to run
+//! one group.
+
+use std::hint::black_box;
+
+use criterion::{criterion_group, criterion_main, BatchSize, BenchmarkId, Criterion, Throughput};
+use lorenz_attractor::config::{IntegrationMethod, SimulationConfig};
+use lorenz_attractor::simulation::gallery::rho_sweep;
+use lorenz_attractor::simulation::integrator::{advance_trajectory, TrailBuffer};
+use lorenz_attractor::simulation::lorenz::{euler_step, rk4_step, LorenzParams, LorenzState};
+
+const DT: f64 = 0.005;
+const ENSEMBLE_SIZES: [usize; 3] = [16, 256, 4096];
+const TRAIL_CAPACITIES: [usize; 4] = [1_000, 25_000, 100_000, 1_000_000];
+
+/// A point on the attractor, so the steppers see typical magnitudes rather
+/// than the transient from (1, 1, 1).
+fn on_attractor(params: &LorenzParams) -> LorenzState {
+    let mut state = LorenzState::new(1.0, 1.0, 1.0);
+    for _ in 0..5_000 {
+        state = rk4_step(&state, params, DT);
+    }
+    state
+}
+
+fn bench_steps(c: &mut Criterion) {
+    let params = LorenzParams::default();
+    let start = on_attractor(&params);
+    let mut group = c.benchmark_group("step");
+    group.bench_function("euler", |b| {
+        b.iter(|| euler_step(black_box(&start), black_box(&params), black_box(DT)))
+    });
+    group.bench_function("rk4", |b| {
+        b.iter(|| rk4_step(black_box(&start), black_box(&params), black_box(DT)))
+    });
+    group.finish();
+}
+
+/// One RK4 step of every member of a ρ sweep, the way the gallery and the
+/// added trajectories advance.
+fn bench_ensemble(c: &mut Criterion) {
+    let base = LorenzParams::default();
+    let start = on_attractor(&base);
+    let mut group = c.benchmark_group("ensemble_rk4");
+    for size in ENSEMBLE_SIZES {
+        let members: Vec<(LorenzParams, LorenzState)> = rho_sweep(20.0, 40.0, size)
+            .into_iter()
+            .map(|rho| (LorenzParams { rho, ..base }, start.clone()))
+            .collect();
+        group.throughput(Throughput::Elements(size as u64));
+        group.bench_with_input(BenchmarkId::from_parameter(size), &members, |b, members| {
+            b.iter_batched_ref(
+                || members.clone(),
+                |members| {
+                    for (params, state) in members.iter_mut() {
+                        *state = rk4_step(state, params, DT);
+                    }
+                },
+                BatchSize::SmallInput,
+            )
+        });
+    }
+    group.finish();
+}
+
+/// A frame of `advance_trajectory` into a trail that is already full, so
+/// every step appends one point and trims the oldest.
+fn bench_trail(c: &mut Criterion) {
+    let params = LorenzParams::default();
+    let start = on_attractor(&params);
+    let config = SimulationConfig {
+        method: IntegrationMethod::RungeKutta4,
+        dt: DT,
+        steps_per_frame: 64,
+        ..Default::default()
+    };
+    let mut group = c.benchmark_group("trail_append_trim");
+    group.throughput(Throughput::Elements(u64::from(config.steps_per_frame)));
+    for capacity in TRAIL_CAPACITIES {
+        let mut state = start.clone();
+        let mut trail = TrailBuffer {
+            points: Default::default(),
+            max_points: capacity,
+        };
+        let full = SimulationConfig {
+            steps_per_frame: capacity as u32,
+            ..config.clone()
+        };
+        advance_trajectory(&mut state, &mut trail, &params, &full, None);
+        group.bench_with_input(BenchmarkId::from_parameter(capacity), &capacity, |b, _| {
+            b.iter(|| advance_trajectory(&mut state, &mut trail, &params, &config, None))
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_steps, bench_ensemble, bench_trail);
+criterion_main!(benches);
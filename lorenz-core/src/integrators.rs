@@ -0,0 +1,129 @@
+use crate::{lorenz_derivatives, LorenzParams, LorenzState};
+
+#[inline]
+pub fn euler_step(state: &LorenzState, params: &LorenzParams, dt: f64) -> LorenzState {
+    euler_step_with(state, dt, |s| lorenz_derivatives(s, params))
+}
+
+#[inline]
+pub fn rk4_step(state: &LorenzState, params: &LorenzParams, dt: f64) -> LorenzState {
+    rk4_step_with(state, dt, |s| lorenz_derivatives(s, params))
+}
+
+/// Euler step of an arbitrary three-dimensional vector field; the result is
+/// `dt` later than `state`.
+#[inline]
+pub fn euler_step_with(
+    state: &LorenzState,
+    dt: f64,
+    derivatives: impl Fn(&LorenzState) -> (f64, f64, f64),
+) -> LorenzState {
+    let (dx, dy, dz) = derivatives(state);
+    LorenzState {
+        x: state.x + dt * dx,
+        y: state.y + dt * dy,
+        z: state.z + dt * dz,
+        t: state.t + dt,
+    }
+}
+
+/// RK4 step of an arbitrary three-dimensional vector field; the result is
+/// `dt` later than `state`.
+#[inline]
+pub fn rk4_step_with(
+    state: &LorenzState,
+    dt: f64,
+    derivatives: impl Fn(&LorenzState) -> (f64, f64, f64),
+) -> LorenzState {
+    let (k1x, k1y, k1z) = derivatives(state);
+
+    let s2 = LorenzState::new(
+        state.x + 0.5 * dt * k1x,
+        state.y + 0.5 * dt * k1y,
+        state.z + 0.5 * dt * k1z,
+    );
+    let (k2x, k2y, k2z) = derivatives(&s2);
+
+    let s3 = LorenzState::new(
+        state.x + 0.5 * dt * k2x,
+        state.y + 0.5 * dt * k2y,
+        state.z + 0.5 * dt * k2z,
+    );
+    let (k3x, k3y, k3z) = derivatives(&s3);
+
+    let s4 = LorenzState::new(
+        state.x + dt * k3x,
+        state.y + dt * k3y,
+        state.z + dt * k3z,
+    );
+    let (k4x, k4y, k4z) = derivatives(&s4);
+
+    let sixth_dt = dt / 6.0;
+    LorenzState {
+        x: state.x + sixth_dt * (k1x + 2.0 * k2x + 2.0 * k3x + k4x),
+        y: state.y + sixth_dt * (k1y + 2.0 * k2y + 2.0 * k3y + k4y),
+        z: state.z + sixth_dt * (k1z + 2.0 * k2z + 2.0 * k3z + k4z),
+        t: state.t + dt,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn std_params() -> LorenzParams {
+        LorenzParams {
+            sigma: 10.0,
+            rho: 28.0,
+            beta: 8.0 / 3.0,
+        }
+    }
+
+    #[test]
+    fn test_euler_advances_state() {
+        let state = LorenzState::new(1.0, 1.0, 1.0);
+        let next = euler_step(&state, &std_params(), 0.01);
+        assert!((next.y - state.y).abs() > 1e-10);
+    }
+
+    #[test]
+    fn test_rk4_advances_state() {
+        let state = LorenzState::new(1.0, 1.0, 1.0);
+        let next = rk4_step(&state, &std_params(), 0.01);
+        assert!((next.x - state.x).abs() > 1e-10);
+    }
+
+    #[test]
+    fn test_steps_advance_time() {
+        let mut state = LorenzState::new(1.0, 1.0, 1.0);
+        state.t = 2.0;
+        assert_eq!(euler_step(&state, &std_params(), 0.5).t, 2.5);
+        assert_eq!(rk4_step(&state, &std_params(), 0.25).t, 2.25);
+    }
+
+    #[test]
+    fn test_rk4_more_accurate_than_euler() {
+        let state = LorenzState::new(1.0, 1.0, 1.0);
+        let params = std_params();
+        let dt_coarse: f64 = 0.1;
+        let dt_fine = 0.0001;
+        let steps_fine = (dt_coarse / dt_fine).round() as usize;
+
+        let mut ref_state = state.clone();
+        for _ in 0..steps_fine {
+            ref_state = rk4_step(&ref_state, &params, dt_fine);
+        }
+
+        let euler_result = euler_step(&state, &params, dt_coarse);
+        let euler_err = (euler_result.x - ref_state.x).powi(2)
+            + (euler_result.y - ref_state.y).powi(2)
+            + (euler_result.z - ref_state.z).powi(2);
+
+        let rk4_result = rk4_step(&state, &params, dt_coarse);
+        let rk4_err = (rk4_result.x - ref_state.x).powi(2)
+            + (rk4_result.y - ref_state.y).powi(2)
+            + (rk4_result.z - ref_state.z).powi(2);
+
+        assert!(rk4_err < euler_err);
+    }
+}
\ No newline at end of file
@@ -0,0 +1,162 @@
+//! The Lorenz system without a renderer: state, parameters, the vector
+//! field, fixed-step Euler and RK4 integrators, and the Lyapunov and
+//! Poincaré-section estimators the app reports.
+//!
+//! ```
+//! use lorenz_core::{rk4_step, LorenzParams, LorenzState};
+//!
+//! let params = LorenzParams::default();
+//! let mut state = LorenzState::new(1.0, 1.0, 1.0);
+//! for _ in 0..1000 {
+//!     state = rk4_step(&state, &params, 0.005);
+//! }
+//! assert!(state.z > 0.0);
+//! ```
+#![cfg_attr(not(feature = "std"), no_std)]
+
+mod integrators;
+#[cfg(feature = "std")]
+mod lyapunov;
+#[cfg(feature = "std")]
+mod poincare;
+
+pub use integrators::{euler_step, euler_step_with, rk4_step, rk4_step_with};
+#[cfg(feature = "std")]
+pub use lyapunov::{LyapunovEstimate, SHADOW_SEPARATION};
+#[cfg(feature = "std")]
+pub use poincare::{section_crossings, Crossing};
+
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "bevy", derive(bevy_ecs::component::Component))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LorenzState {
+    pub x: f64,
+    pub y: f64,
+    pub z: f64,
+    pub t: f64,
+}
+
+impl LorenzState {
+    pub fn new(x: f64, y: f64, z: f64) -> Self {
+        Self { x, y, z, t: 0.0 }
+    }
+
+    /// Position as a Bevy vector, `(x, y, z)`. The app stores its trails this
+    /// way and maps them onto the scene through a configurable `AxisMapping`.
+    #[cfg(feature = "bevy")]
+    pub fn to_vec3(&self) -> bevy_math::Vec3 {
+        bevy_math::Vec3::new(self.x as f32, self.y as f32, self.z as f32)
+    }
+}
+
+impl Default for LorenzState {
+    fn default() -> Self {
+        Self::new(1.0, 1.0, 1.0)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct LorenzParams {
+    pub sigma: f64,
+    pub rho: f64,
+    pub beta: f64,
+}
+
+impl Default for LorenzParams {
+    /// Lorenz's original values.
+    fn default() -> Self {
+        Self {
+            sigma: 10.0,
+            rho: 28.0,
+            beta: 8.0 / 3.0,
+        }
+    }
+}
+
+#[inline]
+pub fn lorenz_derivatives(state: &LorenzState, params: &LorenzParams) -> (f64, f64, f64) {
+    let dx = params.sigma * (state.y - state.x);
+    let dy = state.x * (params.rho - state.z) - state.y;
+    let dz = state.x * state.y - params.beta * state.z;
+    (dx, dy, dz)
+}
+
+/// The non-trivial equilibria C+ and C−, which exist only for ρ > 1.
+#[cfg(feature = "std")]
+pub fn fixed_points(params: &LorenzParams) -> Option<[[f64; 3]; 2]> {
+    if params.rho <= 1.0 {
+        return None;
+    }
+    let r = (params.beta * (params.rho - 1.0)).sqrt();
+    let z = params.rho - 1.0;
+    Some([[r, r, z], [-r, -r, z]])
+}
+
+#[cfg(feature = "std")]
+pub fn velocity_magnitude(state: &LorenzState, params: &LorenzParams) -> f64 {
+    let (dx, dy, dz) = lorenz_derivatives(state, params);
+    (dx * dx + dy * dy + dz * dz).sqrt()
+}
+
+pub fn system_energy(state: &LorenzState) -> f64 {
+    0.5 * (state.x * state.x + state.y * state.y + state.z * state.z)
+}
+
+pub fn divergence(params: &LorenzParams) -> f64 {
+    -(params.sigma + 1.0 + params.beta)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn standard_params() -> LorenzParams {
+        LorenzParams {
+            sigma: 10.0,
+            rho: 28.0,
+            beta: 8.0 / 3.0,
+        }
+    }
+
+    #[test]
+    fn test_equilibrium_origin() {
+        let state = LorenzState::new(0.0, 0.0, 0.0);
+        let (dx, dy, dz) = lorenz_derivatives(&state, &standard_params());
+        assert!((dx.abs() + dy.abs() + dz.abs()) < 1e-15);
+    }
+
+    #[test]
+    fn test_equilibrium_c_plus() {
+        let p = standard_params();
+        let val = (p.beta * (p.rho - 1.0)).sqrt();
+        let state = LorenzState::new(val, val, p.rho - 1.0);
+        let (dx, dy, dz) = lorenz_derivatives(&state, &p);
+        assert!(dx.abs() < 1e-12);
+        assert!(dy.abs() < 1e-12);
+        assert!(dz.abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_fixed_points_are_equilibria() {
+        let p = standard_params();
+        for [x, y, z] in fixed_points(&p).unwrap() {
+            let (dx, dy, dz) = lorenz_derivatives(&LorenzState::new(x, y, z), &p);
+            assert!(dx.abs() + dy.abs() + dz.abs() < 1e-12);
+        }
+        assert!(fixed_points(&LorenzParams { rho: 0.5, ..p }).is_none());
+    }
+
+    #[test]
+    fn test_divergence_value() {
+        let p = standard_params();
+        let div = divergence(&p);
+        let expected = -(10.0 + 1.0 + 8.0 / 3.0);
+        assert!((div - expected).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_energy_positive() {
+        let state = LorenzState::new(5.0, -3.0, 12.0);
+        assert!(system_energy(&state) > 0.0);
+    }
+}
\ No newline at end of file
@@ -0,0 +1,32 @@
+/// An upward pass through a horizontal plane, linearly interpolated between
+/// the two samples either side of it.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Crossing {
+    pub time: f64,
+    pub x: f64,
+    pub y: f64,
+}
+
+/// Upward crossings of the plane z = `plane_z` by `(time, [x, y, z])` samples.
+pub fn section_crossings(
+    samples: impl IntoIterator<Item = (f64, [f64; 3])>,
+    plane_z: f64,
+) -> Vec<Crossing> {
+    let mut crossings = Vec::new();
+    let mut previous: Option<(f64, [f64; 3])> = None;
+    for current in samples {
+        if let Some((t0, [x0, y0, z0])) = previous {
+            let (t1, [x1, y1, z1]) = current;
+            if z0 < plane_z && z1 >= plane_z {
+                let f = (plane_z - z0) / (z1 - z0);
+                crossings.push(Crossing {
+                    time: t0 + f * (t1 - t0),
+                    x: x0 + f * (x1 - x0),
+                    y: y0 + f * (y1 - y0),
+                });
+            }
+        }
+        previous = Some(current);
+    }
+    crossings
+}
\ No newline at end of file
@@ -0,0 +1,228 @@
+//! `import lorenz` from Python: the same steppers and estimators the viewer
+//! runs, so notebook results match what the GUI shows.
+//!
+//! ```python
+//! import lorenz
+//! xyz = lorenz.simulate((10.0, 28.0, 8 / 3), (1.0, 1.0, 1.0), 0.005, 10_000)
+//! lam = lorenz.lyapunov((10.0, 28.0, 8 / 3), (1.0, 1.0, 1.0), 0.01, 50_000)
+//! ```
+use lorenz_core::{
+    euler_step, rk4_step, section_crossings, LorenzParams, LorenzState, LyapunovEstimate,
+};
+use numpy::ndarray::Array2;
+use numpy::{IntoPyArray, PyArray2};
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+
+type Stepper = fn(&LorenzState, &LorenzParams, f64) -> LorenzState;
+
+fn stepper(method: &str) -> Result<Stepper, String> {
+    match method {
+        "rk4" => Ok(rk4_step),
+        "euler" => Ok(euler_step),
+        _ => Err(format!(
+            "unknown method {method:?}, expected \"rk4\" or \"euler\""
+        )),
+    }
+}
+
+fn setup(
+    params: (f64, f64, f64),
+    x0: (f64, f64, f64),
+    dt: f64,
+) -> Result<(LorenzParams, LorenzState), String> {
+    if !(dt.is_finite() && dt > 0.0) {
+        return Err("dt must be positive".into());
+    }
+    let (sigma, rho, beta) = params;
+    Ok((
+        LorenzParams { sigma, rho, beta },
+        LorenzState::new(x0.0, x0.1, x0.2),
+    ))
+}
+
+/// x, y, z of `state` and the `n` states after it, row after row.
+fn trajectory(
+    params: &LorenzParams,
+    mut state: LorenzState,
+    dt: f64,
+    n: usize,
+    step: Stepper,
+) -> Result<Vec<f64>, String> {
+    let mut values = Vec::new();
+    n.checked_add(1)
+        .and_then(|rows| rows.checked_mul(3))
+        .and_then(|len| values.try_reserve_exact(len).ok())
+        .ok_or_else(|| format!("n = {n} steps do not fit in memory"))?;
+    values.extend([state.x, state.y, state.z]);
+    for _ in 0..n {
+        state = step(&state, params, dt);
+        values.extend([state.x, state.y, state.z]);
+    }
+    Ok(values)
+}
+
+fn largest_exponent(
+    params: &LorenzParams,
+    mut state: LorenzState,
+    dt: f64,
+    n: usize,
+    step: Stepper,
+    renormalize_every: u32,
+) -> Option<f64> {
+    let every = renormalize_every.max(1);
+    let mut estimate = LyapunovEstimate::default();
+    estimate.advance(&state, dt, 0, |s| step(s, params, dt));
+    let mut remaining = n;
+    while remaining > 0 {
+        let steps = (every as usize).min(remaining);
+        for _ in 0..steps {
+            state = step(&state, params, dt);
+        }
+        estimate.advance(&state, dt, steps as u32, |s| step(s, params, dt));
+        remaining -= steps;
+    }
+    estimate.exponent()
+}
+
+/// t, x, y of each upward crossing of z = `plane_z`, or of ρ − 1 if `None`.
+fn section(
+    params: &LorenzParams,
+    mut state: LorenzState,
+    dt: f64,
+    n: usize,
+    step: Stepper,
+    plane_z: Option<f64>,
+) -> Vec<f64> {
+    let plane_z = plane_z.unwrap_or(params.rho - 1.0);
+    let samples = (0..=n).map(|i| {
+        if i > 0 {
+            state = step(&state, params, dt);
+        }
+        (i as f64 * dt, [state.x, state.y, state.z])
+    });
+    section_crossings(samples, plane_z)
+        .iter()
+        .flat_map(|c| [c.time, c.x, c.y])
+        .collect()
+}
+
+fn rows(values: Vec<f64>) -> Array2<f64> {
+    Array2::from_shape_vec((values.len() / 3, 3), values).expect("three values per row")
+}
+
+/// Steps from `x0` `n` times and returns the visited states as an
+/// `(n + 1, 3)` array of x, y, z, starting with `x0`.
+#[pyfunction]
+#[pyo3(signature = (params, x0, dt, n, method = "rk4"))]
+fn simulate<'py>(
+    py: Python<'py>,
+    params: (f64, f64, f64),
+    x0: (f64, f64, f64),
+    dt: f64,
+    n: usize,
+    method: &str,
+) -> PyResult<Bound<'py, PyArray2<f64>>> {
+    let step = stepper(method).map_err(PyValueError::new_err)?;
+    let (params, state) = setup(params, x0, dt).map_err(PyValueError::new_err)?;
+    let values = py
+        .allow_threads(|| trajectory(&params, state, dt, n, step))
+        .map_err(PyValueError::new_err)?;
+    Ok(rows(values).into_pyarray(py))
+}
+
+/// Largest Lyapunov exponent over `n` steps, renormalising the shadow
+/// trajectory every `renormalize_every` steps as the viewer does per frame.
+#[pyfunction]
+#[pyo3(signature = (params, x0, dt, n, method = "rk4", renormalize_every = 10))]
+fn lyapunov(
+    py: Python<'_>,
+    params: (f64, f64, f64),
+    x0: (f64, f64, f64),
+    dt: f64,
+    n: usize,
+    method: &str,
+    renormalize_every: u32,
+) -> PyResult<Option<f64>> {
+    let step = stepper(method).map_err(PyValueError::new_err)?;
+    let (params, state) = setup(params, x0, dt).map_err(PyValueError::new_err)?;
+    Ok(py.allow_threads(|| largest_exponent(&params, state, dt, n, step, renormalize_every)))
+}
+
+/// Upward crossings of the plane z = `plane_z` (ρ − 1 by default, through
+/// both fixed points) over `n` steps, as a `(k, 3)` array of t, x, y.
+#[pyfunction]
+#[pyo3(signature = (params, x0, dt, n, method = "rk4", plane_z = None))]
+fn poincare<'py>(
+    py: Python<'py>,
+    params: (f64, f64, f64),
+    x0: (f64, f64, f64),
+    dt: f64,
+    n: usize,
+    method: &str,
+    plane_z: Option<f64>,
+) -> PyResult<Bound<'py, PyArray2<f64>>> {
+    let step = stepper(method).map_err(PyValueError::new_err)?;
+    let (params, state) = setup(params, x0, dt).map_err(PyValueError::new_err)?;
+    let values = py.allow_threads(|| section(&params, state, dt, n, step, plane_z));
+    Ok(rows(values).into_pyarray(py))
+}
+
+#[pymodule]
+fn lorenz(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_function(wrap_pyfunction!(simulate, m)?)?;
+    m.add_function(wrap_pyfunction!(lyapunov, m)?)?;
+    m.add_function(wrap_pyfunction!(poincare, m)?)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const STANDARD: (f64, f64, f64) = (10.0, 28.0, 8.0 / 3.0);
+
+    #[test]
+    fn test_rejects_bad_arguments() {
+        assert!(stepper("midpoint").is_err());
+        for dt in [0.0, -0.01, f64::NAN, f64::INFINITY] {
+            assert!(setup(STANDARD, (1.0, 1.0, 1.0), dt).is_err(), "dt = {dt}");
+        }
+        let (params, state) = setup(STANDARD, (1.0, 1.0, 1.0), 0.01).unwrap();
+        for n in [usize::MAX, usize::MAX / 3] {
+            assert!(
+                trajectory(&params, state.clone(), 0.01, n, rk4_step).is_err(),
+                "n = {n}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_simulate_returns_start_and_n_states() {
+        let (params, state) = setup(STANDARD, (1.0, 2.0, 3.0), 0.01).unwrap();
+        let array = rows(trajectory(&params, state.clone(), 0.01, 4, euler_step).unwrap());
+        assert_eq!(array.dim(), (5, 3));
+        assert_eq!(array.row(0).to_vec(), vec![1.0, 2.0, 3.0]);
+        let next = euler_step(&state, &params, 0.01);
+        assert_eq!(array.row(1).to_vec(), vec![next.x, next.y, next.z]);
+    }
+
+    #[test]
+    fn test_estimators_match_the_classic_attractor() {
+        let (params, state) = setup(STANDARD, (1.0, 1.0, 1.0), 0.01).unwrap();
+        let lambda = largest_exponent(&params, state.clone(), 0.01, 50_000, rk4_step, 10).unwrap();
+        assert!((0.7..1.1).contains(&lambda), "λ = {lambda}");
+
+        // The default plane is z = ρ − 1, through both fixed points.
+        let default = section(&params, state.clone(), 0.01, 5_000, rk4_step, None);
+        assert!(!default.is_empty());
+        assert_eq!(
+            default,
+            section(&params, state.clone(), 0.01, 5_000, rk4_step, Some(27.0))
+        );
+        assert_ne!(
+            default,
+            section(&params, state, 0.01, 5_000, rk4_step, Some(10.0))
+        );
+    }
+}
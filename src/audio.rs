@@ -0,0 +1,404 @@
+//! Audio-reactive modulation: the bass, mid and treble energy of a microphone
+//! or loopback input drive σ, ρ and β for live visuals.
+use std::sync::mpsc::{channel, Receiver, Sender};
+use std::sync::Mutex;
+use std::thread;
+use std::time::Duration;
+
+use bevy::prelude::*;
+use cpal::traits::{DeviceTrait, HostTrait, StreamTrait};
+use cpal::{FromSample, SizedSample};
+use serde::{Deserialize, Serialize};
+
+use crate::config::{SimulationConfig, SliderRanges, SpectrumWindow};
+use crate::i18n::{tr, tr_noop, trf};
+use crate::simulation::spectrum::power_spectrum;
+use crate::ui::toast::Toasts;
+
+/// Samples per analysed block: about 21 ms at 48 kHz.
+const BLOCK: usize = 1024;
+const STARTUP_TIMEOUT: Duration = Duration::from_secs(3);
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum AudioBand {
+    Bass,
+    Mid,
+    Treble,
+}
+
+impl AudioBand {
+    pub const ALL: [Self; 3] = [Self::Bass, Self::Mid, Self::Treble];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Bass => tr_noop("Bass"),
+            Self::Mid => tr_noop("Mid"),
+            Self::Treble => tr_noop("Treble"),
+        }
+    }
+
+    /// Lower and upper edge, in Hz.
+    pub fn frequencies(&self) -> (f64, f64) {
+        match self {
+            Self::Bass => (20.0, 250.0),
+            Self::Mid => (250.0, 2_000.0),
+            Self::Treble => (2_000.0, 8_000.0),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum AudioTarget {
+    Sigma,
+    Rho,
+    Beta,
+}
+
+impl AudioTarget {
+    pub const ALL: [Self; 3] = [Self::Sigma, Self::Rho, Self::Beta];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Sigma => tr_noop("σ (sigma)"),
+            Self::Rho => tr_noop("ρ (rho)"),
+            Self::Beta => tr_noop("β (beta)"),
+        }
+    }
+
+    /// The slider range, which a new route starts out spanning.
+    pub fn slider_range(&self, ranges: &SliderRanges) -> (f64, f64) {
+        match self {
+            Self::Sigma => ranges.sigma,
+            Self::Rho => ranges.rho,
+            Self::Beta => ranges.beta,
+        }
+    }
+
+    fn value_mut<'a>(&self, config: &'a mut SimulationConfig) -> &'a mut f64 {
+        match self {
+            Self::Sigma => &mut config.sigma,
+            Self::Rho => &mut config.rho,
+            Self::Beta => &mut config.beta,
+        }
+    }
+}
+
+/// One parameter following one band: `range.0` in silence, `range.1` at full
+/// level.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct AudioRoute {
+    pub target: AudioTarget,
+    pub band: AudioBand,
+    pub range: (f64, f64),
+}
+
+impl AudioRoute {
+    pub fn apply(&self, levels: &[f32; 3], config: &mut SimulationConfig) {
+        let level = f64::from(levels[self.band as usize].clamp(0.0, 1.0));
+        let (low, high) = self.range;
+        let value = low + (high - low) * level;
+        let current = self.target.value_mut(config);
+        if *current != value {
+            *current = value;
+        }
+    }
+}
+
+#[derive(Resource, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AudioSettings {
+    pub enabled: bool,
+    /// `None` listens to the system's default input.
+    pub device: Option<String>,
+    /// Gain applied to each band's RMS level before it is clamped to 0–1.
+    pub sensitivity: f32,
+    /// 0 follows the music instantly; towards 1 the levels glide.
+    pub smoothing: f32,
+    pub routes: Vec<AudioRoute>,
+}
+
+impl Default for AudioSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            device: None,
+            sensitivity: 10.0,
+            smoothing: 0.8,
+            routes: vec![AudioRoute {
+                target: AudioTarget::Rho,
+                band: AudioBand::Bass,
+                range: (28.0, 50.0),
+            }],
+        }
+    }
+}
+
+impl AudioSettings {
+    pub fn route(&self, target: AudioTarget) -> Option<&AudioRoute> {
+        self.routes.iter().find(|r| r.target == target)
+    }
+
+    /// Points `target` at `band`, keeping its range; `None` removes the route.
+    pub fn set_band(
+        &mut self,
+        target: AudioTarget,
+        band: Option<AudioBand>,
+        ranges: &SliderRanges,
+    ) {
+        match (band, self.routes.iter_mut().find(|r| r.target == target)) {
+            (Some(band), Some(route)) => route.band = band,
+            (Some(band), None) => self.routes.push(AudioRoute {
+                target,
+                band,
+                range: target.slider_range(ranges),
+            }),
+            (None, _) => self.routes.retain(|r| r.target != target),
+        }
+    }
+}
+
+/// The RMS level of each band in one block of mono samples.
+pub fn band_levels(samples: &[f64], sample_rate: f64) -> [f64; 3] {
+    let spectrum = power_spectrum(samples, 1.0 / sample_rate, SpectrumWindow::Hann);
+    let bin_width = sample_rate / samples.len() as f64;
+    let mut energies = [0.0; 3];
+    for [frequency, power] in spectrum {
+        for band in AudioBand::ALL {
+            let (low, high) = band.frequencies();
+            if (low..high).contains(&frequency) {
+                energies[band as usize] += power * bin_width;
+            }
+        }
+    }
+    energies.map(f64::sqrt)
+}
+
+fn list_devices() -> Result<Vec<String>, String> {
+    let devices = cpal::default_host()
+        .input_devices()
+        .map_err(|error| error.to_string())?;
+    Ok(devices.filter_map(|device| device.name().ok()).collect())
+}
+
+/// The input stream stays on the thread that opened it, since cpal streams
+/// cannot move between threads; dropping the capture closes `_stop` and ends it.
+struct Capture {
+    blocks: Receiver<Vec<f32>>,
+    sample_rate: f64,
+    _stop: Sender<()>,
+}
+
+fn start(device: Option<String>) -> Result<Capture, String> {
+    let (ready, started) = channel();
+    let (stop, stopped) = channel::<()>();
+    thread::Builder::new()
+        .name("audio-input".into())
+        .spawn(move || match open_stream(device.as_deref()) {
+            Ok((stream, blocks, sample_rate)) => {
+                let _ = ready.send(Ok((blocks, sample_rate)));
+                // Returns once the sender is dropped.
+                let _ = stopped.recv();
+                drop(stream);
+            }
+            Err(error) => {
+                let _ = ready.send(Err(error));
+            }
+        })
+        .map_err(|error| error.to_string())?;
+    let (blocks, sample_rate) = started
+        .recv_timeout(STARTUP_TIMEOUT)
+        .map_err(|error| error.to_string())??;
+    Ok(Capture {
+        blocks,
+        sample_rate,
+        _stop: stop,
+    })
+}
+
+type OpenStream = (cpal::Stream, Receiver<Vec<f32>>, f64);
+
+fn open_stream(name: Option<&str>) -> Result<OpenStream, String> {
+    let host = cpal::default_host();
+    let device = match name {
+        Some(name) => host
+            .input_devices()
+            .map_err(|error| error.to_string())?
+            .find(|device| device.name().is_ok_and(|n| n == name))
+            .ok_or_else(|| trf("{device} is not connected", &[("device", &name)]))?,
+        None => host
+            .default_input_device()
+            .ok_or_else(|| tr("No audio inputs found").to_string())?,
+    };
+    let supported = device
+        .default_input_config()
+        .map_err(|error| error.to_string())?;
+    let sample_rate = f64::from(supported.sample_rate().0);
+    let config = supported.config();
+    let (sender, blocks) = channel();
+    let stream = match supported.sample_format() {
+        cpal::SampleFormat::F32 => build_stream::<f32>(&device, &config, sender),
+        cpal::SampleFormat::I16 => build_stream::<i16>(&device, &config, sender),
+        cpal::SampleFormat::U16 => build_stream::<u16>(&device, &config, sender),
+        format => return Err(format!("unsupported sample format {format}")),
+    }
+    .map_err(|error| error.to_string())?;
+    stream.play().map_err(|error| error.to_string())?;
+    Ok((stream, blocks, sample_rate))
+}
+
+/// Mixes every frame down to mono and sends it on in blocks of `BLOCK`.
+fn build_stream<T>(
+    device: &cpal::Device,
+    config: &cpal::StreamConfig,
+    sender: Sender<Vec<f32>>,
+) -> Result<cpal::Stream, cpal::BuildStreamError>
+where
+    T: SizedSample,
+    f32: FromSample<T>,
+{
+    let channels = usize::from(config.channels.max(1));
+    let mut block = Vec::with_capacity(BLOCK);
+    device.build_input_stream(
+        config,
+        move |data: &[T], _: &cpal::InputCallbackInfo| {
+            for frame in data.chunks(channels) {
+                let sum: f32 = frame
+                    .iter()
+                    .map(|&s| <f32 as FromSample<T>>::from_sample_(s))
+                    .sum();
+                block.push(sum / channels as f32);
+                if block.len() == BLOCK {
+                    let _ = sender.send(std::mem::replace(&mut block, Vec::with_capacity(BLOCK)));
+                }
+            }
+        },
+        |error| warn!("Audio input failed: {error}"),
+        None,
+    )
+}
+
+#[derive(Resource, Default)]
+pub struct AudioInputState {
+    pub devices: Vec<String>,
+    /// Set to list the devices again on the next frame.
+    pub refresh: bool,
+    pub error: Option<String>,
+    /// The smoothed 0–1 level of each band, indexed by `AudioBand`.
+    pub levels: [f32; 3],
+    /// The latest levels before smoothing.
+    raw: [f32; 3],
+    /// The device last opened, even if that failed.
+    target: Option<Option<String>>,
+    capture: Option<Mutex<Capture>>,
+    listed: bool,
+}
+
+impl AudioInputState {
+    pub fn is_listening(&self) -> bool {
+        self.capture.is_some()
+    }
+}
+
+pub fn audio_system(
+    settings: Res<AudioSettings>,
+    mut audio: ResMut<AudioInputState>,
+    mut config: ResMut<SimulationConfig>,
+    time: Res<Time<Real>>,
+    mut toasts: ResMut<Toasts>,
+) {
+    if audio.refresh || !audio.listed {
+        audio.refresh = false;
+        audio.listed = true;
+        match list_devices() {
+            Ok(devices) => audio.devices = devices,
+            Err(error) => audio.error = Some(error),
+        }
+    }
+
+    let wanted = settings.enabled.then(|| settings.device.clone());
+    if audio.target != wanted {
+        audio.capture = None;
+        audio.error = None;
+        audio.levels = [0.0; 3];
+        audio.raw = [0.0; 3];
+        if let Some(device) = &wanted {
+            match start(device.clone()) {
+                Ok(capture) => audio.capture = Some(Mutex::new(capture)),
+                Err(error) => {
+                    toasts.push(trf("⚠ Audio input failed: {error}", &[("error", &error)]));
+                    audio.error = Some(error);
+                }
+            }
+        }
+        audio.target = wanted;
+    }
+
+    let Some(capture) = &audio.capture else {
+        return;
+    };
+    let (block, sample_rate) = match capture.lock() {
+        Ok(capture) => (capture.blocks.try_iter().last(), capture.sample_rate),
+        Err(_) => return,
+    };
+    if let Some(block) = block {
+        let samples: Vec<f64> = block.into_iter().map(f64::from).collect();
+        let levels = band_levels(&samples, sample_rate);
+        audio.raw = levels.map(|level| (level as f32 * settings.sensitivity).min(1.0));
+    }
+
+    // The same glide at any frame rate: `smoothing` is what is kept per 1/60 s.
+    let keep = settings.smoothing.clamp(0.0, 0.99).powf(time.delta_seconds() * 60.0);
+    let raw = audio.raw;
+    for (level, raw) in audio.levels.iter_mut().zip(raw) {
+        *level = raw + (*level - raw) * keep;
+    }
+    for route in &settings.routes {
+        route.apply(&audio.levels, &mut config);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f64::consts::TAU;
+
+    use super::*;
+
+    #[test]
+    fn test_each_band_hears_its_own_tone() {
+        let sample_rate = 48_000.0;
+        for (band, frequency) in AudioBand::ALL.into_iter().zip([100.0, 1_000.0, 4_000.0]) {
+            let samples: Vec<f64> = (0..BLOCK)
+                .map(|i| 0.5 * (TAU * frequency * i as f64 / sample_rate).sin())
+                .collect();
+            let levels = band_levels(&samples, sample_rate);
+            for other in AudioBand::ALL {
+                let level = levels[other as usize];
+                if other == band {
+                    // The RMS of a sine is its amplitude over √2.
+                    assert!((level - 0.5 / 2f64.sqrt()).abs() < 0.01, "{band:?}: {level}");
+                } else {
+                    assert!(level < 0.01, "{other:?} heard {frequency} Hz: {level}");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_routes_span_their_range() {
+        let ranges = SliderRanges::default();
+        let mut settings = AudioSettings::default();
+        settings.set_band(AudioTarget::Rho, Some(AudioBand::Treble), &ranges);
+        settings.set_band(AudioTarget::Beta, Some(AudioBand::Mid), &ranges);
+        settings.set_band(AudioTarget::Sigma, None, &ranges);
+        assert_eq!(settings.route(AudioTarget::Rho).unwrap().range, (28.0, 50.0));
+        assert_eq!(settings.route(AudioTarget::Beta).unwrap().range, ranges.beta);
+        assert_eq!(settings.route(AudioTarget::Sigma), None);
+
+        let mut config = SimulationConfig::default();
+        for route in &settings.routes {
+            route.apply(&[0.0, 1.0, 0.5], &mut config);
+        }
+        assert_eq!(config.rho, 39.0);
+        assert_eq!(config.beta, ranges.beta.1);
+    }
+}
@@ -0,0 +1,362 @@
+use std::fs;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use bevy::app::{AppExit, ScheduleRunnerPlugin};
+use bevy::prelude::*;
+use bevy::render::renderer::RenderDevice;
+use bevy::window::ExitCondition;
+use bevy::winit::WinitPlugin;
+
+use crate::config::{CameraSettings, SimulationConfig};
+use crate::rendering::camera_controller::{OrbitCamera, FIT_MARGIN};
+use crate::rendering::trail_renderer::{draw_axes, draw_trail};
+use crate::rendering::view_scale::ViewScale;
+use crate::simulation::integrator::{advance_trajectory, TrailBuffer};
+use crate::simulation::lorenz::{LorenzParams, LorenzState};
+use super::still::{
+    spawn_png_writer, spawn_still_camera, unpad_rows, StillGizmos, StillReceiver, StillRenderPlugin,
+    StillTarget, MAX_STILL_DIMENSION,
+};
+use super::video::EncoderHandle;
+use super::{output_dir, CAPTURE_DIR};
+
+const REFERENCE_HEIGHT: f32 = 900.0;
+
+pub const BATCH_USAGE: &str = "\
+Usage: lorenz-attractor --batch [options]
+
+Renders one PNG per parameter set without opening a window.
+
+Options:
+  --params S,R,B       Add a parameter set (σ, ρ, β); repeatable
+  --params-file PATH   Read parameter sets from a file, one S,R,B per line
+  --steps N            Integration steps per image (default 20000)
+  --dt DT              Time step (default 0.005)
+  --size WxH           Image size in pixels (default 1920x1080)
+  --out DIR            Output directory (default captures/batch-<timestamp>)";
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct BatchJob {
+    pub params: Vec<LorenzParams>,
+    pub steps: u32,
+    pub dt: f64,
+    pub size: UVec2,
+    pub out: Option<PathBuf>,
+}
+
+impl Default for BatchJob {
+    fn default() -> Self {
+        Self {
+            params: Vec::new(),
+            steps: 20_000,
+            dt: 0.005,
+            size: UVec2::new(1920, 1080),
+            out: None,
+        }
+    }
+}
+
+fn parse_params(text: &str) -> Result<LorenzParams, String> {
+    let values: Vec<f64> = text
+        .split(',')
+        .map(|v| v.trim().parse::<f64>())
+        .collect::<Result<_, _>>()
+        .map_err(|_| format!("invalid parameter set '{text}' (expected S,R,B)"))?;
+    match values[..] {
+        [sigma, rho, beta] => Ok(LorenzParams { sigma, rho, beta }),
+        _ => Err(format!("invalid parameter set '{text}' (expected S,R,B)")),
+    }
+}
+
+fn parse_size(text: &str) -> Result<UVec2, String> {
+    let (w, h) = text
+        .split_once(['x', 'X'])
+        .ok_or_else(|| format!("invalid size '{text}' (expected WxH)"))?;
+    let size = UVec2::new(
+        w.parse().map_err(|_| format!("invalid width '{w}'"))?,
+        h.parse().map_err(|_| format!("invalid height '{h}'"))?,
+    );
+    if size.min_element() < 16 || size.max_element() > MAX_STILL_DIMENSION {
+        return Err(format!(
+            "size must be between 16 and {MAX_STILL_DIMENSION} pixels per side"
+        ));
+    }
+    Ok(size)
+}
+
+impl BatchJob {
+    pub fn from_args(args: &[String]) -> Result<Option<Self>, String> {
+        if !args.iter().any(|arg| arg == "--batch") {
+            return Ok(None);
+        }
+
+        let mut job = Self::default();
+        let mut args = args.iter();
+        while let Some(arg) = args.next() {
+            if arg == "--batch" {
+                continue;
+            }
+            let mut value = || {
+                args.next()
+                    .ok_or_else(|| format!("missing value for {arg}"))
+            };
+            match arg.as_str() {
+                "--params" => job.params.push(parse_params(value()?)?),
+                "--params-file" => {
+                    let path = value()?;
+                    let text = fs::read_to_string(path)
+                        .map_err(|err| format!("cannot read {path}: {err}"))?;
+                    for line in text.lines().map(str::trim) {
+                        if !line.is_empty() && !line.starts_with('#') {
+                            job.params.push(parse_params(line)?);
+                        }
+                    }
+                }
+                "--steps" => {
+                    job.steps = value()?
+                        .parse()
+                        .ok()
+                        .filter(|steps: &u32| *steps > 0)
+                        .ok_or_else(|| "--steps expects a positive integer".to_string())?;
+                }
+                "--dt" => {
+                    job.dt = value()?
+                        .parse()
+                        .ok()
+                        .filter(|dt: &f64| *dt > 0.0)
+                        .ok_or_else(|| "--dt expects a positive number".to_string())?;
+                }
+                "--size" => job.size = parse_size(value()?)?,
+                "--out" => job.out = Some(PathBuf::from(value()?)),
+                other => return Err(format!("unknown option '{other}'")),
+            }
+        }
+
+        if job.params.is_empty() {
+            let config = SimulationConfig::default();
+            job.params.push(config.params());
+        }
+        Ok(Some(job))
+    }
+}
+
+pub fn image_name(index: usize, params: &LorenzParams) -> String {
+    format!(
+        "{index:03}_sigma{:.3}_rho{:.3}_beta{:.3}.png",
+        params.sigma, params.rho, params.beta
+    )
+}
+
+fn integrate(job: &BatchJob, params: &LorenzParams) -> TrailBuffer {
+    let defaults = SimulationConfig::default();
+    let config = SimulationConfig {
+        dt: job.dt,
+        steps_per_frame: job.steps,
+        ..default()
+    };
+    let mut state = LorenzState::new(defaults.initial_x, defaults.initial_y, defaults.initial_z);
+    let mut trail = TrailBuffer {
+        max_points: job.steps as usize,
+        ..default()
+    };
+    advance_trajectory(&mut state, &mut trail, params, &config, None);
+    trail
+}
+
+fn framing(trail: &TrailBuffer, view: &ViewScale, fov_degrees: f32) -> Transform {
+    let (center, radius) = trail
+        .bounding_sphere_mapped(|position| view.to_world(position))
+        .unwrap_or((Vec3::ZERO, 1.0));
+    let half_fov = fov_degrees.to_radians() * 0.5;
+    let orbit = OrbitCamera {
+        focus: center,
+        radius: radius.max(1.0) * FIT_MARGIN / half_fov.sin(),
+        ..default()
+    };
+    Transform::from_translation(orbit.eye_position()).looking_at(orbit.focus, Vec3::Y)
+}
+
+struct BatchImage {
+    path: PathBuf,
+    trail: TrailBuffer,
+    target: StillTarget,
+}
+
+#[derive(Resource)]
+struct BatchRun {
+    job: BatchJob,
+    dir: PathBuf,
+    index: usize,
+    current: Option<BatchImage>,
+    writers: Vec<(PathBuf, EncoderHandle)>,
+}
+
+#[allow(clippy::too_many_arguments)]
+fn batch_system(
+    mut commands: Commands,
+    mut run: ResMut<BatchRun>,
+    receiver: Res<StillReceiver>,
+    render_device: Res<RenderDevice>,
+    mut images: ResMut<Assets<Image>>,
+    mut gizmos: Gizmos<StillGizmos>,
+    mut exit: EventWriter<AppExit>,
+) {
+    let run = &mut *run;
+
+    if run.current.is_none() {
+        let Some(params) = run.job.params.get(run.index) else {
+            let mut failed = false;
+            for (path, handle) in run.writers.drain(..) {
+                match handle.join() {
+                    Ok(Ok(())) => info!("Saved {}", path.display()),
+                    Ok(Err(err)) => {
+                        error!("Failed to write {}: {err}", path.display());
+                        failed = true;
+                    }
+                    Err(_) => {
+                        error!("Writer thread for {} panicked", path.display());
+                        failed = true;
+                    }
+                }
+            }
+            exit.send(if failed {
+                AppExit::error()
+            } else {
+                AppExit::Success
+            });
+            return;
+        };
+
+        info!(
+            "[{}/{}] σ = {}, ρ = {}, β = {:.4}",
+            run.index + 1,
+            run.job.params.len(),
+            params.sigma,
+            params.rho,
+            params.beta
+        );
+        let fov_degrees = CameraSettings::default().fov_degrees;
+        let trail = integrate(&run.job, params);
+        let projection = Projection::Perspective(PerspectiveProjection {
+            fov: fov_degrees.to_radians(),
+            ..default()
+        });
+        let target = spawn_still_camera(
+            &mut commands,
+            &mut images,
+            &render_device,
+            framing(&trail, &ViewScale::default(), fov_degrees),
+            projection,
+            run.job.size,
+        );
+        run.current = Some(BatchImage {
+            path: run.dir.join(image_name(run.index, params)),
+            trail,
+            target,
+        });
+    }
+
+    let Some(current) = run.current.as_mut() else {
+        return;
+    };
+    // Batch images are framed on the unscaled trail, in the default axes.
+    let view = ViewScale::default();
+    draw_trail(&mut gizmos, &current.trail, &view);
+    draw_axes(&mut gizmos, &view);
+    current.target.tick();
+
+    let Some(data) = receiver.try_recv() else {
+        return;
+    };
+    let current = run.current.take().unwrap();
+    commands.entity(current.target.camera).despawn();
+    let size = current.target.size;
+    let handle = spawn_png_writer(current.path.clone(), size, unpad_rows(&data, size));
+    run.writers.push((current.path, handle));
+    run.index += 1;
+}
+
+fn setup_batch(mut gizmo_store: ResMut<GizmoConfigStore>, run: Res<BatchRun>) {
+    let (config, _) = gizmo_store.config_mut::<StillGizmos>();
+    config.line_width *= (run.job.size.y as f32 / REFERENCE_HEIGHT).max(1.0);
+}
+
+pub fn run_batch(job: BatchJob) -> AppExit {
+    let dir = match &job.out {
+        Some(dir) => fs::create_dir_all(dir)
+            .map(|_| dir.clone())
+            .map_err(|err| format!("{}: {err}", dir.display())),
+        None => output_dir("batch").map_err(|err| format!("under ./{CAPTURE_DIR}: {err}")),
+    };
+    let dir = match dir {
+        Ok(dir) => dir,
+        Err(err) => {
+            eprintln!("Cannot create output directory {err}");
+            return AppExit::error();
+        }
+    };
+
+    App::new()
+        .add_plugins(
+            DefaultPlugins
+                .set(WindowPlugin {
+                    primary_window: None,
+                    exit_condition: ExitCondition::DontExit,
+                    close_when_requested: false,
+                })
+                .disable::<WinitPlugin>(),
+        )
+        .add_plugins(ScheduleRunnerPlugin::run_loop(Duration::from_secs_f64(
+            1.0 / 60.0,
+        )))
+        .add_plugins(StillRenderPlugin)
+        .insert_resource(ClearColor(Color::srgb(0.02, 0.02, 0.04)))
+        .insert_resource(BatchRun {
+            job,
+            dir,
+            index: 0,
+            current: None,
+            writers: Vec::new(),
+        })
+        .add_systems(Startup, setup_batch)
+        .add_systems(Update, batch_system)
+        .run()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(list: &[&str]) -> Vec<String> {
+        list.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn test_batch_args_parse() {
+        assert_eq!(BatchJob::from_args(&args(&["--steps", "10"])), Ok(None));
+
+        let job = BatchJob::from_args(&args(&[
+            "--batch",
+            "--params",
+            "10,28,2.6667",
+            "--params",
+            "10, 99.96, 2.6667",
+            "--size",
+            "640x480",
+            "--steps",
+            "500",
+        ]))
+        .unwrap()
+        .unwrap();
+        assert_eq!(job.params.len(), 2);
+        assert_eq!(job.params[1].rho, 99.96);
+        assert_eq!(job.size, UVec2::new(640, 480));
+        assert_eq!(job.steps, 500);
+
+        assert!(BatchJob::from_args(&args(&["--batch", "--params", "1,2"])).is_err());
+        assert!(BatchJob::from_args(&args(&["--batch", "--size", "99999x10"])).is_err());
+        assert!(BatchJob::from_args(&args(&["--batch", "--bogus"])).is_err());
+        assert!(BatchJob::from_args(&args(&["--batch", "--steps", "0"])).is_err());
+    }
+}
\ No newline at end of file
@@ -0,0 +1,120 @@
+use std::path::PathBuf;
+
+use bevy::prelude::*;
+use bevy::render::view::screenshot::ScreenshotManager;
+use bevy::window::PrimaryWindow;
+
+use crate::config::{CaptureSettings, RecordEvent, UiVisibility};
+use crate::i18n::{tr, trf};
+use crate::ui::toast::Toasts;
+use super::video::VideoRecorder;
+use super::{output_dir, start_fixed_clock, stop_fixed_clock};
+
+pub struct Recording {
+    pub dir: PathBuf,
+    pub frame: u32,
+}
+
+#[derive(Resource, Default)]
+pub struct FrameRecorder {
+    pub active: Option<Recording>,
+}
+
+pub fn frame_path(dir: &std::path::Path, frame: u32) -> PathBuf {
+    dir.join(format!("frame_{frame:06}.png"))
+}
+
+fn stop_recording(
+    commands: &mut Commands,
+    recorder: &mut FrameRecorder,
+    ui_visibility: &mut UiVisibility,
+    toasts: &mut Toasts,
+) {
+    if let Some(recording) = recorder.active.take() {
+        stop_fixed_clock(commands);
+        ui_visibility.hidden = false;
+        toasts.push(trf(
+            "⏹ Saved {count} frames to {path}",
+            &[
+                ("count", &recording.frame),
+                ("path", &recording.dir.display()),
+            ],
+        ));
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn frame_recording_system(
+    mut commands: Commands,
+    keys: Res<ButtonInput<KeyCode>>,
+    settings: Res<CaptureSettings>,
+    mut events: EventReader<RecordEvent>,
+    mut recorder: ResMut<FrameRecorder>,
+    video: Res<VideoRecorder>,
+    mut manager: ResMut<ScreenshotManager>,
+    mut ui_visibility: ResMut<UiVisibility>,
+    mut toasts: ResMut<Toasts>,
+    windows: Query<Entity, With<PrimaryWindow>>,
+) {
+    let toggle = !events.is_empty() || keys.just_pressed(KeyCode::F10);
+    events.clear();
+
+    if toggle {
+        if recorder.active.is_some() {
+            stop_recording(&mut commands, &mut recorder, &mut ui_visibility, &mut toasts);
+            return;
+        }
+        if video.is_recording() {
+            toasts.push(tr("Stop the video recording first"));
+            return;
+        }
+        match output_dir("frames") {
+            Ok(dir) => {
+                start_fixed_clock(&mut commands, settings.fps);
+                toasts.push(trf("⏺ Recording to {path}", &[("path", &dir.display())]));
+                recorder.active = Some(Recording { dir, frame: 0 });
+            }
+            Err(err) => {
+                toasts.push(trf("Recording failed: {err}", &[("err", &err)]));
+                return;
+            }
+        }
+    }
+
+    let Some(recording) = recorder.active.as_mut() else {
+        return;
+    };
+    if settings.max_frames > 0 && recording.frame >= settings.max_frames {
+        stop_recording(&mut commands, &mut recorder, &mut ui_visibility, &mut toasts);
+        return;
+    }
+    let Ok(window) = windows.get_single() else {
+        return;
+    };
+
+    ui_visibility.hidden = !settings.include_ui;
+    if manager
+        .save_screenshot_to_disk(window, frame_path(&recording.dir, recording.frame))
+        .is_ok()
+    {
+        recording.frame += 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_frame_paths_sort_in_capture_order() {
+        let dir = PathBuf::from("frames");
+        let names: Vec<_> = [0, 9, 10, 999_999]
+            .into_iter()
+            .map(|i| frame_path(&dir, i))
+            .collect();
+        let mut sorted = names.clone();
+        sorted.sort();
+        assert_eq!(names, sorted);
+        assert_eq!(names[0], dir.join("frame_000000.png"));
+    }
+}
\ No newline at end of file
@@ -0,0 +1,113 @@
+use std::borrow::Cow;
+use std::fs::File;
+use std::io::BufWriter;
+use std::path::Path;
+use std::sync::mpsc::Receiver;
+use std::thread;
+
+use bevy::math::UVec2;
+use color_quant::NeuQuant;
+use gif::{Encoder, Frame, Repeat};
+
+use crate::config::CaptureSettings;
+use super::video::EncoderHandle;
+
+pub const GIF_PALETTE_SIZES: [usize; 5] = [16, 32, 64, 128, 256];
+const QUANTIZE_SAMPLE_FACTOR: i32 = 10;
+
+fn downscale_factor(size: UVec2, target_width: u32) -> u32 {
+    size.x.div_ceil(target_width.max(1)).max(1)
+}
+
+pub fn downscale_rgba(pixels: &[u8], size: UVec2, target_width: u32) -> (Vec<u8>, UVec2) {
+    let factor = downscale_factor(size, target_width);
+    let out = UVec2::new(size.x / factor, size.y / factor).max(UVec2::ONE);
+    let mut result = Vec::with_capacity((out.x * out.y * 4) as usize);
+
+    for oy in 0..out.y {
+        for ox in 0..out.x {
+            let mut sum = [0u32; 4];
+            for dy in 0..factor {
+                for dx in 0..factor {
+                    let (x, y) = (ox * factor + dx, oy * factor + dy);
+                    let i = ((y * size.x + x) * 4) as usize;
+                    for (c, s) in sum.iter_mut().enumerate() {
+                        *s += pixels[i + c] as u32;
+                    }
+                }
+            }
+            let count = factor * factor;
+            result.extend(sum.iter().map(|s| (s / count) as u8));
+        }
+    }
+    (result, out)
+}
+
+pub fn spawn_gif_encoder(
+    path: &Path,
+    size: UVec2,
+    settings: &CaptureSettings,
+    frames: Receiver<Vec<u8>>,
+) -> std::io::Result<EncoderHandle> {
+    let target_width = settings.gif_width;
+    let colors = settings.gif_colors;
+    let delay = (100.0 / settings.gif_fps.max(1) as f32).round() as u16;
+
+    let factor = downscale_factor(size, target_width);
+    let out = UVec2::new(size.x / factor, size.y / factor).max(UVec2::ONE);
+    let file = BufWriter::new(File::create(path)?);
+    let mut encoder = Encoder::new(file, out.x as u16, out.y as u16, &[])
+        .and_then(|mut encoder| encoder.set_repeat(Repeat::Infinite).map(|_| encoder))
+        .map_err(std::io::Error::other)?;
+
+    Ok(thread::spawn(move || {
+        for pixels in frames {
+            let (scaled, _) = downscale_rgba(&pixels, size, target_width);
+            let quantizer = NeuQuant::new(QUANTIZE_SAMPLE_FACTOR, colors, &scaled);
+            let indices: Vec<u8> = scaled
+                .chunks_exact(4)
+                .map(|p| quantizer.index_of(p) as u8)
+                .collect();
+            let frame = Frame {
+                delay,
+                width: out.x as u16,
+                height: out.y as u16,
+                palette: Some(quantizer.color_map_rgb()),
+                buffer: Cow::Owned(indices),
+                ..Frame::default()
+            };
+            encoder.write_frame(&frame).map_err(|err| err.to_string())?;
+        }
+        Ok(())
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_downscale_averages_blocks() {
+        let size = UVec2::new(4, 2);
+        let mut pixels = Vec::new();
+        for i in 0..8u8 {
+            pixels.extend([i * 10, 0, 255, 255]);
+        }
+
+        let (scaled, out) = downscale_rgba(&pixels, size, 2);
+        assert_eq!(out, UVec2::new(2, 1));
+        assert_eq!(scaled.len(), 2 * 4);
+        assert_eq!(scaled[0], (10 + 40 + 50) / 4);
+        assert_eq!(&scaled[1..4], &[0, 255, 255]);
+    }
+
+    #[test]
+    fn test_downscale_stays_within_target_width() {
+        for width in [1400, 960, 481, 300] {
+            let size = UVec2::new(width, 3);
+            let pixels = vec![0; (size.x * size.y * 4) as usize];
+            let (_, out) = downscale_rgba(&pixels, size, 480);
+            assert!(out.x <= 480, "{width} px wide became {} px", out.x);
+        }
+    }
+}
\ No newline at end of file
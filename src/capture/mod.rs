@@ -0,0 +1,65 @@
+#[cfg(feature = "export")]
+pub mod batch;
+#[cfg(feature = "export")]
+pub mod frame_sequence;
+#[cfg(feature = "export")]
+pub mod gif;
+#[cfg(feature = "export")]
+pub mod screenshot;
+#[cfg(feature = "export")]
+pub mod still;
+#[cfg(feature = "export")]
+pub mod video;
+
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use std::time::Duration;
+
+use bevy::prelude::*;
+use bevy::time::TimeUpdateStrategy;
+use chrono::Local;
+
+pub const CAPTURE_DIR: &str = "captures";
+
+fn stamped(prefix: &str) -> String {
+    format!("{prefix}-{}", Local::now().format("%Y%m%d-%H%M%S-%3f"))
+}
+
+pub fn output_path(prefix: &str, extension: &str) -> io::Result<PathBuf> {
+    let dir = PathBuf::from(CAPTURE_DIR);
+    fs::create_dir_all(&dir)?;
+    Ok(dir.join(format!("{}.{extension}", stamped(prefix))))
+}
+
+/// The file a save dialog chose, with its folder created, or a new
+/// timestamped file in captures/ when none was.
+pub fn chosen_or_output_path(
+    chosen: Option<&Path>,
+    prefix: &str,
+    extension: &str,
+) -> io::Result<PathBuf> {
+    let Some(path) = chosen else {
+        return output_path(prefix, extension);
+    };
+    if let Some(parent) = path.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+        fs::create_dir_all(parent)?;
+    }
+    Ok(path.to_path_buf())
+}
+
+pub fn output_dir(prefix: &str) -> io::Result<PathBuf> {
+    let dir = PathBuf::from(CAPTURE_DIR).join(stamped(prefix));
+    fs::create_dir_all(&dir)?;
+    Ok(dir)
+}
+
+pub fn start_fixed_clock(commands: &mut Commands, fps: u32) {
+    let frame_time = Duration::from_secs_f64(1.0 / fps.max(1) as f64);
+    commands.insert_resource(TimeUpdateStrategy::ManualDuration(frame_time));
+}
+
+pub fn stop_fixed_clock(commands: &mut Commands) {
+    commands.insert_resource(TimeUpdateStrategy::Automatic);
+}
\ No newline at end of file
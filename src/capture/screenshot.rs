@@ -0,0 +1,52 @@
+use bevy::prelude::*;
+use bevy::render::view::screenshot::ScreenshotManager;
+use bevy::window::PrimaryWindow;
+
+use crate::config::{CaptureSettings, ScreenshotEvent, UiVisibility};
+use crate::i18n::{tr, trf};
+use crate::ui::toast::Toasts;
+use super::chosen_or_output_path;
+
+#[allow(clippy::too_many_arguments)]
+pub fn screenshot_system(
+    keys: Res<ButtonInput<KeyCode>>,
+    settings: Res<CaptureSettings>,
+    mut events: EventReader<ScreenshotEvent>,
+    mut manager: ResMut<ScreenshotManager>,
+    mut ui_visibility: ResMut<UiVisibility>,
+    mut toasts: ResMut<Toasts>,
+    windows: Query<Entity, With<PrimaryWindow>>,
+    mut hidden_for_capture: Local<bool>,
+) {
+    if *hidden_for_capture {
+        ui_visibility.hidden = false;
+        *hidden_for_capture = false;
+    }
+
+    let chosen = events.read().last().map(|event| event.path.clone());
+    if chosen.is_none() && !keys.just_pressed(KeyCode::F12) {
+        return;
+    }
+    let Ok(window) = windows.get_single() else {
+        return;
+    };
+
+    let path = match chosen_or_output_path(chosen.flatten().as_deref(), "screenshot", "png") {
+        Ok(path) => path,
+        Err(err) => {
+            toasts.push(trf("Screenshot failed: {err}", &[("err", &err)]));
+            return;
+        }
+    };
+
+    if manager.save_screenshot_to_disk(window, &path).is_err() {
+        toasts.push(tr("Screenshot already in progress"));
+        return;
+    }
+
+    if !settings.include_ui && !ui_visibility.hidden {
+        ui_visibility.hidden = true;
+        *hidden_for_capture = true;
+    }
+    toasts.push(trf("📸 Saved {path}", &[("path", &path.display())]));
+}
\ No newline at end of file
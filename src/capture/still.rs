@@ -0,0 +1,456 @@
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+use bevy::prelude::*;
+use bevy::render::camera::RenderTarget;
+use bevy::render::render_asset::{RenderAssetUsages, RenderAssets};
+use bevy::render::render_graph::{
+    self, NodeRunError, RenderGraph, RenderGraphContext, RenderLabel,
+};
+use bevy::render::render_resource::{
+    Buffer, BufferDescriptor, BufferUsages, Extent3d, ImageCopyBuffer, ImageDataLayout, Maintain,
+    MapMode, PipelineCache, TextureDimension, TextureFormat, TextureUsages,
+};
+use bevy::render::renderer::{RenderContext, RenderDevice};
+use bevy::render::texture::GpuImage;
+use bevy::render::view::RenderLayers;
+use bevy::render::{Extract, Render, RenderApp, RenderSet};
+use bevy::window::PrimaryWindow;
+
+use crate::config::{CaptureSettings, RenderStillEvent, UiVisibility};
+use crate::i18n::{tr, trf};
+use crate::rendering::camera_controller::OrbitCamera;
+use crate::rendering::trail_renderer::{
+    draw_axes, draw_extra_trajectory, draw_head_marker, draw_trail,
+};
+use crate::rendering::view_scale::ViewScale;
+use crate::simulation::integrator::TrailBuffer;
+use crate::simulation::trajectories::{ExtraTrajectory, Trajectories};
+use crate::ui::toast::Toasts;
+use super::chosen_or_output_path;
+use super::video::EncoderHandle;
+
+pub const STILL_PRESETS: [(&str, u32, u32); 4] = [
+    ("4K UHD", 3840, 2160),
+    ("5K", 5120, 2880),
+    ("8K UHD", 7680, 4320),
+    ("A3 @ 300 dpi", 4961, 3508),
+];
+pub const MAX_STILL_DIMENSION: u32 = 8192;
+const PRE_ROLL_FRAMES: u32 = 3;
+/// Offscreen still cameras see only this layer, where the scene is drawn
+/// again with lines widened for the larger image; the window keeps its own.
+pub const STILL_LAYER: usize = 4;
+
+#[derive(Default, Reflect, GizmoConfigGroup)]
+pub struct StillGizmos;
+
+#[derive(Component, Clone)]
+struct StillCopier {
+    source: Handle<Image>,
+    buffer: Buffer,
+    size: UVec2,
+    armed: Arc<AtomicBool>,
+    copied: Arc<AtomicBool>,
+}
+
+#[derive(Resource, Default)]
+struct StillCopiers(Vec<StillCopier>);
+
+#[derive(Resource)]
+struct StillSender(Sender<Vec<u8>>);
+
+#[derive(Resource)]
+pub struct StillReceiver(Mutex<Receiver<Vec<u8>>>);
+
+impl StillReceiver {
+    pub fn try_recv(&self) -> Option<Vec<u8>> {
+        self.0.lock().ok()?.try_recv().ok()
+    }
+}
+
+pub struct StillTarget {
+    pub camera: Entity,
+    pub size: UVec2,
+    frames: u32,
+    armed: Arc<AtomicBool>,
+}
+
+impl StillTarget {
+    pub fn tick(&mut self) {
+        self.frames += 1;
+        if self.frames == PRE_ROLL_FRAMES {
+            self.armed.store(true, Ordering::Release);
+        }
+    }
+}
+
+struct PendingStill {
+    target: StillTarget,
+    path: PathBuf,
+}
+
+#[derive(Resource, Default)]
+pub struct StillRenderer {
+    pending: Option<PendingStill>,
+    saving: Vec<(PathBuf, EncoderHandle)>,
+}
+
+impl StillRenderer {
+    pub fn is_busy(&self) -> bool {
+        self.pending.is_some() || !self.saving.is_empty()
+    }
+}
+
+#[derive(Debug, Hash, PartialEq, Eq, Clone, RenderLabel)]
+struct StillCopyLabel;
+
+#[derive(Default)]
+struct StillCopyNode;
+
+impl render_graph::Node for StillCopyNode {
+    fn run(
+        &self,
+        _graph: &mut RenderGraphContext,
+        render_context: &mut RenderContext,
+        world: &World,
+    ) -> Result<(), NodeRunError> {
+        if world
+            .resource::<PipelineCache>()
+            .waiting_pipelines()
+            .next()
+            .is_some()
+        {
+            return Ok(());
+        }
+
+        let copiers = world.resource::<StillCopiers>();
+        let gpu_images = world.resource::<RenderAssets<GpuImage>>();
+
+        for copier in &copiers.0 {
+            if !copier.armed.load(Ordering::Acquire) {
+                continue;
+            }
+            let Some(image) = gpu_images.get(&copier.source) else {
+                continue;
+            };
+
+            let bytes_per_row = RenderDevice::align_copy_bytes_per_row(copier.size.x as usize * 4);
+            render_context.command_encoder().copy_texture_to_buffer(
+                image.texture.as_image_copy(),
+                ImageCopyBuffer {
+                    buffer: &copier.buffer,
+                    layout: ImageDataLayout {
+                        offset: 0,
+                        bytes_per_row: Some(bytes_per_row as u32),
+                        rows_per_image: None,
+                    },
+                },
+                Extent3d {
+                    width: copier.size.x,
+                    height: copier.size.y,
+                    depth_or_array_layers: 1,
+                },
+            );
+            copier.armed.store(false, Ordering::Release);
+            copier.copied.store(true, Ordering::Release);
+        }
+
+        Ok(())
+    }
+}
+
+pub struct StillRenderPlugin;
+
+impl Plugin for StillRenderPlugin {
+    fn build(&self, app: &mut App) {
+        let (sender, receiver) = mpsc::channel();
+        app.init_resource::<StillRenderer>()
+            .insert_resource(StillReceiver(Mutex::new(receiver)))
+            .insert_gizmo_config(
+                StillGizmos,
+                GizmoConfig {
+                    render_layers: RenderLayers::layer(STILL_LAYER),
+                    ..default()
+                },
+            );
+
+        let render_app = app.sub_app_mut(RenderApp);
+        render_app
+            .init_resource::<StillCopiers>()
+            .insert_resource(StillSender(sender))
+            .add_systems(ExtractSchedule, extract_still_copiers)
+            .add_systems(Render, read_still_buffers.after(RenderSet::Render));
+
+        let mut graph = render_app.world_mut().resource_mut::<RenderGraph>();
+        graph.add_node(StillCopyLabel, StillCopyNode);
+        graph.add_node_edge(bevy::render::graph::CameraDriverLabel, StillCopyLabel);
+    }
+}
+
+fn extract_still_copiers(mut copiers: ResMut<StillCopiers>, query: Extract<Query<&StillCopier>>) {
+    copiers.0 = query.iter().cloned().collect();
+}
+
+fn read_still_buffers(
+    copiers: Res<StillCopiers>,
+    render_device: Res<RenderDevice>,
+    sender: Res<StillSender>,
+) {
+    for copier in &copiers.0 {
+        if !copier.copied.swap(false, Ordering::AcqRel) {
+            continue;
+        }
+
+        let slice = copier.buffer.slice(..);
+        let (tx, rx) = mpsc::channel();
+        slice.map_async(MapMode::Read, move |result| {
+            let _ = tx.send(result);
+        });
+        render_device.poll(Maintain::wait()).panic_on_timeout();
+
+        if let Ok(Ok(())) = rx.recv() {
+            let _ = sender.0.send(slice.get_mapped_range().to_vec());
+        }
+        copier.buffer.unmap();
+    }
+}
+
+pub fn unpad_rows(data: &[u8], size: UVec2) -> Vec<u8> {
+    let row = size.x as usize * 4;
+    let padded = RenderDevice::align_copy_bytes_per_row(row);
+    data.chunks(padded)
+        .take(size.y as usize)
+        .flat_map(|chunk| &chunk[..row])
+        .copied()
+        .collect()
+}
+
+pub fn write_png(path: &Path, size: UVec2, pixels: Vec<u8>) -> Result<(), String> {
+    let image = Image::new(
+        Extent3d {
+            width: size.x,
+            height: size.y,
+            depth_or_array_layers: 1,
+        },
+        TextureDimension::D2,
+        pixels,
+        TextureFormat::Rgba8UnormSrgb,
+        RenderAssetUsages::MAIN_WORLD,
+    );
+    let dynamic = image.try_into_dynamic().map_err(|err| err.to_string())?;
+    dynamic
+        .to_rgba8()
+        .save(path)
+        .map_err(|err| err.to_string())
+}
+
+pub fn spawn_png_writer(path: PathBuf, size: UVec2, pixels: Vec<u8>) -> EncoderHandle {
+    thread::spawn(move || write_png(&path, size, pixels))
+}
+
+pub fn spawn_still_camera(
+    commands: &mut Commands,
+    images: &mut Assets<Image>,
+    render_device: &RenderDevice,
+    transform: Transform,
+    projection: Projection,
+    size: UVec2,
+) -> StillTarget {
+    let extent = Extent3d {
+        width: size.x,
+        height: size.y,
+        depth_or_array_layers: 1,
+    };
+    let mut image = Image::new_fill(
+        extent,
+        TextureDimension::D2,
+        &[0; 4],
+        TextureFormat::Rgba8UnormSrgb,
+        RenderAssetUsages::default(),
+    );
+    image.texture_descriptor.usage =
+        TextureUsages::COPY_SRC | TextureUsages::RENDER_ATTACHMENT | TextureUsages::TEXTURE_BINDING;
+    let source = images.add(image);
+
+    let buffer = render_device.create_buffer(&BufferDescriptor {
+        label: Some("still_readback_buffer"),
+        size: (RenderDevice::align_copy_bytes_per_row(size.x as usize * 4) * size.y as usize)
+            as u64,
+        usage: BufferUsages::MAP_READ | BufferUsages::COPY_DST,
+        mapped_at_creation: false,
+    });
+
+    let armed = Arc::new(AtomicBool::new(false));
+    let camera = commands
+        .spawn((
+            Camera3dBundle {
+                camera: Camera {
+                    order: -10,
+                    target: RenderTarget::Image(source.clone()),
+                    ..default()
+                },
+                transform,
+                projection,
+                ..default()
+            },
+            RenderLayers::layer(STILL_LAYER),
+            StillCopier {
+                source,
+                buffer,
+                size,
+                armed: armed.clone(),
+                copied: Arc::new(AtomicBool::new(false)),
+            },
+        ))
+        .id();
+
+    StillTarget {
+        camera,
+        size,
+        frames: 0,
+        armed,
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn still_render_system(
+    mut commands: Commands,
+    keys: Res<ButtonInput<KeyCode>>,
+    settings: Res<CaptureSettings>,
+    mut events: EventReader<RenderStillEvent>,
+    mut still: ResMut<StillRenderer>,
+    receiver: Res<StillReceiver>,
+    render_device: Res<RenderDevice>,
+    mut images: ResMut<Assets<Image>>,
+    mut gizmo_store: ResMut<GizmoConfigStore>,
+    mut toasts: ResMut<Toasts>,
+    camera_query: Query<(&Transform, &Projection), With<OrbitCamera>>,
+    windows: Query<&Window, With<PrimaryWindow>>,
+) {
+    let finished: Vec<_> = still
+        .saving
+        .iter()
+        .enumerate()
+        .filter(|(_, (_, handle))| handle.is_finished())
+        .map(|(i, _)| i)
+        .rev()
+        .collect();
+    for index in finished {
+        let (path, handle) = still.saving.swap_remove(index);
+        match handle.join() {
+            Ok(Ok(())) => toasts.push(trf("🖼 Saved {path}", &[("path", &path.display())])),
+            Ok(Err(err)) => toasts.push(trf("Still render failed: {err}", &[("err", &err)])),
+            Err(_) => toasts.push(tr("Still render failed: writer thread panicked")),
+        }
+    }
+
+    if let Some(pending) = still.pending.as_mut() {
+        pending.target.tick();
+
+        let Some(data) = receiver.try_recv() else {
+            return;
+        };
+        let pending = still.pending.take().unwrap();
+        let size = pending.target.size;
+        commands.entity(pending.target.camera).despawn();
+
+        let pixels = unpad_rows(&data, size);
+        let handle = spawn_png_writer(pending.path.clone(), size, pixels);
+        still.saving.push((pending.path, handle));
+        return;
+    }
+
+    let chosen = events.read().last().map(|event| event.path.clone());
+    if chosen.is_none() && !keys.just_pressed(KeyCode::F7) {
+        return;
+    }
+    if still.is_busy() {
+        toasts.push(tr("Still render already in progress"));
+        return;
+    }
+    let Ok((transform, projection)) = camera_query.get_single() else {
+        return;
+    };
+
+    let path = match chosen_or_output_path(chosen.flatten().as_deref(), "still", "png") {
+        Ok(path) => path,
+        Err(err) => {
+            toasts.push(trf("Still render failed: {err}", &[("err", &err)]));
+            return;
+        }
+    };
+
+    let size = UVec2::new(settings.still_width, settings.still_height)
+        .clamp(UVec2::splat(16), UVec2::splat(MAX_STILL_DIMENSION));
+    let target = spawn_still_camera(
+        &mut commands,
+        &mut images,
+        &render_device,
+        *transform,
+        projection.clone(),
+        size,
+    );
+
+    let line_width = gizmo_store.config::<DefaultGizmoConfigGroup>().0.line_width;
+    let window_height = windows
+        .get_single()
+        .map(|window| window.physical_height())
+        .unwrap_or(size.y)
+        .max(1);
+    gizmo_store.config_mut::<StillGizmos>().0.line_width =
+        line_width * (size.y as f32 / window_height as f32).max(1.0);
+
+    toasts.push(trf(
+        "Rendering {width} × {height} still…",
+        &[("width", &size.x), ("height", &size.y)],
+    ));
+    still.pending = Some(PendingStill { target, path });
+}
+
+/// Draws what the window shows onto the still's layer while one renders.
+pub fn draw_still_system(
+    mut gizmos: Gizmos<StillGizmos>,
+    still: Res<StillRenderer>,
+    trail: Res<TrailBuffer>,
+    trajectories: Res<Trajectories>,
+    extras: Query<(&ExtraTrajectory, &TrailBuffer)>,
+    visibility: Res<UiVisibility>,
+    view: Res<ViewScale>,
+) {
+    if still.pending.is_none() {
+        return;
+    }
+    if trajectories.main_visible {
+        draw_trail(&mut gizmos, &trail, &view);
+        draw_head_marker(&mut gizmos, &trail, &view);
+    }
+    for (extra, trail) in &extras {
+        draw_extra_trajectory(&mut gizmos, extra, trail, &view);
+    }
+    if !visibility.presentation {
+        draw_axes(&mut gizmos, &view);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unpad_rows_strips_alignment_padding() {
+        let size = UVec2::new(3, 2);
+        let padded = RenderDevice::align_copy_bytes_per_row(12);
+        let mut data = vec![0xEE; padded * 2];
+        data[..12].copy_from_slice(&[1; 12]);
+        data[padded..padded + 12].copy_from_slice(&[2; 12]);
+
+        let pixels = unpad_rows(&data, size);
+        assert_eq!(pixels.len(), 24);
+        assert!(pixels[..12].iter().all(|&b| b == 1));
+        assert!(pixels[12..].iter().all(|&b| b == 2));
+    }
+}
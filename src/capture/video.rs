@@ -0,0 +1,355 @@
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::Arc;
+use std::thread::{self, JoinHandle};
+
+use bevy::prelude::*;
+use bevy::render::view::screenshot::ScreenshotManager;
+use bevy::window::PrimaryWindow;
+
+use crate::config::{CaptureSettings, RecordGifEvent, RecordVideoEvent, UiVisibility, VideoFormat};
+use crate::i18n::{tr, trf};
+use crate::ui::toast::Toasts;
+use super::frame_sequence::FrameRecorder;
+use super::gif::spawn_gif_encoder;
+use super::{output_path, start_fixed_clock, stop_fixed_clock};
+
+pub type EncoderHandle = JoinHandle<Result<(), String>>;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ClipKind {
+    Video,
+    Gif,
+}
+
+/// Screenshots the callbacks passed on to the encoder, and those they dropped
+/// because the window no longer had the size the encoder was started with.
+#[derive(Default)]
+struct FrameCounts {
+    delivered: AtomicU32,
+    dropped: AtomicU32,
+}
+
+struct VideoSession {
+    kind: ClipKind,
+    path: PathBuf,
+    frames: Sender<Vec<u8>>,
+    size: UVec2,
+    /// Screenshots requested, including any still in flight.
+    sent: u32,
+    counts: Arc<FrameCounts>,
+    total: u32,
+    encoder: EncoderHandle,
+}
+
+impl VideoSession {
+    fn delivered(&self) -> u32 {
+        self.counts.delivered.load(Ordering::Relaxed)
+    }
+
+    fn dropped(&self) -> u32 {
+        self.counts.dropped.load(Ordering::Relaxed)
+    }
+}
+
+#[derive(Resource, Default)]
+pub struct VideoRecorder {
+    active: Option<VideoSession>,
+    finishing: Vec<(PathBuf, EncoderHandle)>,
+}
+
+impl VideoRecorder {
+    pub fn is_recording(&self) -> bool {
+        self.active.is_some()
+    }
+
+    pub fn progress(&self) -> Option<(ClipKind, u32, u32)> {
+        self.active.as_ref().map(|s| (s.kind, s.delivered(), s.total))
+    }
+
+    pub fn is_encoding(&self) -> bool {
+        !self.finishing.is_empty()
+    }
+}
+
+pub fn ffmpeg_args(size: UVec2, settings: &CaptureSettings, path: &Path) -> Vec<String> {
+    let scale = match settings.video_resolution.size() {
+        Some((w, h)) => format!(
+            "scale={w}:{h}:force_original_aspect_ratio=decrease,pad={w}:{h}:(ow-iw)/2:(oh-ih)/2"
+        ),
+        None => "scale=trunc(iw/2)*2:trunc(ih/2)*2".to_string(),
+    };
+    let codec = match settings.video_format {
+        VideoFormat::Mp4 => "libx264",
+        VideoFormat::WebM => "libvpx-vp9",
+    };
+
+    let mut args: Vec<String> = vec![
+        "-y".into(),
+        "-loglevel".into(),
+        "error".into(),
+        "-nostats".into(),
+        "-f".into(),
+        "rawvideo".into(),
+        "-pix_fmt".into(),
+        "rgba".into(),
+        "-s".into(),
+        format!("{}x{}", size.x, size.y),
+        "-r".into(),
+        settings.fps.max(1).to_string(),
+        "-i".into(),
+        "-".into(),
+        "-vf".into(),
+        scale,
+        "-c:v".into(),
+        codec.into(),
+        "-b:v".into(),
+        format!("{:.0}k", settings.video_bitrate_mbps * 1000.0),
+        "-pix_fmt".into(),
+        "yuv420p".into(),
+    ];
+    if settings.video_format == VideoFormat::Mp4 {
+        args.extend(["-movflags".into(), "+faststart".into()]);
+    }
+    args.push(path.display().to_string());
+    args
+}
+
+fn spawn_encoder(args: Vec<String>, frames: Receiver<Vec<u8>>) -> std::io::Result<EncoderHandle> {
+    let mut child = Command::new("ffmpeg")
+        .args(&args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .spawn()?;
+    let mut stdin = child.stdin.take().expect("ffmpeg stdin is piped");
+
+    Ok(thread::spawn(move || {
+        for frame in frames {
+            if stdin.write_all(&frame).is_err() {
+                break;
+            }
+        }
+        drop(stdin);
+
+        let output = child.wait_with_output().map_err(|err| err.to_string())?;
+        if output.status.success() {
+            Ok(())
+        } else {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            Err(stderr.lines().last().unwrap_or("ffmpeg failed").to_string())
+        }
+    }))
+}
+
+fn stop_recording(
+    commands: &mut Commands,
+    recorder: &mut VideoRecorder,
+    ui_visibility: &mut UiVisibility,
+    window: &mut Window,
+    toasts: &mut Toasts,
+) {
+    if let Some(session) = recorder.active.take() {
+        stop_fixed_clock(commands);
+        ui_visibility.hidden = false;
+        window.resizable = true;
+        toasts.push(trf("🎞 Encoding {sent} frames…", &[("sent", &session.delivered())]));
+        let dropped = session.dropped();
+        if dropped > 0 {
+            toasts.push(trf(
+                "⚠ Dropped {dropped} frames taken at a different window size",
+                &[("dropped", &dropped)],
+            ));
+        }
+        recorder.finishing.push((session.path, session.encoder));
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn video_recording_system(
+    mut commands: Commands,
+    keys: Res<ButtonInput<KeyCode>>,
+    settings: Res<CaptureSettings>,
+    mut video_events: EventReader<RecordVideoEvent>,
+    mut gif_events: EventReader<RecordGifEvent>,
+    mut recorder: ResMut<VideoRecorder>,
+    frame_recorder: Res<FrameRecorder>,
+    mut manager: ResMut<ScreenshotManager>,
+    mut ui_visibility: ResMut<UiVisibility>,
+    mut toasts: ResMut<Toasts>,
+    mut windows: Query<(Entity, &mut Window), With<PrimaryWindow>>,
+) {
+    let (done, pending) = std::mem::take(&mut recorder.finishing)
+        .into_iter()
+        .partition::<Vec<_>, _>(|(_, encoder)| encoder.is_finished());
+    recorder.finishing = pending;
+    for (path, encoder) in done {
+        match encoder.join() {
+            Ok(Ok(())) => toasts.push(trf("🎞 Saved {path}", &[("path", &path.display())])),
+            Ok(Err(err)) => toasts.push(trf("Video encoding failed: {err}", &[("err", &err)])),
+            Err(_) => toasts.push(tr("Video encoder thread panicked")),
+        }
+    }
+
+    let requested = if !video_events.is_empty() || keys.just_pressed(KeyCode::F9) {
+        Some(ClipKind::Video)
+    } else if !gif_events.is_empty() || keys.just_pressed(KeyCode::F8) {
+        Some(ClipKind::Gif)
+    } else {
+        None
+    };
+    video_events.clear();
+    gif_events.clear();
+    let Ok((window_entity, mut window)) = windows.get_single_mut() else {
+        return;
+    };
+
+    if let Some(kind) = requested {
+        if recorder.active.is_some() {
+            stop_recording(
+                &mut commands,
+                &mut recorder,
+                &mut ui_visibility,
+                &mut window,
+                &mut toasts,
+            );
+            return;
+        }
+        if frame_recorder.active.is_some() {
+            toasts.push(tr("Stop the frame-sequence recording first"));
+            return;
+        }
+
+        let (prefix, extension, fps, seconds) = match kind {
+            ClipKind::Video => (
+                "video",
+                settings.video_format.extension(),
+                settings.fps,
+                settings.video_seconds,
+            ),
+            ClipKind::Gif => ("clip", "gif", settings.gif_fps, settings.gif_seconds),
+        };
+        let path = match output_path(prefix, extension) {
+            Ok(path) => path,
+            Err(err) => {
+                toasts.push(trf("Recording failed: {err}", &[("err", &err)]));
+                return;
+            }
+        };
+        let size = window.physical_size();
+        let (sender, receiver) = mpsc::channel();
+        let encoder = match kind {
+            ClipKind::Video => spawn_encoder(ffmpeg_args(size, &settings, &path), receiver),
+            ClipKind::Gif => spawn_gif_encoder(&path, size, &settings, receiver),
+        };
+        match encoder {
+            Ok(encoder) => {
+                start_fixed_clock(&mut commands, fps);
+                window.resizable = false;
+                let total = (seconds * fps as f32).round().max(1.0) as u32;
+                toasts.push(trf(
+                    "⏺ Recording {total} frames to {path}",
+                    &[("total", &total), ("path", &path.display())],
+                ));
+                recorder.active = Some(VideoSession {
+                    kind,
+                    path,
+                    frames: sender,
+                    size,
+                    sent: 0,
+                    counts: Arc::default(),
+                    total,
+                    encoder,
+                });
+            }
+            Err(err) if kind == ClipKind::Video => {
+                toasts.push(trf(
+                    "Could not start ffmpeg (is it on PATH?): {err}",
+                    &[("err", &err)],
+                ));
+                return;
+            }
+            Err(err) => {
+                toasts.push(trf(
+                    "Could not create {path}: {err}",
+                    &[("path", &path.display()), ("err", &err)],
+                ));
+                return;
+            }
+        }
+    }
+
+    let Some(session) = recorder.active.as_mut() else {
+        return;
+    };
+    // A window stuck at another size would otherwise record forever.
+    let (delivered, dropped) = (session.delivered(), session.dropped());
+    if delivered >= session.total || dropped >= session.total || session.encoder.is_finished() {
+        stop_recording(
+            &mut commands,
+            &mut recorder,
+            &mut ui_visibility,
+            &mut window,
+            &mut toasts,
+        );
+        return;
+    }
+
+    ui_visibility.hidden = !settings.include_ui;
+    // Screenshots in flight are expected to arrive; wait for them rather
+    // than overshoot the total.
+    if session.sent - dropped >= session.total {
+        return;
+    }
+    let frames = session.frames.clone();
+    let counts = session.counts.clone();
+    let size = session.size;
+    let requested = manager.take_screenshot(window_entity, move |image| {
+        let rgba = (image.width() == size.x && image.height() == size.y)
+            .then(|| image.try_into_dynamic().ok())
+            .flatten();
+        match rgba {
+            Some(dynamic) if frames.send(dynamic.to_rgba8().into_raw()).is_ok() => {
+                counts.delivered.fetch_add(1, Ordering::Relaxed);
+            }
+            _ => {
+                counts.dropped.fetch_add(1, Ordering::Relaxed);
+            }
+        }
+    });
+    if requested.is_ok() {
+        session.sent += 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::VideoResolution;
+
+    #[test]
+    fn test_ffmpeg_args_reflect_settings() {
+        let settings = CaptureSettings {
+            video_format: VideoFormat::WebM,
+            video_resolution: VideoResolution::Hd1080,
+            video_bitrate_mbps: 4.5,
+            fps: 30,
+            ..default()
+        };
+        let args = ffmpeg_args(UVec2::new(1400, 900), &settings, Path::new("out.webm"));
+
+        let after = |flag: &str| {
+            let i = args.iter().position(|a| a == flag).unwrap();
+            args[i + 1].as_str()
+        };
+        assert_eq!(after("-s"), "1400x900");
+        assert_eq!(after("-r"), "30");
+        assert_eq!(after("-c:v"), "libvpx-vp9");
+        assert_eq!(after("-b:v"), "4500k");
+        assert!(after("-vf").starts_with("scale=1920:1080"));
+        assert_eq!(args.last().unwrap(), "out.webm");
+    }
+}
\ No newline at end of file
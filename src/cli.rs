@@ -0,0 +1,148 @@
+use clap::Parser;
+
+use crate::config::{IntegrationMethod, SimulationConfig};
+
+pub const DEFAULT_WINDOW: (f32, f32) = (1400.0, 900.0);
+
+fn parse_method(text: &str) -> Result<IntegrationMethod, String> {
+    IntegrationMethod::from_short_name(text).ok_or_else(|| "expected rk4 or euler".into())
+}
+
+fn parse_positive(text: &str) -> Result<f64, String> {
+    match text.parse::<f64>() {
+        Ok(value) if value.is_finite() && value > 0.0 => Ok(value),
+        _ => Err("expected a positive number".into()),
+    }
+}
+
+fn parse_window(text: &str) -> Result<(f32, f32), String> {
+    let (w, h) = text.split_once(['x', 'X']).ok_or("expected WxH, e.g. 1920x1080")?;
+    match (w.parse::<u32>(), h.parse::<u32>()) {
+        (Ok(w), Ok(h)) if w >= 200 && h >= 200 => Ok((w as f32, h as f32)),
+        _ => Err("expected WxH with both sides at least 200".into()),
+    }
+}
+
+/// Options for the interactive app. `--headless-sim` and `--batch` are
+/// handled separately before these are parsed.
+#[derive(Parser, Debug)]
+#[command(
+    name = "lorenz-attractor",
+    version,
+    about = "Real-time 3D Lorenz attractor",
+    after_help = "Also: --headless-sim (CSV to stdout) and --batch (PNG renders); \
+                  see the README for their options."
+)]
+pub struct Cli {
+    /// Prandtl number σ
+    #[arg(long)]
+    pub sigma: Option<f64>,
+    /// Rayleigh number ρ
+    #[arg(long)]
+    pub rho: Option<f64>,
+    /// Geometric factor β
+    #[arg(long)]
+    pub beta: Option<f64>,
+    /// Initial x
+    #[arg(long, allow_hyphen_values = true)]
+    pub x0: Option<f64>,
+    /// Initial y
+    #[arg(long, allow_hyphen_values = true)]
+    pub y0: Option<f64>,
+    /// Initial z
+    #[arg(long, allow_hyphen_values = true)]
+    pub z0: Option<f64>,
+    /// Integration time step
+    #[arg(long, value_parser = parse_positive)]
+    pub dt: Option<f64>,
+    /// Integration method: rk4 or euler
+    #[arg(long, value_parser = parse_method)]
+    pub method: Option<IntegrationMethod>,
+    /// Integration steps per rendered frame
+    #[arg(long, value_parser = clap::value_parser!(u32).range(1..=1000))]
+    pub steps_per_frame: Option<u32>,
+    /// Maximum trail points kept on screen
+    #[arg(long, value_parser = clap::value_parser!(u64).range(2..=10_000_000))]
+    pub trail: Option<u64>,
+    /// Start with the simulation paused
+    #[arg(long)]
+    pub paused: bool,
+    /// Window size as WxH
+    #[arg(long, value_parser = parse_window, value_name = "WxH")]
+    pub window: Option<(f32, f32)>,
+    /// Check simulation and camera invariants every frame (always on in debug builds)
+    #[arg(long)]
+    pub check_invariants: bool,
+}
+
+impl Cli {
+    /// Overrides the saved settings with whatever was given on the command line.
+    pub fn apply(&self, config: &mut SimulationConfig) {
+        let overrides = [
+            (&mut config.sigma, self.sigma),
+            (&mut config.rho, self.rho),
+            (&mut config.beta, self.beta),
+            (&mut config.initial_x, self.x0),
+            (&mut config.initial_y, self.y0),
+            (&mut config.initial_z, self.z0),
+            (&mut config.dt, self.dt),
+        ];
+        for (field, value) in overrides {
+            if let Some(value) = value {
+                *field = value;
+            }
+        }
+        if let Some(method) = self.method {
+            config.method = method;
+        }
+        if let Some(steps) = self.steps_per_frame {
+            config.steps_per_frame = steps;
+        }
+        if let Some(trail) = self.trail {
+            config.max_trail_points = trail as usize;
+        }
+        if self.paused {
+            config.paused = true;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cli_overrides_config() {
+        let cli = Cli::try_parse_from([
+            "lorenz-attractor",
+            "--rho",
+            "99.96",
+            "--dt",
+            "0.001",
+            "--method",
+            "euler",
+            "--paused",
+            "--trail",
+            "100000",
+            "--x0",
+            "-2.5",
+            "--window",
+            "1920x1080",
+        ])
+        .unwrap();
+        let mut config = SimulationConfig::default();
+        cli.apply(&mut config);
+        assert_eq!(config.rho, 99.96);
+        assert_eq!(config.sigma, 10.0);
+        assert_eq!(config.dt, 0.001);
+        assert_eq!(config.method, IntegrationMethod::Euler);
+        assert!(config.paused);
+        assert_eq!(config.max_trail_points, 100_000);
+        assert_eq!(config.initial_x, -2.5);
+        assert_eq!(cli.window, Some((1920.0, 1080.0)));
+
+        assert!(Cli::try_parse_from(["lorenz-attractor", "--dt", "0"]).is_err());
+        assert!(Cli::try_parse_from(["lorenz-attractor", "--method", "rk2"]).is_err());
+        assert!(Cli::try_parse_from(["lorenz-attractor", "--window", "10x10"]).is_err());
+    }
+}
\ No newline at end of file
@@ -1,6 +1,13 @@
+use std::path::PathBuf;
+
 use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+use crate::i18n::{tr_noop, Language};
+use crate::simulation::lorenz::LorenzParams;
+use crate::simulation::statistics::{LobeStats, LyapunovEstimate, TrajectoryStats};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum IntegrationMethod {
     Euler,
     RungeKutta4,
@@ -9,13 +16,44 @@ pub enum IntegrationMethod {
 impl IntegrationMethod {
     pub fn label(&self) -> &'static str {
         match self {
-            Self::Euler => "Euler (1st order)",
-            Self::RungeKutta4 => "Runge-Kutta 4 (4th order)",
+            Self::Euler => tr_noop("Euler (1st order)"),
+            Self::RungeKutta4 => tr_noop("Runge-Kutta 4 (4th order)"),
+        }
+    }
+
+    pub fn short_name(&self) -> &'static str {
+        match self {
+            Self::Euler => "euler",
+            Self::RungeKutta4 => "rk4",
+        }
+    }
+
+    pub fn from_short_name(name: &str) -> Option<Self> {
+        match name.to_ascii_lowercase().as_str() {
+            "euler" => Some(Self::Euler),
+            "rk4" => Some(Self::RungeKutta4),
+            _ => None,
         }
     }
 }
 
-#[derive(Resource)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RotationMode {
+    Turntable,
+    Trackball,
+}
+
+impl RotationMode {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Turntable => tr_noop("Turntable (Y-up orbit)"),
+            Self::Trackball => tr_noop("Trackball (free rotation)"),
+        }
+    }
+}
+
+#[derive(Resource, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
 pub struct SimulationConfig {
     pub sigma: f64,
     pub rho: f64,
@@ -62,14 +100,1067 @@ impl Default for SimulationConfig {
     }
 }
 
+impl SimulationConfig {
+    pub fn params(&self) -> LorenzParams {
+        LorenzParams {
+            sigma: self.sigma,
+            rho: self.rho,
+            beta: self.beta,
+        }
+    }
+}
+
+#[derive(Resource)]
+pub struct ComparisonConfig {
+    pub enabled: bool,
+    pub editing_b: bool,
+
+    pub sigma: f64,
+    pub rho: f64,
+    pub beta: f64,
+
+    pub initial_x: f64,
+    pub initial_y: f64,
+    pub initial_z: f64,
+}
+
+impl Default for ComparisonConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            editing_b: false,
+
+            sigma: 10.0,
+            rho: 28.0,
+            beta: 8.0 / 3.0,
+
+            initial_x: 1.001,
+            initial_y: 1.0,
+            initial_z: 1.0,
+        }
+    }
+}
+
+impl ComparisonConfig {
+    pub fn params(&self) -> LorenzParams {
+        LorenzParams {
+            sigma: self.sigma,
+            rho: self.rho,
+            beta: self.beta,
+        }
+    }
+
+    pub fn copy_from(&mut self, config: &SimulationConfig) {
+        self.sigma = config.sigma;
+        self.rho = config.rho;
+        self.beta = config.beta;
+        self.initial_x = config.initial_x;
+        self.initial_y = config.initial_y;
+        self.initial_z = config.initial_z;
+    }
+}
+
+#[derive(Resource, Default)]
+pub struct OrthoViewsConfig {
+    pub enabled: bool,
+}
+
+#[derive(Resource)]
+pub struct TimeSeriesConfig {
+    /// Span of model time shown, ending at the trajectory head.
+    pub window: f64,
+    pub channels: [bool; 3],
+}
+
+impl Default for TimeSeriesConfig {
+    fn default() -> Self {
+        Self {
+            window: 20.0,
+            channels: [true; 3],
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PhasePair {
+    #[default]
+    XZ,
+    XY,
+    YZ,
+}
+
+impl PhasePair {
+    pub const ALL: [PhasePair; 3] = [Self::XZ, Self::XY, Self::YZ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::XZ => "x–z",
+            Self::XY => "x–y",
+            Self::YZ => "y–z",
+        }
+    }
+
+    /// Indices into `(x, y, z)` of the horizontal and vertical axes.
+    pub fn axes(&self) -> (usize, usize) {
+        match self {
+            Self::XZ => (0, 2),
+            Self::XY => (0, 1),
+            Self::YZ => (1, 2),
+        }
+    }
+}
+
+#[derive(Resource, Default)]
+pub struct PhasePortraitConfig {
+    pub pair: PhasePair,
+}
+
+#[derive(Resource)]
+pub struct HistogramConfig {
+    pub bins: usize,
+    /// Span of model time counted, ending at the trajectory head.
+    pub window: f64,
+}
+
+impl Default for HistogramConfig {
+    fn default() -> Self {
+        Self {
+            bins: 40,
+            window: 50.0,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SpectrumWindow {
+    #[default]
+    Hann,
+    Flat,
+}
+
+impl SpectrumWindow {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Hann => tr_noop("Hann"),
+            Self::Flat => tr_noop("Flat (rectangular)"),
+        }
+    }
+}
+
+pub const SPECTRUM_LENGTHS: [usize; 5] = [1024, 2048, 4096, 8192, 16384];
+
+#[derive(Resource)]
+pub struct SpectrumConfig {
+    /// Samples per transform; always one of `SPECTRUM_LENGTHS`.
+    pub length: usize,
+    pub window: SpectrumWindow,
+}
+
+impl Default for SpectrumConfig {
+    fn default() -> Self {
+        Self {
+            length: 4096,
+            window: SpectrumWindow::Hann,
+        }
+    }
+}
+
+#[derive(Resource)]
+pub struct EmbeddingConfig {
+    /// Longest delay on the mutual-information curve, in time units.
+    pub max_delay: f64,
+    pub bins: usize,
+    /// Reconstruct with the first minimum of the curve instead of `delay`.
+    pub auto_delay: bool,
+    /// Delay of the reconstruction, in time units.
+    pub delay: f64,
+}
+
+impl Default for EmbeddingConfig {
+    fn default() -> Self {
+        Self {
+            max_delay: 1.0,
+            bins: 32,
+            auto_delay: true,
+            delay: 0.16,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum EntropyMeasure {
+    #[default]
+    Sample,
+    Approximate,
+}
+
+impl EntropyMeasure {
+    pub const ALL: [EntropyMeasure; 2] = [Self::Sample, Self::Approximate];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Sample => tr_noop("Sample entropy"),
+            Self::Approximate => tr_noop("Approximate entropy"),
+        }
+    }
+}
+
+#[derive(Resource, Clone, PartialEq)]
+pub struct ComplexityConfig {
+    pub measure: EntropyMeasure,
+    /// Index into `(x, y, z)` of the coordinate measured.
+    pub coordinate: usize,
+    /// Samples in the sliding window.
+    pub window: usize,
+    /// Model time between samples; consecutive steps are too alike to compare.
+    pub spacing: f64,
+    /// Template length m.
+    pub template: usize,
+    /// Match tolerance r, as a fraction of the window's standard deviation.
+    pub tolerance: f64,
+}
+
+impl Default for ComplexityConfig {
+    fn default() -> Self {
+        Self {
+            measure: EntropyMeasure::Sample,
+            coordinate: 0,
+            window: 400,
+            spacing: 0.05,
+            template: 2,
+            tolerance: 0.2,
+        }
+    }
+}
+
+/// Grid sizes offered for the density heatmap, in cells per side.
+pub const DENSITY_RESOLUTIONS: [usize; 4] = [64, 128, 256, 512];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Colormap {
+    #[default]
+    Inferno,
+    Viridis,
+    Magma,
+    Grayscale,
+}
+
+impl Colormap {
+    pub const ALL: [Colormap; 4] = [Self::Inferno, Self::Viridis, Self::Magma, Self::Grayscale];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Inferno => "Inferno",
+            Self::Viridis => "Viridis",
+            Self::Magma => "Magma",
+            Self::Grayscale => tr_noop("Grayscale"),
+        }
+    }
+}
+
+#[derive(Resource)]
+pub struct DensityConfig {
+    /// Accumulate steps into the heatmap; off skips them for good.
+    pub enabled: bool,
+    pub plane: PhasePair,
+    /// Cells per side; always one of `DENSITY_RESOLUTIONS`.
+    pub resolution: usize,
+    pub log_scale: bool,
+    pub colormap: Colormap,
+}
+
+impl Default for DensityConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            plane: PhasePair::XZ,
+            resolution: 256,
+            log_scale: true,
+            colormap: Colormap::Inferno,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VideoFormat {
+    Mp4,
+    WebM,
+}
+
+impl VideoFormat {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Mp4 => "MP4 (H.264)",
+            Self::WebM => "WebM (VP9)",
+        }
+    }
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            Self::Mp4 => "mp4",
+            Self::WebM => "webm",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VideoResolution {
+    Window,
+    Hd720,
+    Hd1080,
+    Qhd1440,
+    Uhd2160,
+}
+
+impl VideoResolution {
+    pub const ALL: [VideoResolution; 5] = [
+        Self::Window,
+        Self::Hd720,
+        Self::Hd1080,
+        Self::Qhd1440,
+        Self::Uhd2160,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Window => tr_noop("Window size"),
+            Self::Hd720 => "1280 × 720",
+            Self::Hd1080 => "1920 × 1080",
+            Self::Qhd1440 => "2560 × 1440",
+            Self::Uhd2160 => "3840 × 2160",
+        }
+    }
+
+    pub fn size(&self) -> Option<(u32, u32)> {
+        match self {
+            Self::Window => None,
+            Self::Hd720 => Some((1280, 720)),
+            Self::Hd1080 => Some((1920, 1080)),
+            Self::Qhd1440 => Some((2560, 1440)),
+            Self::Uhd2160 => Some((3840, 2160)),
+        }
+    }
+}
+
+#[derive(Resource)]
+pub struct CaptureSettings {
+    pub include_ui: bool,
+    pub fps: u32,
+    pub max_frames: u32,
+
+    pub video_format: VideoFormat,
+    pub video_resolution: VideoResolution,
+    pub video_seconds: f32,
+    pub video_bitrate_mbps: f32,
+
+    pub gif_width: u32,
+    pub gif_fps: u32,
+    pub gif_seconds: f32,
+    pub gif_colors: usize,
+
+    pub still_width: u32,
+    pub still_height: u32,
+}
+
+impl Default for CaptureSettings {
+    fn default() -> Self {
+        Self {
+            include_ui: false,
+            fps: 60,
+            max_frames: 0,
+
+            video_format: VideoFormat::Mp4,
+            video_resolution: VideoResolution::Window,
+            video_seconds: 10.0,
+            video_bitrate_mbps: 8.0,
+
+            gif_width: 480,
+            gif_fps: 15,
+            gif_seconds: 5.0,
+            gif_colors: 128,
+
+            still_width: 3840,
+            still_height: 2160,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TrajectoryFormat {
+    Csv,
+    Json,
+    Ply,
+    Obj,
+    Glb,
+    Npy,
+    Npz,
+    #[cfg(feature = "hdf5")]
+    Hdf5,
+}
+
+impl TrajectoryFormat {
+    pub const ALL: &'static [TrajectoryFormat] = &[
+        Self::Csv,
+        Self::Json,
+        Self::Ply,
+        Self::Obj,
+        Self::Glb,
+        Self::Npy,
+        Self::Npz,
+        #[cfg(feature = "hdf5")]
+        Self::Hdf5,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Csv => "CSV",
+            Self::Json => "JSON",
+            Self::Ply => "PLY",
+            Self::Obj => "OBJ",
+            Self::Glb => "glTF",
+            Self::Npy => "NPY",
+            Self::Npz => "NPZ",
+            #[cfg(feature = "hdf5")]
+            Self::Hdf5 => "HDF5",
+        }
+    }
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            Self::Csv => "csv",
+            Self::Json => "json",
+            Self::Ply => "ply",
+            Self::Obj => "obj",
+            Self::Glb => "glb",
+            Self::Npy => "npy",
+            Self::Npz => "npz",
+            #[cfg(feature = "hdf5")]
+            Self::Hdf5 => "h5",
+        }
+    }
+}
+
+#[derive(Resource)]
+pub struct ExportSettings {
+    pub trajectory_format: TrajectoryFormat,
+    pub path: String,
+    pub tube_radius: f32,
+    pub tube_sides: u32,
+}
+
+impl Default for ExportSettings {
+    fn default() -> Self {
+        Self {
+            trajectory_format: TrajectoryFormat::Csv,
+            path: String::new(),
+            tube_radius: 0.15,
+            tube_sides: 8,
+        }
+    }
+}
+
+#[derive(Resource, Clone)]
+pub struct BifurcationSettings {
+    pub rho_min: f64,
+    pub rho_max: f64,
+    pub samples: u32,
+    pub transient_steps: u32,
+    pub record_steps: u32,
+    pub image_width: u32,
+    pub image_height: u32,
+}
+
+impl Default for BifurcationSettings {
+    fn default() -> Self {
+        Self {
+            rho_min: 1.0,
+            rho_max: 250.0,
+            samples: 500,
+            transient_steps: 4000,
+            record_steps: 8000,
+            image_width: 1600,
+            image_height: 1000,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LogFormat {
+    Csv,
+    Jsonl,
+}
+
+impl LogFormat {
+    pub const ALL: [LogFormat; 2] = [Self::Csv, Self::Jsonl];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Csv => "CSV",
+            Self::Jsonl => "JSONL",
+        }
+    }
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            Self::Csv => "csv",
+            Self::Jsonl => "jsonl",
+        }
+    }
+}
+
+#[derive(Resource)]
+pub struct LogSettings {
+    pub enabled: bool,
+    pub format: LogFormat,
+    pub every_n_steps: u32,
+    pub max_megabytes: u32,
+    pub path: String,
+}
+
+impl Default for LogSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            format: LogFormat::Csv,
+            every_n_steps: 10,
+            max_megabytes: 50,
+            path: String::new(),
+        }
+    }
+}
+
+#[derive(Resource)]
+pub struct RandomizeSettings {
+    pub sigma: (f64, f64),
+    pub rho: (f64, f64),
+    pub beta: (f64, f64),
+    pub initial_conditions: bool,
+    pub initial_extent: f64,
+}
+
+impl Default for RandomizeSettings {
+    fn default() -> Self {
+        Self {
+            sigma: (5.0, 20.0),
+            rho: (10.0, 200.0),
+            beta: (0.5, 4.0),
+            initial_conditions: false,
+            initial_extent: 20.0,
+        }
+    }
+}
+
+/// Slider spans for the parameter panels. Typed values outside a span still
+/// apply; with `auto_expand` the span grows to include them.
+#[derive(Resource, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SliderRanges {
+    pub sigma: (f64, f64),
+    pub rho: (f64, f64),
+    pub beta: (f64, f64),
+    pub dt: (f64, f64),
+    pub initial: (f64, f64),
+    pub auto_expand: bool,
+    pub snap_to_notable: bool,
+}
+
+impl Default for SliderRanges {
+    fn default() -> Self {
+        Self {
+            sigma: (0.1, 30.0),
+            rho: (0.1, 50.0),
+            beta: (0.1, 10.0),
+            dt: (0.0001, 0.05),
+            initial: (-20.0, 20.0),
+            auto_expand: true,
+            snap_to_notable: false,
+        }
+    }
+}
+
+#[derive(Resource)]
+pub struct SnapshotSettings {
+    pub path: String,
+    pub include_trail: bool,
+}
+
+impl Default for SnapshotSettings {
+    fn default() -> Self {
+        Self {
+            path: String::new(),
+            include_trail: true,
+        }
+    }
+}
+
 #[derive(Resource, Default)]
+pub struct UiVisibility {
+    pub hidden: bool,
+    /// Presentation mode: hides every panel, window, toast and the axes,
+    /// leaving only the attractor.
+    pub presentation: bool,
+}
+
+#[derive(Resource)]
+pub struct AnaglyphConfig {
+    pub enabled: bool,
+    pub eye_separation: f32,
+}
+
+impl Default for AnaglyphConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            eye_separation: 2.5,
+        }
+    }
+}
+
+#[derive(Resource)]
+pub struct GalleryConfig {
+    pub enabled: bool,
+    pub columns: usize,
+    pub rows: usize,
+    pub rho_min: f64,
+    pub rho_max: f64,
+    pub trail_points: usize,
+}
+
+impl Default for GalleryConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            columns: 4,
+            rows: 4,
+            rho_min: 0.5,
+            rho_max: 32.0,
+            trail_points: 1_500,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum UiTheme {
+    Dark,
+    Light,
+}
+
+impl UiTheme {
+    pub const ALL: [UiTheme; 2] = [Self::Dark, Self::Light];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Dark => tr_noop("Dark"),
+            Self::Light => tr_noop("Light"),
+        }
+    }
+}
+
+#[derive(Resource, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Appearance {
+    pub theme: UiTheme,
+    /// sRGB highlight used for selections, slider fills and links.
+    pub accent: [u8; 3],
+    pub ui_scale: f32,
+    pub panel_opacity: f32,
+    pub language: Language,
+}
+
+impl Default for Appearance {
+    fn default() -> Self {
+        Self {
+            theme: UiTheme::Dark,
+            accent: [0, 92, 128],
+            ui_scale: 1.0,
+            panel_opacity: 1.0,
+            language: Language::English,
+        }
+    }
+}
+
+#[derive(Resource, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CameraSettings {
+    pub rotation_mode: RotationMode,
+
+    pub rotate_sensitivity: f32,
+    pub pan_sensitivity: f32,
+    pub zoom_sensitivity: f32,
+    pub invert_scroll: bool,
+
+    pub follow_head: bool,
+    pub follow_smoothing: f32,
+    pub follow_distance: f32,
+
+    pub auto_orbit: bool,
+    pub auto_orbit_speed: f32,
+
+    pub fov_degrees: f32,
+
+    pub inertia: bool,
+    pub damping: f32,
+}
+
+impl Default for CameraSettings {
+    fn default() -> Self {
+        Self {
+            rotation_mode: RotationMode::Turntable,
+
+            rotate_sensitivity: 0.005,
+            pan_sensitivity: 0.08,
+            zoom_sensitivity: 2.5,
+            invert_scroll: false,
+
+            follow_head: false,
+            follow_smoothing: 0.35,
+            follow_distance: 45.0,
+
+            auto_orbit: false,
+            auto_orbit_speed: 12.0,
+
+            fov_degrees: 45.0,
+
+            inertia: false,
+            damping: 6.0,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PadButton {
+    South,
+    East,
+    West,
+    North,
+    Start,
+    Select,
+}
+
+impl PadButton {
+    pub const ALL: [PadButton; 6] = [
+        Self::South,
+        Self::East,
+        Self::West,
+        Self::North,
+        Self::Start,
+        Self::Select,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::South => tr_noop("South (A / ✕)"),
+            Self::East => tr_noop("East (B / ○)"),
+            Self::West => tr_noop("West (X / □)"),
+            Self::North => tr_noop("North (Y / △)"),
+            Self::Start => "Start",
+            Self::Select => "Select",
+        }
+    }
+
+    pub fn button_type(&self) -> GamepadButtonType {
+        match self {
+            Self::South => GamepadButtonType::South,
+            Self::East => GamepadButtonType::East,
+            Self::West => GamepadButtonType::West,
+            Self::North => GamepadButtonType::North,
+            Self::Start => GamepadButtonType::Start,
+            Self::Select => GamepadButtonType::Select,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum NudgeParameter {
+    Sigma,
+    Rho,
+    Beta,
+}
+
+impl NudgeParameter {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Sigma => "σ",
+            Self::Rho => "ρ",
+            Self::Beta => "β",
+        }
+    }
+
+    pub fn next(&self) -> Self {
+        match self {
+            Self::Sigma => Self::Rho,
+            Self::Rho => Self::Beta,
+            Self::Beta => Self::Sigma,
+        }
+    }
+
+    pub fn previous(&self) -> Self {
+        match self {
+            Self::Sigma => Self::Beta,
+            Self::Rho => Self::Sigma,
+            Self::Beta => Self::Rho,
+        }
+    }
+}
+
+#[derive(Resource, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct GamepadSettings {
+    pub enabled: bool,
+    pub swap_sticks: bool,
+    pub invert_y: bool,
+    pub stick_speed: f32,
+    pub deadzone: f32,
+    pub pause_button: PadButton,
+    pub reset_button: PadButton,
+    pub nudge_target: NudgeParameter,
+}
+
+impl Default for GamepadSettings {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            swap_sticks: false,
+            invert_y: false,
+            stick_speed: 600.0,
+            deadzone: 0.12,
+            pause_button: PadButton::South,
+            reset_button: PadButton::East,
+            nudge_target: NudgeParameter::Rho,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ShortcutAction {
+    TogglePause,
+    Reset,
+    SingleStep,
+    FitView,
+    ToggleUi,
+    UseEuler,
+    UseRungeKutta4,
+    ToggleClickThrough,
+}
+
+impl ShortcutAction {
+    pub const ALL: [ShortcutAction; 8] = [
+        Self::TogglePause,
+        Self::Reset,
+        Self::SingleStep,
+        Self::FitView,
+        Self::ToggleUi,
+        Self::UseEuler,
+        Self::UseRungeKutta4,
+        Self::ToggleClickThrough,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::TogglePause => tr_noop("Pause / resume"),
+            Self::Reset => tr_noop("Reset"),
+            Self::SingleStep => tr_noop("Single step (paused)"),
+            Self::FitView => tr_noop("Zoom to fit"),
+            Self::ToggleUi => tr_noop("Presentation mode (hide UI)"),
+            Self::UseEuler => tr_noop("Euler integrator"),
+            Self::UseRungeKutta4 => tr_noop("RK4 integrator"),
+            Self::ToggleClickThrough => tr_noop("Overlay click-through"),
+        }
+    }
+}
+
+#[derive(Resource, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct KeyBindings {
+    pub toggle_pause: KeyCode,
+    pub reset: KeyCode,
+    pub single_step: KeyCode,
+    pub fit_view: KeyCode,
+    pub toggle_ui: KeyCode,
+    pub use_euler: KeyCode,
+    pub use_runge_kutta4: KeyCode,
+    pub toggle_click_through: KeyCode,
+}
+
+impl Default for KeyBindings {
+    fn default() -> Self {
+        Self {
+            toggle_pause: KeyCode::Space,
+            reset: KeyCode::KeyR,
+            // S already pans the camera.
+            single_step: KeyCode::Period,
+            fit_view: KeyCode::KeyF,
+            toggle_ui: KeyCode::KeyH,
+            use_euler: KeyCode::Digit1,
+            use_runge_kutta4: KeyCode::Digit2,
+            toggle_click_through: KeyCode::KeyT,
+        }
+    }
+}
+
+impl KeyBindings {
+    pub fn key(&self, action: ShortcutAction) -> KeyCode {
+        match action {
+            ShortcutAction::TogglePause => self.toggle_pause,
+            ShortcutAction::Reset => self.reset,
+            ShortcutAction::SingleStep => self.single_step,
+            ShortcutAction::FitView => self.fit_view,
+            ShortcutAction::ToggleUi => self.toggle_ui,
+            ShortcutAction::UseEuler => self.use_euler,
+            ShortcutAction::UseRungeKutta4 => self.use_runge_kutta4,
+            ShortcutAction::ToggleClickThrough => self.toggle_click_through,
+        }
+    }
+
+    fn key_mut(&mut self, action: ShortcutAction) -> &mut KeyCode {
+        match action {
+            ShortcutAction::TogglePause => &mut self.toggle_pause,
+            ShortcutAction::Reset => &mut self.reset,
+            ShortcutAction::SingleStep => &mut self.single_step,
+            ShortcutAction::FitView => &mut self.fit_view,
+            ShortcutAction::ToggleUi => &mut self.toggle_ui,
+            ShortcutAction::UseEuler => &mut self.use_euler,
+            ShortcutAction::UseRungeKutta4 => &mut self.use_runge_kutta4,
+            ShortcutAction::ToggleClickThrough => &mut self.toggle_click_through,
+        }
+    }
+
+    /// Binds `key` to `action`; an action that already used `key` takes over
+    /// the old key, so no key ever triggers two actions.
+    pub fn assign(&mut self, action: ShortcutAction, key: KeyCode) {
+        let previous = self.key(action);
+        if let Some(other) = ShortcutAction::ALL.into_iter().find(|&a| self.key(a) == key) {
+            *self.key_mut(other) = previous;
+        }
+        *self.key_mut(action) = key;
+    }
+}
+
+/// A resource for the main trajectory, and a component on each extra one.
+#[derive(Resource, Component, Default)]
 pub struct SimulationStats {
     pub integration_time_us: f64,
     pub current_energy: f64,
     pub current_velocity: f64,
     pub divergence: f64,
     pub point_count: usize,
+    pub total_steps: u64,
+    /// Every accepted step since the last reset, not just the visible trail.
+    pub running: TrajectoryStats,
+    pub lobes: LobeStats,
+    /// Path length travelled since the last reset, summed over accepted steps.
+    pub arc_length: f64,
+    /// Model time covered by the steps in `arc_length`.
+    pub travel_time: f64,
+    pub lyapunov: LyapunovEstimate,
+}
+
+impl SimulationStats {
+    pub fn average_speed(&self) -> f64 {
+        if self.travel_time > 0.0 {
+            self.arc_length / self.travel_time
+        } else {
+            0.0
+        }
+    }
+}
+
+#[derive(Event)]
+pub struct ResetEvent;
+
+#[derive(Event)]
+pub struct StepEvent;
+
+#[derive(Event)]
+pub struct RandomizeEvent;
+
+#[derive(Event)]
+pub struct FitViewEvent;
+
+#[derive(Event)]
+pub struct ResetViewEvent;
+
+/// Takes a screenshot. Like the other capture and export events, it writes
+/// to `path` when a save dialog chose one, and otherwise to a timestamped
+/// file in captures/.
+#[derive(Event, Default)]
+pub struct ScreenshotEvent {
+    pub path: Option<PathBuf>,
+}
+
+#[derive(Event)]
+pub struct RecordEvent;
+
+#[derive(Event)]
+pub struct RecordVideoEvent;
+
+#[derive(Event)]
+pub struct RecordGifEvent;
+
+#[derive(Event, Default)]
+pub struct RenderStillEvent {
+    pub path: Option<PathBuf>,
+}
+
+#[derive(Event)]
+pub struct ExportTrajectoryEvent;
+
+#[derive(Event, Default)]
+pub struct ExportPoincareEvent {
+    pub path: Option<PathBuf>,
+}
+
+#[derive(Event, Default)]
+pub struct ExportSymbolsEvent {
+    pub path: Option<PathBuf>,
+}
+
+#[derive(Event, Default)]
+pub struct ExportStatsEvent {
+    pub path: Option<PathBuf>,
+}
+
+#[derive(Event)]
+pub struct ButterflyDemoEvent;
+
+#[derive(Event)]
+pub enum TrajectoryEvent {
+    /// Starts at the given point, or just beside x₀, y₀, z₀ for `None`.
+    Add(Option<[f64; 3]>),
+    /// Copies an extra trajectory, or the main one for `None`.
+    Clone(Option<Entity>),
+    Remove(Entity),
+}
+
+#[derive(Event, Default)]
+pub struct ExportBifurcationEvent {
+    pub path: Option<PathBuf>,
 }
 
+#[derive(Event, Default)]
+pub struct ExportManifestEvent {
+    pub path: Option<PathBuf>,
+}
+
+#[derive(Event)]
+pub struct LoadReplayEvent;
+
+#[derive(Event, Default)]
+pub struct RecordSessionEvent {
+    pub path: Option<PathBuf>,
+}
+
+#[derive(Event)]
+pub struct PlaySessionEvent;
+
+#[derive(Event)]
+pub struct SaveSnapshotEvent;
+
+#[derive(Event)]
+pub struct LoadSnapshotEvent;
+
+#[derive(Event)]
+pub struct SaveSettingsEvent;
+
 #[derive(Event)]
-pub struct ResetEvent;
\ No newline at end of file
+pub struct LoadSettingsEvent;
\ No newline at end of file
@@ -0,0 +1,318 @@
+use std::time::{Duration, Instant};
+
+use bevy::prelude::*;
+use bevy::window::{CompositeAlphaMode, PresentMode, PrimaryWindow, WindowLevel, WindowMode};
+use bevy::winit::{UpdateMode, WinitSettings};
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "export")]
+use crate::capture::frame_sequence::FrameRecorder;
+#[cfg(feature = "export")]
+use crate::capture::still::StillRenderer;
+#[cfg(feature = "export")]
+use crate::capture::video::VideoRecorder;
+use crate::cli::DEFAULT_WINDOW;
+use crate::config::SimulationConfig;
+use crate::i18n::tr_noop;
+use crate::rendering::camera_controller::OrbitCamera;
+use crate::session::Session;
+use crate::simulation::replay::Replay;
+
+pub const RESOLUTION_PRESETS: [(u32, u32); 6] = [
+    (1280, 720),
+    (1400, 900),
+    (1600, 900),
+    (1920, 1080),
+    (2560, 1440),
+    (3840, 2160),
+];
+
+/// Frame-rate limits offered besides unlimited.
+pub const FPS_CAPS: [u32; 3] = [30, 60, 120];
+/// Longest sleep while idle. Window input wakes the app at once; this bounds
+/// how late gamepad input and settings-file edits are noticed.
+const IDLE_WAIT: Duration = Duration::from_millis(250);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DisplayMode {
+    Windowed,
+    Borderless,
+    Fullscreen,
+}
+
+impl DisplayMode {
+    pub const ALL: [DisplayMode; 3] = [Self::Windowed, Self::Borderless, Self::Fullscreen];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Windowed => tr_noop("Windowed"),
+            Self::Borderless => tr_noop("Borderless fullscreen"),
+            Self::Fullscreen => tr_noop("Exclusive fullscreen"),
+        }
+    }
+
+    fn window_mode(&self) -> WindowMode {
+        match self {
+            Self::Windowed => WindowMode::Windowed,
+            Self::Borderless => WindowMode::BorderlessFullscreen,
+            Self::Fullscreen => WindowMode::Fullscreen,
+        }
+    }
+}
+
+/// Present modes that every surface supports, because each either is FIFO or
+/// falls back to it; the raw Mailbox and Immediate modes panic where missing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SyncMode {
+    /// Adaptive vsync where available, which tears instead of stalling a late frame.
+    Vsync,
+    StrictVsync,
+    NoVsync,
+}
+
+impl SyncMode {
+    pub const ALL: [SyncMode; 3] = [Self::Vsync, Self::StrictVsync, Self::NoVsync];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Vsync => tr_noop("VSync"),
+            Self::StrictVsync => tr_noop("Strict VSync (FIFO)"),
+            Self::NoVsync => tr_noop("No VSync"),
+        }
+    }
+
+    fn present_mode(&self) -> PresentMode {
+        match self {
+            Self::Vsync => PresentMode::AutoVsync,
+            Self::StrictVsync => PresentMode::Fifo,
+            Self::NoVsync => PresentMode::AutoNoVsync,
+        }
+    }
+}
+
+#[derive(Resource, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DisplaySettings {
+    pub mode: DisplayMode,
+    /// Window size in logical pixels while windowed.
+    pub resolution: (u32, u32),
+    pub sync: SyncMode,
+    /// Frames per second to hold the app to, or `None` for unlimited.
+    pub fps_cap: Option<u32>,
+    pub always_on_top: bool,
+    /// Stop redrawing every frame while paused with a still camera.
+    pub low_power_idle: bool,
+    /// Borderless window with a see-through background, for floating over the
+    /// desktop or capturing as an overlay. Transparency is fixed when the window
+    /// is created, so switching it on takes effect after a restart.
+    pub overlay: bool,
+    /// Let clicks pass through the overlay to whatever is underneath.
+    pub click_through: bool,
+}
+
+impl Default for DisplaySettings {
+    fn default() -> Self {
+        Self {
+            mode: DisplayMode::Windowed,
+            resolution: (DEFAULT_WINDOW.0 as u32, DEFAULT_WINDOW.1 as u32),
+            sync: SyncMode::Vsync,
+            fps_cap: None,
+            always_on_top: false,
+            low_power_idle: true,
+            overlay: false,
+            click_through: false,
+        }
+    }
+}
+
+/// Copies the settings that differ from `previous` onto `window`, so toggling
+/// vsync doesn't undo a size the user dragged the window to.
+pub fn apply_display(
+    previous: Option<&DisplaySettings>,
+    settings: &DisplaySettings,
+    window: &mut Window,
+) {
+    if previous.is_none_or(|p| p.mode != settings.mode) {
+        window.mode = settings.mode.window_mode();
+    }
+    if previous.is_none_or(|p| p.resolution != settings.resolution) {
+        let (width, height) = settings.resolution;
+        window.resolution.set(width as f32, height as f32);
+    }
+    if previous.is_none_or(|p| p.sync != settings.sync) {
+        window.present_mode = settings.sync.present_mode();
+    }
+    if previous.is_none_or(|p| p.always_on_top != settings.always_on_top) {
+        window.window_level = if settings.always_on_top {
+            WindowLevel::AlwaysOnTop
+        } else {
+            WindowLevel::Normal
+        };
+    }
+    if previous.is_none() {
+        window.transparent = settings.overlay;
+        if settings.overlay {
+            window.composite_alpha_mode = overlay_alpha_mode();
+        }
+    }
+    if previous.is_none_or(|p| p.overlay != settings.overlay) {
+        window.decorations = !settings.overlay;
+    }
+    if previous.is_none_or(|p| p.overlay_click_through() != settings.overlay_click_through()) {
+        window.cursor.hit_test = !settings.overlay_click_through();
+    }
+}
+
+impl DisplaySettings {
+    /// Click-through only applies to the overlay, so a normal window can never
+    /// end up ignoring the mouse.
+    pub fn overlay_click_through(&self) -> bool {
+        self.overlay && self.click_through
+    }
+}
+
+/// macOS only blends a transparent surface with post-multiplied alpha; the
+/// other platforms pick a working mode themselves.
+fn overlay_alpha_mode() -> CompositeAlphaMode {
+    if cfg!(target_os = "macos") {
+        CompositeAlphaMode::PostMultiplied
+    } else {
+        CompositeAlphaMode::Auto
+    }
+}
+
+/// Applies live edits; the initial settings are baked into the window at
+/// startup.
+pub fn display_settings_system(
+    settings: Res<DisplaySettings>,
+    mut windows: Query<&mut Window, With<PrimaryWindow>>,
+    mut applied: Local<Option<DisplaySettings>>,
+) {
+    let previous = applied.get_or_insert_with(|| settings.clone());
+    if *previous == *settings {
+        return;
+    }
+    if let Ok(mut window) = windows.get_single_mut() {
+        apply_display(Some(previous), &settings, &mut window);
+    }
+    *previous = settings.clone();
+}
+
+/// Clears to fully transparent while the overlay is on, restoring the
+/// background colour the app started with when it is switched off.
+pub fn overlay_background_system(
+    settings: Res<DisplaySettings>,
+    mut clear_color: ResMut<ClearColor>,
+    mut opaque: Local<Option<Color>>,
+) {
+    if !settings.is_changed() {
+        return;
+    }
+    let background = opaque.get_or_insert(clear_color.0);
+    let target = if settings.overlay {
+        Color::NONE
+    } else {
+        *background
+    };
+    if clear_color.0 != target {
+        clear_color.0 = target;
+    }
+}
+
+/// Sleeps out the rest of the frame budget when an FPS cap is set. The
+/// integrator advances a fixed number of steps per frame, so a cap slows the
+/// animation in wall-clock time but leaves the trajectory itself unchanged.
+pub fn frame_limiter_system(settings: Res<DisplaySettings>, mut frame_end: Local<Option<Instant>>) {
+    if let (Some(fps), Some(last)) = (settings.fps_cap, *frame_end) {
+        let budget = Duration::from_secs_f64(1.0 / f64::from(fps.max(1)));
+        if let Some(remaining) = budget.checked_sub(last.elapsed()) {
+            std::thread::sleep(remaining);
+        }
+    }
+    *frame_end = Some(Instant::now());
+}
+
+/// Switches winit to reactive updates while nothing on screen can change by
+/// itself: the simulation is paused, the camera is still, and no recording,
+/// playback or still render needs frames.
+#[allow(clippy::too_many_arguments)]
+pub fn idle_mode_system(
+    config: Res<SimulationConfig>,
+    settings: Res<DisplaySettings>,
+    #[cfg(feature = "export")]
+    frames: Res<FrameRecorder>,
+    #[cfg(feature = "export")]
+    video: Res<VideoRecorder>,
+    #[cfg(feature = "export")]
+    still: Res<StillRenderer>,
+    session: Res<Session>,
+    replay: Res<Replay>,
+    cameras: Query<&Transform, With<OrbitCamera>>,
+    mut winit: ResMut<WinitSettings>,
+    mut last_camera: Local<Option<Transform>>,
+) {
+    let camera = cameras.get_single().ok().copied();
+    let camera_still = camera.is_some() && camera == *last_camera;
+    *last_camera = camera;
+    #[cfg(feature = "export")]
+    let capturing = frames.active.is_some() || video.is_recording() || still.is_busy();
+    #[cfg(not(feature = "export"))]
+    let capturing = false;
+
+    let idle = settings.low_power_idle
+        && config.paused
+        && camera_still
+        && !capturing
+        && session.playback_progress().is_none()
+        && !(replay.is_active() && replay.playing);
+    let focused_mode = if idle {
+        UpdateMode::reactive_low_power(IDLE_WAIT)
+    } else {
+        UpdateMode::Continuous
+    };
+    if winit.focused_mode != focused_mode {
+        winit.focused_mode = focused_mode;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_apply_display_only_touches_changed_fields() {
+        let before = DisplaySettings::default();
+        let mut window = Window::default();
+        apply_display(None, &before, &mut window);
+        window.resolution.set(1000.0, 700.0);
+
+        let after = DisplaySettings {
+            sync: SyncMode::NoVsync,
+            ..before.clone()
+        };
+        apply_display(Some(&before), &after, &mut window);
+        assert_eq!(window.present_mode, PresentMode::AutoNoVsync);
+        assert_eq!(window.resolution.width(), 1000.0);
+    }
+
+    #[test]
+    fn test_overlay_is_borderless_and_click_through_needs_overlay() {
+        let mut window = Window::default();
+        let normal = DisplaySettings {
+            click_through: true,
+            ..default()
+        };
+        apply_display(None, &normal, &mut window);
+        assert!(window.decorations && window.cursor.hit_test && !window.transparent);
+
+        let overlay = DisplaySettings {
+            overlay: true,
+            ..normal.clone()
+        };
+        apply_display(Some(&normal), &overlay, &mut window);
+        assert!(!window.decorations);
+        assert!(!window.cursor.hit_test);
+        // Transparency can't be switched on an existing window.
+        assert!(!window.transparent);
+    }
+}
\ No newline at end of file
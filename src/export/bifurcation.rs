@@ -0,0 +1,177 @@
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::thread;
+
+use bevy::prelude::*;
+use serde_json::json;
+
+use crate::capture::chosen_or_output_path;
+use crate::capture::still::write_png;
+use crate::capture::video::EncoderHandle;
+use crate::config::{BifurcationSettings, ExportBifurcationEvent, SimulationConfig};
+use crate::i18n::{tr, trf};
+use crate::simulation::bifurcation::scan;
+use crate::simulation::lorenz::LorenzState;
+use crate::ui::toast::Toasts;
+
+const BACKGROUND: [u8; 4] = [5, 5, 10, 255];
+const INK: [u8; 3] = [120, 200, 255];
+
+#[derive(Resource, Default)]
+pub struct BifurcationExport {
+    job: Option<(PathBuf, EncoderHandle)>,
+}
+
+impl BifurcationExport {
+    pub fn is_running(&self) -> bool {
+        self.job.is_some()
+    }
+}
+
+pub fn z_range(points: &[(f64, f64)]) -> (f64, f64) {
+    let top = points.iter().map(|p| p.1).fold(0.0_f64, f64::max);
+    (0.0, if top > 0.0 { top * 1.05 } else { 1.0 })
+}
+
+pub fn rasterize(
+    points: &[(f64, f64)],
+    rho_range: (f64, f64),
+    z_range: (f64, f64),
+    size: UVec2,
+) -> Vec<u8> {
+    let (width, height) = (size.x as usize, size.y as usize);
+    let mut pixels = BACKGROUND.repeat(width * height);
+    let rho_span = (rho_range.1 - rho_range.0).max(f64::EPSILON);
+    let z_span = (z_range.1 - z_range.0).max(f64::EPSILON);
+    for &(rho, z) in points {
+        let u = (rho - rho_range.0) / rho_span;
+        let v = 1.0 - (z - z_range.0) / z_span;
+        if !(0.0..=1.0).contains(&u) || !(0.0..=1.0).contains(&v) {
+            continue;
+        }
+        let px = ((u * (width - 1) as f64).round() as usize).min(width - 1);
+        let py = ((v * (height - 1) as f64).round() as usize).min(height - 1);
+        let pixel = &mut pixels[(py * width + px) * 4..][..3];
+        for (channel, ink) in pixel.iter_mut().zip(INK) {
+            *channel = channel.saturating_add(ink / 3).max(ink / 2);
+        }
+    }
+    pixels
+}
+
+pub fn write_csv<W: Write>(points: &[(f64, f64)], mut out: W) -> io::Result<usize> {
+    writeln!(out, "rho,z_max")?;
+    for (rho, z) in points {
+        writeln!(out, "{rho},{z}")?;
+    }
+    out.flush()?;
+    Ok(points.len())
+}
+
+fn write_files(
+    png: &Path,
+    settings: &BifurcationSettings,
+    config_json: serde_json::Value,
+    points: &[(f64, f64)],
+) -> io::Result<()> {
+    write_csv(points, BufWriter::new(File::create(png.with_extension("csv"))?))?;
+
+    let rho_range = (settings.rho_min, settings.rho_max);
+    let z_range = z_range(points);
+    let sidecar = json!({
+        "scan": {
+            "rho_min": settings.rho_min,
+            "rho_max": settings.rho_max,
+            "samples": settings.samples,
+            "transient_steps": settings.transient_steps,
+            "record_steps": settings.record_steps,
+        },
+        "simulation": config_json,
+        "image": {
+            "width": settings.image_width,
+            "height": settings.image_height,
+            "x_axis": { "label": "rho", "min": rho_range.0, "max": rho_range.1 },
+            "y_axis": { "label": "z_max", "min": z_range.0, "max": z_range.1 },
+        },
+        "points": points.len(),
+    });
+    let mut out = BufWriter::new(File::create(png.with_extension("json"))?);
+    serde_json::to_writer_pretty(&mut out, &sidecar)?;
+    out.flush()?;
+
+    let size = UVec2::new(settings.image_width, settings.image_height);
+    write_png(png, size, rasterize(points, rho_range, z_range, size)).map_err(io::Error::other)
+}
+
+pub fn export_bifurcation_system(
+    mut events: EventReader<ExportBifurcationEvent>,
+    settings: Res<BifurcationSettings>,
+    config: Res<SimulationConfig>,
+    mut export: ResMut<BifurcationExport>,
+    mut toasts: ResMut<Toasts>,
+) {
+    if let Some((path, job)) = export.job.take_if(|(_, job)| job.is_finished()) {
+        match job.join() {
+            Ok(Ok(())) => toasts.push(trf(
+                "💾 Saved bifurcation diagram to {path}",
+                &[("path", &path.display())],
+            )),
+            Ok(Err(err)) => toasts.push(trf("Bifurcation export failed: {err}", &[("err", &err)])),
+            Err(_) => toasts.push(tr("Bifurcation export thread panicked")),
+        }
+    }
+
+    let Some(event) = events.read().last() else {
+        return;
+    };
+    if export.is_running() {
+        return;
+    }
+
+    let path = match chosen_or_output_path(event.path.as_deref(), "bifurcation", "png") {
+        Ok(path) => path,
+        Err(err) => {
+            toasts.push(trf("Bifurcation export failed: {err}", &[("err", &err)]));
+            return;
+        }
+    };
+    let settings = settings.clone();
+    let initial = LorenzState::new(config.initial_x, config.initial_y, config.initial_z);
+    let (sigma, beta, method, dt) = (config.sigma, config.beta, config.method, config.dt);
+    let config_json = json!({
+        "sigma": sigma,
+        "beta": beta,
+        "dt": dt,
+        "method": method.short_name(),
+        "initial_state": [initial.x, initial.y, initial.z],
+    });
+    let png = path.clone();
+    let job = thread::spawn(move || {
+        let points = scan(&settings, &initial, sigma, beta, method, dt);
+        write_files(&png, &settings, config_json, &points).map_err(|err| err.to_string())
+    });
+    toasts.push(tr("📈 Scanning ρ for the bifurcation diagram…"));
+    export.job = Some((path, job));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rasterize_maps_rho_and_z_to_pixels() {
+        let points = [(0.0, 0.0), (10.0, 20.0), (5.0, 40.0)];
+        let pixels = rasterize(&points, (0.0, 10.0), (0.0, 20.0), UVec2::new(11, 5));
+        assert_eq!(pixels.len(), 11 * 5 * 4);
+        let at = |x: usize, y: usize| &pixels[(y * 11 + x) * 4..][..4];
+        assert_ne!(at(0, 4), BACKGROUND);
+        assert_ne!(at(10, 0), BACKGROUND);
+        assert_eq!(at(5, 0), BACKGROUND);
+        assert!((z_range(&points).1 - 42.0).abs() < 1e-9);
+
+        let mut out = Vec::new();
+        write_csv(&points, &mut out).unwrap();
+        assert!(String::from_utf8(out).unwrap().starts_with("rho,z_max\n0,0\n10,20\n"));
+    }
+}
\ No newline at end of file
@@ -0,0 +1,106 @@
+use std::io::{self, Write};
+
+use bevy::prelude::*;
+
+use crate::simulation::integrator::TrailPoint;
+use super::coordinates;
+
+fn rgb(point: &TrailPoint) -> [u8; 3] {
+    let [r, g, b, _] = point.color.to_srgba().to_u8_array();
+    [r, g, b]
+}
+
+pub fn write_ply<'a, W: Write>(
+    points: impl ExactSizeIterator<Item = &'a TrailPoint>,
+    mut out: W,
+) -> io::Result<usize> {
+    let count = points.len();
+    let edges = count.saturating_sub(1);
+    writeln!(out, "ply")?;
+    writeln!(out, "format ascii 1.0")?;
+    writeln!(out, "comment Lorenz attractor trail")?;
+    writeln!(out, "element vertex {count}")?;
+    for axis in ["x", "y", "z"] {
+        writeln!(out, "property float {axis}")?;
+    }
+    for channel in ["red", "green", "blue"] {
+        writeln!(out, "property uchar {channel}")?;
+    }
+    writeln!(out, "element edge {edges}")?;
+    writeln!(out, "property int vertex1")?;
+    writeln!(out, "property int vertex2")?;
+    writeln!(out, "end_header")?;
+
+    for point in points {
+        let (x, y, z) = coordinates(point);
+        let [r, g, b] = rgb(point);
+        writeln!(out, "{x} {y} {z} {r} {g} {b}")?;
+    }
+    for i in 0..edges {
+        writeln!(out, "{i} {}", i + 1)?;
+    }
+    out.flush()?;
+    Ok(count)
+}
+
+pub fn write_obj<'a, W: Write>(
+    points: impl ExactSizeIterator<Item = &'a TrailPoint>,
+    mut out: W,
+) -> io::Result<usize> {
+    let count = points.len();
+    writeln!(out, "# Lorenz attractor trail")?;
+    writeln!(out, "o lorenz_trail")?;
+    for point in points {
+        let (x, y, z) = coordinates(point);
+        let [r, g, b] = point.color.to_srgba().to_f32_array_no_alpha();
+        writeln!(out, "v {x} {y} {z} {r:.4} {g:.4} {b:.4}")?;
+    }
+    if count > 1 {
+        write!(out, "l")?;
+        for i in 1..=count {
+            write!(out, " {i}")?;
+        }
+        writeln!(out)?;
+    }
+    out.flush()?;
+    Ok(count)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_points() -> Vec<TrailPoint> {
+        (0..3)
+            .map(|i| TrailPoint {
+                position: Vec3::new(i as f32, 2.0, 1.0),
+                color: Color::srgb(1.0, 0.0, 0.0),
+                time: 0.0,
+                speed: 0.0,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_ply_header_matches_body() {
+        let mut out = Vec::new();
+        write_ply(sample_points().iter(), &mut out).unwrap();
+        let text = String::from_utf8(out).unwrap();
+        let (header, body) = text.split_once("end_header\n").unwrap();
+        assert!(header.contains("element vertex 3\n"));
+        assert!(header.contains("element edge 2\n"));
+        let lines: Vec<&str> = body.lines().collect();
+        assert_eq!(lines.len(), 5);
+        assert_eq!(lines[2], "2 2 1 255 0 0");
+        assert_eq!(lines[4], "1 2");
+    }
+
+    #[test]
+    fn test_obj_polyline_indices_are_one_based() {
+        let mut out = Vec::new();
+        write_obj(sample_points().iter(), &mut out).unwrap();
+        let text = String::from_utf8(out).unwrap();
+        assert_eq!(text.lines().filter(|l| l.starts_with("v ")).count(), 3);
+        assert_eq!(text.lines().last(), Some("l 1 2 3"));
+    }
+}
\ No newline at end of file
@@ -0,0 +1,251 @@
+use std::io::{self, Write};
+
+use bevy::prelude::*;
+use serde_json::json;
+
+use crate::config::SimulationConfig;
+use crate::rendering::view_scale::AxisMapping;
+use crate::simulation::integrator::TrailPoint;
+
+const GLB_MAGIC: u32 = 0x4654_6C67;
+const CHUNK_JSON: u32 = 0x4E4F_534A;
+const CHUNK_BIN: u32 = 0x004E_4942;
+const ARRAY_BUFFER: u32 = 34962;
+const ELEMENT_ARRAY_BUFFER: u32 = 34963;
+const FLOAT: u32 = 5126;
+const UNSIGNED_INT: u32 = 5125;
+
+#[derive(Default)]
+pub struct TubeMesh {
+    pub positions: Vec<[f32; 3]>,
+    pub normals: Vec<[f32; 3]>,
+    pub colors: Vec<[f32; 4]>,
+    pub indices: Vec<u32>,
+}
+
+fn any_perpendicular(v: Vec3) -> Vec3 {
+    let helper = if v.y.abs() < 0.9 { Vec3::Y } else { Vec3::X };
+    v.cross(helper).normalize()
+}
+
+/// A tube around the trail, laid out in the default axes so that z points
+/// along glTF's up axis.
+pub fn tube_mesh<'a>(
+    points: impl Iterator<Item = &'a TrailPoint>,
+    radius: f32,
+    sides: u32,
+) -> TubeMesh {
+    let mapping = AxisMapping::default();
+    let points: Vec<(Vec3, &TrailPoint)> =
+        points.map(|point| (mapping.to_scene(point.position), point)).collect();
+    let mut mesh = TubeMesh::default();
+    if points.len() < 2 {
+        return mesh;
+    }
+
+    let sides = sides.max(3);
+    let mut tangent = (points[1].0 - points[0].0).normalize_or(Vec3::X);
+    let mut normal = any_perpendicular(tangent);
+
+    for (i, &(position, point)) in points.iter().enumerate() {
+        let next = points.get(i + 1).map_or(position, |next| next.0);
+        let prev = points[i.saturating_sub(1)].0;
+        let new_tangent = (next - prev).normalize_or(tangent);
+        normal = (Quat::from_rotation_arc(tangent, new_tangent) * normal).normalize();
+        tangent = new_tangent;
+        let binormal = tangent.cross(normal);
+
+        let color = point.color.to_linear().to_f32_array();
+        for side in 0..sides {
+            let angle = side as f32 / sides as f32 * std::f32::consts::TAU;
+            let offset = normal * angle.cos() + binormal * angle.sin();
+            mesh.positions
+                .push((position + offset * radius).to_array());
+            mesh.normals.push(offset.to_array());
+            mesh.colors.push(color);
+        }
+    }
+
+    for ring in 0..points.len() as u32 - 1 {
+        let a = ring * sides;
+        let b = a + sides;
+        for side in 0..sides {
+            let next = (side + 1) % sides;
+            mesh.indices.extend_from_slice(&[
+                a + side,
+                b + side,
+                a + next,
+                a + next,
+                b + side,
+                b + next,
+            ]);
+        }
+    }
+    mesh
+}
+
+fn push_floats<const N: usize>(buffer: &mut Vec<u8>, values: &[[f32; N]]) -> (usize, usize) {
+    let offset = buffer.len();
+    for value in values.iter().flatten() {
+        buffer.extend_from_slice(&value.to_le_bytes());
+    }
+    (offset, buffer.len() - offset)
+}
+
+fn bounds(positions: &[[f32; 3]]) -> ([f32; 3], [f32; 3]) {
+    positions
+        .iter()
+        .fold(([f32::MAX; 3], [f32::MIN; 3]), |(mut min, mut max), p| {
+            for axis in 0..3 {
+                min[axis] = min[axis].min(p[axis]);
+                max[axis] = max[axis].max(p[axis]);
+            }
+            (min, max)
+        })
+}
+
+pub fn write_glb<W: Write>(
+    mesh: &TubeMesh,
+    config: &SimulationConfig,
+    point_count: usize,
+    mut out: W,
+) -> io::Result<usize> {
+    let mut bin = Vec::new();
+    let (position_offset, position_len) = push_floats(&mut bin, &mesh.positions);
+    let (normal_offset, normal_len) = push_floats(&mut bin, &mesh.normals);
+    let (color_offset, color_len) = push_floats(&mut bin, &mesh.colors);
+    let index_offset = bin.len();
+    for index in &mesh.indices {
+        bin.extend_from_slice(&index.to_le_bytes());
+    }
+    let index_len = bin.len() - index_offset;
+    while bin.len() % 4 != 0 {
+        bin.push(0);
+    }
+
+    let (min, max) = bounds(&mesh.positions);
+    let vertex_count = mesh.positions.len();
+    let document = json!({
+        "asset": {
+            "version": "2.0",
+            "generator": concat!("lorenz-attractor ", env!("CARGO_PKG_VERSION")),
+        },
+        "scene": 0,
+        "scenes": [{
+            "name": "Lorenz attractor",
+            "nodes": [0],
+            "extras": {
+                "sigma": config.sigma,
+                "rho": config.rho,
+                "beta": config.beta,
+                "dt": config.dt,
+                "method": config.method.label(),
+                "initial_state": [config.initial_x, config.initial_y, config.initial_z],
+                "trail_points": point_count,
+            },
+        }],
+        "nodes": [{ "name": "lorenz_trail", "mesh": 0 }],
+        "meshes": [{
+            "name": "lorenz_trail",
+            "primitives": [{
+                "attributes": { "POSITION": 0, "NORMAL": 1, "COLOR_0": 2 },
+                "indices": 3,
+                "material": 0,
+            }],
+        }],
+        "materials": [{
+            "name": "trail",
+            "pbrMetallicRoughness": {
+                "baseColorFactor": [1.0, 1.0, 1.0, 1.0],
+                "metallicFactor": 0.0,
+                "roughnessFactor": 0.6,
+            },
+        }],
+        "accessors": [
+            { "bufferView": 0, "componentType": FLOAT, "count": vertex_count, "type": "VEC3", "min": min, "max": max },
+            { "bufferView": 1, "componentType": FLOAT, "count": vertex_count, "type": "VEC3" },
+            { "bufferView": 2, "componentType": FLOAT, "count": vertex_count, "type": "VEC4" },
+            { "bufferView": 3, "componentType": UNSIGNED_INT, "count": mesh.indices.len(), "type": "SCALAR" },
+        ],
+        "bufferViews": [
+            { "buffer": 0, "byteOffset": position_offset, "byteLength": position_len, "target": ARRAY_BUFFER },
+            { "buffer": 0, "byteOffset": normal_offset, "byteLength": normal_len, "target": ARRAY_BUFFER },
+            { "buffer": 0, "byteOffset": color_offset, "byteLength": color_len, "target": ARRAY_BUFFER },
+            { "buffer": 0, "byteOffset": index_offset, "byteLength": index_len, "target": ELEMENT_ARRAY_BUFFER },
+        ],
+        "buffers": [{ "byteLength": bin.len() }],
+    });
+
+    let mut json = serde_json::to_vec(&document)?;
+    while json.len() % 4 != 0 {
+        json.push(b' ');
+    }
+
+    let total = 12 + 8 + json.len() + 8 + bin.len();
+    out.write_all(&GLB_MAGIC.to_le_bytes())?;
+    out.write_all(&2u32.to_le_bytes())?;
+    out.write_all(&(total as u32).to_le_bytes())?;
+    out.write_all(&(json.len() as u32).to_le_bytes())?;
+    out.write_all(&CHUNK_JSON.to_le_bytes())?;
+    out.write_all(&json)?;
+    out.write_all(&(bin.len() as u32).to_le_bytes())?;
+    out.write_all(&CHUNK_BIN.to_le_bytes())?;
+    out.write_all(&bin)?;
+    out.flush()?;
+    Ok(point_count)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn helix(count: usize) -> Vec<TrailPoint> {
+        (0..count)
+            .map(|i| {
+                let a = i as f32 * 0.3;
+                TrailPoint {
+                    position: Vec3::new(a.cos() * 5.0, i as f32 * 0.2, a.sin() * 5.0),
+                    color: Color::WHITE,
+                    time: 0.0,
+                    speed: 0.0,
+                }
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_tube_rings_surround_trail() {
+        let points = helix(20);
+        let mesh = tube_mesh(points.iter(), 0.5, 6);
+        assert_eq!(mesh.positions.len(), 20 * 6);
+        assert_eq!(mesh.indices.len(), 19 * 6 * 6);
+        for (i, point) in points.iter().enumerate() {
+            for p in &mesh.positions[i * 6..(i + 1) * 6] {
+                let center = AxisMapping::default().to_scene(point.position);
+                let d = Vec3::from_array(*p).distance(center);
+                assert!((d - 0.5).abs() < 1e-4);
+            }
+        }
+    }
+
+    #[test]
+    fn test_glb_chunks_are_consistent() {
+        let points = helix(10);
+        let mesh = tube_mesh(points.iter(), 0.2, 4);
+        let mut out = Vec::new();
+        write_glb(&mesh, &SimulationConfig::default(), points.len(), &mut out).unwrap();
+
+        let word = |i: usize| u32::from_le_bytes(out[i..i + 4].try_into().unwrap());
+        assert_eq!(word(0), GLB_MAGIC);
+        assert_eq!(word(8) as usize, out.len());
+        let json_len = word(12) as usize;
+        assert_eq!(json_len % 4, 0);
+        assert_eq!(word(16), CHUNK_JSON);
+        let document: serde_json::Value = serde_json::from_slice(&out[20..20 + json_len]).unwrap();
+        assert_eq!(document["scenes"][0]["extras"]["rho"], 28.0);
+        let bin_len = word(20 + json_len) as usize;
+        assert_eq!(word(24 + json_len), CHUNK_BIN);
+        assert_eq!(document["buffers"][0]["byteLength"], bin_len);
+        assert_eq!(28 + json_len + bin_len, out.len());
+    }
+}
\ No newline at end of file
@@ -0,0 +1,83 @@
+use std::io;
+use std::path::Path;
+
+use hdf5::types::VarLenUnicode;
+use hdf5::{Dataset, File};
+
+use crate::config::SimulationConfig;
+use crate::simulation::integrator::TrailPoint;
+use super::coordinates;
+
+const CHUNK_ROWS: usize = 65_536;
+
+fn to_io(err: hdf5::Error) -> io::Error {
+    io::Error::other(err.to_string())
+}
+
+fn dataset(file: &File, name: &str, shape: &[usize], data: &[f64]) -> hdf5::Result<Dataset> {
+    let mut builder = file.new_dataset::<f64>();
+    if shape[0] > 0 {
+        let mut chunk = shape.to_vec();
+        chunk[0] = chunk[0].min(CHUNK_ROWS);
+        builder = builder.chunk(chunk);
+        if hdf5::filters::deflate_available() {
+            builder = builder.shuffle().deflate(4);
+        }
+    }
+    let dataset = builder.shape(shape).create(name)?;
+    dataset.write_raw(data)?;
+    Ok(dataset)
+}
+
+fn write_file<'a>(
+    points: impl ExactSizeIterator<Item = &'a TrailPoint>,
+    config: &SimulationConfig,
+    path: &Path,
+) -> hdf5::Result<usize> {
+    let rows = points.len();
+    let mut time = Vec::with_capacity(rows);
+    let mut state = Vec::with_capacity(rows * 3);
+    let mut speed = Vec::with_capacity(rows);
+    for point in points {
+        let (x, y, z) = coordinates(point);
+        time.push(point.time);
+        state.extend([x as f64, y as f64, z as f64]);
+        speed.push(point.speed as f64);
+    }
+
+    let file = File::create(path)?;
+    dataset(&file, "time", &[rows], &time)?;
+    dataset(&file, "state", &[rows, 3], &state)?;
+    dataset(&file, "speed", &[rows], &speed)?;
+
+    for (name, value) in [
+        ("sigma", config.sigma),
+        ("rho", config.rho),
+        ("beta", config.beta),
+        ("dt", config.dt),
+    ] {
+        file.new_attr::<f64>().create(name)?.write_scalar(&value)?;
+    }
+    file.new_attr::<f64>()
+        .shape(3)
+        .create("initial_state")?
+        .write_raw(&[config.initial_x, config.initial_y, config.initial_z][..])?;
+    let method = config
+        .method
+        .short_name()
+        .parse::<VarLenUnicode>()
+        .map_err(|err| hdf5::Error::from(err.to_string()))?;
+    file.new_attr::<VarLenUnicode>()
+        .create("method")?
+        .write_scalar(&method)?;
+    file.close()?;
+    Ok(rows)
+}
+
+pub fn write_hdf5<'a>(
+    points: impl ExactSizeIterator<Item = &'a TrailPoint>,
+    config: &SimulationConfig,
+    path: &Path,
+) -> io::Result<usize> {
+    write_file(points, config, path).map_err(to_io)
+}
\ No newline at end of file
@@ -0,0 +1,229 @@
+use std::fs::{File, OpenOptions};
+use std::io::{self, BufWriter, Write};
+use std::path::{Path, PathBuf};
+
+use bevy::prelude::*;
+
+use crate::config::{LogFormat, LogSettings, SimulationStats};
+use crate::i18n::trf;
+use crate::simulation::integrator::{TrailBuffer, TrailPoint};
+use crate::ui::toast::Toasts;
+use super::resolve_path;
+use super::coordinates;
+
+struct LogFile {
+    writer: BufWriter<File>,
+    bytes: u64,
+}
+
+#[derive(Resource, Default)]
+pub struct StateLog {
+    file: Option<LogFile>,
+    base: PathBuf,
+    logged_steps: u64,
+    pub segment: u32,
+    pub rows: u64,
+}
+
+impl StateLog {
+    pub fn is_open(&self) -> bool {
+        self.file.is_some()
+    }
+
+    pub fn current_path(&self) -> PathBuf {
+        segment_path(&self.base, self.segment)
+    }
+
+    /// Appends to the segment, so logging again to the same path keeps what
+    /// an earlier run wrote; the header only starts an empty file.
+    fn open(&mut self, format: LogFormat) -> io::Result<()> {
+        let path = self.current_path();
+        let file = OpenOptions::new().create(true).append(true).open(path)?;
+        let mut bytes = file.metadata()?.len();
+        let mut writer = BufWriter::new(file);
+        if format == LogFormat::Csv && bytes == 0 {
+            let header = "t,x,y,z\n";
+            writer.write_all(header.as_bytes())?;
+            bytes = header.len() as u64;
+        }
+        self.file = Some(LogFile { writer, bytes });
+        Ok(())
+    }
+
+    fn close(&mut self) -> io::Result<()> {
+        match self.file.take() {
+            Some(mut file) => file.writer.flush(),
+            None => Ok(()),
+        }
+    }
+}
+
+/// `log.csv` → `log.csv`, `log-1.csv`, `log-2.csv`, …
+pub fn segment_path(base: &Path, segment: u32) -> PathBuf {
+    if segment == 0 {
+        return base.to_path_buf();
+    }
+    let stem = base.file_stem().and_then(|s| s.to_str()).unwrap_or("log");
+    let mut name = format!("{stem}-{segment}");
+    if let Some(extension) = base.extension().and_then(|e| e.to_str()) {
+        name = format!("{name}.{extension}");
+    }
+    base.with_file_name(name)
+}
+
+pub fn format_row(format: LogFormat, point: &TrailPoint) -> String {
+    let (x, y, z) = coordinates(point);
+    match format {
+        LogFormat::Csv => format!("{},{x},{y},{z}\n", point.time),
+        LogFormat::Jsonl => format!(
+            "{{\"t\":{},\"x\":{x},\"y\":{y},\"z\":{z}}}\n",
+            point.time
+        ),
+    }
+}
+
+fn write_new_points(
+    log: &mut StateLog,
+    settings: &LogSettings,
+    trail: &TrailBuffer,
+    total_steps: u64,
+) -> io::Result<()> {
+    let max_bytes = u64::from(settings.max_megabytes.max(1)) * 1024 * 1024;
+    let every = u64::from(settings.every_n_steps.max(1));
+    let new = total_steps - log.logged_steps;
+    let skip = trail.points.len().saturating_sub(new as usize);
+    let first_step = total_steps + 1 - trail.points.len().min(new as usize) as u64;
+
+    for (step, point) in (first_step..).zip(trail.points.iter().skip(skip)) {
+        if step % every != 0 {
+            continue;
+        }
+        let row = format_row(settings.format, point);
+        let Some(file) = log.file.as_mut() else {
+            break;
+        };
+        file.writer.write_all(row.as_bytes())?;
+        file.bytes += row.len() as u64;
+        log.rows += 1;
+
+        if file.bytes >= max_bytes {
+            log.close()?;
+            log.segment += 1;
+            log.open(settings.format)?;
+        }
+    }
+
+    log.logged_steps = total_steps;
+    match log.file.as_mut() {
+        Some(file) => file.writer.flush(),
+        None => Ok(()),
+    }
+}
+
+pub fn state_log_system(
+    mut settings: ResMut<LogSettings>,
+    stats: Res<SimulationStats>,
+    trail: Res<TrailBuffer>,
+    mut log: ResMut<StateLog>,
+    mut toasts: ResMut<Toasts>,
+) {
+    if !settings.enabled {
+        if log.is_open() {
+            let message = match log.close() {
+                Ok(()) => trf(
+                    "🗒 Logged {rows} rows to {path}",
+                    &[("rows", &log.rows), ("path", &log.current_path().display())],
+                ),
+                Err(err) => trf("Closing log failed: {err}", &[("err", &err)]),
+            };
+            toasts.push(message);
+        }
+        return;
+    }
+
+    if !log.is_open() {
+        let opened = resolve_path(&settings.path, "log", settings.format.extension())
+            .and_then(|base| {
+                *log = StateLog {
+                    base,
+                    logged_steps: stats.total_steps,
+                    ..default()
+                };
+                log.open(settings.format)
+            });
+        match opened {
+            Ok(()) => toasts.push(trf(
+                "🗒 Logging to {path}",
+                &[("path", &log.current_path().display())],
+            )),
+            Err(err) => {
+                toasts.push(trf("Logging failed: {err}", &[("err", &err)]));
+                settings.enabled = false;
+                return;
+            }
+        }
+    }
+
+    if stats.total_steps < log.logged_steps {
+        log.logged_steps = stats.total_steps;
+    }
+    if stats.total_steps == log.logged_steps {
+        return;
+    }
+
+    if let Err(err) = write_new_points(&mut log, &settings, &trail, stats.total_steps) {
+        toasts.push(trf("Logging failed: {err}", &[("err", &err)]));
+        log.file = None;
+        settings.enabled = false;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_segment_path_numbers_rotations() {
+        let base = Path::new("captures/run.csv");
+        assert_eq!(segment_path(base, 0), PathBuf::from("captures/run.csv"));
+        assert_eq!(segment_path(base, 2), PathBuf::from("captures/run-2.csv"));
+    }
+
+    #[test]
+    fn test_format_row_uses_lorenz_axes() {
+        let point = TrailPoint {
+            position: Vec3::new(1.0, 2.0, 3.0),
+            color: Color::WHITE,
+            time: 0.5,
+            speed: 0.0,
+        };
+        assert_eq!(format_row(LogFormat::Csv, &point), "0.5,1,2,3\n");
+        assert_eq!(
+            format_row(LogFormat::Jsonl, &point),
+            "{\"t\":0.5,\"x\":1,\"y\":2,\"z\":3}\n"
+        );
+    }
+
+    #[test]
+    fn test_reopening_a_log_appends_after_one_header() {
+        let dir = std::env::temp_dir().join(format!("lorenz-log-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let mut log = StateLog {
+            base: dir.join("run.csv"),
+            ..default()
+        };
+        for row in ["0,1,2,3\n", "1,4,5,6\n"] {
+            log.open(LogFormat::Csv).unwrap();
+            let file = log.file.as_mut().unwrap();
+            file.writer.write_all(row.as_bytes()).unwrap();
+            log.close().unwrap();
+        }
+        let text = std::fs::read_to_string(log.current_path()).unwrap();
+        assert_eq!(text, "t,x,y,z\n0,1,2,3\n1,4,5,6\n");
+
+        log.open(LogFormat::Csv).unwrap();
+        assert_eq!(log.file.as_ref().unwrap().bytes, text.len() as u64);
+        log.close().unwrap();
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
\ No newline at end of file
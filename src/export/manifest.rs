@@ -0,0 +1,129 @@
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+
+use bevy::prelude::*;
+use chrono::Local;
+use serde_json::{json, Value};
+
+use crate::capture::chosen_or_output_path;
+use crate::config::{ComparisonConfig, ExportManifestEvent, SimulationConfig, SimulationStats};
+use crate::i18n::trf;
+use crate::simulation::lorenz::LorenzState;
+use crate::simulation::trajectories::MainTrajectory;
+use crate::ui::toast::Toasts;
+
+pub fn reproduce_command(config: &SimulationConfig, simulated_time: f64) -> String {
+    format!(
+        "lorenz-attractor --headless-sim --sigma {} --rho {} --beta {} --x0 {} --y0 {} --z0 {} \
+         --method {} --dt {} --duration {simulated_time}",
+        config.sigma,
+        config.rho,
+        config.beta,
+        config.initial_x,
+        config.initial_y,
+        config.initial_z,
+        config.method.short_name(),
+        config.dt,
+    )
+}
+
+pub fn manifest(
+    config: &SimulationConfig,
+    comparison: &ComparisonConfig,
+    stats: &SimulationStats,
+    state: Option<&LorenzState>,
+) -> Value {
+    let simulated_time = state.map_or(0.0, |s| s.t);
+    json!({
+        "app": {
+            "name": env!("CARGO_PKG_NAME"),
+            "version": env!("CARGO_PKG_VERSION"),
+        },
+        "created": Local::now().to_rfc3339(),
+        "parameters": {
+            "sigma": config.sigma,
+            "rho": config.rho,
+            "beta": config.beta,
+        },
+        "integrator": {
+            "method": config.method.short_name(),
+            "dt": config.dt,
+            "steps_per_frame": config.steps_per_frame,
+        },
+        "initial_state": [config.initial_x, config.initial_y, config.initial_z],
+        "rng_seed": Value::Null,
+        "run": {
+            "total_steps": stats.total_steps,
+            "simulated_time": simulated_time,
+            "final_state": state.map(|s| [s.x, s.y, s.z]),
+            "trail_points": stats.point_count,
+            "max_trail_points": config.max_trail_points,
+        },
+        "comparison": comparison.enabled.then(|| json!({
+            "parameters": {
+                "sigma": comparison.sigma,
+                "rho": comparison.rho,
+                "beta": comparison.beta,
+            },
+            "initial_state": [comparison.initial_x, comparison.initial_y, comparison.initial_z],
+        })),
+        "reproduce": reproduce_command(config, simulated_time),
+    })
+}
+
+fn write_manifest(path: &Path, manifest: &Value) -> io::Result<()> {
+    let mut out = BufWriter::new(File::create(path)?);
+    serde_json::to_writer_pretty(&mut out, manifest)?;
+    writeln!(out)?;
+    out.flush()
+}
+
+pub fn export_manifest_system(
+    mut events: EventReader<ExportManifestEvent>,
+    config: Res<SimulationConfig>,
+    comparison: Res<ComparisonConfig>,
+    stats: Res<SimulationStats>,
+    states: Query<&LorenzState, MainTrajectory>,
+    mut toasts: ResMut<Toasts>,
+) {
+    let Some(event) = events.read().last() else {
+        return;
+    };
+
+    let manifest = manifest(&config, &comparison, &stats, states.get_single().ok());
+    let result = chosen_or_output_path(event.path.as_deref(), "manifest", "json")
+        .and_then(|path| write_manifest(&path, &manifest).map(|()| path));
+    match result {
+        Ok(path) => toasts.push(trf(
+            "🧾 Saved run manifest to {path}",
+            &[("path", &path.display())],
+        )),
+        Err(err) => toasts.push(trf("Manifest export failed: {err}", &[("err", &err)])),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_manifest_records_run() {
+        let config = SimulationConfig::default();
+        let stats = SimulationStats {
+            total_steps: 2000,
+            ..Default::default()
+        };
+        let mut state = LorenzState::new(1.0, 2.0, 3.0);
+        state.t = 10.0;
+        let value = manifest(&config, &ComparisonConfig::default(), &stats, Some(&state));
+
+        assert_eq!(value["app"]["version"], env!("CARGO_PKG_VERSION"));
+        assert_eq!(value["parameters"]["rho"], config.rho);
+        assert_eq!(value["integrator"]["method"], config.method.short_name());
+        assert_eq!(value["run"]["total_steps"], 2000);
+        assert_eq!(value["run"]["final_state"], json!([1.0, 2.0, 3.0]));
+        assert!(value["comparison"].is_null());
+        assert!(value["reproduce"].as_str().unwrap().ends_with("--duration 10"));
+    }
+}
\ No newline at end of file
@@ -0,0 +1,48 @@
+#[cfg(feature = "export")]
+pub mod bifurcation;
+#[cfg(feature = "export")]
+pub mod geometry;
+#[cfg(feature = "export")]
+pub mod gltf;
+#[cfg(feature = "hdf5")]
+pub mod hdf5;
+#[cfg(feature = "export")]
+pub mod logger;
+#[cfg(feature = "export")]
+pub mod manifest;
+#[cfg(feature = "export")]
+pub mod numpy;
+#[cfg(feature = "export")]
+pub mod poincare;
+#[cfg(feature = "export")]
+pub mod stats;
+#[cfg(feature = "export")]
+pub mod symbols;
+#[cfg(feature = "export")]
+pub mod trajectory;
+
+use std::io;
+use std::path::PathBuf;
+
+use crate::capture::output_path;
+use crate::simulation::integrator::TrailPoint;
+
+pub fn coordinates(point: &TrailPoint) -> (f32, f32, f32) {
+    point.position.into()
+}
+
+pub fn resolve_path(requested: &str, prefix: &str, extension: &str) -> io::Result<PathBuf> {
+    let requested = requested.trim();
+    if requested.is_empty() {
+        return output_path(prefix, extension);
+    }
+
+    let mut path = PathBuf::from(requested);
+    if path.extension().is_none() {
+        path.set_extension(extension);
+    }
+    if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+        std::fs::create_dir_all(parent)?;
+    }
+    Ok(path)
+}
\ No newline at end of file
@@ -0,0 +1,211 @@
+use std::io::{self, Write};
+
+use crc32fast::Hasher;
+
+use crate::config::SimulationConfig;
+use crate::simulation::integrator::TrailPoint;
+use super::coordinates;
+
+pub const TRAJECTORY_COLUMNS: [&str; 5] = ["t", "x", "y", "z", "speed"];
+
+fn shape_literal(shape: &[usize]) -> String {
+    match shape {
+        [] => "()".to_string(),
+        [n] => format!("({n},)"),
+        _ => {
+            let dims: Vec<String> = shape.iter().map(|d| d.to_string()).collect();
+            format!("({})", dims.join(", "))
+        }
+    }
+}
+
+pub fn npy_header(descr: &str, shape: &[usize]) -> Vec<u8> {
+    let dict = format!(
+        "{{'descr': '{descr}', 'fortran_order': False, 'shape': {}, }}",
+        shape_literal(shape)
+    );
+    let unpadded = 10 + dict.len() + 1;
+    let padding = (64 - unpadded % 64) % 64;
+    let header_len = dict.len() + padding + 1;
+
+    let mut bytes = b"\x93NUMPY\x01\x00".to_vec();
+    bytes.extend_from_slice(&(header_len as u16).to_le_bytes());
+    bytes.extend_from_slice(dict.as_bytes());
+    bytes.resize(bytes.len() + padding, b' ');
+    bytes.push(b'\n');
+    bytes
+}
+
+pub fn npy_f64(shape: &[usize], values: impl IntoIterator<Item = f64>) -> Vec<u8> {
+    let mut bytes = npy_header("<f8", shape);
+    for value in values {
+        bytes.extend_from_slice(&value.to_le_bytes());
+    }
+    bytes
+}
+
+pub fn npy_str(value: &str) -> Vec<u8> {
+    let chars: Vec<char> = value.chars().collect();
+    let mut bytes = npy_header(&format!("<U{}", chars.len().max(1)), &[]);
+    for c in &chars {
+        bytes.extend_from_slice(&(*c as u32).to_le_bytes());
+    }
+    if chars.is_empty() {
+        bytes.extend_from_slice(&[0; 4]);
+    }
+    bytes
+}
+
+pub fn trajectory_npy<'a>(points: impl ExactSizeIterator<Item = &'a TrailPoint>) -> Vec<u8> {
+    let rows = points.len();
+    npy_f64(
+        &[rows, TRAJECTORY_COLUMNS.len()],
+        points.flat_map(|point| {
+            let (x, y, z) = coordinates(point);
+            [point.time, x as f64, y as f64, z as f64, point.speed as f64]
+        }),
+    )
+}
+
+pub struct StoredZip<W: Write> {
+    out: W,
+    offset: u32,
+    entries: Vec<(String, u32, u32, u32)>,
+}
+
+impl<W: Write> StoredZip<W> {
+    pub fn new(out: W) -> Self {
+        Self {
+            out,
+            offset: 0,
+            entries: Vec::new(),
+        }
+    }
+
+    pub fn add(&mut self, name: &str, data: &[u8]) -> io::Result<()> {
+        let mut hasher = Hasher::new();
+        hasher.update(data);
+        let crc = hasher.finalize();
+        let size = u32::try_from(data.len())
+            .map_err(|_| io::Error::new(io::ErrorKind::InvalidInput, "npz entry over 4 GiB"))?;
+
+        let mut header = Vec::with_capacity(30 + name.len());
+        header.extend_from_slice(&0x0403_4b50u32.to_le_bytes());
+        header.extend_from_slice(&20u16.to_le_bytes());
+        header.extend_from_slice(&[0; 8]);
+        header.extend_from_slice(&crc.to_le_bytes());
+        header.extend_from_slice(&size.to_le_bytes());
+        header.extend_from_slice(&size.to_le_bytes());
+        header.extend_from_slice(&(name.len() as u16).to_le_bytes());
+        header.extend_from_slice(&0u16.to_le_bytes());
+        header.extend_from_slice(name.as_bytes());
+        self.out.write_all(&header)?;
+        self.out.write_all(data)?;
+
+        self.entries.push((name.to_string(), crc, size, self.offset));
+        self.offset += header.len() as u32 + size;
+        Ok(())
+    }
+
+    pub fn finish(mut self) -> io::Result<W> {
+        let directory_offset = self.offset;
+        let mut directory = Vec::new();
+        for (name, crc, size, offset) in &self.entries {
+            directory.extend_from_slice(&0x0201_4b50u32.to_le_bytes());
+            directory.extend_from_slice(&20u16.to_le_bytes());
+            directory.extend_from_slice(&20u16.to_le_bytes());
+            directory.extend_from_slice(&[0; 8]);
+            directory.extend_from_slice(&crc.to_le_bytes());
+            directory.extend_from_slice(&size.to_le_bytes());
+            directory.extend_from_slice(&size.to_le_bytes());
+            directory.extend_from_slice(&(name.len() as u16).to_le_bytes());
+            directory.extend_from_slice(&[0; 12]);
+            directory.extend_from_slice(&offset.to_le_bytes());
+            directory.extend_from_slice(name.as_bytes());
+        }
+
+        let count = self.entries.len() as u16;
+        directory.extend_from_slice(&0x0605_4b50u32.to_le_bytes());
+        directory.extend_from_slice(&[0; 4]);
+        directory.extend_from_slice(&count.to_le_bytes());
+        directory.extend_from_slice(&count.to_le_bytes());
+        directory.extend_from_slice(&(directory.len() as u32 - 12).to_le_bytes());
+        directory.extend_from_slice(&directory_offset.to_le_bytes());
+        directory.extend_from_slice(&0u16.to_le_bytes());
+        self.out.write_all(&directory)?;
+        self.out.flush()?;
+        Ok(self.out)
+    }
+}
+
+pub fn write_npy<'a, W: Write>(
+    points: impl ExactSizeIterator<Item = &'a TrailPoint>,
+    mut out: W,
+) -> io::Result<usize> {
+    let rows = points.len();
+    out.write_all(&trajectory_npy(points))?;
+    out.flush()?;
+    Ok(rows)
+}
+
+pub fn write_npz<'a, W: Write>(
+    points: impl ExactSizeIterator<Item = &'a TrailPoint>,
+    config: &SimulationConfig,
+    out: W,
+) -> io::Result<usize> {
+    let rows = points.len();
+    let mut zip = StoredZip::new(out);
+    zip.add("trajectory.npy", &trajectory_npy(points))?;
+    zip.add("columns.npy", &npy_str(&TRAJECTORY_COLUMNS.join(",")))?;
+    for (name, value) in [
+        ("sigma", config.sigma),
+        ("rho", config.rho),
+        ("beta", config.beta),
+        ("dt", config.dt),
+    ] {
+        zip.add(&format!("{name}.npy"), &npy_f64(&[], [value]))?;
+    }
+    zip.add(
+        "initial_state.npy",
+        &npy_f64(&[3], [config.initial_x, config.initial_y, config.initial_z]),
+    )?;
+    zip.add("method.npy", &npy_str(config.method.short_name()))?;
+    zip.finish()?;
+    Ok(rows)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_npy_header_is_aligned() {
+        let header = npy_header("<f8", &[25_000, 5]);
+        assert_eq!(header.len() % 64, 0);
+        assert_eq!(&header[..8], b"\x93NUMPY\x01\x00");
+        let len = u16::from_le_bytes([header[8], header[9]]) as usize;
+        assert_eq!(10 + len, header.len());
+        let dict = std::str::from_utf8(&header[10..]).unwrap();
+        assert!(dict.contains("'shape': (25000, 5)"));
+        assert!(dict.ends_with('\n'));
+        assert!(std::str::from_utf8(&npy_header("<f8", &[3])[10..])
+            .unwrap()
+            .contains("'shape': (3,)"));
+    }
+
+    #[test]
+    fn test_npz_directory_lists_entries() {
+        let mut out = Vec::new();
+        write_npz(std::iter::empty(), &SimulationConfig::default(), &mut out).unwrap();
+
+        let eocd = out.len() - 22;
+        assert_eq!(&out[eocd..eocd + 4], &0x0605_4b50u32.to_le_bytes());
+        let count = u16::from_le_bytes([out[eocd + 10], out[eocd + 11]]);
+        assert_eq!(count, 8);
+        let size = u32::from_le_bytes(out[eocd + 12..eocd + 16].try_into().unwrap()) as usize;
+        let offset = u32::from_le_bytes(out[eocd + 16..eocd + 20].try_into().unwrap()) as usize;
+        assert_eq!(offset + size, eocd);
+        assert_eq!(&out[offset..offset + 4], &0x0201_4b50u32.to_le_bytes());
+        assert_eq!(&out[..4], &0x0403_4b50u32.to_le_bytes());
+    }
+}
\ No newline at end of file
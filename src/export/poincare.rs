@@ -0,0 +1,107 @@
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+
+use bevy::prelude::*;
+
+use crate::capture::chosen_or_output_path;
+use crate::config::{ExportPoincareEvent, SimulationConfig};
+use crate::i18n::trf;
+use crate::simulation::integrator::{TrailBuffer, TrailPoint};
+use crate::ui::toast::Toasts;
+use crate::simulation::lorenz;
+use super::coordinates;
+
+pub use lorenz::Crossing;
+
+/// The plane z = ρ − 1 passes through both non-trivial fixed points C±.
+pub fn section_height(config: &SimulationConfig) -> f64 {
+    config.rho - 1.0
+}
+
+/// Upward crossings of the plane z = `plane_z` by the trail.
+pub fn section_crossings<'a>(
+    points: impl Iterator<Item = &'a TrailPoint>,
+    plane_z: f64,
+) -> Vec<Crossing> {
+    let samples = points.map(|point| {
+        let (x, y, z) = coordinates(point);
+        (point.time, [x as f64, y as f64, z as f64])
+    });
+    lorenz::section_crossings(samples, plane_z)
+}
+
+pub fn write_csv<W: Write>(crossings: &[Crossing], mut out: W) -> io::Result<usize> {
+    writeln!(out, "n,t,x,y,return_time")?;
+    let mut last_time: Option<f64> = None;
+    for (n, crossing) in crossings.iter().enumerate() {
+        let return_time = last_time.map(|t| (crossing.time - t).to_string()).unwrap_or_default();
+        writeln!(out, "{n},{},{},{},{return_time}", crossing.time, crossing.x, crossing.y)?;
+        last_time = Some(crossing.time);
+    }
+    out.flush()?;
+    Ok(crossings.len())
+}
+
+pub fn export_poincare_system(
+    mut events: EventReader<ExportPoincareEvent>,
+    config: Res<SimulationConfig>,
+    trail: Res<TrailBuffer>,
+    mut toasts: ResMut<Toasts>,
+) {
+    let Some(event) = events.read().last() else {
+        return;
+    };
+
+    let crossings = section_crossings(trail.points.iter(), section_height(&config));
+    let chosen = event.path.as_deref();
+    let result = chosen_or_output_path(chosen, "poincare", "csv").and_then(|path| {
+        write_csv(&crossings, BufWriter::new(File::create(&path)?)).map(|rows| (path, rows))
+    });
+    match result {
+        Ok((path, rows)) => {
+            toasts.push(trf(
+                "💾 Exported {rows} crossings to {path}",
+                &[("rows", &rows), ("path", &path.display())],
+            ))
+        }
+        Err(err) => toasts.push(trf("Export failed: {err}", &[("err", &err)])),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn point(t: f64, x: f32, y: f32, z: f32) -> TrailPoint {
+        TrailPoint {
+            position: Vec3::new(x, y, z),
+            color: Color::WHITE,
+            time: t,
+            speed: 0.0,
+        }
+    }
+
+    #[test]
+    fn test_upward_crossings_are_interpolated() {
+        let points = [
+            point(0.0, 0.0, 0.0, 20.0),
+            point(1.0, 2.0, 4.0, 30.0),
+            point(2.0, 2.0, 4.0, 20.0),
+            point(3.0, 6.0, 0.0, 27.0),
+        ];
+        let crossings = section_crossings(points.iter(), 27.0);
+        assert_eq!(crossings.len(), 2);
+        assert!((crossings[0].time - 0.7).abs() < 1e-9);
+        assert!((crossings[0].x - 1.4).abs() < 1e-6);
+        assert!((crossings[0].y - 2.8).abs() < 1e-6);
+        assert_eq!(crossings[1], Crossing { time: 3.0, x: 6.0, y: 0.0 });
+
+        let mut out = Vec::new();
+        write_csv(&crossings, &mut out).unwrap();
+        let text = String::from_utf8(out).unwrap();
+        let lines: Vec<&str> = text.lines().collect();
+        assert_eq!(lines[0], "n,t,x,y,return_time");
+        assert!(lines[1].ends_with(','));
+        assert!(lines[2].starts_with("1,3,6,0,2.3"));
+    }
+}
\ No newline at end of file
@@ -0,0 +1,87 @@
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+
+use bevy::prelude::*;
+
+use crate::capture::chosen_or_output_path;
+use crate::config::ExportStatsEvent;
+use crate::i18n::trf;
+use crate::simulation::statistics::{StatsHistory, StatsSample};
+use crate::ui::toast::Toasts;
+
+pub fn write_csv<'a, W: Write>(
+    samples: impl Iterator<Item = &'a StatsSample>,
+    mut out: W,
+) -> io::Result<usize> {
+    writeln!(out, "t,energy,speed,average_speed,arc_length,lyapunov,lobe_switches")?;
+    let mut rows = 0;
+    for sample in samples {
+        let lyapunov = sample.lyapunov.map(|l| l.to_string()).unwrap_or_default();
+        writeln!(
+            out,
+            "{},{},{},{},{},{lyapunov},{}",
+            sample.time,
+            sample.energy,
+            sample.speed,
+            sample.average_speed,
+            sample.arc_length,
+            sample.lobe_switches
+        )?;
+        rows += 1;
+    }
+    out.flush()?;
+    Ok(rows)
+}
+
+pub fn export_stats_system(
+    mut events: EventReader<ExportStatsEvent>,
+    history: Res<StatsHistory>,
+    mut toasts: ResMut<Toasts>,
+) {
+    let Some(event) = events.read().last() else {
+        return;
+    };
+
+    let chosen = event.path.as_deref();
+    let result = chosen_or_output_path(chosen, "stats", "csv").and_then(|path| {
+        write_csv(history.samples.iter(), BufWriter::new(File::create(&path)?))
+            .map(|rows| (path, rows))
+    });
+    match result {
+        Ok((path, rows)) => toasts.push(trf(
+            "💾 Exported {rows} samples to {path}",
+            &[("rows", &rows), ("path", &path.display())],
+        )),
+        Err(err) => toasts.push(trf("Export failed: {err}", &[("err", &err)])),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_missing_lyapunov_is_an_empty_cell() {
+        let sample = StatsSample {
+            time: 0.5,
+            energy: 12.0,
+            speed: 3.0,
+            average_speed: 2.5,
+            arc_length: 1.25,
+            lyapunov: None,
+            lobe_switches: 0,
+        };
+        let later = StatsSample {
+            time: 0.6,
+            lyapunov: Some(0.75),
+            lobe_switches: 1,
+            ..sample
+        };
+        let mut out = Vec::new();
+        assert_eq!(write_csv([sample, later].iter(), &mut out).unwrap(), 2);
+        let text = String::from_utf8(out).unwrap();
+        let lines: Vec<&str> = text.lines().collect();
+        assert_eq!(lines[1], "0.5,12,3,2.5,1.25,,0");
+        assert_eq!(lines[2], "0.6,12,3,2.5,1.25,0.75,1");
+    }
+}
\ No newline at end of file
@@ -0,0 +1,42 @@
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+
+use bevy::prelude::*;
+
+use crate::capture::chosen_or_output_path;
+use crate::config::{ExportSymbolsEvent, SimulationStats};
+use crate::i18n::trf;
+use crate::ui::toast::Toasts;
+
+/// One `L`/`R` symbol per wing visit, wrapped at 80 columns.
+pub fn write_symbols<W: Write>(symbols: &str, mut out: W) -> io::Result<usize> {
+    for line in symbols.as_bytes().chunks(80) {
+        out.write_all(line)?;
+        out.write_all(b"\n")?;
+    }
+    out.flush()?;
+    Ok(symbols.len())
+}
+
+pub fn export_symbols_system(
+    mut events: EventReader<ExportSymbolsEvent>,
+    stats: Res<SimulationStats>,
+    mut toasts: ResMut<Toasts>,
+) {
+    let Some(event) = events.read().last() else {
+        return;
+    };
+
+    let chosen = event.path.as_deref();
+    let result = chosen_or_output_path(chosen, "symbols", "txt").and_then(|path| {
+        write_symbols(&stats.lobes.symbols, BufWriter::new(File::create(&path)?))
+            .map(|count| (path, count))
+    });
+    match result {
+        Ok((path, count)) => toasts.push(trf(
+            "💾 Exported {count} symbols to {path}",
+            &[("count", &count), ("path", &path.display())],
+        )),
+        Err(err) => toasts.push(trf("Export failed: {err}", &[("err", &err)])),
+    }
+}
\ No newline at end of file
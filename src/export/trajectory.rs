@@ -0,0 +1,140 @@
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+
+use bevy::prelude::*;
+
+use crate::config::{ExportSettings, ExportTrajectoryEvent, SimulationConfig, TrajectoryFormat};
+use crate::i18n::trf;
+use crate::simulation::integrator::{TrailBuffer, TrailPoint};
+use crate::ui::toast::Toasts;
+use super::geometry::{write_obj, write_ply};
+use super::gltf::{tube_mesh, write_glb};
+use super::numpy::{write_npy, write_npz};
+use super::{coordinates, resolve_path};
+
+pub fn write_csv<'a, W: Write>(
+    points: impl Iterator<Item = &'a TrailPoint>,
+    mut out: W,
+) -> io::Result<usize> {
+    writeln!(out, "t,x,y,z,speed")?;
+    let mut rows = 0;
+    for point in points {
+        let (x, y, z) = coordinates(point);
+        writeln!(out, "{},{x},{y},{z},{}", point.time, point.speed)?;
+        rows += 1;
+    }
+    out.flush()?;
+    Ok(rows)
+}
+
+pub fn write_json<'a, W: Write>(
+    points: impl Iterator<Item = &'a TrailPoint>,
+    mut out: W,
+) -> io::Result<usize> {
+    write!(out, "[")?;
+    let mut rows = 0;
+    for point in points {
+        let (x, y, z) = coordinates(point);
+        let separator = if rows == 0 { "" } else { "," };
+        write!(
+            out,
+            "{separator}\n  {{\"t\":{},\"x\":{x},\"y\":{y},\"z\":{z},\"speed\":{}}}",
+            point.time, point.speed
+        )?;
+        rows += 1;
+    }
+    writeln!(out, "\n]")?;
+    out.flush()?;
+    Ok(rows)
+}
+
+pub fn export_trail(
+    trail: &TrailBuffer,
+    settings: &ExportSettings,
+    config: &SimulationConfig,
+    path: &Path,
+) -> io::Result<usize> {
+    let out = || File::create(path).map(BufWriter::new);
+    match settings.trajectory_format {
+        TrajectoryFormat::Csv => write_csv(trail.points.iter(), out()?),
+        TrajectoryFormat::Json => write_json(trail.points.iter(), out()?),
+        TrajectoryFormat::Ply => write_ply(trail.points.iter(), out()?),
+        TrajectoryFormat::Obj => write_obj(trail.points.iter(), out()?),
+        TrajectoryFormat::Glb => {
+            let mesh = tube_mesh(
+                trail.points.iter(),
+                settings.tube_radius,
+                settings.tube_sides,
+            );
+            write_glb(&mesh, config, trail.points.len(), out()?)
+        }
+        TrajectoryFormat::Npy => write_npy(trail.points.iter(), out()?),
+        TrajectoryFormat::Npz => write_npz(trail.points.iter(), config, out()?),
+        #[cfg(feature = "hdf5")]
+        TrajectoryFormat::Hdf5 => super::hdf5::write_hdf5(trail.points.iter(), config, path),
+    }
+}
+
+pub fn export_trajectory_system(
+    mut events: EventReader<ExportTrajectoryEvent>,
+    settings: Res<ExportSettings>,
+    config: Res<SimulationConfig>,
+    trail: Res<TrailBuffer>,
+    mut toasts: ResMut<Toasts>,
+) {
+    if events.read().count() == 0 {
+        return;
+    }
+
+    let format = settings.trajectory_format;
+    let result = resolve_path(&settings.path, "trajectory", format.extension())
+        .and_then(|path| export_trail(&trail, &settings, &config, &path).map(|rows| (path, rows)));
+    match result {
+        Ok((path, rows)) => toasts.push(trf(
+            "💾 Exported {rows} points to {path}",
+            &[("rows", &rows), ("path", &path.display())],
+        )),
+        Err(err) => toasts.push(trf("Export failed: {err}", &[("err", &err)])),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_points() -> Vec<TrailPoint> {
+        (0..3)
+            .map(|i| TrailPoint {
+                position: Vec3::new(i as f32, 0.5, 2.0 * i as f32),
+                color: Color::WHITE,
+                time: i as f64 * 0.25,
+                speed: 10.0,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_csv_writes_lorenz_axes() {
+        let mut out = Vec::new();
+        assert_eq!(write_csv(sample_points().iter(), &mut out).unwrap(), 3);
+        let text = String::from_utf8(out).unwrap();
+        let lines: Vec<&str> = text.lines().collect();
+        assert_eq!(lines[0], "t,x,y,z,speed");
+        assert_eq!(lines[3], "0.5,2,0.5,4,10");
+    }
+
+    #[test]
+    fn test_json_is_array_of_records() {
+        let mut out = Vec::new();
+        write_json(sample_points().iter(), &mut out).unwrap();
+        let text = String::from_utf8(out).unwrap();
+        assert!(text.starts_with("[\n  {\"t\":0,\"x\":0,\"y\":0.5,\"z\":0,\"speed\":10},"));
+        assert!(text.trim_end().ends_with("}\n]"));
+        assert_eq!(text.matches('{').count(), 3);
+
+        let mut empty = Vec::new();
+        write_json(std::iter::empty(), &mut empty).unwrap();
+        assert_eq!(String::from_utf8(empty).unwrap(), "[\n]\n");
+    }
+}
\ No newline at end of file
@@ -0,0 +1,178 @@
+//! UI translation. Strings are written in English at the call site and looked
+//! up in a per-language TOML catalog keyed by the English text, falling back
+//! to English when a catalog has no entry.
+
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::sync::atomic::{AtomicU8, Ordering};
+use std::sync::OnceLock;
+
+use serde::{Deserialize, Serialize};
+
+static LANGUAGE: AtomicU8 = AtomicU8::new(Language::English as u8);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum Language {
+    #[default]
+    English,
+    Spanish,
+}
+
+impl Language {
+    pub const ALL: [Language; 2] = [Self::English, Self::Spanish];
+
+    /// The language's own name, so it can be found without reading the
+    /// current one.
+    pub fn native_name(&self) -> &'static str {
+        match self {
+            Self::English => "English",
+            Self::Spanish => "Español",
+        }
+    }
+
+    fn catalog(&self) -> Option<&'static HashMap<String, String>> {
+        static SPANISH: OnceLock<HashMap<String, String>> = OnceLock::new();
+        match self {
+            Self::English => None,
+            Self::Spanish => Some(SPANISH.get_or_init(|| parse_catalog(SPANISH_CATALOG))),
+        }
+    }
+}
+
+const SPANISH_CATALOG: &str = include_str!("../locales/es.toml");
+
+fn parse_catalog(text: &str) -> HashMap<String, String> {
+    toml::from_str(text).unwrap_or_else(|err| {
+        bevy::log::warn!("Ignoring malformed translation catalog: {err}");
+        HashMap::new()
+    })
+}
+
+pub fn set_language(language: Language) {
+    LANGUAGE.store(language as u8, Ordering::Relaxed);
+}
+
+pub fn language() -> Language {
+    match LANGUAGE.load(Ordering::Relaxed) {
+        1 => Language::Spanish,
+        _ => Language::English,
+    }
+}
+
+fn translate(language: Language, text: &str) -> &str {
+    language
+        .catalog()
+        .and_then(|catalog| catalog.get(text))
+        .map_or(text, String::as_str)
+}
+
+/// Translates `text` into the current language.
+pub fn tr(text: &str) -> &str {
+    translate(language(), text)
+}
+
+/// Translates `text`, then fills `{name}` placeholders from `args`.
+pub fn trf(text: &str, args: &[(&str, &dyn Display)]) -> String {
+    let mut out = tr(text).to_string();
+    for (name, value) in args {
+        out = out.replace(&format!("{{{name}}}"), &value.to_string());
+    }
+    out
+}
+
+/// Marks `text` for translation without translating it, for labels that are
+/// also written to files. Pass the result through `tr` where it is displayed.
+pub const fn tr_noop(text: &'static str) -> &'static str {
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeSet;
+    use std::fs;
+    use std::path::Path;
+
+    use super::*;
+
+    /// Reads the string literal starting just after its opening quote.
+    fn read_literal(source: &str) -> String {
+        let mut out = String::new();
+        let mut chars = source.chars();
+        while let Some(c) = chars.next() {
+            match c {
+                '"' => break,
+                '\\' => match chars.next() {
+                    Some('n') => out.push('\n'),
+                    Some('\n') => {
+                        let rest: String = chars.clone().collect();
+                        let skipped = rest.len() - rest.trim_start().len();
+                        for _ in rest[..skipped].chars() {
+                            chars.next();
+                        }
+                    }
+                    Some(other) => out.push(other),
+                    None => break,
+                },
+                c => out.push(c),
+            }
+        }
+        out
+    }
+
+    fn marked_strings(dir: &Path, found: &mut BTreeSet<String>) {
+        for entry in fs::read_dir(dir).unwrap().flatten() {
+            let path = entry.path();
+            if path.is_dir() {
+                marked_strings(&path, found);
+                continue;
+            }
+            if path.extension().is_none_or(|e| e != "rs") {
+                continue;
+            }
+            let source = fs::read_to_string(&path).unwrap();
+            for name in ["tr", "trf", "tr_noop"] {
+                let marker = format!("{name}(");
+                for (at, _) in source.match_indices(&marker) {
+                    let preceding = source[..at].chars().next_back();
+                    if preceding.is_some_and(|c| c.is_alphanumeric() || c == '_') {
+                        continue;
+                    }
+                    let argument = source[at + marker.len()..].trim_start();
+                    if let Some(literal) = argument.strip_prefix('"') {
+                        found.insert(read_literal(literal));
+                    }
+                }
+            }
+        }
+    }
+
+    fn placeholders(text: &str) -> BTreeSet<&str> {
+        text.split('{')
+            .skip(1)
+            .filter_map(|part| part.split_once('}').map(|(name, _)| name))
+            .collect()
+    }
+
+    #[test]
+    fn test_spanish_catalog_covers_every_marked_string() {
+        let mut marked = BTreeSet::new();
+        marked_strings(&Path::new(env!("CARGO_MANIFEST_DIR")).join("src"), &mut marked);
+        marked.remove("");
+        let catalog: HashMap<String, String> = toml::from_str(SPANISH_CATALOG).unwrap();
+
+        let missing: Vec<_> = marked.iter().filter(|t| !catalog.contains_key(*t)).collect();
+        assert!(missing.is_empty(), "untranslated: {missing:#?}");
+        let unused: Vec<_> = catalog.keys().filter(|k| !marked.contains(*k)).collect();
+        assert!(unused.is_empty(), "no longer used: {unused:#?}");
+        for (english, spanish) in &catalog {
+            assert_eq!(placeholders(english), placeholders(spanish), "{english}");
+        }
+    }
+
+    #[test]
+    fn test_falls_back_to_english() {
+        assert_eq!(translate(Language::Spanish, "Not in any catalog"), "Not in any catalog");
+        let text = "{n} apples, {n} pears";
+        assert_eq!(trf(text, &[("n", &3)]), "3 apples, 3 pears");
+    }
+}
\ No newline at end of file
@@ -0,0 +1,209 @@
+//! Debug checks of properties the simulation and camera must keep on every
+//! frame. A violation is reported once as a toast when it appears and stays
+//! listed in the Performance section until it clears, so a regression shows
+//! up while developing and can be quoted in a bug report.
+
+use std::iter;
+
+use bevy::prelude::*;
+
+use crate::config::{SimulationConfig, SimulationStats};
+use crate::i18n::{tr, tr_noop, trf};
+use crate::rendering::camera_controller::{OrbitCamera, MAX_RADIUS, MIN_RADIUS};
+use crate::simulation::integrator::TrailBuffer;
+use crate::simulation::trajectories::ExtraTrajectory;
+use crate::ui::toast::Toasts;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Invariant {
+    TrailWithinCap,
+    FiniteStats,
+    MonotoneTime,
+    CameraRadius,
+}
+
+impl Invariant {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::TrailWithinCap => tr_noop("Trail longer than its cap"),
+            Self::FiniteStats => tr_noop("Non-finite statistics"),
+            Self::MonotoneTime => tr_noop("Simulation time went backwards"),
+            Self::CameraRadius => tr_noop("Camera distance out of bounds"),
+        }
+    }
+}
+
+#[derive(Resource)]
+pub struct InvariantChecks {
+    /// On by default in debug builds; `--check-invariants` turns it on in
+    /// release builds.
+    pub enabled: bool,
+    /// What was violated on the last checked frame, with the offending values.
+    pub violations: Vec<(Invariant, String)>,
+    /// Frames with at least one violation since the checks were turned on.
+    pub failed_frames: u64,
+}
+
+impl Default for InvariantChecks {
+    fn default() -> Self {
+        Self {
+            enabled: cfg!(debug_assertions),
+            violations: Vec::new(),
+            failed_frames: 0,
+        }
+    }
+}
+
+pub fn trail_over_cap(trail: &TrailBuffer) -> Option<String> {
+    (trail.points.len() > trail.max_points)
+        .then(|| format!("{} > {}", trail.points.len(), trail.max_points))
+}
+
+/// Names of the statistics that are NaN or infinite, if any.
+pub fn non_finite_stats(stats: &SimulationStats) -> Option<String> {
+    let running = &stats.running;
+    let values = [
+        ("integration_time_us", stats.integration_time_us),
+        ("energy", stats.current_energy),
+        ("velocity", stats.current_velocity),
+        ("divergence", stats.divergence),
+        ("arc_length", stats.arc_length),
+        ("travel_time", stats.travel_time),
+        ("mean_x", running.coordinates[0].mean),
+        ("mean_y", running.coordinates[1].mean),
+        ("mean_z", running.coordinates[2].mean),
+        ("mean_speed", running.speed.mean),
+        ("lyapunov", stats.lyapunov.exponent().unwrap_or_default()),
+    ];
+    let bad: Vec<&str> = values
+        .iter()
+        .filter(|(_, value)| !value.is_finite())
+        .map(|(name, _)| *name)
+        .collect();
+    (!bad.is_empty()).then(|| bad.join(", "))
+}
+
+/// First pair among the newest `recent` trail points whose time does not
+/// increase. Only the points added since the last frame need checking.
+pub fn time_reversal(trail: &TrailBuffer, recent: usize) -> Option<String> {
+    let start = trail.points.len().saturating_sub(recent + 1);
+    let times: Vec<f64> = trail
+        .points
+        .range(start..)
+        .map(|point| point.time)
+        .collect();
+    times
+        .windows(2)
+        .find(|pair| pair[1] <= pair[0])
+        .map(|pair| format!("t {} → {}", pair[0], pair[1]))
+}
+
+pub fn camera_radius(radius: f32) -> Option<String> {
+    (!(MIN_RADIUS..=MAX_RADIUS).contains(&radius))
+        .then(|| format!("{radius} ∉ [{MIN_RADIUS}, {MAX_RADIUS}]"))
+}
+
+pub fn invariant_check_system(
+    mut checks: ResMut<InvariantChecks>,
+    config: Res<SimulationConfig>,
+    trail: Res<TrailBuffer>,
+    stats: Res<SimulationStats>,
+    extras: Query<(&ExtraTrajectory, &TrailBuffer, &SimulationStats)>,
+    cameras: Query<&OrbitCamera>,
+    mut toasts: ResMut<Toasts>,
+) {
+    if !checks.enabled {
+        if !checks.violations.is_empty() {
+            checks.violations.clear();
+        }
+        return;
+    }
+
+    let recent = config.steps_per_frame as usize;
+    let trajectories = iter::once((tr("Main").to_string(), &*trail, &*stats)).chain(
+        extras
+            .iter()
+            .map(|(extra, trail, stats)| (extra.name.clone(), trail, stats)),
+    );
+    let mut found = Vec::new();
+    for (name, trail, stats) in trajectories {
+        let checked = [
+            (Invariant::TrailWithinCap, trail_over_cap(trail)),
+            (Invariant::FiniteStats, non_finite_stats(stats)),
+            (Invariant::MonotoneTime, time_reversal(trail, recent)),
+        ];
+        for (invariant, detail) in checked {
+            if let Some(detail) = detail {
+                found.push((invariant, format!("{name}: {detail}")));
+            }
+        }
+    }
+    for orbit in &cameras {
+        if let Some(detail) = camera_radius(orbit.radius) {
+            found.push((Invariant::CameraRadius, detail));
+        }
+    }
+
+    for (invariant, detail) in &found {
+        if !checks.violations.iter().any(|(seen, _)| seen == invariant) {
+            toasts.push(trf(
+                "⚠ {invariant}: {detail}",
+                &[("invariant", &tr(invariant.label())), ("detail", detail)],
+            ));
+        }
+    }
+    if !found.is_empty() {
+        checks.failed_frames += 1;
+    }
+    checks.violations = found;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::simulation::integrator::TrailPoint;
+
+    fn trail(times: &[f64], max_points: usize) -> TrailBuffer {
+        TrailBuffer {
+            points: times
+                .iter()
+                .map(|&time| TrailPoint {
+                    position: Vec3::ZERO,
+                    color: Color::WHITE,
+                    time,
+                    speed: 0.0,
+                })
+                .collect(),
+            max_points,
+        }
+    }
+
+    #[test]
+    fn test_trail_checks_catch_overflow_and_time_reversal() {
+        let good = trail(&[0.0, 0.1, 0.2, 0.3], 4);
+        assert_eq!(trail_over_cap(&good), None);
+        assert_eq!(time_reversal(&good, 8), None);
+
+        let bad = trail(&[0.0, 0.2, 0.1, 0.3, 0.4], 4);
+        assert_eq!(trail_over_cap(&bad).as_deref(), Some("5 > 4"));
+        assert!(time_reversal(&bad, 3).is_some());
+        // The reversal is older than the last frame's points.
+        assert_eq!(time_reversal(&bad, 2), None);
+    }
+
+    #[test]
+    fn test_stats_and_camera_checks() {
+        let mut stats = SimulationStats::default();
+        assert_eq!(non_finite_stats(&stats), None);
+        stats.current_energy = f64::NAN;
+        stats.arc_length = f64::INFINITY;
+        assert_eq!(
+            non_finite_stats(&stats).as_deref(),
+            Some("energy, arc_length")
+        );
+
+        assert_eq!(camera_radius(65.0), None);
+        assert!(camera_radius(MAX_RADIUS * 2.0).is_some());
+        assert!(camera_radius(f32::NAN).is_some());
+    }
+}
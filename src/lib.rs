@@ -0,0 +1,32 @@
+#[cfg(feature = "audio")]
+pub mod audio;
+pub mod capture;
+pub mod cli;
+pub mod config;
+pub mod display;
+pub mod export;
+pub mod i18n;
+pub mod invariants;
+#[cfg(feature = "midi")]
+pub mod midi;
+pub mod osc;
+pub mod persistence;
+pub mod plugin;
+pub mod presets;
+pub mod recovery;
+#[cfg(feature = "rerun")]
+pub mod rerun_log;
+pub mod rendering;
+#[cfg(feature = "scripting")]
+pub mod scripting;
+pub mod session;
+pub mod share;
+pub mod simulation;
+pub mod snapshot;
+pub mod ui;
+pub mod undo;
+pub mod view_sync;
+#[cfg(feature = "websocket")]
+pub mod websocket;
+
+pub use plugin::{LorenzAttractorPlugin, LorenzSet};
\ No newline at end of file
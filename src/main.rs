@@ -1,81 +1,95 @@
-mod config;
-mod rendering;
-mod simulation;
-mod ui;
-
-use bevy::diagnostic::FrameTimeDiagnosticsPlugin;
 use bevy::prelude::*;
-use bevy_egui::EguiPlugin;
+use clap::Parser;
 
-use config::{ResetEvent, SimulationConfig, SimulationStats};
-use rendering::camera_controller::{camera_control_system, EguiWantsPointer, OrbitCamera};
-use rendering::trail_renderer::{draw_axes_system, draw_head_marker_system, draw_trail_system};
-use simulation::integrator::{simulation_system, TrailBuffer};
-use simulation::lorenz::LorenzState;
-use ui::controls::ui_system;
+#[cfg(feature = "export")]
+use lorenz_attractor::capture::batch::{run_batch, BatchJob, BATCH_USAGE};
+use lorenz_attractor::cli::Cli;
+use lorenz_attractor::config::SimulationConfig;
+use lorenz_attractor::display::{apply_display, DisplaySettings};
+use lorenz_attractor::invariants::InvariantChecks;
+use lorenz_attractor::persistence::PersistedSettings;
+use lorenz_attractor::share::{page_query, UrlParams};
+use lorenz_attractor::simulation::headless::{HeadlessSim, HEADLESS_SIM_USAGE};
+use lorenz_attractor::LorenzAttractorPlugin;
 
-fn main() {
-    App::new()
-        .add_plugins(DefaultPlugins.set(WindowPlugin {
-            primary_window: Some(Window {
-                title: "Lorenz Attractor — RK4 / Euler Simulation".into(),
-                resolution: (1400.0, 900.0).into(),
-                ..default()
-            }),
-            ..default()
-        }))
-        .add_plugins(EguiPlugin)
-        .add_plugins(FrameTimeDiagnosticsPlugin::default())
-        .init_resource::<SimulationConfig>()
-        .init_resource::<SimulationStats>()
-        .init_resource::<TrailBuffer>()
-        .init_resource::<EguiWantsPointer>()
-        .add_event::<ResetEvent>()
-        .insert_resource(ClearColor(Color::srgb(0.02, 0.02, 0.04)))
-        .add_systems(Startup, setup_scene)
-        .add_systems(
-            Update,
-            (
-                ui_system,
-                simulation_system,
-                draw_trail_system,
-                draw_head_marker_system,
-                draw_axes_system,
-                camera_control_system,
-            )
-                .chain(),
-        )
-        .run();
-}
+fn main() -> AppExit {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    match HeadlessSim::from_args(&args) {
+        Ok(Some(sim)) => {
+            return match sim.run() {
+                Ok(_) => AppExit::Success,
+                Err(err) if err.kind() == std::io::ErrorKind::BrokenPipe => AppExit::Success,
+                Err(err) => {
+                    eprintln!("error: {err}");
+                    AppExit::error()
+                }
+            };
+        }
+        Ok(None) => {}
+        Err(err) => {
+            eprintln!("error: {err}\n\n{HEADLESS_SIM_USAGE}");
+            return AppExit::error();
+        }
+    }
+    #[cfg(feature = "export")]
+    match BatchJob::from_args(&args) {
+        Ok(Some(job)) => return run_batch(job),
+        Ok(None) => {}
+        Err(err) => {
+            eprintln!("error: {err}\n\n{BATCH_USAGE}");
+            return AppExit::error();
+        }
+    }
 
-fn setup_scene(mut commands: Commands, config: Res<SimulationConfig>) {
-    let orbit = OrbitCamera::default();
+    let argv = std::iter::once("lorenz-attractor".to_string()).chain(args);
+    let cli = match Cli::try_parse_from(argv) {
+        Ok(cli) => cli,
+        Err(err) => {
+            let _ = err.print();
+            return if err.use_stderr() { AppExit::error() } else { AppExit::Success };
+        }
+    };
+    let mut config = SimulationConfig::load_or_default();
+    cli.apply(&mut config);
+    let url_params = page_query().map(|query| UrlParams::parse(&query));
+    if let Some(params) = &url_params {
+        params.apply(&mut config);
+    }
+    let mut display = DisplaySettings::load_or_default();
+    if let Some((width, height)) = cli.window {
+        display.resolution = (width as u32, height as u32);
+    }
+    let mut window = Window {
+        title: "Lorenz Attractor — RK4 / Euler Simulation".into(),
+        ..default()
+    };
+    apply_display(None, &display, &mut window);
 
-    let x = orbit.radius * orbit.theta.sin() * orbit.phi.cos();
-    let y = orbit.radius * orbit.theta.cos();
-    let z = orbit.radius * orbit.theta.sin() * orbit.phi.sin();
-    let camera_pos = orbit.focus + Vec3::new(x, y, z);
+    let default_plugins = DefaultPlugins.set(WindowPlugin {
+        primary_window: Some(window),
+        ..default()
+    });
+    #[cfg(feature = "vr")]
+    let default_plugins = bevy_mod_openxr::add_xr_plugins(default_plugins);
 
-    commands.spawn((
-        Camera3dBundle {
-            transform: Transform::from_translation(camera_pos)
-                .looking_at(orbit.focus, Vec3::Y),
+    let mut app = App::new();
+    app.add_plugins(default_plugins)
+        .insert_resource(config)
+        .insert_resource(display)
+        .insert_resource(ClearColor(Color::srgb(0.02, 0.02, 0.04)))
+        .add_plugins(LorenzAttractorPlugin::default());
+    if let Some(params) = url_params {
+        app.insert_resource(params);
+    }
+    if cli.check_invariants {
+        app.insert_resource(InvariantChecks {
+            enabled: true,
             ..default()
-        },
-        orbit,
-    ));
+        });
+    }
 
-    commands.spawn(LorenzState::new(
-        config.initial_x,
-        config.initial_y,
-        config.initial_z,
-    ));
+    #[cfg(feature = "vr")]
+    app.add_plugins(lorenz_attractor::rendering::vr::VrPlugin);
 
-    info!("Lorenz attractor simulation initialized.");
-    info!("  σ = {}, ρ = {}, β = {:.4}", config.sigma, config.rho, config.beta);
-    info!("  dt = {}, method = {:?}", config.dt, config.method);
-    info!(
-        "  Initial state: ({}, {}, {})",
-        config.initial_x, config.initial_y, config.initial_z
-    );
+    app.run()
 }
\ No newline at end of file
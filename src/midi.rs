@@ -0,0 +1,274 @@
+//! MIDI-learn: hardware knobs and faders bound to σ, ρ, β, dt and the trail
+//! length, with the bindings saved per device.
+use std::collections::BTreeMap;
+use std::sync::mpsc::{channel, Receiver};
+use std::sync::Mutex;
+
+use bevy::prelude::*;
+use midir::{Ignore, MidiInput, MidiInputConnection};
+use serde::{Deserialize, Serialize};
+
+use crate::config::{SimulationConfig, SliderRanges};
+use crate::i18n::{tr, tr_noop, trf};
+use crate::ui::toast::Toasts;
+
+const CLIENT_NAME: &str = "lorenz-attractor";
+/// The span of the trail slider in the Trail panel, in points.
+const TRAIL_RANGE: (f64, f64) = (1_000.0, 100_000.0);
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum MidiTarget {
+    Sigma,
+    Rho,
+    Beta,
+    Dt,
+    TrailLength,
+}
+
+impl MidiTarget {
+    pub const ALL: [Self; 5] = [
+        Self::Sigma,
+        Self::Rho,
+        Self::Beta,
+        Self::Dt,
+        Self::TrailLength,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Sigma => tr_noop("σ (sigma)"),
+            Self::Rho => tr_noop("ρ (rho)"),
+            Self::Beta => tr_noop("β (beta)"),
+            Self::Dt => tr_noop("dt (time step)"),
+            Self::TrailLength => tr_noop("Trail length"),
+        }
+    }
+
+    /// Sets the target from a 0–127 controller value spread over its slider
+    /// range; dt is spread logarithmically, like its slider.
+    pub fn apply(&self, value: u8, config: &mut SimulationConfig, ranges: &SliderRanges) {
+        let f = f64::from(value.min(127)) / 127.0;
+        let linear = |(low, high): (f64, f64)| low + (high - low) * f;
+        match self {
+            Self::Sigma => config.sigma = linear(ranges.sigma),
+            Self::Rho => config.rho = linear(ranges.rho),
+            Self::Beta => config.beta = linear(ranges.beta),
+            Self::Dt => {
+                let (low, high) = ranges.dt;
+                config.dt = low * (high / low).powf(f);
+            }
+            Self::TrailLength => config.max_trail_points = linear(TRAIL_RANGE).round() as usize,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MidiBinding {
+    pub target: MidiTarget,
+    /// 0-based; shown as 1–16.
+    pub channel: u8,
+    pub controller: u8,
+}
+
+/// The chosen device and, for every device used so far, its bindings.
+#[derive(Resource, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MidiMappings {
+    pub device: Option<String>,
+    pub devices: BTreeMap<String, Vec<MidiBinding>>,
+}
+
+impl MidiMappings {
+    pub fn binding(&self, device: &str, target: MidiTarget) -> Option<&MidiBinding> {
+        self.devices
+            .get(device)?
+            .iter()
+            .find(|b| b.target == target)
+    }
+
+    /// Binds `target` to a controller, replacing whatever either was bound to.
+    pub fn learn(&mut self, device: &str, binding: MidiBinding) {
+        let bindings = self.devices.entry(device.to_string()).or_default();
+        bindings.retain(|b| {
+            b.target != binding.target
+                && (b.channel, b.controller) != (binding.channel, binding.controller)
+        });
+        bindings.push(binding);
+    }
+
+    pub fn forget(&mut self, device: &str, target: MidiTarget) {
+        if let Some(bindings) = self.devices.get_mut(device) {
+            bindings.retain(|b| b.target != target);
+        }
+    }
+}
+
+/// Channel, controller and value of a Control Change message.
+pub fn control_change(message: &[u8]) -> Option<(u8, u8, u8)> {
+    match *message {
+        [status, controller, value, ..] if status & 0xF0 == 0xB0 => {
+            Some((status & 0x0F, controller, value))
+        }
+        _ => None,
+    }
+}
+
+fn list_ports() -> Result<Vec<String>, String> {
+    let input = MidiInput::new(CLIENT_NAME).map_err(|error| error.to_string())?;
+    Ok(input
+        .ports()
+        .iter()
+        .filter_map(|port| input.port_name(port).ok())
+        .collect())
+}
+
+type Connection = (MidiInputConnection<()>, Receiver<(u8, u8, u8)>);
+
+fn connect(device: &str) -> Result<Connection, String> {
+    let mut input = MidiInput::new(CLIENT_NAME).map_err(|error| error.to_string())?;
+    input.ignore(Ignore::All);
+    let port = input
+        .ports()
+        .into_iter()
+        .find(|port| input.port_name(port).is_ok_and(|name| name == device))
+        .ok_or_else(|| trf("{device} is not connected", &[("device", &device)]))?;
+    let (sender, receiver) = channel();
+    let connection = input
+        .connect(
+            &port,
+            CLIENT_NAME,
+            move |_, message, _| {
+                if let Some(change) = control_change(message) {
+                    let _ = sender.send(change);
+                }
+            },
+            (),
+        )
+        .map_err(|error| error.to_string())?;
+    Ok((connection, receiver))
+}
+
+#[derive(Resource, Default)]
+pub struct MidiInputState {
+    pub ports: Vec<String>,
+    /// Set to list the ports again on the next frame.
+    pub refresh: bool,
+    pub learning: Option<MidiTarget>,
+    pub error: Option<String>,
+    connected: Option<String>,
+    connection: Option<Mutex<Connection>>,
+    listed: bool,
+}
+
+impl MidiInputState {
+    pub fn is_connected(&self) -> bool {
+        self.connection.is_some()
+    }
+}
+
+pub fn midi_system(
+    mut mappings: ResMut<MidiMappings>,
+    mut midi: ResMut<MidiInputState>,
+    mut config: ResMut<SimulationConfig>,
+    ranges: Res<SliderRanges>,
+    mut toasts: ResMut<Toasts>,
+) {
+    if midi.refresh || !midi.listed {
+        midi.refresh = false;
+        midi.listed = true;
+        match list_ports() {
+            Ok(ports) => midi.ports = ports,
+            Err(error) => midi.error = Some(error),
+        }
+    }
+
+    if midi.connected != mappings.device {
+        midi.connection = None;
+        midi.error = None;
+        midi.connected = mappings.device.clone();
+        if let Some(device) = &mappings.device {
+            match connect(device) {
+                Ok(connection) => midi.connection = Some(Mutex::new(connection)),
+                Err(error) => {
+                    toasts.push(trf("⚠ MIDI input failed: {error}", &[("error", &error)]));
+                    midi.error = Some(error);
+                }
+            }
+        }
+    }
+
+    let (Some(device), Some(connection)) = (&midi.connected, &midi.connection) else {
+        return;
+    };
+    let changes: Vec<_> = match connection.lock() {
+        Ok(connection) => connection.1.try_iter().collect(),
+        Err(_) => return,
+    };
+    let device = device.clone();
+    for (channel, controller, value) in changes {
+        if let Some(target) = midi.learning.take() {
+            mappings.learn(
+                &device,
+                MidiBinding {
+                    target,
+                    channel,
+                    controller,
+                },
+            );
+            toasts.push(trf(
+                "🎹 {target} follows CC {cc} on channel {channel}",
+                &[
+                    ("target", &tr(target.label())),
+                    ("cc", &controller),
+                    ("channel", &(channel + 1)),
+                ],
+            ));
+            continue;
+        }
+        let Some(bindings) = mappings.devices.get(&device) else {
+            continue;
+        };
+        for binding in bindings {
+            if (binding.channel, binding.controller) == (channel, controller) {
+                binding.target.apply(value, &mut config, &ranges);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_control_change_parsing() {
+        assert_eq!(control_change(&[0xB3, 21, 64]), Some((3, 21, 64)));
+        assert_eq!(control_change(&[0x90, 60, 100]), None);
+        assert_eq!(control_change(&[0xB0, 7]), None);
+    }
+
+    #[test]
+    fn test_learning_replaces_old_bindings_and_values_span_ranges() {
+        let mut mappings = MidiMappings::default();
+        let bind = |target, controller| MidiBinding {
+            target,
+            channel: 0,
+            controller,
+        };
+        mappings.learn("Knobs", bind(MidiTarget::Rho, 21));
+        mappings.learn("Knobs", bind(MidiTarget::Sigma, 21));
+        mappings.learn("Knobs", bind(MidiTarget::Rho, 22));
+        assert_eq!(
+            mappings.devices["Knobs"],
+            [bind(MidiTarget::Sigma, 21), bind(MidiTarget::Rho, 22)]
+        );
+        assert_eq!(mappings.binding("Other", MidiTarget::Rho), None);
+
+        let ranges = SliderRanges::default();
+        let mut config = SimulationConfig::default();
+        MidiTarget::Dt.apply(0, &mut config, &ranges);
+        assert_eq!(config.dt, ranges.dt.0);
+        MidiTarget::Rho.apply(127, &mut config, &ranges);
+        assert_eq!(config.rho, ranges.rho.1);
+    }
+}
\ No newline at end of file
@@ -0,0 +1,239 @@
+//! Open Sound Control output of the main trajectory, for driving synths in
+//! SuperCollider, Max or Pure Data. Packets are built on the main thread and
+//! sent from a dedicated one, so a slow network never stalls a frame.
+//!
+//! | Address | Arguments |
+//! |---------|-----------|
+//! | `/lorenz/state` | x, y, z, t |
+//! | `/lorenz/speed` | \|v\| |
+//! | `/lorenz/params` | σ, ρ, β |
+//! | `/lorenz/switch` | wing (`"L"`/`"R"`), switch count, seconds spent in the previous wing |
+use std::io;
+use std::net::{ToSocketAddrs, UdpSocket};
+use std::sync::mpsc::{channel, Receiver, Sender};
+use std::sync::Mutex;
+use std::thread;
+
+use bevy::prelude::*;
+use rosc::{encoder, OscMessage, OscPacket, OscType};
+use serde::{Deserialize, Serialize};
+
+use crate::config::{SimulationConfig, SimulationStats};
+use crate::i18n::trf;
+use crate::simulation::lorenz::LorenzState;
+use crate::simulation::statistics::LobeStats;
+use crate::simulation::trajectories::MainTrajectory;
+use crate::ui::toast::Toasts;
+
+pub const MIN_RATE: f32 = 1.0;
+pub const MAX_RATE: f32 = 240.0;
+
+#[derive(Resource, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct OscSettings {
+    pub enabled: bool,
+    pub host: String,
+    pub port: u16,
+    /// State messages per second; lobe switches are sent as they happen.
+    pub rate: f32,
+}
+
+impl Default for OscSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            host: "127.0.0.1".into(),
+            // SuperCollider's language port.
+            port: 57120,
+            rate: 30.0,
+        }
+    }
+}
+
+fn message(addr: &str, args: Vec<OscType>) -> OscPacket {
+    OscPacket::Message(OscMessage {
+        addr: addr.into(),
+        args,
+    })
+}
+
+pub fn state_packets(state: &LorenzState, speed: f64, config: &SimulationConfig) -> Vec<OscPacket> {
+    let floats = |values: &[f64]| values.iter().map(|v| OscType::Float(*v as f32)).collect();
+    vec![
+        message("/lorenz/state", floats(&[state.x, state.y, state.z, state.t])),
+        message("/lorenz/speed", floats(&[speed])),
+        message("/lorenz/params", floats(&[config.sigma, config.rho, config.beta])),
+    ]
+}
+
+pub fn switch_packet(lobes: &LobeStats) -> Option<OscPacket> {
+    let wing = lobes.current?;
+    let residence = lobes.durations.back().copied().unwrap_or(0.0);
+    Some(message(
+        "/lorenz/switch",
+        vec![
+            OscType::String(wing.symbol().to_string()),
+            OscType::Int(lobes.switches.min(i32::MAX as u64) as i32),
+            OscType::Float(residence as f32),
+        ],
+    ))
+}
+
+/// A sender thread bound to one destination; it exits once dropped.
+struct OscLink {
+    packets: Sender<OscPacket>,
+    errors: Mutex<Receiver<String>>,
+}
+
+impl OscLink {
+    fn start(host: &str, port: u16) -> io::Result<Self> {
+        let target = (host, port).to_socket_addrs()?.next().ok_or_else(|| {
+            io::Error::new(io::ErrorKind::NotFound, format!("{host} has no address"))
+        })?;
+        let local = if target.is_ipv4() { "0.0.0.0:0" } else { "[::]:0" };
+        let socket = UdpSocket::bind(local)?;
+        let (packets, queue) = channel::<OscPacket>();
+        let (report, errors) = channel();
+        thread::Builder::new()
+            .name("osc-sender".into())
+            .spawn(move || {
+                let mut reported = false;
+                for packet in queue {
+                    let result = encoder::encode(&packet)
+                        .map_err(|error| format!("{error:?}"))
+                        .and_then(|bytes| {
+                            socket.send_to(&bytes, target).map_err(|error| error.to_string())
+                        });
+                    // One report per destination is enough; later failures
+                    // are almost always the same one.
+                    if let (Err(error), false) = (result, reported) {
+                        reported = true;
+                        let _ = report.send(error);
+                    }
+                }
+            })?;
+        Ok(Self {
+            packets,
+            errors: Mutex::new(errors),
+        })
+    }
+
+    fn send(&self, packet: OscPacket) {
+        let _ = self.packets.send(packet);
+    }
+}
+
+#[derive(Resource, Default)]
+pub struct OscOutput {
+    link: Option<OscLink>,
+    /// The destination last connected to, even if that failed.
+    target: Option<(String, u16)>,
+    since_send: f32,
+    switches_seen: u64,
+    pub error: Option<String>,
+}
+
+impl OscOutput {
+    pub fn is_connected(&self) -> bool {
+        self.link.is_some()
+    }
+}
+
+pub fn osc_output_system(
+    settings: Res<OscSettings>,
+    mut output: ResMut<OscOutput>,
+    time: Res<Time>,
+    config: Res<SimulationConfig>,
+    stats: Res<SimulationStats>,
+    state: Query<&LorenzState, MainTrajectory>,
+    mut toasts: ResMut<Toasts>,
+) {
+    let wanted = settings.enabled.then(|| (settings.host.clone(), settings.port));
+    if output.target != wanted {
+        output.link = None;
+        output.error = None;
+        if let Some((host, port)) = &wanted {
+            match OscLink::start(host, *port) {
+                Ok(link) => output.link = Some(link),
+                Err(error) => {
+                    toasts.push(trf("⚠ OSC output failed: {error}", &[("error", &error)]));
+                    output.error = Some(error.to_string());
+                }
+            }
+        }
+        output.target = wanted;
+    }
+
+    let output = &mut *output;
+    let Some(link) = &output.link else {
+        return;
+    };
+    if let Some(error) = link.errors.lock().ok().and_then(|errors| errors.try_recv().ok()) {
+        toasts.push(trf("⚠ OSC output failed: {error}", &[("error", &error)]));
+        output.error = Some(error);
+    }
+    let Ok(state) = state.get_single() else {
+        return;
+    };
+
+    let switches = stats.lobes.switches;
+    if switches > output.switches_seen {
+        if let Some(packet) = switch_packet(&stats.lobes) {
+            link.send(packet);
+        }
+    }
+    output.switches_seen = switches;
+
+    output.since_send += time.delta_seconds();
+    if output.since_send < 1.0 / settings.rate.clamp(MIN_RATE, MAX_RATE) {
+        return;
+    }
+    output.since_send = 0.0;
+    for packet in state_packets(state, stats.current_velocity, &config) {
+        link.send(packet);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn test_link_delivers_packets_over_udp() {
+        let receiver = UdpSocket::bind("127.0.0.1:0").unwrap();
+        receiver.set_read_timeout(Some(Duration::from_secs(5))).unwrap();
+        let port = receiver.local_addr().unwrap().port();
+
+        let link = OscLink::start("127.0.0.1", port).unwrap();
+        let state = LorenzState::new(1.5, -2.0, 20.0);
+        for packet in state_packets(&state, 3.0, &SimulationConfig::default()) {
+            link.send(packet);
+        }
+
+        let mut buffer = [0; 1024];
+        let size = receiver.recv(&mut buffer).unwrap();
+        let (_, packet) = rosc::decoder::decode_udp(&buffer[..size]).unwrap();
+        let OscPacket::Message(message) = packet else {
+            panic!("expected a message");
+        };
+        assert_eq!(message.addr, "/lorenz/state");
+        assert_eq!(message.args[..3], [1.5, -2.0, 20.0].map(OscType::Float));
+    }
+
+    #[test]
+    fn test_switch_packet_reports_wing_and_residence() {
+        let mut lobes = LobeStats::default();
+        assert_eq!(switch_packet(&lobes), None);
+        for (x, t) in [(10.0, 0.0), (-10.0, 1.0), (10.0, 2.5)] {
+            lobes.record(x, t);
+        }
+        let Some(OscPacket::Message(message)) = switch_packet(&lobes) else {
+            panic!("expected a message");
+        };
+        assert_eq!(
+            message.args,
+            [OscType::String("R".into()), OscType::Int(2), OscType::Float(1.5)]
+        );
+    }
+}
\ No newline at end of file
@@ -0,0 +1,396 @@
+use std::collections::HashSet;
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+use std::sync::mpsc::{channel, Receiver};
+use std::sync::Mutex;
+
+use bevy::prelude::*;
+use notify::{EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+#[cfg(feature = "audio")]
+use crate::audio::AudioSettings;
+use crate::config::{
+    Appearance, CameraSettings, GamepadSettings, KeyBindings, LoadSettingsEvent,
+    SaveSettingsEvent, SimulationConfig, SliderRanges,
+};
+use crate::display::DisplaySettings;
+use crate::i18n::trf;
+#[cfg(feature = "midi")]
+use crate::midi::MidiMappings;
+use crate::osc::OscSettings;
+use crate::presets::UserPresets;
+#[cfg(feature = "rerun")]
+use crate::rerun_log::RerunSettings;
+#[cfg(feature = "scripting")]
+use crate::scripting::ScriptSettings;
+#[cfg(feature = "ui")]
+use crate::ui::file_dialog::FileDialogSettings;
+use crate::ui::layout::PanelLayout;
+use crate::ui::toast::Toasts;
+#[cfg(feature = "ui")]
+use crate::ui::tutorial::TutorialProgress;
+use crate::view_sync::ViewSyncSettings;
+#[cfg(feature = "websocket")]
+use crate::websocket::WebSocketSettings;
+
+const APP_DIR: &str = "lorenz-attractor";
+const SAVE_DEBOUNCE_SECONDS: f32 = 1.0;
+
+pub fn config_path(file: &str) -> Option<PathBuf> {
+    dirs::config_dir().map(|dir| dir.join(APP_DIR).join(file))
+}
+
+pub fn load_toml<T: DeserializeOwned>(file: &str) -> Option<T> {
+    let path = config_path(file)?;
+    let text = fs::read_to_string(&path).ok()?;
+    match toml::from_str(&text) {
+        Ok(value) => Some(value),
+        Err(err) => {
+            warn!("Ignoring malformed {}: {}", path.display(), err);
+            None
+        }
+    }
+}
+
+pub fn save_toml<T: Serialize>(file: &str, value: &T) -> io::Result<()> {
+    let path = config_path(file)
+        .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "no config directory"))?;
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let text = toml::to_string_pretty(value)
+        .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+    fs::write(path, text)
+}
+
+pub trait PersistedSettings:
+    Resource + Clone + PartialEq + Serialize + DeserializeOwned + Default
+{
+    const FILE: &'static str;
+
+    fn load_or_default() -> Self {
+        load_toml(Self::FILE).unwrap_or_default()
+    }
+}
+
+impl PersistedSettings for SimulationConfig {
+    const FILE: &'static str = "simulation.toml";
+}
+
+impl PersistedSettings for CameraSettings {
+    const FILE: &'static str = "camera.toml";
+}
+
+impl PersistedSettings for GamepadSettings {
+    const FILE: &'static str = "gamepad.toml";
+}
+
+impl PersistedSettings for KeyBindings {
+    const FILE: &'static str = "keybindings.toml";
+}
+
+impl PersistedSettings for SliderRanges {
+    const FILE: &'static str = "slider_ranges.toml";
+}
+
+impl PersistedSettings for Appearance {
+    const FILE: &'static str = "appearance.toml";
+}
+
+impl PersistedSettings for PanelLayout {
+    const FILE: &'static str = "layout.toml";
+}
+
+impl PersistedSettings for DisplaySettings {
+    const FILE: &'static str = "display.toml";
+}
+
+impl PersistedSettings for UserPresets {
+    const FILE: &'static str = "presets.toml";
+}
+
+impl PersistedSettings for OscSettings {
+    const FILE: &'static str = "osc.toml";
+}
+
+#[cfg(feature = "ui")]
+impl PersistedSettings for TutorialProgress {
+    const FILE: &'static str = "tutorial.toml";
+}
+
+#[cfg(feature = "ui")]
+impl PersistedSettings for FileDialogSettings {
+    const FILE: &'static str = "file_dialogs.toml";
+}
+
+#[cfg(feature = "scripting")]
+impl PersistedSettings for ScriptSettings {
+    const FILE: &'static str = "script.toml";
+}
+
+#[cfg(feature = "midi")]
+impl PersistedSettings for MidiMappings {
+    const FILE: &'static str = "midi.toml";
+}
+
+#[cfg(feature = "audio")]
+impl PersistedSettings for AudioSettings {
+    const FILE: &'static str = "audio.toml";
+}
+
+impl PersistedSettings for ViewSyncSettings {
+    const FILE: &'static str = "view_sync.toml";
+}
+
+#[cfg(feature = "websocket")]
+impl PersistedSettings for WebSocketSettings {
+    const FILE: &'static str = "websocket.toml";
+}
+
+#[cfg(feature = "rerun")]
+impl PersistedSettings for RerunSettings {
+    const FILE: &'static str = "rerun.toml";
+}
+
+pub fn autosave_system<T: PersistedSettings>(
+    value: Res<T>,
+    time: Res<Time<Real>>,
+    mut last_seen: Local<Option<T>>,
+    mut dirty_since: Local<Option<f32>>,
+) {
+    let now = time.elapsed_seconds();
+
+    match last_seen.as_ref() {
+        None => *last_seen = Some(value.clone()),
+        Some(previous) if *previous != *value => {
+            *last_seen = Some(value.clone());
+            *dirty_since = Some(now);
+        }
+        Some(_) => {}
+    }
+
+    if dirty_since.is_some_and(|t| now - t > SAVE_DEBOUNCE_SECONDS) {
+        *dirty_since = None;
+        if let Err(err) = save_toml(T::FILE, &*value) {
+            warn!("Failed to save {}: {}", T::FILE, err);
+        }
+    }
+}
+
+pub fn save_on_exit_system<T: PersistedSettings>(mut exits: EventReader<AppExit>, value: Res<T>) {
+    if exits.read().count() == 0 {
+        return;
+    }
+    if let Err(err) = save_toml(T::FILE, &*value) {
+        warn!("Failed to save {}: {}", T::FILE, err);
+    }
+}
+
+/// Watches the config directory so edits made in an external editor or script
+/// are applied while the app runs.
+#[derive(Resource)]
+pub struct ConfigWatcher {
+    _watcher: RecommendedWatcher,
+    changes: Mutex<Receiver<String>>,
+}
+
+impl ConfigWatcher {
+    pub fn start() -> Option<Self> {
+        let dir = config_path("")?;
+        if let Err(err) = fs::create_dir_all(&dir) {
+            warn!("Config hot-reload disabled: {}", err);
+            return None;
+        }
+
+        let (sender, receiver) = channel();
+        let watcher = notify::recommended_watcher(move |res: notify::Result<notify::Event>| {
+            let Ok(event) = res else { return };
+            if !matches!(event.kind, EventKind::Create(_) | EventKind::Modify(_)) {
+                return;
+            }
+            for path in event.paths {
+                if let Some(name) = path.file_name().and_then(|n| n.to_str()) {
+                    let _ = sender.send(name.to_string());
+                }
+            }
+        });
+        let mut watcher = match watcher {
+            Ok(watcher) => watcher,
+            Err(err) => {
+                warn!("Config hot-reload disabled: {}", err);
+                return None;
+            }
+        };
+        if let Err(err) = watcher.watch(&dir, RecursiveMode::NonRecursive) {
+            warn!("Config hot-reload disabled: {}", err);
+            return None;
+        }
+
+        Some(Self {
+            _watcher: watcher,
+            changes: Mutex::new(receiver),
+        })
+    }
+
+    fn changed_files(&self) -> HashSet<String> {
+        self.changes
+            .lock()
+            .map(|receiver| receiver.try_iter().collect())
+            .unwrap_or_default()
+    }
+}
+
+/// Replaces `value` with the file contents if the file changed and parses to
+/// something different. Our own saves round-trip to an equal value, so they
+/// never trigger a reload.
+fn reload_if_changed<T: PersistedSettings>(
+    changed: &HashSet<String>,
+    value: &mut ResMut<T>,
+) -> bool {
+    if !changed.contains(T::FILE) {
+        return false;
+    }
+    match load_toml::<T>(T::FILE) {
+        Some(loaded) if loaded != **value => {
+            **value = loaded;
+            true
+        }
+        _ => false,
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn hot_reload_system(
+    watcher: Option<Res<ConfigWatcher>>,
+    mut simulation: ResMut<SimulationConfig>,
+    mut camera: ResMut<CameraSettings>,
+    mut gamepad: ResMut<GamepadSettings>,
+    mut bindings: ResMut<KeyBindings>,
+    mut ranges: ResMut<SliderRanges>,
+    mut appearance: ResMut<Appearance>,
+    mut layout: ResMut<PanelLayout>,
+    mut display: ResMut<DisplaySettings>,
+    mut toasts: ResMut<Toasts>,
+) {
+    let Some(watcher) = watcher else {
+        return;
+    };
+    let changed = watcher.changed_files();
+    if changed.is_empty() {
+        return;
+    }
+
+    let mut reloaded = Vec::new();
+    if reload_if_changed(&changed, &mut simulation) {
+        reloaded.push(SimulationConfig::FILE);
+    }
+    if reload_if_changed(&changed, &mut camera) {
+        reloaded.push(CameraSettings::FILE);
+    }
+    if reload_if_changed(&changed, &mut gamepad) {
+        reloaded.push(GamepadSettings::FILE);
+    }
+    if reload_if_changed(&changed, &mut bindings) {
+        reloaded.push(KeyBindings::FILE);
+    }
+    if reload_if_changed(&changed, &mut ranges) {
+        reloaded.push(SliderRanges::FILE);
+    }
+    if reload_if_changed(&changed, &mut appearance) {
+        reloaded.push(Appearance::FILE);
+    }
+    if reload_if_changed(&changed, &mut layout) {
+        reloaded.push(PanelLayout::FILE);
+    }
+    if reload_if_changed(&changed, &mut display) {
+        reloaded.push(DisplaySettings::FILE);
+    }
+    if !reloaded.is_empty() {
+        toasts.push(trf("♻ Reloaded {files}", &[("files", &reloaded.join(", "))]));
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn settings_file_system(
+    mut save_events: EventReader<SaveSettingsEvent>,
+    mut load_events: EventReader<LoadSettingsEvent>,
+    mut simulation: ResMut<SimulationConfig>,
+    mut camera: ResMut<CameraSettings>,
+    mut gamepad: ResMut<GamepadSettings>,
+    mut bindings: ResMut<KeyBindings>,
+    mut ranges: ResMut<SliderRanges>,
+    mut appearance: ResMut<Appearance>,
+    mut layout: ResMut<PanelLayout>,
+    mut display: ResMut<DisplaySettings>,
+    mut toasts: ResMut<Toasts>,
+) {
+    let dir = config_path("").map_or_else(|| "?".to_string(), |p| p.display().to_string());
+
+    if save_events.read().count() > 0 {
+        let result = save_toml(SimulationConfig::FILE, &*simulation)
+            .and_then(|()| save_toml(CameraSettings::FILE, &*camera))
+            .and_then(|()| save_toml(GamepadSettings::FILE, &*gamepad))
+            .and_then(|()| save_toml(KeyBindings::FILE, &*bindings))
+            .and_then(|()| save_toml(SliderRanges::FILE, &*ranges))
+            .and_then(|()| save_toml(Appearance::FILE, &*appearance))
+            .and_then(|()| save_toml(PanelLayout::FILE, &*layout))
+            .and_then(|()| save_toml(DisplaySettings::FILE, &*display));
+        match result {
+            Ok(()) => toasts.push(trf("💾 Saved settings to {dir}", &[("dir", &dir)])),
+            Err(err) => toasts.push(trf("Saving settings failed: {err}", &[("err", &err)])),
+        }
+    }
+
+    if load_events.read().count() > 0 {
+        *simulation = SimulationConfig::load_or_default();
+        *camera = CameraSettings::load_or_default();
+        *gamepad = GamepadSettings::load_or_default();
+        *bindings = KeyBindings::load_or_default();
+        *ranges = SliderRanges::load_or_default();
+        *appearance = Appearance::load_or_default();
+        *layout = PanelLayout::load_or_default();
+        *display = DisplaySettings::load_or_default();
+        toasts.push(trf("📂 Loaded settings from {dir}", &[("dir", &dir)]));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_camera_settings_round_trip() {
+        let settings = CameraSettings {
+            invert_scroll: true,
+            zoom_sensitivity: 4.0,
+            ..default()
+        };
+        let text = toml::to_string_pretty(&settings).unwrap();
+        let loaded: CameraSettings = toml::from_str(&text).unwrap();
+        assert!(loaded == settings);
+    }
+
+    #[test]
+    fn test_simulation_config_round_trip() {
+        let config = SimulationConfig {
+            rho: 99.96,
+            method: crate::config::IntegrationMethod::Euler,
+            max_trail_points: 100_000,
+            ..default()
+        };
+        let text = toml::to_string_pretty(&config).unwrap();
+        assert!(text.contains("method = \"Euler\""));
+        let loaded: SimulationConfig = toml::from_str(&text).unwrap();
+        assert!(loaded == config);
+    }
+
+    #[test]
+    fn test_missing_fields_use_defaults() {
+        let loaded: CameraSettings = toml::from_str("invert_scroll = true").unwrap();
+        assert!(loaded.invert_scroll);
+        assert_eq!(loaded.fov_degrees, CameraSettings::default().fov_degrees);
+    }
+}
\ No newline at end of file
@@ -0,0 +1,669 @@
+use bevy::prelude::*;
+use bevy::sprite::Material2dPlugin;
+#[cfg(feature = "ui")]
+use bevy_egui::EguiPlugin;
+
+#[cfg(feature = "audio")]
+use crate::audio::{audio_system, AudioInputState, AudioSettings};
+#[cfg(feature = "export")]
+use crate::capture::frame_sequence::{frame_recording_system, FrameRecorder};
+#[cfg(feature = "export")]
+use crate::capture::screenshot::screenshot_system;
+#[cfg(feature = "export")]
+use crate::capture::still::{draw_still_system, still_render_system, StillRenderPlugin};
+#[cfg(feature = "export")]
+use crate::capture::video::{video_recording_system, VideoRecorder};
+use crate::config::{
+    AnaglyphConfig, Appearance, BifurcationSettings, ButterflyDemoEvent, CameraSettings,
+    CaptureSettings, ComparisonConfig, ComplexityConfig, DensityConfig, EmbeddingConfig,
+    ExportBifurcationEvent, ExportManifestEvent, ExportPoincareEvent, ExportSettings,
+    ExportStatsEvent, ExportSymbolsEvent, ExportTrajectoryEvent, FitViewEvent, GalleryConfig,
+    GamepadSettings, HistogramConfig, KeyBindings, LoadReplayEvent, LoadSettingsEvent,
+    LoadSnapshotEvent, LogSettings, OrthoViewsConfig, PhasePortraitConfig, PlaySessionEvent,
+    RandomizeEvent, RandomizeSettings, RecordEvent, RecordGifEvent, RecordSessionEvent,
+    RecordVideoEvent, RenderStillEvent, ResetEvent, ResetViewEvent, SaveSettingsEvent,
+    SaveSnapshotEvent, ScreenshotEvent, SimulationConfig, SimulationStats, SliderRanges,
+    SnapshotSettings, SpectrumConfig, StepEvent, TimeSeriesConfig, TrajectoryEvent, UiVisibility,
+};
+use crate::display::{
+    display_settings_system, frame_limiter_system, idle_mode_system, overlay_background_system,
+    DisplaySettings,
+};
+#[cfg(feature = "export")]
+use crate::export::bifurcation::{export_bifurcation_system, BifurcationExport};
+#[cfg(feature = "export")]
+use crate::export::logger::{state_log_system, StateLog};
+#[cfg(feature = "export")]
+use crate::export::manifest::export_manifest_system;
+#[cfg(feature = "export")]
+use crate::export::poincare::export_poincare_system;
+#[cfg(feature = "export")]
+use crate::export::stats::export_stats_system;
+#[cfg(feature = "export")]
+use crate::export::symbols::export_symbols_system;
+#[cfg(feature = "export")]
+use crate::export::trajectory::export_trajectory_system;
+use crate::invariants::{invariant_check_system, InvariantChecks};
+#[cfg(feature = "midi")]
+use crate::midi::{midi_system, MidiInputState, MidiMappings};
+use crate::osc::{osc_output_system, OscOutput, OscSettings};
+use crate::persistence::{
+    autosave_system, hot_reload_system, save_on_exit_system, settings_file_system,
+    ConfigWatcher, PersistedSettings,
+};
+use crate::presets::{apply_preset_system, morph_system, ApplyPresetEvent, Presets, UserPresets};
+#[cfg(feature = "ui")]
+use crate::recovery::recovery_prompt_system;
+use crate::recovery::{recovery_autosave_system, recovery_clean_exit_system, Recovery};
+use crate::rendering::anaglyph::{anaglyph_system, setup_anaglyph, AnaglyphMaterial};
+#[cfg(feature = "ui")]
+use crate::rendering::annotations::annotations_system;
+use crate::rendering::annotations::Annotations;
+use crate::rendering::camera_animation::{
+    camera_path_system, camera_transition_system, CameraPath, CameraTransition,
+};
+use crate::rendering::camera_controller::{
+    camera_control_system, camera_projection_system, fit_view_system, recenter_on_head_system,
+    reset_view_system, EguiWantsKeyboard, EguiWantsPointer, OrbitCamera, MAX_RADIUS,
+};
+use crate::rendering::camera_input::{touch_camera_system, CameraInput};
+#[cfg(feature = "ui")]
+use crate::rendering::gallery_view::{gallery_view_system, setup_gallery_view, GalleryGizmos};
+use crate::rendering::head_drag::{head_drag_system, HeadDrag};
+#[cfg(feature = "ui")]
+use crate::rendering::hover::trail_hover_system;
+#[cfg(feature = "ui")]
+use crate::rendering::ortho_views::ortho_labels_system;
+use crate::rendering::ortho_views::{ortho_views_system, setup_ortho_views};
+use crate::rendering::placement::{placement_system, Placement};
+use crate::rendering::split_view::{
+    draw_comparison_system, setup_split_view, split_view_system, ComparisonGizmos,
+};
+#[cfg(feature = "ui")]
+use crate::rendering::trail_renderer::axis_labels_system;
+use crate::rendering::trail_renderer::{
+    draw_axes_system, draw_extra_trajectories_system, draw_head_marker_system, draw_trail_system,
+};
+use crate::rendering::view_scale::{view_scale_system, ViewScale};
+#[cfg(feature = "rerun")]
+use crate::rerun_log::{rerun_log_system, RerunLog, RerunSettings};
+#[cfg(feature = "scripting")]
+use crate::scripting::{script_system, ScriptEvent, ScriptHost, ScriptSettings};
+use crate::session::{session_system, Session};
+use crate::share::apply_url_camera_system;
+use crate::simulation::butterfly::{butterfly_demo_system, separation_system, Separation};
+use crate::simulation::comparison::{comparison_simulation_system, ComparisonTrajectory};
+use crate::simulation::complexity::{complexity_system, Complexity};
+use crate::simulation::density::{density_system, DensityMap};
+use crate::simulation::gallery::{gallery_simulation_system, Gallery};
+use crate::simulation::history::{history_record_system, history_scrub_system, History};
+use crate::simulation::integrator::{simulation_system, CustomSystem, TrailBuffer};
+use crate::simulation::lorenz::LorenzState;
+use crate::simulation::randomize::randomize_system;
+use crate::simulation::replay::{is_replaying, load_replay_system, replay_system, Replay};
+use crate::simulation::spectrum::{spectrum_sampler_system, SpectrumSampler};
+use crate::simulation::statistics::{stats_history_system, StatsHistory};
+use crate::simulation::trajectories::{
+    manage_trajectories_system, trajectories_simulation_system, Trajectories,
+};
+use crate::snapshot::snapshot_system;
+#[cfg(feature = "ui")]
+use crate::ui::appearance::appearance_system;
+#[cfg(feature = "ui")]
+use crate::ui::controls::ui_system;
+#[cfg(feature = "ui")]
+use crate::ui::file_dialog::{FileDialogSettings, FileDialogs};
+use crate::ui::gamepad::gamepad_system;
+use crate::ui::layout::PanelLayout;
+#[cfg(feature = "ui")]
+use crate::ui::performance::{performance_history_system, PerformanceHistory};
+#[cfg(feature = "analysis")]
+use crate::ui::plots::DensityTexture;
+#[cfg(feature = "ui")]
+use crate::ui::shortcuts::{presentation_hint_system, shortcut_system, KeyRebind};
+#[cfg(feature = "ui")]
+use crate::ui::toast::toast_system;
+use crate::ui::toast::Toasts;
+#[cfg(feature = "ui")]
+use crate::ui::tutorial::{tutorial_system, Tutorial, TutorialProgress};
+#[cfg(feature = "ui")]
+use crate::undo::{edit_history_system, undo_shortcut_system};
+use crate::undo::EditHistory;
+use crate::view_sync::{view_sync_system, ViewSync, ViewSyncSettings};
+#[cfg(feature = "websocket")]
+use crate::websocket::{websocket_system, WebSocketServer, WebSocketSettings};
+
+/// The attractor's simulation, trail rendering, camera, panels, capture and
+/// settings persistence, packaged for embedding in another Bevy app.
+///
+/// Every subsystem is on by default; switch pieces off with the builder
+/// methods, e.g. `LorenzAttractorPlugin::minimal().camera(true)` for a
+/// background visual driven by the host's input. The simulation and trail
+/// drawing are always included. Resources the host inserts before adding the
+/// plugin (such as a custom [`SimulationConfig`]) are kept as they are.
+/// Parts left out by the `ui`, `analysis` and `export` cargo features stay
+/// off whatever their flag says.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct LorenzAttractorPlugin {
+    pub camera: bool,
+    pub ui: bool,
+    pub views: bool,
+    pub capture: bool,
+    pub persistence: bool,
+    pub display: bool,
+}
+
+impl Default for LorenzAttractorPlugin {
+    fn default() -> Self {
+        Self {
+            camera: true,
+            ui: true,
+            views: true,
+            capture: true,
+            persistence: true,
+            display: true,
+        }
+    }
+}
+
+impl LorenzAttractorPlugin {
+    /// Simulation and trail drawing only.
+    pub fn minimal() -> Self {
+        Self {
+            camera: false,
+            ui: false,
+            views: false,
+            capture: false,
+            persistence: false,
+            display: false,
+        }
+    }
+
+    /// Orbit camera spawn, mouse/touch/gamepad control, head dragging and
+    /// click-to-place.
+    pub fn camera(mut self, enabled: bool) -> Self {
+        self.camera = enabled;
+        self
+    }
+
+    /// The egui control panel, plots, toasts, tour, shortcuts, hover tooltips
+    /// and annotation labels.
+    pub fn ui(mut self, enabled: bool) -> Self {
+        self.ui = enabled;
+        self
+    }
+
+    /// Split view, orthographic views, gallery and anaglyph cameras.
+    pub fn views(mut self, enabled: bool) -> Self {
+        self.views = enabled;
+        self
+    }
+
+    /// Screenshots, recordings, still renders, exporters, sessions,
+    /// snapshots and the state log.
+    pub fn capture(mut self, enabled: bool) -> Self {
+        self.capture = enabled;
+        self
+    }
+
+    /// Loading, autosaving and hot-reloading the settings files, plus crash
+    /// recovery.
+    pub fn persistence(mut self, enabled: bool) -> Self {
+        self.persistence = enabled;
+        self
+    }
+
+    /// Applying [`DisplaySettings`] to the primary window, idle mode and the
+    /// frame limiter.
+    pub fn display(mut self, enabled: bool) -> Self {
+        self.display = enabled;
+        self
+    }
+
+    fn insert_settings<T: PersistedSettings>(&self, app: &mut App) {
+        if app.world().contains_resource::<T>() {
+            return;
+        }
+        if self.persistence {
+            app.insert_resource(T::load_or_default());
+        } else {
+            app.init_resource::<T>();
+        }
+    }
+}
+
+/// Stages of the attractor's frame, run in this order; hosts can order their
+/// own systems against them.
+#[derive(SystemSet, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum LorenzSet {
+    Capture,
+    Ui,
+    Input,
+    Simulation,
+    Draw,
+    Camera,
+    Record,
+    Views,
+}
+
+impl Plugin for LorenzAttractorPlugin {
+    fn build(&self, app: &mut App) {
+        #[cfg(feature = "ui")]
+        if (self.ui || self.views) && !app.is_plugin_added::<EguiPlugin>() {
+            app.add_plugins(EguiPlugin);
+        }
+
+        self.insert_settings::<SimulationConfig>(app);
+        self.insert_settings::<DisplaySettings>(app);
+        self.insert_settings::<CameraSettings>(app);
+        self.insert_settings::<GamepadSettings>(app);
+        self.insert_settings::<KeyBindings>(app);
+        self.insert_settings::<UserPresets>(app);
+        self.insert_settings::<SliderRanges>(app);
+        self.insert_settings::<Appearance>(app);
+        self.insert_settings::<PanelLayout>(app);
+        self.insert_settings::<OscSettings>(app);
+        self.insert_settings::<ViewSyncSettings>(app);
+        #[cfg(feature = "ui")]
+        self.insert_settings::<TutorialProgress>(app);
+        #[cfg(feature = "ui")]
+        self.insert_settings::<FileDialogSettings>(app);
+        #[cfg(feature = "scripting")]
+        self.insert_settings::<ScriptSettings>(app);
+        #[cfg(feature = "midi")]
+        self.insert_settings::<MidiMappings>(app);
+        #[cfg(feature = "audio")]
+        self.insert_settings::<AudioSettings>(app);
+        #[cfg(feature = "websocket")]
+        self.insert_settings::<WebSocketSettings>(app);
+        #[cfg(feature = "rerun")]
+        self.insert_settings::<RerunSettings>(app);
+        // Only the panel's prompt resolves a found recovery file; without it
+        // the file would block autosave for the whole run.
+        if self.persistence && cfg!(feature = "ui") && self.ui {
+            app.insert_resource(Recovery::detect());
+        } else {
+            app.init_resource::<Recovery>();
+        }
+
+        app.init_resource::<SimulationStats>()
+            .init_resource::<ComparisonConfig>()
+            .init_resource::<OrthoViewsConfig>()
+            .init_resource::<TimeSeriesConfig>()
+            .init_resource::<PhasePortraitConfig>()
+            .init_resource::<SpectrumConfig>()
+            .init_resource::<HistogramConfig>()
+            .init_resource::<SpectrumSampler>()
+            .init_resource::<EmbeddingConfig>()
+            .init_resource::<ComplexityConfig>()
+            .init_resource::<Complexity>()
+            .init_resource::<StatsHistory>()
+            .init_resource::<DensityConfig>()
+            .init_resource::<DensityMap>()
+            .init_resource::<Trajectories>()
+            .init_resource::<Separation>()
+            .init_resource::<GalleryConfig>()
+            .init_resource::<AnaglyphConfig>()
+            .init_resource::<CaptureSettings>()
+            .init_resource::<ExportSettings>()
+            .init_resource::<BifurcationSettings>()
+            .init_resource::<UiVisibility>()
+            .init_resource::<Toasts>()
+            .init_resource::<Gallery>()
+            .init_resource::<Presets>()
+            .init_resource::<RandomizeSettings>()
+            .init_resource::<EditHistory>()
+            .init_resource::<TrailBuffer>()
+            .init_resource::<CustomSystem>()
+            .init_resource::<Replay>()
+            .init_resource::<History>()
+            .init_resource::<Session>()
+            .init_resource::<SnapshotSettings>()
+            .init_resource::<LogSettings>()
+            .init_resource::<EguiWantsPointer>()
+            .init_resource::<EguiWantsKeyboard>()
+            .init_resource::<CameraInput>()
+            .init_resource::<CameraPath>()
+            .init_resource::<CameraTransition>()
+            .init_resource::<Placement>()
+            .init_resource::<ViewScale>()
+            .init_resource::<HeadDrag>()
+            .init_resource::<Annotations>()
+            .init_resource::<OscOutput>()
+            .init_resource::<ViewSync>()
+            .init_resource::<InvariantChecks>()
+            .add_event::<ResetEvent>()
+            .add_event::<StepEvent>()
+            .add_event::<ApplyPresetEvent>()
+            .add_event::<RandomizeEvent>()
+            .add_event::<FitViewEvent>()
+            .add_event::<ResetViewEvent>()
+            .add_event::<ScreenshotEvent>()
+            .add_event::<RecordEvent>()
+            .add_event::<RecordVideoEvent>()
+            .add_event::<RecordGifEvent>()
+            .add_event::<RenderStillEvent>()
+            .add_event::<ExportTrajectoryEvent>()
+            .add_event::<ExportPoincareEvent>()
+            .add_event::<ExportSymbolsEvent>()
+            .add_event::<ExportStatsEvent>()
+            .add_event::<TrajectoryEvent>()
+            .add_event::<ButterflyDemoEvent>()
+            .add_event::<ExportBifurcationEvent>()
+            .add_event::<ExportManifestEvent>()
+            .add_event::<LoadReplayEvent>()
+            .add_event::<RecordSessionEvent>()
+            .add_event::<PlaySessionEvent>()
+            .add_event::<SaveSnapshotEvent>()
+            .add_event::<LoadSnapshotEvent>()
+            .add_event::<SaveSettingsEvent>()
+            .add_event::<LoadSettingsEvent>()
+            .configure_sets(
+                Update,
+                (
+                    LorenzSet::Capture,
+                    LorenzSet::Ui,
+                    LorenzSet::Input,
+                    LorenzSet::Simulation,
+                    LorenzSet::Draw,
+                    LorenzSet::Camera,
+                    LorenzSet::Record,
+                    LorenzSet::Views,
+                )
+                    .chain(),
+            )
+            .add_systems(Startup, setup_scene)
+            .add_systems(
+                Update,
+                (
+                    (randomize_system, apply_preset_system, morph_system).chain(),
+                    (
+                        load_replay_system,
+                        replay_system,
+                        (
+                            history_scrub_system,
+                            simulation_system,
+                            spectrum_sampler_system,
+                            stats_history_system,
+                            density_system,
+                            complexity_system,
+                            history_record_system,
+                            comparison_simulation_system,
+                            (
+                                butterfly_demo_system,
+                                manage_trajectories_system,
+                                trajectories_simulation_system,
+                                separation_system,
+                            )
+                                .chain(),
+                            gallery_simulation_system,
+                        )
+                            .chain()
+                            .run_if(not(is_replaying)),
+                    )
+                        .chain(),
+                )
+                    .chain()
+                    .in_set(LorenzSet::Simulation),
+            )
+            .add_systems(
+                Update,
+                (
+                    view_scale_system,
+                    draw_trail_system,
+                    draw_head_marker_system,
+                    draw_extra_trajectories_system,
+                    draw_axes_system,
+                )
+                    .chain()
+                    .in_set(LorenzSet::Draw),
+            )
+            .add_systems(Update, osc_output_system.in_set(LorenzSet::Record))
+            .add_systems(Update, invariant_check_system.in_set(LorenzSet::Record))
+            .add_systems(Update, view_sync_system.in_set(LorenzSet::Input));
+
+        #[cfg(feature = "ui")]
+        app.init_resource::<PerformanceHistory>()
+            .init_resource::<Tutorial>()
+            .init_resource::<KeyRebind>()
+            .init_resource::<FileDialogs>();
+        #[cfg(feature = "analysis")]
+        app.init_resource::<DensityTexture>();
+        #[cfg(feature = "export")]
+        app.init_resource::<FrameRecorder>()
+            .init_resource::<VideoRecorder>()
+            .init_resource::<BifurcationExport>()
+            .init_resource::<StateLog>();
+        #[cfg(feature = "scripting")]
+        app.init_resource::<ScriptHost>()
+            .add_event::<ScriptEvent>()
+            .add_systems(Update, script_system.in_set(LorenzSet::Input));
+        #[cfg(feature = "midi")]
+        app.init_resource::<MidiInputState>()
+            .add_systems(Update, midi_system.in_set(LorenzSet::Input));
+        #[cfg(feature = "audio")]
+        app.init_resource::<AudioInputState>()
+            .add_systems(Update, audio_system.in_set(LorenzSet::Input));
+        #[cfg(feature = "websocket")]
+        app.init_resource::<WebSocketServer>()
+            .add_systems(Update, websocket_system.in_set(LorenzSet::Record));
+        #[cfg(feature = "rerun")]
+        app.init_resource::<RerunLog>()
+            .add_systems(Update, rerun_log_system.in_set(LorenzSet::Record));
+
+        if self.camera {
+            app.add_systems(Startup, setup_camera)
+                .add_systems(PostStartup, apply_url_camera_system)
+                .add_systems(Update, gamepad_system.in_set(LorenzSet::Input))
+                .add_systems(
+                    Update,
+                    (
+                        touch_camera_system,
+                        head_drag_system,
+                        camera_control_system,
+                        fit_view_system,
+                        reset_view_system,
+                        recenter_on_head_system,
+                        camera_transition_system,
+                        camera_path_system,
+                        camera_projection_system,
+                        placement_system,
+                    )
+                        .chain()
+                        .in_set(LorenzSet::Camera),
+                );
+        }
+
+        #[cfg(feature = "ui")]
+        if self.ui {
+            app.add_systems(
+                Update,
+                (
+                    (performance_history_system, appearance_system, ui_system).chain(),
+                    (toast_system, tutorial_system),
+                )
+                    .chain()
+                    .in_set(LorenzSet::Ui),
+            )
+            .add_systems(
+                Update,
+                (shortcut_system, presentation_hint_system)
+                    .chain()
+                    .after(gamepad_system)
+                    .in_set(LorenzSet::Input),
+            )
+            .add_systems(
+                Update,
+                (trail_hover_system, annotations_system, axis_labels_system)
+                    .chain()
+                    .after(draw_axes_system)
+                    .in_set(LorenzSet::Draw),
+            )
+            .add_systems(Update, (undo_shortcut_system, edit_history_system).chain());
+        }
+
+        if self.views {
+            app.add_plugins(Material2dPlugin::<AnaglyphMaterial>::default())
+                .init_gizmo_group::<ComparisonGizmos>()
+                .add_systems(Startup, (setup_split_view, setup_ortho_views, setup_anaglyph))
+                .add_systems(
+                    Update,
+                    (
+                        split_view_system,
+                        draw_comparison_system,
+                        ortho_views_system,
+                        anaglyph_system,
+                    )
+                        .chain()
+                        .in_set(LorenzSet::Views),
+                );
+            // The gallery tiles fill the area egui leaves free.
+            #[cfg(feature = "ui")]
+            app.init_gizmo_group::<GalleryGizmos>()
+                .add_systems(Startup, setup_gallery_view)
+                .add_systems(
+                    Update,
+                    (ortho_labels_system.after(ortho_views_system), gallery_view_system)
+                        .in_set(LorenzSet::Views),
+                );
+        }
+
+        if self.capture {
+            app.add_systems(
+                Update,
+                (session_system, snapshot_system)
+                    .chain()
+                    .in_set(LorenzSet::Record),
+            );
+            #[cfg(feature = "export")]
+            app.add_plugins(StillRenderPlugin)
+                .add_systems(
+                    Update,
+                    (
+                        screenshot_system,
+                        frame_recording_system,
+                        video_recording_system,
+                        (still_render_system, draw_still_system).chain(),
+                        (export_trajectory_system, export_stats_system),
+                        (export_poincare_system, export_symbols_system),
+                        export_bifurcation_system,
+                        export_manifest_system,
+                    )
+                        .chain()
+                        .in_set(LorenzSet::Capture),
+                )
+                .add_systems(
+                    Update,
+                    state_log_system
+                        .after(snapshot_system)
+                        .in_set(LorenzSet::Record),
+                );
+        }
+
+        if self.persistence {
+            app.add_systems(
+                Update,
+                (
+                    autosave_system::<CameraSettings>,
+                    autosave_system::<GamepadSettings>,
+                    autosave_system::<KeyBindings>,
+                    autosave_system::<UserPresets>,
+                    autosave_system::<SliderRanges>,
+                    autosave_system::<Appearance>,
+                    autosave_system::<PanelLayout>,
+                    autosave_system::<DisplaySettings>,
+                    autosave_system::<OscSettings>,
+                    autosave_system::<ViewSyncSettings>,
+                    settings_file_system,
+                    hot_reload_system,
+                    recovery_autosave_system,
+                ),
+            )
+            .add_systems(
+                Last,
+                (save_on_exit_system::<SimulationConfig>, recovery_clean_exit_system),
+            );
+            #[cfg(feature = "ui")]
+            if self.ui {
+                app.add_systems(
+                    Update,
+                    (
+                        autosave_system::<TutorialProgress>,
+                        autosave_system::<FileDialogSettings>,
+                        recovery_prompt_system,
+                    ),
+                );
+            }
+            #[cfg(feature = "scripting")]
+            app.add_systems(Update, autosave_system::<ScriptSettings>);
+            #[cfg(feature = "midi")]
+            app.add_systems(Update, autosave_system::<MidiMappings>);
+            #[cfg(feature = "audio")]
+            app.add_systems(Update, autosave_system::<AudioSettings>);
+            #[cfg(feature = "websocket")]
+            app.add_systems(Update, autosave_system::<WebSocketSettings>);
+            #[cfg(feature = "rerun")]
+            app.add_systems(Update, autosave_system::<RerunSettings>);
+            if let Some(watcher) = ConfigWatcher::start() {
+                app.insert_resource(watcher);
+            }
+        }
+
+        if self.display {
+            app.add_systems(
+                Update,
+                (display_settings_system, overlay_background_system, idle_mode_system),
+            )
+            .add_systems(Last, frame_limiter_system);
+        }
+    }
+}
+
+fn setup_camera(mut commands: Commands) {
+    let orbit = OrbitCamera::default();
+    let camera_pos = orbit.eye_position();
+
+    commands.spawn((
+        Camera3dBundle {
+            transform: Transform::from_translation(camera_pos)
+                .looking_at(orbit.focus, Vec3::Y),
+            projection: PerspectiveProjection {
+                far: MAX_RADIUS * 4.0,
+                ..default()
+            }
+            .into(),
+            ..default()
+        },
+        orbit,
+    ));
+}
+
+fn setup_scene(
+    mut commands: Commands,
+    config: Res<SimulationConfig>,
+    comparison: Res<ComparisonConfig>,
+) {
+    commands.spawn(LorenzState::new(
+        config.initial_x,
+        config.initial_y,
+        config.initial_z,
+    ));
+
+    commands.spawn((
+        LorenzState::new(
+            comparison.initial_x,
+            comparison.initial_y,
+            comparison.initial_z,
+        ),
+        TrailBuffer::default(),
+        ComparisonTrajectory,
+    ));
+
+    info!("Lorenz attractor simulation initialized.");
+    info!("  σ = {}, ρ = {}, β = {:.4}", config.sigma, config.rho, config.beta);
+    info!("  dt = {}, method = {:?}", config.dt, config.method);
+    info!(
+        "  Initial state: ({}, {}, {})",
+        config.initial_x, config.initial_y, config.initial_z
+    );
+}
\ No newline at end of file
@@ -0,0 +1,329 @@
+use std::f32::consts::TAU;
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::config::SimulationConfig;
+use crate::i18n::{tr, tr_noop, trf};
+use crate::rendering::camera_animation::{CameraKeyframe, CameraTransition};
+use crate::rendering::camera_controller::{OrbitCamera, TRANSITION_SECONDS};
+use crate::ui::toast::Toasts;
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct PresetCamera {
+    pub focus: [f32; 3],
+    pub radius: f32,
+    pub theta: f32,
+    pub phi: f32,
+}
+
+impl PresetCamera {
+    pub fn capture(orbit: &OrbitCamera) -> Self {
+        Self {
+            focus: orbit.focus.to_array(),
+            radius: orbit.radius,
+            theta: orbit.theta,
+            phi: orbit.phi,
+        }
+    }
+
+    /// The home view scaled with ρ, since the attractor's height and extent
+    /// grow roughly linearly with it.
+    pub fn framing(rho: f64) -> Self {
+        let home = OrbitCamera::default();
+        let scale = rho as f32 / 28.0;
+        Self {
+            focus: (home.focus * scale).to_array(),
+            radius: home.radius * scale,
+            ..Self::capture(&home)
+        }
+    }
+
+    /// Keyframe for this view, with φ shifted by whole turns so the
+    /// transition from `orbit` takes the short way round.
+    fn keyframe_from(&self, orbit: &OrbitCamera) -> CameraKeyframe {
+        let turns = ((orbit.phi - self.phi) / TAU).round();
+        CameraKeyframe {
+            time: 0.0,
+            focus: Vec3::from_array(self.focus),
+            radius: self.radius,
+            theta: self.theta,
+            phi: self.phi + turns * TAU,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Preset {
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    pub sigma: f64,
+    pub rho: f64,
+    pub beta: f64,
+    pub dt: f64,
+    pub camera: PresetCamera,
+}
+
+impl Preset {
+    pub fn classic(name: &str, description: &str, rho: f64, dt: f64) -> Self {
+        Self {
+            name: name.to_string(),
+            description: description.to_string(),
+            sigma: 10.0,
+            rho,
+            beta: 8.0 / 3.0,
+            dt,
+            camera: PresetCamera::framing(rho),
+        }
+    }
+
+    pub fn capture(
+        name: &str,
+        description: &str,
+        config: &SimulationConfig,
+        orbit: &OrbitCamera,
+    ) -> Self {
+        Self {
+            name: name.to_string(),
+            description: description.to_string(),
+            sigma: config.sigma,
+            rho: config.rho,
+            beta: config.beta,
+            dt: config.dt,
+            camera: PresetCamera::capture(orbit),
+        }
+    }
+
+    pub fn apply(&self, config: &mut SimulationConfig) {
+        config.sigma = self.sigma;
+        config.rho = self.rho;
+        config.beta = self.beta;
+        config.dt = self.dt;
+    }
+}
+
+/// Values the parameter sliders snap to and mark on their rails.
+pub const NOTABLE_SIGMA: [f64; 2] = [10.0, 16.0];
+pub const NOTABLE_RHO: [f64; 9] = [1.0, 13.926, 24.06, 24.74, 28.0, 45.92, 99.96, 166.3, 313.0];
+pub const NOTABLE_BETA: [f64; 2] = [8.0 / 3.0, 4.0];
+
+pub fn builtin_presets() -> Vec<Preset> {
+    vec![
+        Preset::classic(
+            tr_noop("Classic chaos (ρ = 28)"),
+            tr_noop("Lorenz's original parameters: the two-lobed strange attractor."),
+            28.0,
+            0.005,
+        ),
+        Preset::classic(
+            tr_noop("Stable spirals (ρ = 15)"),
+            tr_noop(
+                "Below the chaotic regime; every orbit spirals into one of the fixed points C±.",
+            ),
+            15.0,
+            0.005,
+        ),
+        Preset::classic(
+            tr_noop("Homoclinic explosion (ρ ≈ 13.926)"),
+            tr_noop("Orbits from the origin return to it; just above this, chaotic sets are born."),
+            13.926,
+            0.005,
+        ),
+        Preset::classic(
+            tr_noop("Pre-chaotic (ρ = 24)"),
+            tr_noop(
+                "Transient chaos: the trajectory wanders chaotically for a long time before \
+                 settling into C±, which are still stable below ρ ≈ 24.74.",
+            ),
+            24.0,
+            0.005,
+        ),
+        Preset::classic(
+            tr_noop("Periodic window (ρ ≈ 99.96)"),
+            tr_noop(
+                "Chaos gives way to a stable, knotted periodic orbit inside a periodic window.",
+            ),
+            99.96,
+            0.002,
+        ),
+        Preset::classic(
+            tr_noop("Intermittency (ρ ≈ 166.3)"),
+            tr_noop(
+                "Just past the end of a periodic window: long nearly periodic stretches \
+                 interrupted by chaotic bursts (type I intermittency).",
+            ),
+            166.3,
+            0.001,
+        ),
+        Preset::classic(
+            tr_noop("Large-ρ limit cycle (ρ = 350)"),
+            tr_noop("For ρ above about 313 the attractor is a single symmetric periodic orbit."),
+            350.0,
+            0.0005,
+        ),
+    ]
+}
+
+#[derive(Resource, Clone, PartialEq, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct UserPresets {
+    pub presets: Vec<Preset>,
+}
+
+/// A running interpolation from the parameters at its start to a preset.
+pub struct Morph {
+    from: SimulationConfig,
+    pub to: Preset,
+    elapsed: f32,
+    duration: f32,
+}
+
+impl Morph {
+    pub fn progress(&self) -> f32 {
+        (self.elapsed / self.duration).min(1.0)
+    }
+
+    fn apply(&self, config: &mut SimulationConfig) {
+        let p = self.progress();
+        let t = f64::from(p * p * (3.0 - 2.0 * p));
+        let lerp = |a: f64, b: f64| a + (b - a) * t;
+        config.sigma = lerp(self.from.sigma, self.to.sigma);
+        config.rho = lerp(self.from.rho, self.to.rho);
+        config.beta = lerp(self.from.beta, self.to.beta);
+        // Geometric, so a 10× change in dt is spread evenly over the morph.
+        config.dt = self.from.dt * (self.to.dt / self.from.dt).powf(t);
+    }
+}
+
+#[derive(Resource)]
+pub struct Presets {
+    pub builtin: Vec<Preset>,
+    pub active: Option<Preset>,
+    pub new_name: String,
+    pub new_description: String,
+    /// 0 applies presets instantly.
+    pub morph_seconds: f32,
+    pub morph: Option<Morph>,
+}
+
+impl Default for Presets {
+    fn default() -> Self {
+        Self {
+            builtin: builtin_presets(),
+            active: None,
+            new_name: String::new(),
+            new_description: String::new(),
+            morph_seconds: 0.0,
+            morph: None,
+        }
+    }
+}
+
+#[derive(Event)]
+pub struct ApplyPresetEvent(pub Preset);
+
+pub fn apply_preset_system(
+    mut events: EventReader<ApplyPresetEvent>,
+    mut config: ResMut<SimulationConfig>,
+    mut presets: ResMut<Presets>,
+    mut transition: ResMut<CameraTransition>,
+    mut camera_query: Query<&mut OrbitCamera>,
+    mut toasts: ResMut<Toasts>,
+) {
+    let Some(ApplyPresetEvent(preset)) = events.read().last() else {
+        return;
+    };
+
+    let morph_seconds = presets.morph_seconds;
+    if let Ok(mut orbit) = camera_query.get_single_mut() {
+        orbit.rotate_velocity = Vec2::ZERO;
+        orbit.pan_velocity = Vec3::ZERO;
+        orbit.zoom_velocity = 0.0;
+        let from = CameraKeyframe::from_orbit(0.0, &orbit);
+        let duration = morph_seconds.max(TRANSITION_SECONDS);
+        transition.start(from, preset.camera.keyframe_from(&orbit), duration);
+    }
+
+    if morph_seconds > 0.0 {
+        presets.morph = Some(Morph {
+            from: config.clone(),
+            to: preset.clone(),
+            elapsed: 0.0,
+            duration: morph_seconds,
+        });
+        toasts.push(trf(
+            "🎛 Morphing to {name} over {seconds} s",
+            &[
+                ("name", &tr(&preset.name)),
+                ("seconds", &format!("{:.0}", morph_seconds)),
+            ],
+        ));
+    } else {
+        presets.morph = None;
+        preset.apply(&mut config);
+        presets.active = Some(preset.clone());
+        toasts.push(format!("🎛 {}", tr(&preset.name)));
+    }
+}
+
+pub fn morph_system(
+    time: Res<Time<Real>>,
+    mut config: ResMut<SimulationConfig>,
+    mut presets: ResMut<Presets>,
+) {
+    let Some(morph) = presets.morph.as_mut() else {
+        return;
+    };
+
+    morph.elapsed += time.delta_seconds();
+    morph.apply(&mut config);
+    if morph.progress() >= 1.0 {
+        presets.active = presets.morph.take().map(|morph| morph.to);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_classic_preset_frames_home_view() {
+        let preset = &builtin_presets()[0];
+        assert_eq!(preset.rho, 28.0);
+        assert_eq!(preset.camera, PresetCamera::capture(&OrbitCamera::default()));
+    }
+
+    #[test]
+    fn test_morph_eases_between_endpoints() {
+        let to = &builtin_presets()[4];
+        let mut morph = Morph {
+            from: SimulationConfig::default(),
+            to: to.clone(),
+            elapsed: 2.5,
+            duration: 5.0,
+        };
+        let mut config = SimulationConfig::default();
+        morph.apply(&mut config);
+        assert!((config.rho - (28.0 + 99.96) / 2.0).abs() < 1e-9);
+        assert!((config.dt - (0.005f64 * 0.002).sqrt()).abs() < 1e-12);
+
+        morph.elapsed = 6.0;
+        morph.apply(&mut config);
+        assert_eq!((config.rho, config.dt), (to.rho, to.dt));
+    }
+
+    #[test]
+    fn test_user_presets_round_trip() {
+        let config = SimulationConfig {
+            rho: 99.96,
+            ..default()
+        };
+        let presets = UserPresets {
+            presets: vec![Preset::capture("Mine", "", &config, &OrbitCamera::default())],
+        };
+        let text = toml::to_string_pretty(&presets).unwrap();
+        let loaded: UserPresets = toml::from_str(&text).unwrap();
+        assert!(loaded == presets);
+    }
+}
\ No newline at end of file
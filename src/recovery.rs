@@ -0,0 +1,166 @@
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::thread::{self, JoinHandle};
+
+use bevy::prelude::*;
+#[cfg(feature = "ui")]
+use bevy_egui::{egui, EguiContexts};
+
+#[cfg(feature = "ui")]
+use crate::i18n::{tr, trf};
+use crate::persistence::config_path;
+use crate::snapshot::{decode, encode, Snapshot, SnapshotTarget};
+#[cfg(feature = "ui")]
+use crate::ui::toast::Toasts;
+
+const RECOVERY_FILE: &str = "recovery.ron";
+const AUTOSAVE_SECONDS: f32 = 60.0;
+const RECOVERY_TRAIL_POINTS: usize = 200_000;
+
+/// Periodic autosave of the session snapshot. The file is removed on a clean
+/// exit, so finding it at launch means the previous run crashed or was killed.
+#[derive(Resource, Default)]
+pub struct Recovery {
+    pending: Option<Snapshot>,
+    writer: Option<JoinHandle<io::Result<()>>>,
+    last_save: f32,
+}
+
+impl Recovery {
+    pub fn detect() -> Self {
+        let pending = config_path(RECOVERY_FILE)
+            .and_then(|path| fs::read_to_string(path).ok())
+            .and_then(|text| match decode(&text) {
+                Ok(snapshot) => Some(snapshot),
+                Err(err) => {
+                    warn!("Ignoring unreadable {}: {}", RECOVERY_FILE, err);
+                    None
+                }
+            });
+        Self {
+            pending,
+            ..default()
+        }
+    }
+}
+
+fn write_replacing(path: &Path, text: &str) -> io::Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let partial = path.with_extension("ron.partial");
+    fs::write(&partial, text)?;
+    fs::rename(partial, path)
+}
+
+pub fn recovery_autosave_system(
+    mut recovery: ResMut<Recovery>,
+    target: SnapshotTarget,
+    time: Res<Time<Real>>,
+) {
+    if recovery.writer.as_ref().is_some_and(JoinHandle::is_finished) {
+        if let Some(Ok(Err(err))) = recovery.writer.take().map(JoinHandle::join) {
+            warn!("Recovery autosave failed: {}", err);
+        }
+    }
+
+    // Keep the crashed session's file until the user has answered the prompt.
+    let now = time.elapsed_seconds();
+    if recovery.pending.is_some()
+        || recovery.writer.is_some()
+        || now - recovery.last_save < AUTOSAVE_SECONDS
+    {
+        return;
+    }
+    recovery.last_save = now;
+
+    let (Some(path), Some(snapshot)) =
+        (config_path(RECOVERY_FILE), target.capture(RECOVERY_TRAIL_POINTS))
+    else {
+        return;
+    };
+    recovery.writer = Some(thread::spawn(move || write_replacing(&path, &encode(&snapshot)?)));
+}
+
+pub fn recovery_clean_exit_system(mut exits: EventReader<AppExit>, mut recovery: ResMut<Recovery>) {
+    if exits.read().count() == 0 {
+        return;
+    }
+    if let Some(writer) = recovery.writer.take() {
+        let _ = writer.join();
+    }
+    if recovery.pending.is_none() {
+        if let Some(path) = config_path(RECOVERY_FILE) {
+            let _ = fs::remove_file(path);
+        }
+    }
+}
+
+#[cfg(feature = "ui")]
+pub fn recovery_prompt_system(
+    mut contexts: EguiContexts,
+    mut recovery: ResMut<Recovery>,
+    mut target: SnapshotTarget,
+    mut toasts: ResMut<Toasts>,
+) {
+    let Some(snapshot) = recovery.pending.as_ref() else {
+        return;
+    };
+
+    let mut restore = None;
+    egui::Window::new(tr("Restore previous session?"))
+        .collapsible(false)
+        .resizable(false)
+        .anchor(egui::Align2::CENTER_CENTER, egui::Vec2::ZERO)
+        .show(contexts.ctx_mut(), |ui| {
+            ui.label(tr("The last session did not exit cleanly."));
+            ui.label(trf(
+                "Autosaved at t = {t} (ρ = {rho}, {steps} steps, {points} trail points)",
+                &[
+                    ("t", &format!("{:.2}", snapshot.state.t)),
+                    ("rho", &format!("{:.2}", snapshot.config.rho)),
+                    ("steps", &snapshot.total_steps),
+                    ("points", &snapshot.trail.as_ref().map_or(0, Vec::len)),
+                ],
+            ));
+            ui.horizontal(|ui| {
+                if ui.button(tr("♻ Restore")).clicked() {
+                    restore = Some(true);
+                }
+                if ui.button(tr("🗑 Discard")).clicked() {
+                    restore = Some(false);
+                }
+            });
+        });
+
+    match restore {
+        Some(true) => {
+            if let Some(snapshot) = recovery.pending.take() {
+                target.restore(&snapshot);
+                toasts.push(trf(
+                    "♻ Restored session at t = {t}",
+                    &[("t", &format!("{:.2}", snapshot.state.t))],
+                ));
+            }
+        }
+        Some(false) => recovery.pending = None,
+        None => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_write_replacing_leaves_no_partial_file() {
+        let dir = std::env::temp_dir().join(format!("lorenz-recovery-{}", std::process::id()));
+        let path = dir.join(RECOVERY_FILE);
+        write_replacing(&path, "first").unwrap();
+        write_replacing(&path, "second").unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), "second");
+        assert!(!path.with_extension("ron.partial").exists());
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
\ No newline at end of file
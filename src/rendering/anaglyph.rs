@@ -0,0 +1,222 @@
+use bevy::core_pipeline::tonemapping::Tonemapping;
+use bevy::prelude::*;
+use bevy::render::render_resource::{
+    AsBindGroup, Extent3d, ShaderRef, TextureDescriptor, TextureDimension, TextureFormat,
+    TextureUsages,
+};
+use bevy::render::view::RenderLayers;
+use bevy::sprite::{Material2d, MaterialMesh2dBundle};
+use bevy::window::PrimaryWindow;
+
+use crate::config::AnaglyphConfig;
+use super::camera_controller::OrbitCamera;
+
+pub const ANAGLYPH_LAYER: usize = 3;
+pub const ANAGLYPH_SHADER_HANDLE: Handle<Shader> =
+    Handle::weak_from_u128(0x6c6f_7265_6e7a_5f61_6e61_676c_7970_6800);
+
+#[derive(Asset, TypePath, AsBindGroup, Clone)]
+pub struct AnaglyphMaterial {
+    #[texture(0)]
+    #[sampler(1)]
+    left: Handle<Image>,
+    #[texture(2)]
+    #[sampler(3)]
+    right: Handle<Image>,
+}
+
+impl Material2d for AnaglyphMaterial {
+    fn fragment_shader() -> ShaderRef {
+        ANAGLYPH_SHADER_HANDLE.into()
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Eye {
+    Left,
+    Right,
+}
+
+#[derive(Component)]
+pub struct EyeCamera(Eye);
+
+#[derive(Component)]
+pub struct AnaglyphComposite;
+
+#[derive(Resource)]
+pub struct AnaglyphTargets {
+    left: Handle<Image>,
+    right: Handle<Image>,
+    material: Handle<AnaglyphMaterial>,
+}
+
+fn eye_target(size: Extent3d) -> Image {
+    let mut image = Image {
+        texture_descriptor: TextureDescriptor {
+            label: Some("anaglyph_eye"),
+            size,
+            dimension: TextureDimension::D2,
+            format: TextureFormat::Bgra8UnormSrgb,
+            mip_level_count: 1,
+            sample_count: 1,
+            usage: TextureUsages::TEXTURE_BINDING
+                | TextureUsages::COPY_DST
+                | TextureUsages::RENDER_ATTACHMENT,
+            view_formats: &[],
+        },
+        ..default()
+    };
+    image.resize(size);
+    image
+}
+
+pub fn setup_anaglyph(
+    mut commands: Commands,
+    mut shaders: ResMut<Assets<Shader>>,
+    mut images: ResMut<Assets<Image>>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<AnaglyphMaterial>>,
+) {
+    shaders.insert(
+        &ANAGLYPH_SHADER_HANDLE,
+        Shader::from_wgsl(include_str!("anaglyph.wgsl"), file!()),
+    );
+
+    let size = Extent3d {
+        width: 1,
+        height: 1,
+        ..default()
+    };
+    let left = images.add(eye_target(size));
+    let right = images.add(eye_target(size));
+
+    for (i, (eye, target)) in [(Eye::Left, &left), (Eye::Right, &right)]
+        .into_iter()
+        .enumerate()
+    {
+        commands.spawn((
+            Camera3dBundle {
+                camera: Camera {
+                    order: -2 + i as isize,
+                    is_active: false,
+                    target: target.clone().into(),
+                    ..default()
+                },
+                ..default()
+            },
+            EyeCamera(eye),
+        ));
+    }
+
+    let material = materials.add(AnaglyphMaterial {
+        left: left.clone(),
+        right: right.clone(),
+    });
+
+    commands.spawn((
+        Camera2dBundle {
+            camera: Camera {
+                order: 5,
+                is_active: false,
+                ..default()
+            },
+            tonemapping: Tonemapping::None,
+            ..default()
+        },
+        RenderLayers::layer(ANAGLYPH_LAYER),
+        AnaglyphComposite,
+    ));
+    commands.spawn((
+        MaterialMesh2dBundle {
+            mesh: meshes.add(Rectangle::new(1.0, 1.0)).into(),
+            material: material.clone(),
+            ..default()
+        },
+        RenderLayers::layer(ANAGLYPH_LAYER),
+    ));
+
+    commands.insert_resource(AnaglyphTargets {
+        left,
+        right,
+        material,
+    });
+}
+
+#[allow(clippy::too_many_arguments, clippy::type_complexity)]
+pub fn anaglyph_system(
+    anaglyph: Res<AnaglyphConfig>,
+    targets: Res<AnaglyphTargets>,
+    windows: Query<&Window, With<PrimaryWindow>>,
+    mut images: ResMut<Assets<Image>>,
+    mut materials: ResMut<Assets<AnaglyphMaterial>>,
+    main_query: Query<(&OrbitCamera, &Transform, &Projection), Without<EyeCamera>>,
+    mut eye_query: Query<
+        (&EyeCamera, &mut Camera, &mut Transform, &mut Projection),
+        Without<OrbitCamera>,
+    >,
+    mut composite_query: Query<
+        &mut Camera,
+        (With<AnaglyphComposite>, Without<EyeCamera>, Without<OrbitCamera>),
+    >,
+    mut quad_query: Query<
+        &mut Transform,
+        (
+            With<Handle<AnaglyphMaterial>>,
+            Without<EyeCamera>,
+            Without<OrbitCamera>,
+        ),
+    >,
+) {
+    let Ok(mut composite) = composite_query.get_single_mut() else {
+        return;
+    };
+    if composite.is_active != anaglyph.enabled {
+        composite.is_active = anaglyph.enabled;
+        for (_, mut camera, _, _) in eye_query.iter_mut() {
+            camera.is_active = anaglyph.enabled;
+        }
+    }
+    if !anaglyph.enabled {
+        return;
+    }
+
+    let (Ok(window), Ok((orbit, main_transform, main_projection))) =
+        (windows.get_single(), main_query.get_single())
+    else {
+        return;
+    };
+
+    let size = window.physical_size().max(UVec2::ONE);
+    let extent = Extent3d {
+        width: size.x,
+        height: size.y,
+        ..default()
+    };
+    let current = images.get(&targets.left).map(|image| image.texture_descriptor.size);
+    if current != Some(extent) {
+        for handle in [&targets.left, &targets.right] {
+            if let Some(image) = images.get_mut(handle) {
+                image.resize(extent);
+            }
+        }
+        // The material keeps the same handles, but the resized eye textures
+        // only reach the composite once it is re-extracted; taking it mutably
+        // marks it changed.
+        let _ = materials.get_mut(&targets.material);
+    }
+
+    if let Ok(mut quad) = quad_query.get_single_mut() {
+        quad.scale = Vec3::new(window.width(), window.height(), 1.0);
+    }
+
+    let half_separation = main_transform.right() * anaglyph.eye_separation * 0.5;
+    for (eye, _, mut transform, mut projection) in eye_query.iter_mut() {
+        let offset = match eye.0 {
+            Eye::Left => -half_separation,
+            Eye::Right => half_separation,
+        };
+        *transform = Transform::from_translation(main_transform.translation + offset)
+            .looking_at(orbit.focus, *main_transform.up());
+        *projection = main_projection.clone();
+    }
+}
\ No newline at end of file
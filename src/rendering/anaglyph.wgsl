@@ -0,0 +1,13 @@
+#import bevy_sprite::mesh2d_vertex_output::VertexOutput
+
+@group(2) @binding(0) var left_texture: texture_2d<f32>;
+@group(2) @binding(1) var left_sampler: sampler;
+@group(2) @binding(2) var right_texture: texture_2d<f32>;
+@group(2) @binding(3) var right_sampler: sampler;
+
+@fragment
+fn fragment(mesh: VertexOutput) -> @location(0) vec4<f32> {
+    let left = textureSample(left_texture, left_sampler, mesh.uv);
+    let right = textureSample(right_texture, right_sampler, mesh.uv);
+    return vec4<f32>(left.r, right.g, right.b, 1.0);
+}
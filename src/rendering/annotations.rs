@@ -0,0 +1,98 @@
+use bevy::prelude::*;
+#[cfg(feature = "ui")]
+use bevy_egui::{egui, EguiContexts, EguiSettings};
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "ui")]
+use super::camera_controller::OrbitCamera;
+#[cfg(feature = "ui")]
+use super::view_scale::ViewScale;
+
+#[cfg(feature = "ui")]
+const MARKER_RADIUS: f32 = 0.4;
+#[cfg(feature = "ui")]
+const MARKER_COLOR: Color = Color::srgb(1.0, 0.75, 0.3);
+
+/// A labeled point placed in the scene, in Lorenz coordinates.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Annotation {
+    pub label: String,
+    pub position: [f64; 3],
+}
+
+impl Annotation {
+    pub fn new(label: impl Into<String>, position: [f64; 3]) -> Self {
+        Self {
+            label: label.into(),
+            position,
+        }
+    }
+
+    /// Position as the trail stores it.
+    pub fn trail_position(&self) -> Vec3 {
+        Vec3::from_array(self.position.map(|c| c as f32))
+    }
+}
+
+/// Scene annotations; saved with recorded sessions.
+#[derive(Resource)]
+pub struct Annotations {
+    pub visible: bool,
+    pub items: Vec<Annotation>,
+}
+
+impl Default for Annotations {
+    fn default() -> Self {
+        Self {
+            visible: true,
+            items: Vec::new(),
+        }
+    }
+}
+
+/// Draws a marker at each annotation and its label beside it, always facing
+/// the viewer.
+#[cfg(feature = "ui")]
+pub fn annotations_system(
+    mut contexts: EguiContexts,
+    mut gizmos: Gizmos,
+    annotations: Res<Annotations>,
+    cameras: Query<(&Camera, &GlobalTransform), With<OrbitCamera>>,
+    egui_settings: Res<EguiSettings>,
+    view: Res<ViewScale>,
+) {
+    if !annotations.visible || annotations.items.is_empty() {
+        return;
+    }
+    let Ok((camera, camera_transform)) = cameras.get_single() else {
+        return;
+    };
+    let Some(viewport) = camera.logical_viewport_rect() else {
+        return;
+    };
+
+    let ctx = contexts.ctx_mut();
+    for (i, annotation) in annotations.items.iter().enumerate() {
+        let position = view.to_world(annotation.trail_position());
+        gizmos.sphere(position, Quat::IDENTITY, MARKER_RADIUS, MARKER_COLOR);
+        let Some(on_screen) = camera.world_to_viewport(camera_transform, position) else {
+            continue;
+        };
+        let anchor = on_screen + viewport.min;
+        if !viewport.contains(anchor) {
+            continue;
+        }
+        let anchor = anchor / egui_settings.scale_factor;
+        egui::Area::new(egui::Id::new(("annotation", i)))
+            .fixed_pos(egui::pos2(anchor.x + 8.0, anchor.y - 8.0))
+            .order(egui::Order::Background)
+            .interactable(false)
+            .show(ctx, |ui| {
+                ui.label(
+                    egui::RichText::new(&annotation.label)
+                        .color(egui::Color32::from_rgb(255, 191, 77))
+                        .background_color(egui::Color32::from_black_alpha(140)),
+                );
+            });
+    }
+}
\ No newline at end of file
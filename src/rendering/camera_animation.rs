@@ -0,0 +1,313 @@
+use std::f32::consts::TAU;
+
+use bevy::prelude::*;
+
+use crate::config::{CameraSettings, RotationMode};
+use super::camera_controller::OrbitCamera;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct CameraKeyframe {
+    pub time: f32,
+    pub focus: Vec3,
+    pub radius: f32,
+    pub theta: f32,
+    pub phi: f32,
+}
+
+impl CameraKeyframe {
+    pub fn from_orbit(time: f32, orbit: &OrbitCamera) -> Self {
+        Self {
+            time,
+            focus: orbit.focus,
+            radius: orbit.radius,
+            theta: orbit.theta,
+            phi: orbit.phi,
+        }
+    }
+
+    fn channels(&self) -> [f32; 6] {
+        [
+            self.focus.x,
+            self.focus.y,
+            self.focus.z,
+            self.radius,
+            self.theta,
+            self.phi,
+        ]
+    }
+
+    fn from_channels(time: f32, c: [f32; 6]) -> Self {
+        Self {
+            time,
+            focus: Vec3::new(c[0], c[1], c[2]),
+            radius: c[3],
+            theta: c[4],
+            phi: c[5],
+        }
+    }
+}
+
+#[derive(Resource)]
+pub struct CameraPath {
+    pub keyframes: Vec<CameraKeyframe>,
+    pub segment_duration: f32,
+    pub playing: bool,
+    pub looping: bool,
+    pub playhead: f32,
+}
+
+impl Default for CameraPath {
+    fn default() -> Self {
+        Self {
+            keyframes: Vec::new(),
+            segment_duration: 3.0,
+            playing: false,
+            looping: false,
+            playhead: 0.0,
+        }
+    }
+}
+
+impl CameraPath {
+    pub fn duration(&self) -> f32 {
+        self.keyframes.last().map_or(0.0, |k| k.time)
+    }
+
+    pub fn record(&mut self, orbit: &OrbitCamera) {
+        let time = match self.keyframes.last() {
+            Some(last) => last.time + self.segment_duration,
+            None => 0.0,
+        };
+        let mut keyframe = CameraKeyframe::from_orbit(time, orbit);
+        if let Some(last) = self.keyframes.last() {
+            keyframe.phi = nearest_turn(keyframe.phi, last.phi);
+        }
+        self.keyframes.push(keyframe);
+    }
+
+    pub fn remove(&mut self, index: usize) {
+        if index >= self.keyframes.len() {
+            return;
+        }
+        let removed = self.keyframes.remove(index);
+        let next_time = self.keyframes.get(index).map(|k| k.time);
+        if let Some(next_time) = next_time {
+            let shift = if index == 0 {
+                next_time
+            } else {
+                next_time - removed.time
+            };
+            for k in &mut self.keyframes[index..] {
+                k.time -= shift;
+            }
+        }
+        if let (Some(previous), Some(next)) = (
+            index.checked_sub(1).and_then(|i| self.keyframes.get(i)),
+            self.keyframes.get(index),
+        ) {
+            let turns = nearest_turn(next.phi, previous.phi) - next.phi;
+            for k in &mut self.keyframes[index..] {
+                k.phi += turns;
+            }
+        }
+    }
+
+    pub fn play(&mut self) {
+        if self.keyframes.len() >= 2 {
+            self.playhead = 0.0;
+            self.playing = true;
+        }
+    }
+
+    pub fn sample(&self, time: f32) -> Option<CameraKeyframe> {
+        let keys = &self.keyframes;
+        match keys.len() {
+            0 => return None,
+            1 => return Some(keys[0]),
+            _ => {}
+        }
+
+        let time = time.clamp(0.0, self.duration());
+        let i = keys
+            .windows(2)
+            .position(|w| time <= w[1].time)
+            .unwrap_or(keys.len() - 2);
+
+        let p1 = keys[i];
+        let p2 = keys[i + 1];
+        let p0 = if i > 0 { keys[i - 1] } else { p1 };
+        let p3 = keys.get(i + 2).copied().unwrap_or(p2);
+
+        let span = p2.time - p1.time;
+        let u = if span > 0.0 {
+            (time - p1.time) / span
+        } else {
+            1.0
+        };
+
+        let (c0, c1, c2, c3) = (p0.channels(), p1.channels(), p2.channels(), p3.channels());
+        let mut out = [0.0; 6];
+        for (j, value) in out.iter_mut().enumerate() {
+            *value = catmull_rom(c0[j], c1[j], c2[j], c3[j], u);
+        }
+        Some(CameraKeyframe::from_channels(time, out))
+    }
+}
+
+/// `phi` shifted by whole turns to within half a turn of `reference`, so
+/// interpolating between the two takes the short way round; `orbit.phi`
+/// keeps growing under auto-orbit.
+fn nearest_turn(phi: f32, reference: f32) -> f32 {
+    phi - ((phi - reference) / TAU).round() * TAU
+}
+
+fn catmull_rom(p0: f32, p1: f32, p2: f32, p3: f32, u: f32) -> f32 {
+    let u2 = u * u;
+    let u3 = u2 * u;
+    0.5 * (2.0 * p1
+        + (-p0 + p2) * u
+        + (2.0 * p0 - 5.0 * p1 + 4.0 * p2 - p3) * u2
+        + (-p0 + 3.0 * p1 - 3.0 * p2 + p3) * u3)
+}
+
+#[derive(Resource, Default)]
+pub struct CameraTransition {
+    active: Option<TransitionState>,
+}
+
+struct TransitionState {
+    from: CameraKeyframe,
+    to: CameraKeyframe,
+    rotate: bool,
+    elapsed: f32,
+    duration: f32,
+}
+
+impl CameraTransition {
+    pub fn start(&mut self, from: CameraKeyframe, to: CameraKeyframe, duration: f32) {
+        let rotate = (from.theta - to.theta).abs() > 1e-4 || (from.phi - to.phi).abs() > 1e-4;
+        self.active = Some(TransitionState {
+            from,
+            to,
+            rotate,
+            elapsed: 0.0,
+            duration: duration.max(1e-3),
+        });
+    }
+}
+
+pub fn camera_transition_system(
+    mut transition: ResMut<CameraTransition>,
+    mut camera_query: Query<(&mut OrbitCamera, &mut Transform)>,
+    settings: Res<CameraSettings>,
+    time: Res<Time>,
+) {
+    let Some(state) = transition.active.as_mut() else {
+        return;
+    };
+    let Ok((mut orbit, mut transform)) = camera_query.get_single_mut() else {
+        return;
+    };
+
+    state.elapsed += time.delta_seconds();
+    let t = (state.elapsed / state.duration).min(1.0);
+    let eased = t * t * (3.0 - 2.0 * t);
+
+    orbit.focus = state.from.focus.lerp(state.to.focus, eased);
+    orbit.radius = state.from.radius + (state.to.radius - state.from.radius) * eased;
+
+    if state.rotate {
+        orbit.theta = state.from.theta + (state.to.theta - state.from.theta) * eased;
+        orbit.phi = state.from.phi + (state.to.phi - state.from.phi) * eased;
+        orbit.apply_to(&mut transform, RotationMode::Turntable);
+    } else {
+        orbit.apply_to(&mut transform, settings.rotation_mode);
+    }
+
+    if t >= 1.0 {
+        transition.active = None;
+    }
+}
+
+pub fn camera_path_system(
+    mut path: ResMut<CameraPath>,
+    mut camera_query: Query<(&mut OrbitCamera, &mut Transform)>,
+    time: Res<Time>,
+) {
+    if !path.playing {
+        return;
+    }
+
+    let Ok((mut orbit, mut transform)) = camera_query.get_single_mut() else {
+        return;
+    };
+
+    path.playhead += time.delta_seconds();
+    if path.playhead > path.duration() {
+        if path.looping {
+            path.playhead = 0.0;
+        } else {
+            path.playhead = path.duration();
+            path.playing = false;
+        }
+    }
+
+    let Some(frame) = path.sample(path.playhead) else {
+        path.playing = false;
+        return;
+    };
+
+    orbit.focus = frame.focus;
+    orbit.radius = frame.radius;
+    orbit.theta = frame.theta.clamp(0.05, std::f32::consts::PI - 0.05);
+    orbit.phi = frame.phi;
+
+    orbit.apply_to(&mut transform, RotationMode::Turntable);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn path_with(radii: &[f32]) -> CameraPath {
+        let mut path = CameraPath::default();
+        for &radius in radii {
+            let orbit = OrbitCamera {
+                radius,
+                ..default()
+            };
+            path.record(&orbit);
+        }
+        path
+    }
+
+    #[test]
+    fn test_sample_hits_keyframes() {
+        let path = path_with(&[20.0, 60.0, 40.0]);
+        for k in &path.keyframes {
+            let s = path.sample(k.time).unwrap();
+            assert!((s.radius - k.radius).abs() < 1e-4);
+        }
+    }
+
+    #[test]
+    fn test_playback_takes_the_short_way_across_a_full_turn() {
+        let mut path = CameraPath::default();
+        for phi in [TAU - 0.1, 2.0 * TAU + 0.1] {
+            path.record(&OrbitCamera {
+                phi,
+                ..default()
+            });
+        }
+        let middle = path.sample(path.duration() / 2.0).unwrap();
+        assert!((middle.phi - TAU).abs() < 1e-3, "φ = {}", middle.phi);
+    }
+
+    #[test]
+    fn test_remove_closes_gap() {
+        let mut path = path_with(&[20.0, 60.0, 40.0]);
+        path.remove(1);
+        assert_eq!(path.keyframes.len(), 2);
+        assert!((path.duration() - path.segment_duration).abs() < 1e-6);
+    }
+}
\ No newline at end of file
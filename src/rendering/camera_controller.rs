@@ -1,81 +1,437 @@
-use bevy::input::mouse::{MouseMotion, MouseWheel};
+use bevy::input::mouse::{MouseMotion, MouseScrollUnit, MouseWheel};
 use bevy::prelude::*;
 
+use crate::config::{CameraSettings, FitViewEvent, ResetViewEvent, RotationMode};
+use crate::simulation::integrator::TrailBuffer;
+use crate::simulation::lorenz::LorenzState;
+use crate::simulation::trajectories::MainTrajectory;
+use super::camera_animation::{CameraKeyframe, CameraTransition};
+use super::camera_input::CameraInput;
+use super::head_drag::HeadDrag;
+use super::placement::Placement;
+use super::view_scale::ViewScale;
+
+const AUTO_ORBIT_RESUME_DELAY: f32 = 2.0;
+const KEY_ORBIT_SPEED: f32 = 1.5;
+const KEY_PAN_SPEED: f32 = 0.8;
+const KEY_ZOOM_SPEED: f32 = 1.2;
+pub const FIT_MARGIN: f32 = 1.15;
+pub const TRANSITION_SECONDS: f32 = 0.6;
+const DOUBLE_CLICK_SECONDS: f32 = 0.35;
+const PIXELS_PER_SCROLL_LINE: f32 = 40.0;
+pub const MIN_RADIUS: f32 = 5.0;
+/// Large enough to frame the attractor at ρ ≈ 350.
+pub const MAX_RADIUS: f32 = 1000.0;
+/// Where the home view looks: the middle of the classic attractor.
+pub const HOME_FOCUS: Vec3 = Vec3::new(0.0, 23.0, 0.0);
+
 #[derive(Component)]
 pub struct OrbitCamera {
     pub focus: Vec3,
     pub radius: f32,
     pub theta: f32,
     pub phi: f32,
-    pub rotate_sensitivity: f32,
-    pub pan_sensitivity: f32,
-    pub zoom_sensitivity: f32,
+    pub orientation: Quat,
+    pub rotate_velocity: Vec2,
+    pub pan_velocity: Vec3,
+    pub zoom_velocity: f32,
 }
 
 impl Default for OrbitCamera {
     fn default() -> Self {
-        Self {
-            focus: Vec3::new(0.0, 23.0, 0.0),
+        let mut orbit = Self {
+            focus: HOME_FOCUS,
             radius: 65.0,
             theta: 1.2,
             phi: -0.5,
-            rotate_sensitivity: 0.005,
-            pan_sensitivity: 0.08,
-            zoom_sensitivity: 2.5,
+            orientation: Quat::IDENTITY,
+            rotate_velocity: Vec2::ZERO,
+            pan_velocity: Vec3::ZERO,
+            zoom_velocity: 0.0,
+        };
+        orbit.orientation = orbit.turntable_rotation();
+        orbit
+    }
+}
+
+impl OrbitCamera {
+    pub fn eye_position(&self) -> Vec3 {
+        let x = self.radius * self.theta.sin() * self.phi.cos();
+        let y = self.radius * self.theta.cos();
+        let z = self.radius * self.theta.sin() * self.phi.sin();
+        self.focus + Vec3::new(x, y, z)
+    }
+
+    pub fn turntable_rotation(&self) -> Quat {
+        Transform::from_translation(self.eye_position())
+            .looking_at(self.focus, Vec3::Y)
+            .rotation
+    }
+
+    pub fn sync_angles_from_orientation(&mut self) {
+        let dir = self.orientation * Vec3::Z;
+        self.theta = dir.y.clamp(-1.0, 1.0).acos();
+        if dir.x.abs() > 1e-6 || dir.z.abs() > 1e-6 {
+            let phi = dir.z.atan2(dir.x);
+            let turns = ((self.phi - phi) / std::f32::consts::TAU).round();
+            self.phi = phi + turns * std::f32::consts::TAU;
+        }
+    }
+
+    pub fn apply_to(&mut self, transform: &mut Transform, mode: RotationMode) {
+        match mode {
+            RotationMode::Turntable => {
+                transform.translation = self.eye_position();
+                transform.look_at(self.focus, Vec3::Y);
+                self.orientation = transform.rotation;
+            }
+            RotationMode::Trackball => {
+                self.orientation = self.orientation.normalize();
+                transform.translation = self.focus + self.orientation * Vec3::Z * self.radius;
+                transform.rotation = self.orientation;
+                self.sync_angles_from_orientation();
+            }
         }
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn camera_control_system(
     mouse_button: Res<ButtonInput<MouseButton>>,
+    keys: Res<ButtonInput<KeyCode>>,
     mut mouse_motion: EventReader<MouseMotion>,
     mut scroll_events: EventReader<MouseWheel>,
+    mut extra_input: ResMut<CameraInput>,
     mut camera_query: Query<(&mut OrbitCamera, &mut Transform)>,
+    head_query: Query<&LorenzState, MainTrajectory>,
+    mut settings: ResMut<CameraSettings>,
+    time: Res<Time>,
     egui_wants: Res<EguiWantsPointer>,
+    egui_keyboard: Res<EguiWantsKeyboard>,
+    head_drag: Res<HeadDrag>,
+    view_scale: Res<ViewScale>,
+    mut idle_time: Local<f32>,
 ) {
+    let Ok((mut orbit, mut transform)) = camera_query.get_single_mut() else {
+        return;
+    };
+
+    let dt = time.delta_seconds();
+    let mut interacted = false;
+    let mut rotating = false;
+    let mut panning = false;
+    let mut rotate_delta = Vec2::ZERO;
+    let mut pan_delta = Vec3::ZERO;
+    let mut zoom_delta = 0.0;
+
     if egui_wants.0 {
         mouse_motion.read().for_each(|_| {});
         scroll_events.read().for_each(|_| {});
+    } else {
+        interacted = mouse_button.any_pressed([MouseButton::Left, MouseButton::Right]);
+        rotating = mouse_button.pressed(MouseButton::Left) && !head_drag.active();
+        panning = mouse_button.pressed(MouseButton::Right) && !settings.follow_head;
+
+        let mut delta = Vec2::ZERO;
+        for event in mouse_motion.read() {
+            delta += event.delta;
+        }
+
+        if rotating {
+            rotate_delta = -delta * settings.rotate_sensitivity;
+        }
+
+        if panning {
+            pan_delta = transform.right() * (-delta.x * settings.pan_sensitivity)
+                + transform.up() * (delta.y * settings.pan_sensitivity);
+        }
+
+        for event in scroll_events.read() {
+            interacted = true;
+            let lines = match event.unit {
+                MouseScrollUnit::Line => event.y,
+                MouseScrollUnit::Pixel => event.y / PIXELS_PER_SCROLL_LINE,
+            };
+            let scroll = if settings.invert_scroll { -lines } else { lines };
+            zoom_delta -= scroll * settings.zoom_sensitivity;
+        }
+    }
+
+    let extra = extra_input.take();
+    if extra.orbit != Vec2::ZERO {
+        rotating = true;
+        rotate_delta -= extra.orbit * settings.rotate_sensitivity;
+    }
+    if extra.pan != Vec2::ZERO && !settings.follow_head {
+        panning = true;
+        pan_delta += transform.right() * (-extra.pan.x * settings.pan_sensitivity)
+            + transform.up() * (extra.pan.y * settings.pan_sensitivity);
+    }
+    zoom_delta -= extra.zoom * settings.zoom_sensitivity;
+    interacted |= extra.orbit != Vec2::ZERO || extra.pan != Vec2::ZERO || extra.zoom != 0.0;
+
+    if !egui_keyboard.0 {
+        let axis = |pos: &[KeyCode], neg: &[KeyCode]| -> f32 {
+            let mut value = 0.0;
+            if keys.any_pressed(pos.iter().copied()) {
+                value += 1.0;
+            }
+            if keys.any_pressed(neg.iter().copied()) {
+                value -= 1.0;
+            }
+            value
+        };
+
+        let orbit_keys = Vec2::new(
+            axis(&[KeyCode::ArrowLeft], &[KeyCode::ArrowRight]),
+            axis(&[KeyCode::ArrowDown], &[KeyCode::ArrowUp]),
+        );
+        if orbit_keys != Vec2::ZERO {
+            rotating = true;
+            rotate_delta += orbit_keys * KEY_ORBIT_SPEED * dt;
+        }
+
+        let pan_keys = Vec2::new(
+            axis(&[KeyCode::KeyD], &[KeyCode::KeyA]),
+            axis(&[KeyCode::KeyW], &[KeyCode::KeyS]),
+        );
+        if pan_keys != Vec2::ZERO && !settings.follow_head {
+            panning = true;
+            pan_delta += (transform.right() * pan_keys.x + transform.up() * pan_keys.y)
+                * KEY_PAN_SPEED
+                * orbit.radius
+                * dt;
+        }
+
+        let zoom_keys = axis(
+            &[KeyCode::KeyQ, KeyCode::Minus, KeyCode::NumpadSubtract],
+            &[KeyCode::KeyE, KeyCode::Equal, KeyCode::NumpadAdd],
+        );
+        if zoom_keys != 0.0 {
+            zoom_delta += zoom_keys * KEY_ZOOM_SPEED * orbit.radius * dt;
+        }
+
+        interacted |= orbit_keys != Vec2::ZERO || pan_keys != Vec2::ZERO || zoom_keys != 0.0;
+    }
+
+    if settings.inertia && dt > 0.0 {
+        let grab = smoothing_factor(0.05, dt);
+        if rotating {
+            orbit.rotate_velocity = orbit.rotate_velocity.lerp(rotate_delta / dt, grab);
+        } else {
+            rotate_delta = orbit.rotate_velocity * dt;
+        }
+        if panning {
+            orbit.pan_velocity = orbit.pan_velocity.lerp(pan_delta / dt, grab);
+        } else {
+            pan_delta = orbit.pan_velocity * dt;
+        }
+        orbit.zoom_velocity += zoom_delta * settings.damping;
+        zoom_delta = orbit.zoom_velocity * dt;
+
+        let decay = (-settings.damping * dt).exp();
+        if !rotating {
+            orbit.rotate_velocity *= decay;
+        }
+        if !panning {
+            orbit.pan_velocity *= decay;
+        }
+        orbit.zoom_velocity *= decay;
+    } else {
+        orbit.rotate_velocity = Vec2::ZERO;
+        orbit.pan_velocity = Vec3::ZERO;
+        orbit.zoom_velocity = 0.0;
+    }
+
+    match settings.rotation_mode {
+        RotationMode::Turntable => {
+            orbit.phi += rotate_delta.x;
+            orbit.theta += rotate_delta.y;
+            orbit.theta = orbit.theta.clamp(0.05, std::f32::consts::PI - 0.05);
+        }
+        RotationMode::Trackball => {
+            orbit.orientation = orbit.orientation
+                * Quat::from_rotation_y(-rotate_delta.x)
+                * Quat::from_rotation_x(rotate_delta.y);
+        }
+    }
+
+    if !settings.follow_head {
+        orbit.focus += pan_delta;
+    }
+
+    if settings.follow_head {
+        settings.follow_distance = (settings.follow_distance + zoom_delta).clamp(5.0, 200.0);
+    } else {
+        orbit.radius = (orbit.radius + zoom_delta).clamp(MIN_RADIUS, MAX_RADIUS);
+    }
+
+    if interacted {
+        *idle_time = 0.0;
+    } else {
+        *idle_time += dt;
+    }
+
+    if settings.auto_orbit && *idle_time > AUTO_ORBIT_RESUME_DELAY {
+        let step = settings.auto_orbit_speed.to_radians() * dt;
+        match settings.rotation_mode {
+            RotationMode::Turntable => orbit.phi += step,
+            RotationMode::Trackball => {
+                orbit.orientation = Quat::from_rotation_y(-step) * orbit.orientation;
+            }
+        }
+    }
+
+    if settings.follow_head {
+        if let Ok(head) = head_query.get_single() {
+            let blend = smoothing_factor(settings.follow_smoothing, dt);
+            orbit.focus = orbit.focus.lerp(view_scale.to_world(head.to_vec3()), blend);
+            orbit.radius += (settings.follow_distance - orbit.radius) * blend;
+        }
+    }
+
+    orbit.apply_to(&mut transform, settings.rotation_mode);
+}
+
+pub fn fit_view_system(
+    mut fit_events: EventReader<FitViewEvent>,
+    trail: Res<TrailBuffer>,
+    view_scale: Res<ViewScale>,
+    mut settings: ResMut<CameraSettings>,
+    mut transition: ResMut<CameraTransition>,
+    camera_query: Query<&OrbitCamera>,
+) {
+    if fit_events.read().count() == 0 {
         return;
     }
 
-    let Ok((mut orbit, mut transform)) = camera_query.get_single_mut() else {
+    let sphere = trail.bounding_sphere_mapped(|position| view_scale.to_world(position));
+    let (Ok(orbit), Some((center, radius))) = (camera_query.get_single(), sphere) else {
         return;
     };
 
-    let mut delta = Vec2::ZERO;
-    for event in mouse_motion.read() {
-        delta += event.delta;
+    let half_fov = settings.fov_degrees.to_radians() * 0.5;
+    let distance = (radius.max(1.0) * FIT_MARGIN / half_fov.sin()).clamp(MIN_RADIUS, MAX_RADIUS);
+
+    let from = CameraKeyframe::from_orbit(0.0, orbit);
+    let to = CameraKeyframe {
+        focus: center,
+        radius: distance,
+        ..from
+    };
+    transition.start(from, to, TRANSITION_SECONDS);
+
+    if settings.follow_head {
+        settings.follow_distance = distance;
     }
+}
 
-    if mouse_button.pressed(MouseButton::Left) {
-        orbit.phi -= delta.x * orbit.rotate_sensitivity;
-        orbit.theta -= delta.y * orbit.rotate_sensitivity;
-        orbit.theta = orbit.theta.clamp(0.05, std::f32::consts::PI - 0.05);
+pub fn reset_view_system(
+    mut reset_events: EventReader<ResetViewEvent>,
+    keys: Res<ButtonInput<KeyCode>>,
+    egui_keyboard: Res<EguiWantsKeyboard>,
+    mut camera_query: Query<&mut OrbitCamera>,
+    mut transition: ResMut<CameraTransition>,
+) {
+    let hotkey = !egui_keyboard.0 && keys.just_pressed(KeyCode::Home);
+    if reset_events.read().count() == 0 && !hotkey {
+        return;
     }
 
-    if mouse_button.pressed(MouseButton::Right) {
-        let forward = (orbit.focus - transform.translation).normalize();
-        let right = forward.cross(Vec3::Y).normalize();
-        let up = right.cross(forward).normalize();
+    let Ok(mut orbit) = camera_query.get_single_mut() else {
+        return;
+    };
+
+    orbit.rotate_velocity = Vec2::ZERO;
+    orbit.pan_velocity = Vec3::ZERO;
+    orbit.zoom_velocity = 0.0;
 
-        let pan = right * (-delta.x * orbit.pan_sensitivity)
-            + up * (delta.y * orbit.pan_sensitivity);
-        orbit.focus += pan;
+    let home = OrbitCamera::default();
+    let turns = ((orbit.phi - home.phi) / std::f32::consts::TAU).round();
+    let from = CameraKeyframe::from_orbit(0.0, &orbit);
+    let to = CameraKeyframe {
+        phi: home.phi + turns * std::f32::consts::TAU,
+        ..CameraKeyframe::from_orbit(0.0, &home)
+    };
+    transition.start(from, to, TRANSITION_SECONDS);
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn recenter_on_head_system(
+    mouse_button: Res<ButtonInput<MouseButton>>,
+    egui_wants: Res<EguiWantsPointer>,
+    placement: Res<Placement>,
+    head_query: Query<&LorenzState, MainTrajectory>,
+    view_scale: Res<ViewScale>,
+    camera_query: Query<&OrbitCamera>,
+    mut transition: ResMut<CameraTransition>,
+    time: Res<Time<Real>>,
+    mut last_click: Local<Option<f32>>,
+) {
+    if egui_wants.0 || placement.active || !mouse_button.just_pressed(MouseButton::Left) {
+        return;
     }
 
-    for event in scroll_events.read() {
-        orbit.radius -= event.y * orbit.zoom_sensitivity;
-        orbit.radius = orbit.radius.clamp(5.0, 200.0);
+    let now = time.elapsed_seconds();
+    let is_double = last_click.is_some_and(|t| now - t < DOUBLE_CLICK_SECONDS);
+    if !is_double {
+        *last_click = Some(now);
+        return;
     }
+    *last_click = None;
 
-    let x = orbit.radius * orbit.theta.sin() * orbit.phi.cos();
-    let y = orbit.radius * orbit.theta.cos();
-    let z = orbit.radius * orbit.theta.sin() * orbit.phi.sin();
+    let (Ok(orbit), Ok(head)) = (camera_query.get_single(), head_query.get_single()) else {
+        return;
+    };
 
-    transform.translation = orbit.focus + Vec3::new(x, y, z);
-    transform.look_at(orbit.focus, Vec3::Y);
+    let from = CameraKeyframe::from_orbit(0.0, orbit);
+    let to = CameraKeyframe {
+        focus: view_scale.to_world(head.to_vec3()),
+        ..from
+    };
+    transition.start(from, to, TRANSITION_SECONDS);
 }
 
+pub fn camera_projection_system(
+    settings: Res<CameraSettings>,
+    mut projection_query: Query<&mut Projection, With<OrbitCamera>>,
+) {
+    if !settings.is_changed() {
+        return;
+    }
+
+    for mut projection in projection_query.iter_mut() {
+        if let Projection::Perspective(perspective) = projection.as_mut() {
+            perspective.fov = settings.fov_degrees.to_radians();
+        }
+    }
+}
+
+fn smoothing_factor(time_constant: f32, dt: f32) -> f32 {
+    if time_constant <= 0.0 {
+        1.0
+    } else {
+        1.0 - (-dt / time_constant).exp()
+    }
+}
+
+#[derive(Resource, Default)]
+pub struct EguiWantsPointer(pub bool);
+
 #[derive(Resource, Default)]
-pub struct EguiWantsPointer(pub bool);
\ No newline at end of file
+pub struct EguiWantsKeyboard(pub bool);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_orientation_round_trips_to_angles() {
+        let mut orbit = OrbitCamera::default();
+        let (theta, phi) = (orbit.theta, orbit.phi);
+        orbit.theta = 0.0;
+        orbit.sync_angles_from_orientation();
+        assert!((orbit.theta - theta).abs() < 1e-4);
+        assert!((orbit.phi - phi).abs() < 1e-4);
+    }
+}
\ No newline at end of file
@@ -0,0 +1,55 @@
+use bevy::input::gestures::{PanGesture, PinchGesture};
+use bevy::input::touch::Touches;
+use bevy::prelude::*;
+
+use super::camera_controller::EguiWantsPointer;
+
+const PINCH_ZOOM_SCALE: f32 = 0.05;
+const TRACKPAD_PINCH_SCALE: f32 = 8.0;
+
+#[derive(Resource, Default)]
+pub struct CameraInput {
+    pub orbit: Vec2,
+    pub pan: Vec2,
+    pub zoom: f32,
+}
+
+impl CameraInput {
+    pub fn take(&mut self) -> Self {
+        std::mem::take(self)
+    }
+}
+
+pub fn touch_camera_system(
+    touches: Res<Touches>,
+    mut pinch_events: EventReader<PinchGesture>,
+    mut pan_events: EventReader<PanGesture>,
+    egui_wants: Res<EguiWantsPointer>,
+    mut input: ResMut<CameraInput>,
+) {
+    if egui_wants.0 {
+        pinch_events.read().for_each(|_| {});
+        pan_events.read().for_each(|_| {});
+        return;
+    }
+
+    for pinch in pinch_events.read() {
+        input.zoom += pinch.0 * TRACKPAD_PINCH_SCALE;
+    }
+    for pan in pan_events.read() {
+        input.pan += pan.0;
+    }
+
+    let active: Vec<_> = touches.iter().collect();
+    match active.as_slice() {
+        [one] => input.orbit += one.delta(),
+        [a, b] => {
+            input.pan += (a.delta() + b.delta()) * 0.5;
+
+            let previous = a.previous_position().distance(b.previous_position());
+            let current = a.position().distance(b.position());
+            input.zoom += (current - previous) * PINCH_ZOOM_SCALE;
+        }
+        _ => {}
+    }
+}
\ No newline at end of file
@@ -0,0 +1,168 @@
+use bevy::prelude::*;
+use bevy::render::camera::ScalingMode;
+use bevy::render::view::RenderLayers;
+use bevy::window::PrimaryWindow;
+use bevy_egui::{egui, EguiContexts, EguiSettings};
+
+use crate::config::GalleryConfig;
+use crate::simulation::gallery::Gallery;
+use super::split_view::set_viewport;
+use super::view_scale::AxisMapping;
+
+pub const GALLERY_LAYER: usize = 2;
+const TILE_FILL: f32 = 0.88;
+
+#[derive(Default, Reflect, GizmoConfigGroup)]
+pub struct GalleryGizmos;
+
+#[derive(Component)]
+pub struct GalleryCamera;
+
+pub fn setup_gallery_view(mut commands: Commands, mut gizmo_store: ResMut<GizmoConfigStore>) {
+    let (gizmo_config, _) = gizmo_store.config_mut::<GalleryGizmos>();
+    gizmo_config.render_layers = RenderLayers::layer(GALLERY_LAYER);
+
+    commands.spawn((
+        Camera3dBundle {
+            camera: Camera {
+                order: 10,
+                is_active: false,
+                ..default()
+            },
+            projection: Projection::Orthographic(OrthographicProjection::default()),
+            transform: Transform::from_xyz(0.0, 0.0, 100.0).looking_at(Vec3::ZERO, Vec3::Y),
+            ..default()
+        },
+        RenderLayers::layer(GALLERY_LAYER),
+        GalleryCamera,
+    ));
+}
+
+fn tile_center(index: usize, columns: usize, rows: usize) -> Vec2 {
+    let (col, row) = ((index % columns) as f32, (index / columns) as f32);
+    Vec2::new(
+        col + 0.5 - columns as f32 / 2.0,
+        rows as f32 / 2.0 - row - 0.5,
+    )
+}
+
+pub fn gallery_view_system(
+    mut contexts: EguiContexts,
+    mut gizmos: Gizmos<GalleryGizmos>,
+    gallery_config: Res<GalleryConfig>,
+    gallery: Res<Gallery>,
+    windows: Query<&Window, With<PrimaryWindow>>,
+    egui_settings: Res<EguiSettings>,
+    mut camera_query: Query<(&mut Camera, &mut Projection), With<GalleryCamera>>,
+) {
+    let Ok((mut camera, mut projection)) = camera_query.get_single_mut() else {
+        return;
+    };
+
+    if !gallery_config.enabled {
+        if camera.is_active {
+            camera.is_active = false;
+        }
+        return;
+    }
+    let Ok(window) = windows.get_single() else {
+        return;
+    };
+
+    let ctx = contexts.ctx_mut();
+    let area = ctx.available_rect();
+    // egui points are scaled by both the window DPI and the UI scale setting.
+    let scale = window.scale_factor() * egui_settings.scale_factor;
+    let position = UVec2::new(
+        (area.min.x * scale).max(0.0) as u32,
+        (area.min.y * scale).max(0.0) as u32,
+    );
+    let size = UVec2::new(
+        (area.width() * scale) as u32,
+        (area.height() * scale) as u32,
+    )
+    .min(window.physical_size().saturating_sub(position));
+    if size.x < 1 || size.y < 1 {
+        return;
+    }
+
+    if !camera.is_active {
+        camera.is_active = true;
+    }
+    set_viewport(&mut camera, Some((position, size)));
+
+    let (columns, rows) = (gallery_config.columns, gallery_config.rows);
+    if let Projection::Orthographic(ortho) = projection.as_mut() {
+        ortho.scaling_mode = ScalingMode::AutoMin {
+            min_width: columns as f32,
+            min_height: rows as f32,
+        };
+    }
+
+    // Tiles show the classic figure, z up, seen along the remaining axis.
+    let mapping = AxisMapping::default();
+    let spheres: Vec<_> = gallery
+        .tiles
+        .iter()
+        .map(|tile| tile.trail.bounding_sphere_mapped(|p| mapping.to_scene(p)))
+        .collect();
+    let max_radius = spheres
+        .iter()
+        .flatten()
+        .map(|(_, r)| *r)
+        .fold(1.0, f32::max);
+    let fit = TILE_FILL / (2.0 * max_radius);
+
+    let pixels_per_tile = (area.width() / columns as f32).min(area.height() / rows as f32);
+    let grid_origin = area.center()
+        - egui::vec2(columns as f32, rows as f32) * pixels_per_tile / 2.0;
+
+    for (i, (tile, sphere)) in gallery.tiles.iter().zip(&spheres).enumerate() {
+        let cell = tile_center(i, columns, rows).extend(0.0);
+        gizmos.rect(
+            cell,
+            Quat::IDENTITY,
+            Vec2::splat(0.96),
+            Color::srgba(1.0, 1.0, 1.0, 0.12),
+        );
+
+        let Some((center, _)) = sphere else {
+            continue;
+        };
+        let to_tile = |p: Vec3| cell + (mapping.to_scene(p) - *center) * fit;
+        if tile.trail.points.len() >= 2 {
+            gizmos.linestrip_gradient(
+                tile.trail.points.iter().map(|p| (to_tile(p.position), p.color)),
+            );
+        }
+        if let Some(head) = tile.trail.points.back() {
+            gizmos.circle(to_tile(head.position), Dir3::Z, 0.012, Color::WHITE);
+        }
+
+        let (col, row) = ((i % columns) as f32, (i / columns) as f32);
+        let corner = grid_origin + egui::vec2(col, row) * pixels_per_tile;
+        egui::Area::new(egui::Id::new(("gallery_label", i)))
+            .fixed_pos(corner + egui::vec2(6.0, 4.0))
+            .interactable(false)
+            .show(ctx, |ui| {
+                ui.label(
+                    egui::RichText::new(format!("ρ = {:.2}", tile.rho))
+                        .small()
+                        .monospace()
+                        .color(egui::Color32::from_gray(200)),
+                );
+            });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tile_centers_fill_grid_row_major() {
+        assert_eq!(tile_center(0, 4, 4), Vec2::new(-1.5, 1.5));
+        assert_eq!(tile_center(3, 4, 4), Vec2::new(1.5, 1.5));
+        assert_eq!(tile_center(15, 4, 4), Vec2::new(1.5, -1.5));
+    }
+}
\ No newline at end of file
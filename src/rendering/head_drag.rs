@@ -0,0 +1,88 @@
+use bevy::prelude::*;
+use bevy::window::PrimaryWindow;
+
+use crate::config::{SimulationConfig, SimulationStats};
+use crate::simulation::integrator::{trail_point, TrailBuffer};
+use crate::simulation::lorenz::LorenzState;
+use crate::simulation::trajectories::{MainTrajectory, Trajectories};
+use super::camera_controller::{EguiWantsPointer, OrbitCamera};
+use super::placement::{cursor_ray, plane_hit, Placement};
+use super::view_scale::ViewScale;
+
+/// How close, in logical pixels, a press must land to the head to grab it.
+const GRAB_PIXELS: f32 = 14.0;
+const DRAG_COLOR: Color = Color::srgb(1.0, 0.85, 0.3);
+
+/// Grabbing the head marker while paused; holds where the drag started.
+#[derive(Resource, Default)]
+pub struct HeadDrag {
+    from: Option<Vec3>,
+}
+
+impl HeadDrag {
+    pub fn active(&self) -> bool {
+        self.from.is_some()
+    }
+}
+
+/// Drags the main state in a plane parallel to the screen. On release the
+/// new state joins the trail, so resuming continues from where it was dropped.
+#[allow(clippy::too_many_arguments)]
+pub fn head_drag_system(
+    mut drag: ResMut<HeadDrag>,
+    mouse_button: Res<ButtonInput<MouseButton>>,
+    windows: Query<&Window, With<PrimaryWindow>>,
+    cameras: Query<(&Camera, &GlobalTransform), With<OrbitCamera>>,
+    egui_wants: Res<EguiWantsPointer>,
+    placement: Res<Placement>,
+    trajectories: Res<Trajectories>,
+    config: Res<SimulationConfig>,
+    mut state_query: Query<&mut LorenzState, MainTrajectory>,
+    mut trail: ResMut<TrailBuffer>,
+    mut stats: ResMut<SimulationStats>,
+    view: Res<ViewScale>,
+    mut gizmos: Gizmos,
+) {
+    let (Ok(window), Ok((camera, camera_transform)), Ok(mut state)) =
+        (windows.get_single(), cameras.get_single(), state_query.get_single_mut())
+    else {
+        return;
+    };
+    let cursor = cursor_ray(window, camera, camera_transform);
+    let head = state.to_vec3();
+
+    let Some(from) = drag.from else {
+        let can_grab = config.paused && trajectories.main_visible && !placement.active;
+        if !can_grab || egui_wants.0 || !mouse_button.just_pressed(MouseButton::Left) {
+            return;
+        }
+        let on_screen = camera.world_to_viewport(camera_transform, view.to_world(head));
+        if let (Some(on_screen), Some((cursor, _))) = (on_screen, cursor) {
+            if on_screen.distance(cursor) <= GRAB_PIXELS {
+                drag.from = Some(head);
+            }
+        }
+        return;
+    };
+
+    if mouse_button.pressed(MouseButton::Left) && config.paused {
+        let origin = view.to_world(head);
+        let back = camera_transform.back();
+        let hit = cursor.and_then(|(_, ray)| plane_hit(ray, origin, back, &view));
+        if let Some([x, y, z]) = hit {
+            state.x = x;
+            state.y = y;
+            state.z = z;
+        }
+        let to = view.to_world(state.to_vec3());
+        gizmos.line(view.to_world(from), to, DRAG_COLOR);
+        gizmos.sphere(to, Quat::IDENTITY, 0.35, DRAG_COLOR);
+        return;
+    }
+
+    drag.from = None;
+    if state.to_vec3() != from {
+        trail.points.push_back(trail_point(&state, &config.params()));
+        stats.lyapunov.rebase();
+    }
+}
\ No newline at end of file
@@ -0,0 +1,122 @@
+use bevy::prelude::*;
+use bevy::window::PrimaryWindow;
+use bevy_egui::{egui, EguiContexts, EguiSettings};
+
+use crate::config::{SimulationConfig, UiVisibility};
+use crate::export::coordinates;
+use crate::i18n::tr;
+use crate::simulation::integrator::TrailBuffer;
+use crate::simulation::lorenz::{lorenz_derivatives, LorenzState};
+use crate::simulation::trajectories::Trajectories;
+use super::camera_controller::{EguiWantsPointer, OrbitCamera};
+use super::head_drag::HeadDrag;
+use super::view_scale::ViewScale;
+
+/// How close, in logical pixels, the cursor must be to a trail point.
+const HOVER_PIXELS: f32 = 10.0;
+/// The arrow spans the distance the flow covers in this much model time.
+const ARROW_TIME: f32 = 0.05;
+const ARROW_COLOR: Color = Color::srgb(1.0, 1.0, 0.4);
+
+/// Index of the point closest to `cursor`, if any lies within `radius`.
+/// Later points win ties, so the newest pass of the trail is picked.
+pub fn nearest_on_screen(
+    points: impl Iterator<Item = Option<Vec2>>,
+    cursor: Vec2,
+    radius: f32,
+) -> Option<usize> {
+    points
+        .enumerate()
+        .filter_map(|(i, p)| p.map(|p| (i, p.distance_squared(cursor))))
+        .filter(|&(_, d)| d <= radius * radius)
+        .min_by(|a, b| a.1.total_cmp(&b.1).then(b.0.cmp(&a.0)))
+        .map(|(i, _)| i)
+}
+
+/// Shows the local speed and tangent of the main trail under the cursor.
+#[allow(clippy::too_many_arguments)]
+pub fn trail_hover_system(
+    mut contexts: EguiContexts,
+    mut gizmos: Gizmos,
+    windows: Query<&Window, With<PrimaryWindow>>,
+    cameras: Query<(&Camera, &GlobalTransform), With<OrbitCamera>>,
+    mouse_button: Res<ButtonInput<MouseButton>>,
+    egui_wants: Res<EguiWantsPointer>,
+    head_drag: Res<HeadDrag>,
+    ui_visibility: Res<UiVisibility>,
+    egui_settings: Res<EguiSettings>,
+    trajectories: Res<Trajectories>,
+    trail: Res<TrailBuffer>,
+    view: Res<ViewScale>,
+    config: Res<SimulationConfig>,
+) {
+    let busy = egui_wants.0 || head_drag.active() || mouse_button.get_pressed().next().is_some();
+    if busy || ui_visibility.hidden || ui_visibility.presentation || !trajectories.main_visible {
+        return;
+    }
+    let (Ok(window), Ok((camera, camera_transform))) = (windows.get_single(), cameras.get_single())
+    else {
+        return;
+    };
+    let Some(cursor) = window.cursor_position() else {
+        return;
+    };
+    let offset = camera.logical_viewport_rect().map_or(Vec2::ZERO, |rect| rect.min);
+    let on_screen = trail
+        .points
+        .iter()
+        .map(|p| camera.world_to_viewport(camera_transform, view.to_world(p.position)));
+    let Some(index) = nearest_on_screen(on_screen, cursor - offset, HOVER_PIXELS) else {
+        return;
+    };
+
+    let point = &trail.points[index];
+    let (x, y, z) = coordinates(point);
+    let state = LorenzState::new(x.into(), y.into(), z.into());
+    let (dx, dy, dz) = lorenz_derivatives(&state, &config.params());
+    let velocity = Vec3::new(dx as f32, dy as f32, dz as f32);
+    let tip = point.position + velocity * ARROW_TIME;
+    gizmos.arrow(view.to_world(point.position), view.to_world(tip), ARROW_COLOR);
+
+    let rows = [
+        ("t", point.time),
+        ("|v|", (dx * dx + dy * dy + dz * dz).sqrt()),
+        ("dx/dt", dx),
+        ("dy/dt", dy),
+        ("dz/dt", dz),
+    ];
+    let anchor = cursor / egui_settings.scale_factor;
+    egui::Area::new(egui::Id::new("trail_hover"))
+        .fixed_pos(egui::pos2(anchor.x + 16.0, anchor.y + 16.0))
+        .interactable(false)
+        .show(contexts.ctx_mut(), |ui| {
+            egui::Frame::popup(ui.style()).show(ui, |ui| {
+                ui.label(tr("Local velocity"));
+                egui::Grid::new("trail_hover_grid").num_columns(2).show(ui, |ui| {
+                    for (label, value) in rows {
+                        ui.label(label);
+                        ui.monospace(format!("{value:.3}"));
+                        ui.end_row();
+                    }
+                });
+            });
+        });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_nearest_on_screen_skips_far_and_offscreen_points() {
+        let points = [
+            Some(Vec2::new(100.0, 100.0)),
+            None,
+            Some(Vec2::new(13.0, 10.0)),
+            Some(Vec2::new(11.0, 10.0)),
+        ];
+        let cursor = Vec2::new(10.0, 10.0);
+        assert_eq!(nearest_on_screen(points.into_iter(), cursor, 5.0), Some(3));
+        assert_eq!(nearest_on_screen(points.into_iter(), cursor, 0.5), None);
+    }
+}
\ No newline at end of file
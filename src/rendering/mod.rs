@@ -1,2 +1,17 @@
 pub mod trail_renderer;
-pub mod camera_controller;
\ No newline at end of file
+pub mod camera_controller;
+pub mod camera_animation;
+pub mod camera_input;
+pub mod split_view;
+pub mod ortho_views;
+#[cfg(feature = "ui")]
+pub mod gallery_view;
+#[cfg(feature = "vr")]
+pub mod vr;
+pub mod anaglyph;
+pub mod placement;
+pub mod head_drag;
+#[cfg(feature = "ui")]
+pub mod hover;
+pub mod annotations;
+pub mod view_scale;
\ No newline at end of file
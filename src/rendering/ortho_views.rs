@@ -0,0 +1,228 @@
+use bevy::prelude::*;
+use bevy::render::camera::ScalingMode;
+use bevy::window::PrimaryWindow;
+#[cfg(feature = "ui")]
+use bevy_egui::{egui, EguiContexts, EguiSettings};
+
+use crate::config::OrthoViewsConfig;
+use crate::simulation::integrator::TrailBuffer;
+use super::camera_controller::OrbitCamera;
+use super::split_view::set_viewport;
+use super::view_scale::{AxisMapping, ViewScale};
+
+const ORTHO_DISTANCE: f32 = 500.0;
+const ORTHO_MARGIN: f32 = 1.1;
+const FRAMING_SMOOTHING: f32 = 8.0;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OrthoAxis {
+    XY,
+    XZ,
+    YZ,
+}
+
+impl OrthoAxis {
+    pub const ALL: [OrthoAxis; 3] = [OrthoAxis::XY, OrthoAxis::XZ, OrthoAxis::YZ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            OrthoAxis::XY => "xy  (x →, y ↑)",
+            OrthoAxis::XZ => "xz  (x →, z ↑)",
+            OrthoAxis::YZ => "yz  (y →, z ↑)",
+        }
+    }
+
+    /// Lorenz coordinates across and up the pane.
+    fn coordinates(&self) -> (usize, usize) {
+        match self {
+            OrthoAxis::XY => (0, 1),
+            OrthoAxis::XZ => (0, 2),
+            OrthoAxis::YZ => (1, 2),
+        }
+    }
+
+    /// The side the pane's camera looks from, so that the first coordinate
+    /// runs right and the second up wherever `mapping` puts them.
+    fn eye_offset(&self, mapping: &AxisMapping) -> Vec3 {
+        let (across, up) = self.coordinates();
+        mapping.direction(across).cross(*mapping.direction(up))
+    }
+
+    fn up(&self, mapping: &AxisMapping) -> Vec3 {
+        *mapping.direction(self.coordinates().1)
+    }
+
+    fn cell(&self) -> UVec2 {
+        match self {
+            OrthoAxis::XY => UVec2::new(1, 0),
+            OrthoAxis::XZ => UVec2::new(0, 1),
+            OrthoAxis::YZ => UVec2::new(1, 1),
+        }
+    }
+}
+
+#[derive(Component)]
+pub struct OrthoView {
+    pub axis: OrthoAxis,
+}
+
+pub fn setup_ortho_views(mut commands: Commands, view: Res<ViewScale>) {
+    for (i, axis) in OrthoAxis::ALL.into_iter().enumerate() {
+        commands.spawn((
+            Camera3dBundle {
+                camera: Camera {
+                    order: 2 + i as isize,
+                    is_active: false,
+                    ..default()
+                },
+                projection: Projection::Orthographic(OrthographicProjection {
+                    far: ORTHO_DISTANCE * 2.0,
+                    ..default()
+                }),
+                transform: Transform::from_translation(
+                    axis.eye_offset(&view.mapping) * ORTHO_DISTANCE,
+                )
+                .looking_at(Vec3::ZERO, axis.up(&view.mapping)),
+                ..default()
+            },
+            OrthoView { axis },
+        ));
+    }
+}
+
+fn cell_rect(size: UVec2, cell: UVec2) -> (UVec2, UVec2) {
+    let half = size / 2;
+    (cell * half, half)
+}
+
+#[allow(clippy::too_many_arguments, clippy::type_complexity)]
+pub fn ortho_views_system(
+    views: Res<OrthoViewsConfig>,
+    trail: Res<TrailBuffer>,
+    view: Res<ViewScale>,
+    time: Res<Time>,
+    windows: Query<&Window, With<PrimaryWindow>>,
+    mut main_query: Query<&mut Camera, (With<OrbitCamera>, Without<OrthoView>)>,
+    mut ortho_query: Query<
+        (&OrthoView, &mut Camera, &mut Transform, &mut Projection),
+        Without<OrbitCamera>,
+    >,
+    mut framing: Local<Option<(Vec3, f32)>>,
+) {
+    let Ok(window) = windows.get_single() else {
+        return;
+    };
+    let size = window.physical_size();
+
+    if !views.enabled || size.x < 2 || size.y < 2 {
+        for (_, mut camera, _, _) in ortho_query.iter_mut() {
+            if camera.is_active {
+                camera.is_active = false;
+            }
+        }
+        return;
+    }
+
+    if let Ok(mut main_camera) = main_query.get_single_mut() {
+        set_viewport(&mut main_camera, Some(cell_rect(size, UVec2::ZERO)));
+    }
+
+    let (target_center, target_radius) = trail
+        .bounding_sphere_mapped(|position| view.to_world(position))
+        .unwrap_or((Vec3::ZERO, 30.0));
+    let (center, radius) = match *framing {
+        Some((center, radius)) => {
+            let blend = 1.0 - (-FRAMING_SMOOTHING * time.delta_seconds()).exp();
+            (
+                center.lerp(target_center, blend),
+                radius + (target_radius - radius) * blend,
+            )
+        }
+        None => (target_center, target_radius),
+    };
+    *framing = Some((center, radius));
+
+    let extent = (radius * 2.0 * ORTHO_MARGIN).max(1.0);
+    for (pane, mut camera, mut transform, mut projection) in ortho_query.iter_mut() {
+        if !camera.is_active {
+            camera.is_active = true;
+        }
+        set_viewport(&mut camera, Some(cell_rect(size, pane.axis.cell())));
+
+        let eye = center + pane.axis.eye_offset(&view.mapping) * ORTHO_DISTANCE;
+        *transform =
+            Transform::from_translation(eye).looking_at(center, pane.axis.up(&view.mapping));
+        if let Projection::Orthographic(ortho) = projection.as_mut() {
+            ortho.scaling_mode = ScalingMode::AutoMin {
+                min_width: extent,
+                min_height: extent,
+            };
+        }
+    }
+}
+
+#[cfg(feature = "ui")]
+pub fn ortho_labels_system(
+    mut contexts: EguiContexts,
+    views: Res<OrthoViewsConfig>,
+    windows: Query<&Window, With<PrimaryWindow>>,
+    egui_settings: Res<EguiSettings>,
+) {
+    if !views.enabled {
+        return;
+    }
+    let Ok(window) = windows.get_single() else {
+        return;
+    };
+    let half_points =
+        Vec2::new(window.width(), window.height()) / (2.0 * egui_settings.scale_factor);
+
+    let ctx = contexts.ctx_mut();
+    for axis in OrthoAxis::ALL {
+        let origin = axis.cell().as_vec2() * half_points;
+        egui::Area::new(egui::Id::new(("ortho_label", axis.label())))
+            .fixed_pos(egui::pos2(origin.x + 8.0, origin.y + 8.0))
+            .interactable(false)
+            .show(ctx, |ui| {
+                ui.label(
+                    egui::RichText::new(axis.label())
+                        .monospace()
+                        .color(egui::Color32::from_gray(200)),
+                );
+            });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ortho_axes_map_lorenz_coordinates_to_screen() {
+        use crate::simulation::lorenz::LorenzState;
+
+        let cases = [
+            (OrthoAxis::XY, LorenzState::new(1.0, 0.0, 0.0), LorenzState::new(0.0, 1.0, 0.0)),
+            (OrthoAxis::XZ, LorenzState::new(1.0, 0.0, 0.0), LorenzState::new(0.0, 0.0, 1.0)),
+            (OrthoAxis::YZ, LorenzState::new(0.0, 1.0, 0.0), LorenzState::new(0.0, 0.0, 1.0)),
+        ];
+        for up_axis in 0..3 {
+            for swap_horizontal in [false, true] {
+                let mut view = ViewScale::default();
+                view.mapping = AxisMapping {
+                    up: up_axis,
+                    swap_horizontal,
+                };
+                for (axis, right, up) in &cases {
+                    let eye = axis.eye_offset(&view.mapping) * ORTHO_DISTANCE;
+                    let transform = Transform::from_translation(eye)
+                        .looking_at(Vec3::ZERO, axis.up(&view.mapping));
+                    let right = view.to_world(right.to_vec3());
+                    let up = view.to_world(up.to_vec3());
+                    assert!(transform.right().dot(right) > 0.999, "{axis:?} right, {up_axis}");
+                    assert!(transform.up().dot(up) > 0.999, "{axis:?} up, {up_axis}");
+                }
+            }
+        }
+    }
+}
\ No newline at end of file
@@ -0,0 +1,192 @@
+use bevy::prelude::*;
+use bevy::window::PrimaryWindow;
+
+use crate::config::{ResetEvent, SimulationConfig, TrajectoryEvent};
+use crate::i18n::{tr_noop, trf};
+use crate::ui::toast::Toasts;
+use super::annotations::{Annotation, Annotations};
+use super::camera_controller::{EguiWantsKeyboard, EguiWantsPointer, OrbitCamera};
+use super::view_scale::ViewScale;
+
+/// A press that moves further than this before release is a camera drag.
+const CLICK_SLOP: f32 = 4.0;
+const MARKER_RADIUS: f32 = 1.5;
+const MARKER_COLOR: Color = Color::srgb(0.4, 1.0, 0.6);
+
+#[derive(Clone, Copy, PartialEq, Eq, Default)]
+pub enum PlacementPlane {
+    /// The horizontal plane z = ρ − 1 through both non-trivial equilibria.
+    #[default]
+    Equilibria,
+    /// Faces the camera at the depth of the orbit focus.
+    Focus,
+}
+
+impl PlacementPlane {
+    pub const ALL: [PlacementPlane; 2] = [Self::Equilibria, Self::Focus];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Equilibria => tr_noop("z = ρ − 1 (through C±)"),
+            Self::Focus => tr_noop("Facing the camera, through the focus"),
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Default)]
+pub enum PlacementTarget {
+    #[default]
+    InitialCondition,
+    NewTrajectory,
+    Annotation,
+}
+
+impl PlacementTarget {
+    pub const ALL: [PlacementTarget; 3] =
+        [Self::InitialCondition, Self::NewTrajectory, Self::Annotation];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::InitialCondition => tr_noop("Set x₀, y₀, z₀ and reset"),
+            Self::NewTrajectory => tr_noop("Spawn a new trajectory"),
+            Self::Annotation => tr_noop("Add an annotation"),
+        }
+    }
+}
+
+/// Click-to-place mode for initial conditions.
+#[derive(Resource, Default)]
+pub struct Placement {
+    pub active: bool,
+    pub plane: PlacementPlane,
+    pub target: PlacementTarget,
+}
+
+/// Where a ray meets the placement plane, in Lorenz coordinates of the point
+/// drawn there under `view`.
+pub fn plane_hit(ray: Ray3d, origin: Vec3, normal: Dir3, view: &ViewScale) -> Option<[f64; 3]> {
+    let distance = ray.intersect_plane(origin, InfinitePlane3d::new(normal))?;
+    let hit = view.from_world(ray.get_point(distance));
+    Some([hit.x as f64, hit.y as f64, hit.z as f64])
+}
+
+/// The cursor position and the ray under it, in the camera's viewport.
+pub fn cursor_ray(
+    window: &Window,
+    camera: &Camera,
+    camera_transform: &GlobalTransform,
+) -> Option<(Vec2, Ray3d)> {
+    let offset = camera.logical_viewport_rect().map_or(Vec2::ZERO, |rect| rect.min);
+    let cursor = window.cursor_position()? - offset;
+    Some((cursor, camera.viewport_to_world(camera_transform, cursor)?))
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn placement_system(
+    mut placement: ResMut<Placement>,
+    mouse_button: Res<ButtonInput<MouseButton>>,
+    keys: Res<ButtonInput<KeyCode>>,
+    windows: Query<&Window, With<PrimaryWindow>>,
+    cameras: Query<(&Camera, &GlobalTransform, &OrbitCamera)>,
+    egui_wants: Res<EguiWantsPointer>,
+    egui_keyboard: Res<EguiWantsKeyboard>,
+    mut config: ResMut<SimulationConfig>,
+    mut reset_events: EventWriter<ResetEvent>,
+    mut trajectory_events: EventWriter<TrajectoryEvent>,
+    mut annotations: ResMut<Annotations>,
+    mut toasts: ResMut<Toasts>,
+    view: Res<ViewScale>,
+    mut gizmos: Gizmos,
+    mut pressed_at: Local<Option<Vec2>>,
+) {
+    if !placement.active {
+        *pressed_at = None;
+        return;
+    }
+    if keys.just_pressed(KeyCode::Escape) && !egui_keyboard.0 {
+        placement.active = false;
+        return;
+    }
+
+    let (Ok(window), Ok((camera, camera_transform, orbit))) =
+        (windows.get_single(), cameras.get_single())
+    else {
+        return;
+    };
+    let Some((cursor, ray)) = cursor_ray(window, camera, camera_transform) else {
+        return;
+    };
+    let (origin, normal) = match placement.plane {
+        PlacementPlane::Equilibria => (
+            view.to_world(Vec3::Z * (config.rho - 1.0) as f32),
+            view.mapping.direction(2),
+        ),
+        PlacementPlane::Focus => (orbit.focus, camera_transform.back()),
+    };
+    let Some(point) = plane_hit(ray, origin, normal, &view) else {
+        return;
+    };
+
+    if !egui_wants.0 {
+        let position = view.to_world(Vec3::from_array(point.map(|c| c as f32)));
+        gizmos.circle(position, normal, MARKER_RADIUS, MARKER_COLOR);
+        gizmos.sphere(position, Quat::IDENTITY, 0.3, MARKER_COLOR);
+    }
+
+    if mouse_button.just_pressed(MouseButton::Left) && !egui_wants.0 {
+        *pressed_at = Some(cursor);
+    }
+    if !mouse_button.just_released(MouseButton::Left) {
+        return;
+    }
+    let Some(start) = pressed_at.take() else {
+        return;
+    };
+    if start.distance(cursor) > CLICK_SLOP {
+        return;
+    }
+
+    let [x, y, z] = point;
+    let coordinates = format!("({x:.3}, {y:.3}, {z:.3})");
+    match placement.target {
+        PlacementTarget::InitialCondition => {
+            config.initial_x = x;
+            config.initial_y = y;
+            config.initial_z = z;
+            reset_events.send(ResetEvent);
+            toasts.push(trf("Initial condition set to {point}", &[("point", &coordinates)]));
+        }
+        PlacementTarget::NewTrajectory => {
+            trajectory_events.send(TrajectoryEvent::Add(Some(point)));
+            toasts.push(trf("Trajectory added at {point}", &[("point", &coordinates)]));
+        }
+        PlacementTarget::Annotation => {
+            let n = annotations.items.len() + 1;
+            annotations.items.push(Annotation::new(trf("Note {n}", &[("n", &n)]), point));
+            toasts.push(trf("Annotation added at {point}", &[("point", &coordinates)]));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_plane_hit_maps_to_lorenz_coordinates() {
+        let ray = Ray3d::new(Vec3::new(3.0, 50.0, -4.0), Vec3::NEG_Y);
+        let hit = plane_hit(ray, Vec3::Y * 27.0, Dir3::Y, &ViewScale::default()).unwrap();
+        assert_eq!(hit, [3.0, -4.0, 27.0]);
+
+        let mut stretched = ViewScale::default();
+        stretched.axes = [1.0, 2.0, 3.0];
+        let hit = plane_hit(ray, Vec3::Y * 27.0, Dir3::Y, &stretched).unwrap();
+        assert_eq!(hit, [3.0, -2.0, 9.0]);
+    }
+
+    #[test]
+    fn test_plane_behind_ray_is_missed() {
+        let ray = Ray3d::new(Vec3::ZERO, Vec3::Y);
+        assert!(plane_hit(ray, Vec3::NEG_Y, Dir3::Y, &ViewScale::default()).is_none());
+    }
+}
\ No newline at end of file
@@ -0,0 +1,116 @@
+use bevy::prelude::*;
+use bevy::render::camera::Viewport;
+use bevy::render::view::RenderLayers;
+use bevy::window::PrimaryWindow;
+
+use crate::config::{ComparisonConfig, OrthoViewsConfig};
+use crate::simulation::comparison::ComparisonTrajectory;
+use crate::simulation::integrator::TrailBuffer;
+use super::camera_controller::OrbitCamera;
+use super::trail_renderer::{draw_axes, draw_head_marker, draw_trail};
+use super::view_scale::ViewScale;
+
+pub const COMPARISON_LAYER: usize = 1;
+
+#[derive(Default, Reflect, GizmoConfigGroup)]
+pub struct ComparisonGizmos;
+
+#[derive(Component)]
+pub struct ComparisonCamera;
+
+pub fn setup_split_view(mut commands: Commands, mut gizmo_store: ResMut<GizmoConfigStore>) {
+    let (gizmo_config, _) = gizmo_store.config_mut::<ComparisonGizmos>();
+    gizmo_config.render_layers = RenderLayers::layer(COMPARISON_LAYER);
+
+    commands.spawn((
+        Camera3dBundle {
+            camera: Camera {
+                order: 1,
+                is_active: false,
+                ..default()
+            },
+            ..default()
+        },
+        RenderLayers::layer(COMPARISON_LAYER),
+        ComparisonCamera,
+    ));
+}
+
+pub fn set_viewport(camera: &mut Camera, viewport: Option<(UVec2, UVec2)>) {
+    let current = camera
+        .viewport
+        .as_ref()
+        .map(|v| (v.physical_position, v.physical_size));
+    if current == viewport {
+        return;
+    }
+    camera.viewport = viewport.map(|(physical_position, physical_size)| Viewport {
+        physical_position,
+        physical_size,
+        ..default()
+    });
+}
+
+#[allow(clippy::type_complexity)]
+pub fn split_view_system(
+    comparison: Res<ComparisonConfig>,
+    ortho_views: Res<OrthoViewsConfig>,
+    windows: Query<&Window, With<PrimaryWindow>>,
+    mut main_query: Query<
+        (&mut Camera, &Transform, &Projection),
+        (With<OrbitCamera>, Without<ComparisonCamera>),
+    >,
+    mut comparison_query: Query<
+        (&mut Camera, &mut Transform, &mut Projection),
+        (With<ComparisonCamera>, Without<OrbitCamera>),
+    >,
+) {
+    let (Ok(window), Ok((mut main_camera, main_transform, main_projection))) =
+        (windows.get_single(), main_query.get_single_mut())
+    else {
+        return;
+    };
+    let Ok((mut b_camera, mut b_transform, mut b_projection)) = comparison_query.get_single_mut()
+    else {
+        return;
+    };
+
+    let size = window.physical_size();
+    if !comparison.enabled || ortho_views.enabled || size.x < 2 || size.y < 1 {
+        if !ortho_views.enabled {
+            set_viewport(&mut main_camera, None);
+        }
+        if b_camera.is_active {
+            b_camera.is_active = false;
+        }
+        return;
+    }
+
+    let half = UVec2::new(size.x / 2, size.y);
+    set_viewport(&mut main_camera, Some((UVec2::ZERO, half)));
+    set_viewport(&mut b_camera, Some((UVec2::new(size.x / 2, 0), half)));
+    if !b_camera.is_active {
+        b_camera.is_active = true;
+    }
+
+    *b_transform = *main_transform;
+    *b_projection = main_projection.clone();
+}
+
+pub fn draw_comparison_system(
+    mut gizmos: Gizmos<ComparisonGizmos>,
+    comparison: Res<ComparisonConfig>,
+    trail_query: Query<&TrailBuffer, With<ComparisonTrajectory>>,
+    view: Res<ViewScale>,
+) {
+    if !comparison.enabled {
+        return;
+    }
+    let Ok(trail) = trail_query.get_single() else {
+        return;
+    };
+
+    draw_trail(&mut gizmos, trail, &view);
+    draw_head_marker(&mut gizmos, trail, &view);
+    draw_axes(&mut gizmos, &view);
+}
\ No newline at end of file
@@ -1,47 +1,165 @@
+use bevy::gizmos::config::GizmoConfigGroup;
 use bevy::prelude::*;
+#[cfg(feature = "ui")]
+use bevy_egui::{egui, EguiContexts, EguiSettings};
 
+use crate::config::UiVisibility;
 use crate::simulation::integrator::TrailBuffer;
+use crate::simulation::trajectories::{ExtraTrajectory, Trajectories};
+#[cfg(feature = "ui")]
+use super::camera_controller::OrbitCamera;
+use super::view_scale::ViewScale;
 
-pub fn draw_trail_system(mut gizmos: Gizmos, trail: Res<TrailBuffer>) {
+const AXIS_HALF_LENGTH: f32 = 5.0;
+const AXIS_ALPHA: f32 = 0.25;
+/// The x, y and z axes, in the colors the plots use for them.
+const AXES: [(&str, Color); 3] = [
+    ("x", Color::srgb(1.0, 0.3, 0.3)),
+    ("y", Color::srgb(0.3, 0.3, 1.0)),
+    ("z", Color::srgb(0.3, 1.0, 0.3)),
+];
+
+pub fn draw_trail<T: GizmoConfigGroup>(
+    gizmos: &mut Gizmos<T>,
+    trail: &TrailBuffer,
+    view: &ViewScale,
+) {
     let len = trail.points.len();
     if len < 2 {
         return;
     }
 
     gizmos.linestrip_gradient(
-        trail.points.iter().map(|p| (p.position, p.color)),
+        trail.points.iter().map(|p| (view.to_world(p.position), p.color)),
     );
 }
 
+pub fn draw_head_marker<T: GizmoConfigGroup>(
+    gizmos: &mut Gizmos<T>,
+    trail: &TrailBuffer,
+    view: &ViewScale,
+) {
+    if let Some(head) = trail.points.back() {
+        let radius = 0.35;
+        gizmos.sphere(view.to_world(head.position), Quat::IDENTITY, radius, Color::WHITE);
+    }
+}
+
+/// Short lines through the model's origin along each Lorenz axis, pointing
+/// wherever the view's axis mapping sends it.
+pub fn draw_axes<T: GizmoConfigGroup>(gizmos: &mut Gizmos<T>, view: &ViewScale) {
+    let origin = view.to_world(Vec3::ZERO);
+    for (axis, (_, color)) in AXES.into_iter().enumerate() {
+        let half = *view.mapping.direction(axis) * AXIS_HALF_LENGTH;
+        gizmos.line(origin - half, origin + half, color.with_alpha(AXIS_ALPHA));
+    }
+}
+
+pub fn draw_trail_system(
+    mut gizmos: Gizmos,
+    trail: Res<TrailBuffer>,
+    trajectories: Res<Trajectories>,
+    view: Res<ViewScale>,
+) {
+    if trajectories.main_visible {
+        draw_trail(&mut gizmos, &trail, &view);
+    }
+}
+
 pub fn draw_head_marker_system(
     mut gizmos: Gizmos,
     trail: Res<TrailBuffer>,
+    trajectories: Res<Trajectories>,
+    view: Res<ViewScale>,
 ) {
+    if trajectories.main_visible {
+        draw_head_marker(&mut gizmos, &trail, &view);
+    }
+}
+
+/// Extra trajectories are drawn in a single color each, so they stay
+/// distinguishable from the speed-colored main trail.
+pub fn draw_extra_trajectory<T: GizmoConfigGroup>(
+    gizmos: &mut Gizmos<T>,
+    extra: &ExtraTrajectory,
+    trail: &TrailBuffer,
+    view: &ViewScale,
+) {
+    if !extra.visible {
+        return;
+    }
+    if trail.points.len() >= 2 {
+        let points = trail.points.iter().map(|p| view.to_world(p.position));
+        gizmos.linestrip(points, extra.color);
+    }
     if let Some(head) = trail.points.back() {
-        let radius = 0.35;
-        gizmos.sphere(head.position, Quat::IDENTITY, radius, Color::WHITE);
+        gizmos.sphere(view.to_world(head.position), Quat::IDENTITY, 0.35, extra.color);
     }
 }
 
-pub fn draw_axes_system(mut gizmos: Gizmos) {
-    let half_len = 5.0;
-    let alpha = 0.25;
+pub fn draw_extra_trajectories_system(
+    mut gizmos: Gizmos,
+    query: Query<(&ExtraTrajectory, &TrailBuffer)>,
+    view: Res<ViewScale>,
+) {
+    for (extra, trail) in &query {
+        draw_extra_trajectory(&mut gizmos, extra, trail, &view);
+    }
+}
 
-    gizmos.line(
-        Vec3::new(-half_len, 0.0, 0.0),
-        Vec3::new(half_len, 0.0, 0.0),
-        Color::srgba(1.0, 0.3, 0.3, alpha),
-    );
+pub fn draw_axes_system(
+    mut gizmos: Gizmos,
+    visibility: Res<UiVisibility>,
+    view: Res<ViewScale>,
+) {
+    if visibility.presentation {
+        return;
+    }
+    draw_axes(&mut gizmos, &view);
+}
 
-    gizmos.line(
-        Vec3::new(0.0, -half_len, 0.0),
-        Vec3::new(0.0, half_len, 0.0),
-        Color::srgba(0.3, 1.0, 0.3, alpha),
-    );
+/// Names each axis line at its positive end.
+#[cfg(feature = "ui")]
+pub fn axis_labels_system(
+    mut contexts: EguiContexts,
+    visibility: Res<UiVisibility>,
+    view: Res<ViewScale>,
+    cameras: Query<(&Camera, &GlobalTransform), With<OrbitCamera>>,
+    egui_settings: Res<EguiSettings>,
+) {
+    if visibility.presentation || visibility.hidden {
+        return;
+    }
+    let Ok((camera, camera_transform)) = cameras.get_single() else {
+        return;
+    };
+    let Some(viewport) = camera.logical_viewport_rect() else {
+        return;
+    };
 
-    gizmos.line(
-        Vec3::new(0.0, 0.0, -half_len),
-        Vec3::new(0.0, 0.0, half_len),
-        Color::srgba(0.3, 0.3, 1.0, alpha),
-    );
+    let origin = view.to_world(Vec3::ZERO);
+    let ctx = contexts.ctx_mut();
+    for (axis, (name, color)) in AXES.into_iter().enumerate() {
+        let tip = origin + *view.mapping.direction(axis) * AXIS_HALF_LENGTH;
+        let Some(on_screen) = camera.world_to_viewport(camera_transform, tip) else {
+            continue;
+        };
+        let anchor = on_screen + viewport.min;
+        if !viewport.contains(anchor) {
+            continue;
+        }
+        let anchor = anchor / egui_settings.scale_factor;
+        let [r, g, b, _] = color.to_srgba().to_u8_array();
+        egui::Area::new(egui::Id::new(("axis_label", axis)))
+            .fixed_pos(egui::pos2(anchor.x + 4.0, anchor.y - 8.0))
+            .order(egui::Order::Background)
+            .interactable(false)
+            .show(ctx, |ui| {
+                ui.label(
+                    egui::RichText::new(name)
+                        .monospace()
+                        .color(egui::Color32::from_rgb(r, g, b)),
+                );
+            });
+    }
 }
\ No newline at end of file
@@ -0,0 +1,202 @@
+//! Where trail positions land in the scene. An axis mapping picks which
+//! coordinate runs along each scene axis and which one points up, per-axis
+//! scale factors stretch the picture, and auto-normalize maps the main
+//! trail's bounding box onto a cube the size of the classic attractor, so
+//! systems far larger, smaller or flatter than Lorenz fill the home view
+//! without moving the camera. Only drawing and picking go through this; the
+//! trail, plots and exports keep model coordinates.
+
+use bevy::prelude::*;
+
+use crate::simulation::integrator::TrailBuffer;
+use super::camera_controller::HOME_FOCUS;
+
+/// Edge of the cube a normalized bounding box fills, centered on `HOME_FOCUS`.
+pub const CUBE_SIZE: f32 = 50.0;
+/// How quickly the normalized box follows the trail's, per second.
+const BOUNDS_SMOOTHING: f32 = 4.0;
+/// Thinnest side of the box, relative to its widest, that is stretched to
+/// the full cube; a planar system stays flat instead of blowing up noise.
+const MIN_ASPECT: f32 = 1e-3;
+
+/// Which Lorenz coordinate runs along each scene axis. Scene Y is up, for the
+/// turntable camera and the home view; the other two coordinates run along
+/// X and Z in the order x, y, z, or the other way round when swapped. The
+/// default puts z up with x and y across, as the classic figure is drawn.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct AxisMapping {
+    /// Index into `(x, y, z)` of the coordinate that points up.
+    pub up: usize,
+    pub swap_horizontal: bool,
+}
+
+impl Default for AxisMapping {
+    fn default() -> Self {
+        Self {
+            up: 2,
+            swap_horizontal: false,
+        }
+    }
+}
+
+impl AxisMapping {
+    /// Index of the Lorenz coordinate along scene X, Y and Z.
+    pub fn axes(&self) -> [usize; 3] {
+        let up = self.up.min(2);
+        let mut across = (0..3).filter(|&axis| axis != up);
+        let (first, second) = (across.next().unwrap_or(0), across.next().unwrap_or(1));
+        if self.swap_horizontal {
+            [second, up, first]
+        } else {
+            [first, up, second]
+        }
+    }
+
+    /// Scene direction in which Lorenz coordinate `axis` grows.
+    pub fn direction(&self, axis: usize) -> Dir3 {
+        match self.axes().iter().position(|&a| a == axis) {
+            Some(0) => Dir3::X,
+            Some(1) => Dir3::Y,
+            _ => Dir3::Z,
+        }
+    }
+
+    /// A vector in Lorenz coordinates, in scene axes.
+    pub fn to_scene(&self, lorenz: Vec3) -> Vec3 {
+        let [x, y, z] = self.axes();
+        Vec3::new(lorenz[x], lorenz[y], lorenz[z])
+    }
+
+    pub fn from_scene(&self, scene: Vec3) -> Vec3 {
+        let mut lorenz = Vec3::ZERO;
+        for (i, axis) in self.axes().into_iter().enumerate() {
+            lorenz[axis] = scene[i];
+        }
+        lorenz
+    }
+}
+
+#[derive(Resource, Clone, Copy, PartialEq)]
+pub struct ViewScale {
+    pub mapping: AxisMapping,
+    /// Stretch along the Lorenz x, y and z axes, applied after normalizing.
+    pub axes: [f32; 3],
+    pub normalize: bool,
+    /// Center and size, in Lorenz coordinates, of the box being normalized;
+    /// eases toward the main trail's.
+    bounds: Option<(Vec3, Vec3)>,
+}
+
+impl Default for ViewScale {
+    fn default() -> Self {
+        Self {
+            mapping: AxisMapping::default(),
+            axes: [1.0; 3],
+            normalize: false,
+            bounds: None,
+        }
+    }
+}
+
+impl ViewScale {
+    pub fn is_identity(&self) -> bool {
+        !self.normalize && self.axes == [1.0; 3]
+    }
+
+    fn normalization(&self) -> Option<(Vec3, Vec3)> {
+        let (center, size) = self.bounds.filter(|_| self.normalize)?;
+        let floor = (size.max_element() * MIN_ASPECT).max(f32::EPSILON);
+        Some((center, size.max(Vec3::splat(floor))))
+    }
+
+    /// Scene position of a trail position.
+    pub fn to_world(&self, position: Vec3) -> Vec3 {
+        let (origin, relative) = match self.normalization() {
+            Some((center, size)) => (HOME_FOCUS, (position - center) / size * CUBE_SIZE),
+            None => (Vec3::ZERO, position),
+        };
+        origin + self.mapping.to_scene(relative * Vec3::from(self.axes))
+    }
+
+    /// Trail position drawn at a scene position; the inverse of `to_world`.
+    pub fn from_world(&self, world: Vec3) -> Vec3 {
+        let normalization = self.normalization();
+        let origin = if normalization.is_some() { HOME_FOCUS } else { Vec3::ZERO };
+        let relative = self.mapping.from_scene(world - origin) / Vec3::from(self.axes);
+        match normalization {
+            Some((center, size)) => center + relative / CUBE_SIZE * size,
+            None => relative,
+        }
+    }
+}
+
+/// Eases the normalized box toward the main trail's bounding box, so that it
+/// settles as the trail fills in instead of jumping on every excursion.
+pub fn view_scale_system(mut view: ResMut<ViewScale>, trail: Res<TrailBuffer>, time: Res<Time>) {
+    if !view.normalize {
+        if view.bounds.is_some() {
+            view.bounds = None;
+        }
+        return;
+    }
+    let Some((min, max)) = trail.bounding_box() else {
+        return;
+    };
+    let target = ((min + max) * 0.5, max - min);
+    view.bounds = Some(match view.bounds {
+        Some((center, size)) => {
+            let blend = 1.0 - (-BOUNDS_SMOOTHING * time.delta_seconds()).exp();
+            (center.lerp(target.0, blend), size.lerp(target.1, blend))
+        }
+        None => target,
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::simulation::lorenz::LorenzState;
+
+    #[test]
+    fn test_normalize_maps_the_box_onto_the_cube_and_back() {
+        let view = ViewScale {
+            axes: [1.0, 1.0, 2.0],
+            normalize: true,
+            bounds: Some((Vec3::new(100.0, 0.0, 0.0), Vec3::new(400.0, 0.0, 4.0))),
+            ..default()
+        };
+        let corner = view.to_world(Vec3::new(300.0, 0.0, 2.0));
+        assert!(corner.abs_diff_eq(HOME_FOCUS + Vec3::new(25.0, 50.0, 0.0), 1e-4));
+        // The flat axis is only stretched as far as the aspect floor allows.
+        let flat = view.to_world(Vec3::new(100.0, 0.2, 0.0));
+        assert!((flat.z - HOME_FOCUS.z - 25.0).abs() < 1e-3, "{flat}");
+
+        let position = Vec3::new(-37.0, 0.1, 1.5);
+        assert!(view.from_world(view.to_world(position)).abs_diff_eq(position, 1e-4));
+    }
+
+    #[test]
+    fn test_axis_mapping_is_a_permutation_that_puts_up_on_scene_y() {
+        let point = Vec3::new(1.0, 2.0, 3.0);
+        // The default puts z up, with x and y across.
+        let classic = Vec3::new(1.0, 3.0, 2.0);
+        assert_eq!(AxisMapping::default().to_scene(point), classic);
+        assert_eq!(ViewScale::default().to_world(point), classic);
+        assert_eq!(LorenzState::new(1.0, 2.0, 3.0).to_vec3(), point);
+
+        for up in 0..3 {
+            for swap_horizontal in [false, true] {
+                let mapping = AxisMapping { up, swap_horizontal };
+                let scene = mapping.to_scene(point);
+                assert_eq!(scene.y, point[up]);
+                assert_eq!(mapping.from_scene(scene), point);
+                assert_eq!(*mapping.direction(up), Vec3::Y);
+            }
+        }
+        let sideways = AxisMapping {
+            up: 0,
+            swap_horizontal: true,
+        };
+        assert_eq!(sideways.to_scene(point), Vec3::new(3.0, 1.0, 2.0));
+    }
+}
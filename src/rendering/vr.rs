@@ -0,0 +1,234 @@
+use bevy::prelude::*;
+use bevy_mod_openxr::action_binding::OxrSendActionBindings;
+use bevy_mod_xr::actions::ActionType;
+use bevy_mod_xr::session::{session_running, XrSessionCreated, XrTrackingRoot};
+use bevy_xr_utils::tracking_utils::{
+    suggest_action_bindings, TrackingUtilitiesPlugin, XrTrackedLeftGrip, XrTrackedRightGrip,
+};
+use bevy_xr_utils::xr_utils_actions::{
+    ActiveSet, XRUtilsAction, XRUtilsActionSet, XRUtilsActionState, XRUtilsActionSystemSet,
+    XRUtilsActionsPlugin, XRUtilsBinding,
+};
+
+use crate::config::ResetViewEvent;
+
+const ROOM_SCALE: f32 = 1.0 / 40.0;
+const MODEL_CENTER: Vec3 = Vec3::new(0.0, 25.0, 0.0);
+const MODEL_PLACEMENT: Vec3 = Vec3::new(0.0, 1.3, -1.2);
+const GRAB_THRESHOLD: f32 = 0.5;
+const MIN_GRAB_SPAN: f32 = 0.02;
+
+const CONTROLLER_PROFILES: [&str; 2] = [
+    "/interaction_profiles/oculus/touch_controller",
+    "/interaction_profiles/valve/index_controller",
+];
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Hand {
+    Left,
+    Right,
+}
+
+#[derive(Component)]
+struct GrabAction(Hand);
+
+struct GrabStart {
+    left: Option<Transform>,
+    right: Option<Transform>,
+    model: Transform,
+}
+
+#[derive(Resource)]
+pub struct VrModel {
+    pub model: Transform,
+    grab: Option<GrabStart>,
+}
+
+impl Default for VrModel {
+    fn default() -> Self {
+        Self {
+            model: Transform::from_translation(MODEL_PLACEMENT)
+                * Transform::from_scale(Vec3::splat(ROOM_SCALE))
+                * Transform::from_translation(-MODEL_CENTER),
+            grab: None,
+        }
+    }
+}
+
+pub struct VrPlugin;
+
+impl Plugin for VrPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugins((TrackingUtilitiesPlugin, XRUtilsActionsPlugin))
+            .init_resource::<VrModel>()
+            .add_systems(OxrSendActionBindings, suggest_action_bindings)
+            .add_systems(
+                Startup,
+                create_grab_actions.before(XRUtilsActionSystemSet::CreateEvents),
+            )
+            .add_systems(XrSessionCreated, spawn_tracked_hands)
+            .add_systems(
+                Update,
+                (vr_grab_system, apply_vr_model_system)
+                    .chain()
+                    .run_if(session_running),
+            );
+    }
+}
+
+fn create_grab_actions(mut commands: Commands) {
+    let set = commands
+        .spawn((
+            XRUtilsActionSet {
+                name: "lorenz_grab".into(),
+                pretty_name: "Attractor manipulation".into(),
+                priority: u32::MIN,
+            },
+            ActiveSet,
+        ))
+        .id();
+
+    for (hand, side) in [(Hand::Left, "left"), (Hand::Right, "right")] {
+        let action = commands
+            .spawn((
+                XRUtilsAction {
+                    action_name: format!("grab_{side}").into(),
+                    localized_name: format!("Grab ({side} hand)").into(),
+                    action_type: ActionType::Float,
+                },
+                GrabAction(hand),
+            ))
+            .id();
+        for profile in CONTROLLER_PROFILES {
+            let binding = commands
+                .spawn(XRUtilsBinding {
+                    profile: profile.into(),
+                    binding: format!("/user/hand/{side}/input/squeeze/value").into(),
+                })
+                .id();
+            commands.entity(action).add_child(binding);
+        }
+        commands.entity(set).add_child(action);
+    }
+}
+
+fn spawn_tracked_hands(mut commands: Commands) {
+    commands.spawn((SpatialBundle::default(), XrTrackedLeftGrip));
+    commands.spawn((SpatialBundle::default(), XrTrackedRightGrip));
+}
+
+fn one_hand_grab(hand_start: &Transform, hand: &Transform, model_start: &Transform) -> Transform {
+    let delta = hand.compute_affine() * hand_start.compute_affine().inverse();
+    Transform::from_matrix((delta * model_start.compute_affine()).into())
+}
+
+fn two_hand_grab(
+    (left_start, right_start): (Vec3, Vec3),
+    (left, right): (Vec3, Vec3),
+    model_start: &Transform,
+) -> Transform {
+    let span_start = right_start - left_start;
+    let span = right - left;
+    if span_start.length() < MIN_GRAB_SPAN || span.length() < MIN_GRAB_SPAN {
+        return *model_start;
+    }
+
+    let scale = span.length() / span_start.length();
+    let rotation = Quat::from_rotation_arc(span_start.normalize(), span.normalize());
+    let pivot_start = (left_start + right_start) * 0.5;
+    let pivot = (left + right) * 0.5;
+
+    Transform::from_translation(pivot)
+        * Transform::from_rotation(rotation)
+        * Transform::from_scale(Vec3::splat(scale))
+        * Transform::from_translation(-pivot_start)
+        * *model_start
+}
+
+fn vr_grab_system(
+    mut vr_model: ResMut<VrModel>,
+    actions: Query<(&GrabAction, &XRUtilsActionState)>,
+    left_hand: Query<&Transform, With<XrTrackedLeftGrip>>,
+    right_hand: Query<&Transform, With<XrTrackedRightGrip>>,
+    mut reset_view_events: EventReader<ResetViewEvent>,
+) {
+    if !reset_view_events.is_empty() {
+        reset_view_events.clear();
+        *vr_model = VrModel::default();
+        return;
+    }
+
+    let mut left = None;
+    let mut right = None;
+    for (action, state) in actions.iter() {
+        let XRUtilsActionState::Float(value) = state else {
+            continue;
+        };
+        if value.current_state < GRAB_THRESHOLD {
+            continue;
+        }
+        match action.0 {
+            Hand::Left => left = left_hand.get_single().ok().copied(),
+            Hand::Right => right = right_hand.get_single().ok().copied(),
+        }
+    }
+
+    let same_hands = vr_model
+        .grab
+        .as_ref()
+        .is_some_and(|g| g.left.is_some() == left.is_some() && g.right.is_some() == right.is_some());
+    if !same_hands {
+        vr_model.grab = (left.is_some() || right.is_some()).then(|| GrabStart {
+            left,
+            right,
+            model: vr_model.model,
+        });
+        return;
+    }
+
+    let Some(grab) = vr_model.grab.as_ref() else {
+        return;
+    };
+    let model = match (grab.left, grab.right, left, right) {
+        (Some(l0), Some(r0), Some(l), Some(r)) => two_hand_grab(
+            (l0.translation, r0.translation),
+            (l.translation, r.translation),
+            &grab.model,
+        ),
+        (Some(h0), None, Some(h), None) | (None, Some(h0), None, Some(h)) => {
+            one_hand_grab(&h0, &h, &grab.model)
+        }
+        _ => grab.model,
+    };
+    vr_model.model = model;
+}
+
+fn apply_vr_model_system(
+    vr_model: Res<VrModel>,
+    mut root_query: Query<&mut Transform, With<XrTrackingRoot>>,
+) {
+    let Ok(mut root) = root_query.get_single_mut() else {
+        return;
+    };
+    *root = Transform::from_matrix(vr_model.model.compute_matrix().inverse());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_two_hand_grab_scales_about_midpoint() {
+        let model = VrModel::default().model;
+        let pivot = Vec3::new(0.0, 1.0, -0.5);
+        let start = (pivot - Vec3::X * 0.1, pivot + Vec3::X * 0.1);
+        let now = (pivot - Vec3::X * 0.2, pivot + Vec3::X * 0.2);
+
+        let grabbed = two_hand_grab(start, now, &model);
+        assert!((grabbed.scale.x - model.scale.x * 2.0).abs() < 1e-5);
+
+        let world_at_pivot = model.compute_matrix().inverse().transform_point3(pivot);
+        let moved = grabbed.transform_point(world_at_pivot);
+        assert!(moved.distance(pivot) < 1e-4);
+    }
+}
\ No newline at end of file
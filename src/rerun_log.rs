@@ -0,0 +1,270 @@
+//! Rerun logging: the main trajectory, the parameters and the diagnostic
+//! scalars streamed to a Rerun viewer or saved as an `.rrd` recording, for
+//! time-scrubbing and archiving runs next to the live view.
+//!
+//! Everything is logged on two timelines: `step`, which keeps counting across
+//! resets, and `sim_time`, the model time of each point.
+use bevy::prelude::*;
+use rerun::{
+    LineStrips3D, Points3D, RecordingStream, RecordingStreamBuilder, RecordingStreamResult,
+    Scalar, TextLog, ViewCoordinates,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::config::{SimulationConfig, SimulationStats};
+use crate::export::{coordinates, resolve_path};
+use crate::i18n::{tr, tr_noop, trf};
+use crate::simulation::integrator::{TrailBuffer, TrailPoint};
+use crate::ui::toast::Toasts;
+
+const APPLICATION_ID: &str = "lorenz_attractor";
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RerunSink {
+    /// Spawns the `rerun` viewer, or connects to one already running.
+    #[default]
+    Viewer,
+    File,
+}
+
+impl RerunSink {
+    pub const ALL: [Self; 2] = [Self::Viewer, Self::File];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Viewer => tr_noop("Viewer"),
+            Self::File => tr_noop(".rrd file"),
+        }
+    }
+}
+
+#[derive(Resource, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RerunSettings {
+    pub enabled: bool,
+    pub sink: RerunSink,
+    /// Where `.rrd` recordings go; empty means a timestamped file in captures/.
+    pub path: String,
+    pub every_n_steps: u32,
+}
+
+impl Default for RerunSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            sink: RerunSink::Viewer,
+            path: String::new(),
+            every_n_steps: 4,
+        }
+    }
+}
+
+/// The trail points added since `logged_steps`, numbered by step and thinned
+/// to every `every`-th step. Steps that already left the trail are skipped.
+pub fn new_points(
+    trail: &TrailBuffer,
+    logged_steps: u64,
+    total_steps: u64,
+    every: u32,
+) -> impl Iterator<Item = (u64, &TrailPoint)> {
+    let every = u64::from(every.max(1));
+    let new = total_steps.saturating_sub(logged_steps) as usize;
+    let skip = trail.points.len().saturating_sub(new);
+    let first_step = total_steps + 1 - trail.points.len().min(new) as u64;
+    (first_step..)
+        .zip(trail.points.iter().skip(skip))
+        .filter(move |(step, _)| step % every == 0)
+}
+
+#[derive(Resource, Default)]
+pub struct RerunLog {
+    stream: Option<RecordingStream>,
+    /// The sink last opened, even if that failed.
+    target: Option<(RerunSink, String)>,
+    logged_steps: u64,
+    /// Steps logged before the last reset, so `step` never runs backwards.
+    step_base: u64,
+    /// The end of the last trajectory segment, joined to the next one.
+    tail: Option<[f32; 3]>,
+    params: Option<[f64; 4]>,
+    pub destination: Option<String>,
+    pub error: Option<String>,
+}
+
+impl RerunLog {
+    pub fn is_open(&self) -> bool {
+        self.stream.is_some()
+    }
+
+    fn open(&mut self, sink: RerunSink, path: &str, total_steps: u64) -> Result<(), String> {
+        let builder = RecordingStreamBuilder::new(APPLICATION_ID);
+        let (stream, destination) = match sink {
+            RerunSink::Viewer => (builder.spawn(), tr("Rerun viewer").to_string()),
+            RerunSink::File => {
+                let path = resolve_path(path, "lorenz", "rrd").map_err(|e| e.to_string())?;
+                (builder.save(&path), path.display().to_string())
+            }
+        };
+        let stream = stream.map_err(|e| e.to_string())?;
+        stream
+            .log_static("world", &ViewCoordinates::RIGHT_HAND_Z_UP())
+            .map_err(|e| e.to_string())?;
+        *self = Self {
+            stream: Some(stream),
+            logged_steps: total_steps,
+            destination: Some(destination),
+            ..default()
+        };
+        Ok(())
+    }
+
+    fn log_frame(
+        &mut self,
+        every_n_steps: u32,
+        config: &SimulationConfig,
+        stats: &SimulationStats,
+        trail: &TrailBuffer,
+    ) -> RecordingStreamResult<()> {
+        let Some(rec) = self.stream.clone() else {
+            return Ok(());
+        };
+        if stats.total_steps < self.logged_steps {
+            self.step_base += self.logged_steps;
+            self.logged_steps = stats.total_steps;
+            self.tail = None;
+            rec.set_time_sequence("step", self.step_base as i64);
+            rec.log("events", &TextLog::new("reset"))?;
+        }
+        if stats.total_steps == self.logged_steps {
+            return Ok(());
+        }
+
+        let mut segment: Vec<[f32; 3]> = self.tail.into_iter().collect();
+        for (step, point) in new_points(trail, self.logged_steps, stats.total_steps, every_n_steps)
+        {
+            let (x, y, z) = coordinates(point);
+            rec.set_time_sequence("step", (self.step_base + step) as i64);
+            rec.set_time_seconds("sim_time", point.time);
+            rec.log("world/state", &Points3D::new([[x, y, z]]).with_radii([0.4]))?;
+            rec.log("diagnostics/speed", &Scalar::new(f64::from(point.speed)))?;
+            segment.push([x, y, z]);
+        }
+        self.logged_steps = stats.total_steps;
+        if segment.len() < 2 {
+            return Ok(());
+        }
+        self.tail = segment.last().copied();
+        rec.log("world/trajectory", &LineStrips3D::new([segment]).with_radii([0.1]))?;
+
+        rec.log("diagnostics/energy", &Scalar::new(stats.current_energy))?;
+        rec.log("diagnostics/divergence", &Scalar::new(stats.divergence))?;
+        rec.log("diagnostics/switches", &Scalar::new(stats.lobes.switches as f64))?;
+        if let Some(exponent) = stats.lyapunov.exponent() {
+            rec.log("diagnostics/lyapunov", &Scalar::new(exponent))?;
+        }
+
+        let params = [config.sigma, config.rho, config.beta, config.dt];
+        if self.params != Some(params) {
+            for (name, value) in ["sigma", "rho", "beta", "dt"].into_iter().zip(params) {
+                rec.log(format!("params/{name}"), &Scalar::new(value))?;
+            }
+            self.params = Some(params);
+        }
+        Ok(())
+    }
+}
+
+pub fn rerun_log_system(
+    settings: Res<RerunSettings>,
+    mut log: ResMut<RerunLog>,
+    config: Res<SimulationConfig>,
+    stats: Res<SimulationStats>,
+    trail: Res<TrailBuffer>,
+    mut toasts: ResMut<Toasts>,
+) {
+    let wanted = settings
+        .enabled
+        .then(|| (settings.sink, settings.path.clone()));
+    if log.target != wanted {
+        *log = RerunLog::default();
+        if let Some((sink, path)) = &wanted {
+            match log.open(*sink, path, stats.total_steps) {
+                Ok(()) => {
+                    let destination = log.destination.clone().unwrap_or_default();
+                    toasts.push(trf(
+                        "📼 Logging to {destination}",
+                        &[("destination", &destination)],
+                    ));
+                }
+                Err(error) => {
+                    toasts.push(trf("⚠ Rerun logging failed: {error}", &[("error", &error)]));
+                    log.error = Some(error);
+                }
+            }
+        }
+        log.target = wanted;
+    }
+
+    if let Err(error) = log.log_frame(settings.every_n_steps, &config, &stats, &trail) {
+        toasts.push(trf("⚠ Rerun logging failed: {error}", &[("error", &error)]));
+        log.error = Some(error.to_string());
+        log.stream = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn trail(len: usize) -> TrailBuffer {
+        let mut trail = TrailBuffer::default();
+        for i in 0..len {
+            trail.points.push_back(TrailPoint {
+                position: Vec3::splat(i as f32),
+                color: Color::WHITE,
+                time: i as f64 * 0.01,
+                speed: 1.0,
+            });
+        }
+        trail
+    }
+
+    #[test]
+    fn test_new_points_are_numbered_by_step() {
+        let trail = trail(5);
+        let steps = |logged, every| -> Vec<u64> {
+            new_points(&trail, logged, 10, every)
+                .map(|(step, _)| step)
+                .collect()
+        };
+        assert_eq!(steps(7, 1), [8, 9, 10]);
+        // The trail only holds steps 6–10.
+        assert_eq!(steps(0, 2), [6, 8, 10]);
+        assert!(steps(10, 1).is_empty());
+    }
+
+    #[test]
+    fn test_frames_reach_the_recording() {
+        let (stream, storage) = RecordingStreamBuilder::new("lorenz_test").memory().unwrap();
+        let mut log = RerunLog {
+            stream: Some(stream.clone()),
+            ..default()
+        };
+        let stats = SimulationStats {
+            total_steps: 5,
+            ..default()
+        };
+        log.log_frame(1, &SimulationConfig::default(), &stats, &trail(5))
+            .unwrap();
+        assert_eq!(log.logged_steps, 5);
+        assert_eq!(log.tail, Some([4.0, 4.0, 4.0]));
+
+        let reset = SimulationStats::default();
+        log.log_frame(1, &SimulationConfig::default(), &reset, &trail(0))
+            .unwrap();
+        assert_eq!((log.step_base, log.tail), (5, None));
+
+        stream.flush_blocking();
+        assert!(storage.num_msgs() > 0);
+    }
+}
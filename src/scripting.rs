@@ -0,0 +1,533 @@
+//! Rhai scripts that replace the Lorenz equations with a custom vector field
+//! and automate the app from frame to frame.
+//!
+//! Scripts run in a sandboxed engine: no file, network or module access, and
+//! every call is cut off after `MAX_OPERATIONS`. They reach the app only
+//! through the functions registered in [`sandboxed_engine`].
+use std::sync::{Arc, Mutex, MutexGuard};
+
+use bevy::ecs::system::SystemParam;
+use bevy::prelude::*;
+use rhai::module_resolvers::DummyModuleResolver;
+use rhai::{Array, CallFnOptions, Dynamic, Engine, EvalAltResult, FuncArgs, Map, Scope, AST};
+use serde::{Deserialize, Serialize};
+
+use crate::config::{
+    ExportManifestEvent, ExportPoincareEvent, ExportStatsEvent, ExportSymbolsEvent,
+    ExportTrajectoryEvent, ResetEvent, ScreenshotEvent, SimulationConfig,
+};
+use crate::i18n::{tr, trf};
+use crate::simulation::integrator::{CustomSystem, Derivatives};
+use crate::simulation::lorenz::{velocity_magnitude, LorenzParams, LorenzState};
+use crate::simulation::trajectories::MainTrajectory;
+use crate::ui::toast::Toasts;
+
+/// Operations one call may take before it is aborted, so a runaway loop
+/// cannot freeze the app.
+pub const MAX_OPERATIONS: u64 = 100_000;
+/// Names `get` can read.
+pub const READABLE: [&str; 10] = [
+    "sigma", "rho", "beta", "dt", "steps", "x", "y", "z", "t", "speed",
+];
+/// Names `set` can write.
+pub const WRITABLE: [&str; 5] = ["sigma", "rho", "beta", "dt", "steps"];
+/// Kinds `export_file` accepts.
+pub const EXPORTS: [&str; 6] = [
+    "screenshot",
+    "trajectory",
+    "poincare",
+    "symbols",
+    "stats",
+    "manifest",
+];
+
+pub const EXAMPLE_SCRIPT: &str = r#"// Top-level statements run once when the script starts.
+set("rho", 28.0);
+
+// Replaces the Lorenz equations while the script runs.
+fn derivatives(x, y, z, sigma, rho, beta) {
+    [sigma * (y - x), x * (rho - z) - y, x * y - beta * z]
+}
+
+// Called every unpaused frame with the model time. `this` is a map that
+// persists between frames.
+fn on_frame(t) {
+    set("rho", 28.0 + 8.0 * sin(t / 10.0));
+    if t > 60.0 && this.exported != true {
+        this.exported = true;
+        export_file("trajectory");
+    }
+}
+"#;
+
+#[derive(Resource, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ScriptSettings {
+    pub source: String,
+}
+
+impl Default for ScriptSettings {
+    fn default() -> Self {
+        Self {
+            source: EXAMPLE_SCRIPT.to_string(),
+        }
+    }
+}
+
+#[derive(Event, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ScriptEvent {
+    Run,
+    Stop,
+}
+
+/// A request a script made through the API, applied by `script_system`.
+#[derive(Clone, Debug, PartialEq)]
+pub enum ScriptCommand {
+    Set(String, f64),
+    Pause(bool),
+    Reset,
+    Export(String),
+    Print(String),
+}
+
+/// The values `get` returns, refreshed before each call.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct ScriptInputs {
+    pub sigma: f64,
+    pub rho: f64,
+    pub beta: f64,
+    pub dt: f64,
+    pub steps: f64,
+    pub x: f64,
+    pub y: f64,
+    pub z: f64,
+    pub t: f64,
+    pub speed: f64,
+}
+
+impl ScriptInputs {
+    pub fn new(config: &SimulationConfig, state: Option<&LorenzState>) -> Self {
+        let state = state.cloned().unwrap_or_default();
+        Self {
+            sigma: config.sigma,
+            rho: config.rho,
+            beta: config.beta,
+            dt: config.dt,
+            steps: f64::from(config.steps_per_frame),
+            x: state.x,
+            y: state.y,
+            z: state.z,
+            t: state.t,
+            speed: velocity_magnitude(&state, &config.params()),
+        }
+    }
+
+    fn get(&self, name: &str) -> Option<f64> {
+        let value = match name {
+            "sigma" => self.sigma,
+            "rho" => self.rho,
+            "beta" => self.beta,
+            "dt" => self.dt,
+            "steps" => self.steps,
+            "x" => self.x,
+            "y" => self.y,
+            "z" => self.z,
+            "t" => self.t,
+            "speed" => self.speed,
+            _ => return None,
+        };
+        Some(value)
+    }
+}
+
+#[derive(Default)]
+struct Shared {
+    inputs: ScriptInputs,
+    commands: Vec<ScriptCommand>,
+    /// The first error raised inside `derivatives` during integration.
+    failure: Option<String>,
+}
+
+fn lock(shared: &Mutex<Shared>) -> MutexGuard<'_, Shared> {
+    shared
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+/// An engine with resource limits, without `eval` or module imports, and
+/// with the app API bound to `shared`.
+fn sandboxed_engine(shared: &Arc<Mutex<Shared>>) -> Engine {
+    let mut engine = Engine::new();
+    engine
+        .set_max_operations(MAX_OPERATIONS)
+        .set_max_call_levels(32)
+        .set_max_expr_depths(64, 32)
+        .set_max_string_size(1024)
+        .set_max_array_size(1024)
+        .set_max_map_size(256)
+        .set_max_modules(0)
+        .set_module_resolver(DummyModuleResolver::new());
+    engine.disable_symbol("eval");
+
+    let queue = |shared: &Arc<Mutex<Shared>>| {
+        let shared = shared.clone();
+        move |command| lock(&shared).commands.push(command)
+    };
+
+    let push = queue(shared);
+    engine.on_print(move |text| push(ScriptCommand::Print(text.to_string())));
+    engine.on_debug(|text, _, _| info!("script: {text}"));
+
+    let inputs = shared.clone();
+    engine.register_fn(
+        "get",
+        move |name: &str| -> Result<f64, Box<EvalAltResult>> {
+            lock(&inputs).inputs.get(name).ok_or_else(|| {
+                format!("get: unknown name {name:?}, expected one of {READABLE:?}").into()
+            })
+        },
+    );
+    let push = queue(shared);
+    let set = move |name: &str, value: f64| -> Result<(), Box<EvalAltResult>> {
+        if !WRITABLE.contains(&name) {
+            return Err(format!("set: unknown name {name:?}, expected one of {WRITABLE:?}").into());
+        }
+        if !value.is_finite() || (matches!(name, "dt" | "steps") && value <= 0.0) {
+            return Err(format!("set: invalid value {value} for {name:?}").into());
+        }
+        push(ScriptCommand::Set(name.to_string(), value));
+        Ok(())
+    };
+    let set_int = set.clone();
+    engine.register_fn("set", set);
+    engine.register_fn("set", move |name: &str, value: i64| {
+        set_int(name, value as f64)
+    });
+    let push = queue(shared);
+    engine.register_fn("pause", move || push(ScriptCommand::Pause(true)));
+    let push = queue(shared);
+    engine.register_fn("resume", move || push(ScriptCommand::Pause(false)));
+    let push = queue(shared);
+    engine.register_fn("reset", move || push(ScriptCommand::Reset));
+    let push = queue(shared);
+    engine.register_fn(
+        "export_file",
+        move |kind: &str| -> Result<(), Box<EvalAltResult>> {
+            if !EXPORTS.contains(&kind) {
+                return Err(format!(
+                    "export_file: unknown kind {kind:?}, expected one of {EXPORTS:?}"
+                )
+                .into());
+            }
+            push(ScriptCommand::Export(kind.to_string()));
+            Ok(())
+        },
+    );
+    engine
+}
+
+fn number(value: &Dynamic) -> Option<f64> {
+    value
+        .as_float()
+        .ok()
+        .or_else(|| value.as_int().ok().map(|i| i as f64))
+}
+
+fn vector(value: &Dynamic) -> Option<(f64, f64, f64)> {
+    let parts = value.read_lock::<Array>()?;
+    match parts[..] {
+        [ref x, ref y, ref z] => Some((number(x)?, number(y)?, number(z)?)),
+        _ => None,
+    }
+}
+
+/// The compiled script and its engine; `None` while no script runs.
+#[derive(Resource)]
+pub struct ScriptHost {
+    engine: Arc<Engine>,
+    shared: Arc<Mutex<Shared>>,
+    ast: Option<Arc<AST>>,
+    memory: Dynamic,
+    pub error: Option<String>,
+}
+
+impl Default for ScriptHost {
+    fn default() -> Self {
+        let shared = Arc::default();
+        Self {
+            engine: Arc::new(sandboxed_engine(&shared)),
+            shared,
+            ast: None,
+            memory: Dynamic::UNIT,
+            error: None,
+        }
+    }
+}
+
+impl ScriptHost {
+    /// Compiles `source` and runs its top-level statements.
+    pub fn start(&mut self, source: &str, inputs: ScriptInputs) -> Result<(), String> {
+        self.stop();
+        lock(&self.shared).inputs = inputs;
+        let result = self
+            .engine
+            .compile(source)
+            .map_err(|error| error.to_string())
+            .and_then(|ast| {
+                self.engine
+                    .run_ast(&ast)
+                    .map_err(|error| error.to_string())?;
+                Ok(ast)
+            });
+        match result {
+            Ok(ast) => {
+                self.ast = Some(Arc::new(ast));
+                self.memory = Dynamic::from_map(Map::new());
+                Ok(())
+            }
+            Err(error) => {
+                self.error = Some(error.clone());
+                Err(error)
+            }
+        }
+    }
+
+    pub fn stop(&mut self) {
+        self.ast = None;
+        self.error = None;
+        lock(&self.shared).failure = None;
+    }
+
+    /// Stops the script after an error it raised while running.
+    pub fn fail(&mut self, error: String) {
+        self.stop();
+        self.error = Some(error);
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.ast.is_some()
+    }
+
+    fn defines(&self, name: &str, arity: usize) -> bool {
+        self.ast.as_ref().is_some_and(|ast| {
+            ast.iter_functions()
+                .any(|f| f.name == name && f.params.len() == arity)
+        })
+    }
+
+    pub fn has_derivatives(&self) -> bool {
+        self.defines("derivatives", 6)
+    }
+
+    /// The script's `derivatives` as a vector field. Errors and malformed
+    /// results yield NaN, which the integrator rejects, and are reported
+    /// through `take_failure`.
+    pub fn derivatives(&self) -> Option<Derivatives> {
+        if !self.has_derivatives() {
+            return None;
+        }
+        let engine = self.engine.clone();
+        let ast = self.ast.clone()?;
+        let shared = self.shared.clone();
+        Some(Arc::new(
+            move |state: &LorenzState, params: &LorenzParams| {
+                let args = (
+                    state.x,
+                    state.y,
+                    state.z,
+                    params.sigma,
+                    params.rho,
+                    params.beta,
+                );
+                call(&engine, &ast, None, "derivatives", args)
+                    .and_then(|value| {
+                        vector(&value)
+                            .ok_or_else(|| "derivatives must return [dx, dy, dz]".to_string())
+                    })
+                    .unwrap_or_else(|error| {
+                        lock(&shared).failure.get_or_insert(error);
+                        (f64::NAN, f64::NAN, f64::NAN)
+                    })
+            },
+        ))
+    }
+
+    /// Calls `on_frame(t)` if the script defines it.
+    pub fn on_frame(&mut self, inputs: ScriptInputs) -> Result<(), String> {
+        if !self.defines("on_frame", 1) {
+            return Ok(());
+        }
+        let Some(ast) = self.ast.clone() else {
+            return Ok(());
+        };
+        lock(&self.shared).inputs = inputs;
+        call(
+            &self.engine,
+            &ast,
+            Some(&mut self.memory),
+            "on_frame",
+            (inputs.t,),
+        )
+        .map(|_| ())
+    }
+
+    pub fn take_failure(&self) -> Option<String> {
+        lock(&self.shared).failure.take()
+    }
+
+    pub fn take_commands(&self) -> Vec<ScriptCommand> {
+        std::mem::take(&mut lock(&self.shared).commands)
+    }
+}
+
+fn call(
+    engine: &Engine,
+    ast: &AST,
+    this: Option<&mut Dynamic>,
+    name: &str,
+    args: impl FuncArgs,
+) -> Result<Dynamic, String> {
+    let mut options = CallFnOptions::new().eval_ast(false);
+    if let Some(this) = this {
+        options = options.bind_this_ptr(this);
+    }
+    engine
+        .call_fn_with_options::<Dynamic>(options, &mut Scope::new(), ast, name, args)
+        .map_err(|error| error.to_string())
+}
+
+#[derive(SystemParam)]
+pub struct ScriptEvents<'w> {
+    reset: EventWriter<'w, ResetEvent>,
+    screenshot: EventWriter<'w, ScreenshotEvent>,
+    trajectory: EventWriter<'w, ExportTrajectoryEvent>,
+    poincare: EventWriter<'w, ExportPoincareEvent>,
+    symbols: EventWriter<'w, ExportSymbolsEvent>,
+    stats: EventWriter<'w, ExportStatsEvent>,
+    manifest: EventWriter<'w, ExportManifestEvent>,
+}
+
+/// Starts and stops scripts, calls `on_frame` and applies what they asked for.
+#[allow(clippy::too_many_arguments)]
+pub fn script_system(
+    mut requests: EventReader<ScriptEvent>,
+    settings: Res<ScriptSettings>,
+    mut host: ResMut<ScriptHost>,
+    mut custom: ResMut<CustomSystem>,
+    mut config: ResMut<SimulationConfig>,
+    state: Query<&LorenzState, MainTrajectory>,
+    mut events: ScriptEvents,
+    mut toasts: ResMut<Toasts>,
+) {
+    let inputs = ScriptInputs::new(&config, state.get_single().ok());
+    for request in requests.read() {
+        let had_derivatives = custom.0.take().is_some();
+        match request {
+            ScriptEvent::Run => match host.start(&settings.source, inputs) {
+                Ok(()) => {
+                    custom.0 = host.derivatives();
+                    toasts.push(tr("📜 Script started"));
+                }
+                Err(error) => toasts.push(trf("⚠ Script error: {error}", &[("error", &error)])),
+            },
+            ScriptEvent::Stop => host.stop(),
+        }
+        // A trail drawn by one vector field should not run into another.
+        if had_derivatives || custom.0.is_some() {
+            events.reset.send(ResetEvent);
+        }
+    }
+
+    let mut failure = host.take_failure();
+    if failure.is_none() && host.is_running() && !config.paused {
+        failure = host.on_frame(inputs).err();
+    }
+    if let Some(error) = failure {
+        toasts.push(trf("⚠ Script error: {error}", &[("error", &error)]));
+        host.fail(error);
+        custom.0 = None;
+    }
+
+    for command in host.take_commands() {
+        match command {
+            ScriptCommand::Set(name, value) => match name.as_str() {
+                "sigma" => config.sigma = value,
+                "rho" => config.rho = value,
+                "beta" => config.beta = value,
+                "dt" => config.dt = value,
+                _ => config.steps_per_frame = value.round().clamp(1.0, 10_000.0) as u32,
+            },
+            ScriptCommand::Pause(paused) => config.paused = paused,
+            ScriptCommand::Reset => {
+                events.reset.send(ResetEvent);
+            }
+            ScriptCommand::Export(kind) => match kind.as_str() {
+                "screenshot" => {
+                    events.screenshot.send(ScreenshotEvent::default());
+                }
+                "trajectory" => {
+                    events.trajectory.send(ExportTrajectoryEvent);
+                }
+                "poincare" => {
+                    events.poincare.send(ExportPoincareEvent::default());
+                }
+                "symbols" => {
+                    events.symbols.send(ExportSymbolsEvent::default());
+                }
+                "stats" => {
+                    events.stats.send(ExportStatsEvent::default());
+                }
+                _ => {
+                    events.manifest.send(ExportManifestEvent::default());
+                }
+            },
+            ScriptCommand::Print(text) => toasts.push(text),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::simulation::lorenz::lorenz_derivatives;
+
+    #[test]
+    fn test_example_script_matches_lorenz_and_queues_commands() {
+        let mut host = ScriptHost::default();
+        let inputs = ScriptInputs::new(&SimulationConfig::default(), None);
+        host.start(EXAMPLE_SCRIPT, inputs).unwrap();
+        assert_eq!(host.take_commands(), [ScriptCommand::Set("rho".into(), 28.0)]);
+
+        let field = host.derivatives().unwrap();
+        let (state, params) = (LorenzState::new(1.5, -2.0, 20.0), LorenzParams::default());
+        assert_eq!(field(&state, &params), lorenz_derivatives(&state, &params));
+
+        // The export fires once, remembered in `this` across frames.
+        for t in [61.0, 62.0] {
+            host.on_frame(ScriptInputs { t, ..inputs }).unwrap();
+        }
+        let exports = host
+            .take_commands()
+            .into_iter()
+            .filter(|c| matches!(c, ScriptCommand::Export(_)))
+            .count();
+        assert_eq!(exports, 1);
+    }
+
+    #[test]
+    fn test_sandbox_rejects_runaway_and_unknown_calls() {
+        let mut host = ScriptHost::default();
+        let inputs = ScriptInputs::default();
+        assert!(host.start("loop {}", inputs).is_err());
+        assert!(host.start(r#"eval("1")"#, inputs).is_err());
+        assert!(host.start(r#"import "file" as f;"#, inputs).is_err());
+        assert!(host.start(r#"set("gravity", 1.0)"#, inputs).is_err());
+        assert!(!host.is_running());
+
+        host.start("fn derivatives(x, y, z, s, r, b) { [x] }", inputs).unwrap();
+        let field = host.derivatives().unwrap();
+        let (dx, _, _) = field(&LorenzState::default(), &LorenzParams::default());
+        assert!(dx.is_nan());
+        assert!(host.take_failure().is_some());
+    }
+}
\ No newline at end of file
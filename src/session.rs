@@ -0,0 +1,361 @@
+use std::fs::{self, File};
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+
+use bevy::core::FrameCount;
+use bevy::ecs::event::ManualEventReader;
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::capture::chosen_or_output_path;
+use crate::config::{
+    IntegrationMethod, PlaySessionEvent, RecordSessionEvent, ResetEvent, SimulationConfig,
+};
+use crate::i18n::{tr, trf};
+use crate::rendering::annotations::{Annotation, Annotations};
+use crate::rendering::camera_controller::OrbitCamera;
+use crate::ui::toast::Toasts;
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct SessionParams {
+    pub sigma: f64,
+    pub rho: f64,
+    pub beta: f64,
+    pub dt: f64,
+    pub method: IntegrationMethod,
+    pub steps_per_frame: u32,
+    pub paused: bool,
+    pub max_trail_points: usize,
+    pub initial_state: [f64; 3],
+}
+
+impl SessionParams {
+    pub fn capture(config: &SimulationConfig) -> Self {
+        Self {
+            sigma: config.sigma,
+            rho: config.rho,
+            beta: config.beta,
+            dt: config.dt,
+            method: config.method,
+            steps_per_frame: config.steps_per_frame,
+            paused: config.paused,
+            max_trail_points: config.max_trail_points,
+            initial_state: [config.initial_x, config.initial_y, config.initial_z],
+        }
+    }
+
+    pub fn apply(&self, config: &mut SimulationConfig) {
+        config.sigma = self.sigma;
+        config.rho = self.rho;
+        config.beta = self.beta;
+        config.dt = self.dt;
+        config.method = self.method;
+        config.steps_per_frame = self.steps_per_frame;
+        config.paused = self.paused;
+        config.max_trail_points = self.max_trail_points;
+        [config.initial_x, config.initial_y, config.initial_z] = self.initial_state;
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct CameraPose {
+    pub focus: [f32; 3],
+    pub radius: f32,
+    pub theta: f32,
+    pub phi: f32,
+    pub orientation: [f32; 4],
+}
+
+impl CameraPose {
+    pub fn capture(orbit: &OrbitCamera) -> Self {
+        Self {
+            focus: orbit.focus.to_array(),
+            radius: orbit.radius,
+            theta: orbit.theta,
+            phi: orbit.phi,
+            orientation: orbit.orientation.to_array(),
+        }
+    }
+
+    pub fn apply(&self, orbit: &mut OrbitCamera, transform: &mut Transform) {
+        orbit.focus = Vec3::from_array(self.focus);
+        orbit.radius = self.radius;
+        orbit.theta = self.theta;
+        orbit.phi = self.phi;
+        orbit.orientation = Quat::from_array(self.orientation);
+        orbit.rotate_velocity = Vec2::ZERO;
+        orbit.pan_velocity = Vec3::ZERO;
+        orbit.zoom_velocity = 0.0;
+        transform.rotation = orbit.orientation;
+        transform.translation = orbit.focus + orbit.orientation * Vec3::Z * orbit.radius;
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "action", rename_all = "snake_case")]
+pub enum SessionAction {
+    Params(SessionParams),
+    Reset,
+    Camera(CameraPose),
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct SessionEntry {
+    pub frame: u32,
+    pub time: f32,
+    #[serde(flatten)]
+    pub action: SessionAction,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct SessionFile {
+    pub version: String,
+    pub entries: Vec<SessionEntry>,
+    /// Scene annotations at the end of recording; absent in older files.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub annotations: Option<Vec<Annotation>>,
+}
+
+struct Recording {
+    start_frame: u32,
+    start_time: f32,
+    entries: Vec<SessionEntry>,
+    params: Option<SessionParams>,
+    camera: Option<CameraPose>,
+}
+
+impl Recording {
+    fn push(&mut self, frame: u32, time: f32, action: SessionAction) {
+        self.entries.push(SessionEntry {
+            frame: frame.wrapping_sub(self.start_frame),
+            time: time - self.start_time,
+            action,
+        });
+    }
+}
+
+struct Playback {
+    start_frame: u32,
+    entries: Vec<SessionEntry>,
+    next: usize,
+}
+
+#[derive(Resource, Default)]
+pub struct Session {
+    pub path: String,
+    recording: Option<Recording>,
+    playback: Option<Playback>,
+}
+
+impl Session {
+    pub fn is_recording(&self) -> bool {
+        self.recording.is_some()
+    }
+
+    pub fn recorded_entries(&self) -> usize {
+        self.recording.as_ref().map_or(0, |r| r.entries.len())
+    }
+
+    pub fn playback_progress(&self) -> Option<(usize, usize)> {
+        self.playback.as_ref().map(|p| (p.next, p.entries.len()))
+    }
+
+    pub fn stop_playback(&mut self) {
+        self.playback = None;
+    }
+}
+
+pub fn load_session(path: &Path) -> io::Result<SessionFile> {
+    Ok(serde_json::from_str(&fs::read_to_string(path)?)?)
+}
+
+fn save_session(
+    path: &Path,
+    entries: Vec<SessionEntry>,
+    annotations: &[Annotation],
+) -> io::Result<()> {
+    let file = SessionFile {
+        version: env!("CARGO_PKG_VERSION").to_string(),
+        entries,
+        annotations: Some(annotations.to_vec()),
+    };
+    let mut out = BufWriter::new(File::create(path)?);
+    serde_json::to_writer_pretty(&mut out, &file)?;
+    writeln!(out)?;
+    out.flush()
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn session_system(
+    mut record_events: EventReader<RecordSessionEvent>,
+    mut play_events: EventReader<PlaySessionEvent>,
+    mut resets: ResMut<Events<ResetEvent>>,
+    mut reset_reader: Local<ManualEventReader<ResetEvent>>,
+    mut session: ResMut<Session>,
+    mut config: ResMut<SimulationConfig>,
+    mut cameras: Query<(&mut OrbitCamera, &mut Transform)>,
+    mut annotations: ResMut<Annotations>,
+    frame: Res<FrameCount>,
+    time: Res<Time<Real>>,
+    mut toasts: ResMut<Toasts>,
+) {
+    let (frame, now) = (frame.0, time.elapsed_seconds());
+    let session = &mut *session;
+    let reset_seen = reset_reader.read(&resets).count() > 0;
+
+    if let Some(event) = record_events.read().last() {
+        if let Some(recording) = session.recording.take() {
+            let count = recording.entries.len();
+            match chosen_or_output_path(event.path.as_deref(), "session", "json")
+                .and_then(|path| {
+                    save_session(&path, recording.entries, &annotations.items).map(|()| path)
+                })
+            {
+                Ok(path) => {
+                    session.path = path.display().to_string();
+                    toasts.push(trf(
+                        "⏹ Saved {count} session actions to {path}",
+                        &[("count", &count), ("path", &path.display())],
+                    ));
+                }
+                Err(err) => toasts.push(trf("Session save failed: {err}", &[("err", &err)])),
+            }
+        } else {
+            session.playback = None;
+            let mut recording = Recording {
+                start_frame: frame,
+                start_time: now,
+                entries: Vec::new(),
+                params: None,
+                camera: None,
+            };
+            recording.push(frame, now, SessionAction::Reset);
+            resets.send(ResetEvent);
+            reset_reader.read(&resets).for_each(|_| {});
+            session.recording = Some(recording);
+            toasts.push(tr("⏺ Recording session"));
+        }
+    }
+
+    if play_events.read().count() > 0 && !session.is_recording() {
+        match load_session(Path::new(session.path.trim())) {
+            Ok(file) => {
+                toasts.push(trf(
+                    "▶ Playing {count} session actions",
+                    &[("count", &file.entries.len())],
+                ));
+                if let Some(items) = file.annotations {
+                    annotations.items = items;
+                }
+                session.playback = Some(Playback {
+                    start_frame: frame,
+                    entries: file.entries,
+                    next: 0,
+                });
+            }
+            Err(err) => toasts.push(trf("Session playback failed: {err}", &[("err", &err)])),
+        }
+    }
+
+    let camera = cameras.get_single_mut().ok();
+
+    if let Some(recording) = session.recording.as_mut() {
+        if reset_seen {
+            recording.push(frame, now, SessionAction::Reset);
+        }
+        let params = SessionParams::capture(&config);
+        if recording.params.as_ref() != Some(&params) {
+            recording.push(frame, now, SessionAction::Params(params.clone()));
+            recording.params = Some(params);
+        }
+        if let Some((orbit, _)) = camera {
+            let pose = CameraPose::capture(&orbit);
+            if recording.camera.as_ref() != Some(&pose) {
+                recording.push(frame, now, SessionAction::Camera(pose.clone()));
+                recording.camera = Some(pose);
+            }
+        }
+        return;
+    }
+
+    let Some(playback) = session.playback.as_mut() else {
+        return;
+    };
+    let elapsed = frame.wrapping_sub(playback.start_frame);
+    let mut camera = camera;
+    while let Some(entry) = playback.entries.get(playback.next) {
+        if entry.frame > elapsed {
+            break;
+        }
+        match &entry.action {
+            SessionAction::Params(params) => params.apply(&mut config),
+            SessionAction::Reset => {
+                resets.send(ResetEvent);
+            }
+            SessionAction::Camera(pose) => {
+                if let Some((orbit, transform)) = camera.as_mut() {
+                    pose.apply(orbit, transform);
+                }
+            }
+        }
+        playback.next += 1;
+    }
+    reset_reader.read(&resets).for_each(|_| {});
+    if playback.next >= playback.entries.len() {
+        session.playback = None;
+        toasts.push(tr("⏹ Session playback finished"));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_session_entries_round_trip() {
+        let config = SimulationConfig {
+            rho: 99.96,
+            method: IntegrationMethod::Euler,
+            ..Default::default()
+        };
+        let entries = vec![
+            SessionEntry { frame: 0, time: 0.0, action: SessionAction::Reset },
+            SessionEntry {
+                frame: 3,
+                time: 0.05,
+                action: SessionAction::Params(SessionParams::capture(&config)),
+            },
+            SessionEntry {
+                frame: 4,
+                time: 0.06,
+                action: SessionAction::Camera(CameraPose::capture(&OrbitCamera::default())),
+            },
+        ];
+        let text = serde_json::to_string(&entries).unwrap();
+        assert!(text.starts_with(r#"[{"frame":0,"time":0.0,"action":"reset"}"#));
+        let loaded: Vec<SessionEntry> = serde_json::from_str(&text).unwrap();
+        assert_eq!(loaded, entries);
+
+        let mut restored = SimulationConfig::default();
+        let SessionAction::Params(params) = &loaded[1].action else {
+            panic!("expected params");
+        };
+        params.apply(&mut restored);
+        assert_eq!(SessionParams::capture(&restored), *params);
+    }
+
+    #[test]
+    fn test_session_annotations_are_optional() {
+        let old: SessionFile = serde_json::from_str(r#"{"version":"0.1.0","entries":[]}"#).unwrap();
+        assert_eq!(old.annotations, None);
+
+        let file = SessionFile {
+            version: String::new(),
+            entries: Vec::new(),
+            annotations: Some(vec![Annotation::new("C+", [8.5, 8.5, 27.0])]),
+        };
+        let loaded: SessionFile = serde_json::from_str(&serde_json::to_string(&file).unwrap())
+            .unwrap();
+        assert_eq!(loaded.annotations, file.annotations);
+    }
+}
\ No newline at end of file
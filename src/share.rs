@@ -0,0 +1,315 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::config::{IntegrationMethod, RotationMode, SimulationConfig};
+use crate::i18n::{tr, trf};
+use crate::presets::PresetCamera;
+use crate::rendering::camera_controller::OrbitCamera;
+
+/// Launch settings carried in a page URL query, e.g. `?rho=99.96&method=rk4`.
+/// Unknown keys and unparsable values are ignored.
+#[derive(Resource, Clone, Debug, Default, PartialEq)]
+pub struct UrlParams {
+    pub sigma: Option<f64>,
+    pub rho: Option<f64>,
+    pub beta: Option<f64>,
+    pub x0: Option<f64>,
+    pub y0: Option<f64>,
+    pub z0: Option<f64>,
+    pub dt: Option<f64>,
+    pub method: Option<IntegrationMethod>,
+    pub camera: Option<PresetCamera>,
+}
+
+impl UrlParams {
+    pub fn parse(query: &str) -> Self {
+        let mut params = Self::default();
+        let mut camera = PresetCamera::capture(&OrbitCamera::default());
+        let mut has_camera = false;
+        for pair in query.trim_start_matches('?').split('&') {
+            let Some((key, value)) = pair.split_once('=') else {
+                continue;
+            };
+            let number = value.parse::<f64>().ok().filter(|v| v.is_finite());
+            match key {
+                "sigma" => params.sigma = number,
+                "rho" => params.rho = number,
+                "beta" => params.beta = number,
+                "x0" => params.x0 = number,
+                "y0" => params.y0 = number,
+                "z0" => params.z0 = number,
+                "dt" => params.dt = number.filter(|dt| *dt > 0.0),
+                "method" => params.method = IntegrationMethod::from_short_name(value),
+                "theta" | "phi" | "radius" => {
+                    let Some(number) = number else {
+                        continue;
+                    };
+                    match key {
+                        "theta" => camera.theta = number as f32,
+                        "phi" => camera.phi = number as f32,
+                        _ => camera.radius = (number as f32).max(1.0),
+                    }
+                    has_camera = true;
+                }
+                "focus" => {
+                    let parts: Vec<f32> =
+                        value.split(',').filter_map(|p| p.parse().ok()).collect();
+                    if let [x, y, z] = parts[..] {
+                        camera.focus = [x, y, z];
+                        has_camera = true;
+                    }
+                }
+                _ => {}
+            }
+        }
+        params.camera = has_camera.then_some(camera);
+        params
+    }
+
+    /// The current parameters and view, for a shareable link.
+    pub fn current(config: &SimulationConfig, orbit: &OrbitCamera) -> Self {
+        Self {
+            sigma: Some(config.sigma),
+            rho: Some(config.rho),
+            beta: Some(config.beta),
+            x0: Some(config.initial_x),
+            y0: Some(config.initial_y),
+            z0: Some(config.initial_z),
+            dt: Some(config.dt),
+            method: Some(config.method),
+            camera: Some(PresetCamera::capture(orbit)),
+        }
+    }
+
+    pub fn to_query(&self) -> String {
+        let numbers = [
+            ("sigma", self.sigma),
+            ("rho", self.rho),
+            ("beta", self.beta),
+            ("x0", self.x0),
+            ("y0", self.y0),
+            ("z0", self.z0),
+            ("dt", self.dt),
+        ];
+        let mut pairs: Vec<String> = numbers
+            .iter()
+            .filter_map(|(key, value)| value.map(|v| format!("{key}={v}")))
+            .collect();
+        if let Some(method) = self.method {
+            pairs.push(format!("method={}", method.short_name()));
+        }
+        if let Some(camera) = &self.camera {
+            let [x, y, z] = camera.focus;
+            pairs.push(format!("theta={:.4}", camera.theta));
+            pairs.push(format!("phi={:.4}", camera.phi));
+            pairs.push(format!("radius={:.2}", camera.radius));
+            pairs.push(format!("focus={x:.2},{y:.2},{z:.2}"));
+        }
+        pairs.join("&")
+    }
+
+    pub fn apply(&self, config: &mut SimulationConfig) {
+        let overrides = [
+            (&mut config.sigma, self.sigma),
+            (&mut config.rho, self.rho),
+            (&mut config.beta, self.beta),
+            (&mut config.initial_x, self.x0),
+            (&mut config.initial_y, self.y0),
+            (&mut config.initial_z, self.z0),
+            (&mut config.dt, self.dt),
+        ];
+        for (field, value) in overrides {
+            if let Some(value) = value {
+                *field = value;
+            }
+        }
+        if let Some(method) = self.method {
+            config.method = method;
+        }
+    }
+}
+
+/// The system every copied parameter set names, so sets from other models are
+/// turned away instead of half-applied.
+pub const SYSTEM: &str = "lorenz";
+
+fn lorenz() -> String {
+    SYSTEM.to_string()
+}
+
+/// The full parameter set as one line of JSON for the clipboard, e.g.
+/// `{"system":"lorenz","sigma":10.0,"rho":28.0,…,"method":"rk4","initial":[1.0,1.0,1.0]}`.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ParamSet {
+    #[serde(default = "lorenz")]
+    pub system: String,
+    pub sigma: f64,
+    pub rho: f64,
+    pub beta: f64,
+    pub dt: f64,
+    #[serde(serialize_with = "method_name", deserialize_with = "method_from_name")]
+    pub method: IntegrationMethod,
+    pub initial: [f64; 3],
+}
+
+fn method_name<S: Serializer>(
+    method: &IntegrationMethod,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    serializer.serialize_str(method.short_name())
+}
+
+fn method_from_name<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<IntegrationMethod, D::Error> {
+    let name = String::deserialize(deserializer)?;
+    IntegrationMethod::from_short_name(&name)
+        .ok_or_else(|| serde::de::Error::custom(format!("unknown method \"{name}\"")))
+}
+
+impl ParamSet {
+    pub fn current(config: &SimulationConfig) -> Self {
+        Self {
+            system: lorenz(),
+            sigma: config.sigma,
+            rho: config.rho,
+            beta: config.beta,
+            dt: config.dt,
+            method: config.method,
+            initial: [config.initial_x, config.initial_y, config.initial_z],
+        }
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).unwrap_or_default()
+    }
+
+    /// Reads a pasted set; surrounding whitespace is fine, anything else is not.
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let set: Self = serde_json::from_str(text.trim())
+            .map_err(|error| trf("Not a parameter set: {error}", &[("error", &error)]))?;
+        if set.system != SYSTEM {
+            return Err(trf(
+                "These are {system} parameters, not Lorenz ones",
+                &[("system", &set.system)],
+            ));
+        }
+        if set.dt <= 0.0 {
+            return Err(tr("dt must be positive").to_string());
+        }
+        Ok(set)
+    }
+
+    pub fn apply(&self, config: &mut SimulationConfig) {
+        config.sigma = self.sigma;
+        config.rho = self.rho;
+        config.beta = self.beta;
+        config.dt = self.dt;
+        config.method = self.method;
+        [config.initial_x, config.initial_y, config.initial_z] = self.initial;
+    }
+}
+
+/// The query string of the page the web build runs in.
+#[cfg(target_arch = "wasm32")]
+pub fn page_query() -> Option<String> {
+    web_sys::window()?.location().search().ok()
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub fn page_query() -> Option<String> {
+    None
+}
+
+/// The page address without its query, to build shareable links on.
+#[cfg(target_arch = "wasm32")]
+pub fn page_base() -> Option<String> {
+    let location = web_sys::window()?.location();
+    Some(format!("{}{}", location.origin().ok()?, location.pathname().ok()?))
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub fn page_base() -> Option<String> {
+    None
+}
+
+/// Moves the freshly spawned orbit camera to the view given in the URL.
+pub fn apply_url_camera_system(
+    params: Option<Res<UrlParams>>,
+    mut cameras: Query<(&mut OrbitCamera, &mut Transform)>,
+) {
+    let Some(camera) = params.as_ref().and_then(|p| p.camera.as_ref()) else {
+        return;
+    };
+    for (mut orbit, mut transform) in &mut cameras {
+        orbit.focus = Vec3::from_array(camera.focus);
+        orbit.radius = camera.radius;
+        orbit.theta = camera.theta;
+        orbit.phi = camera.phi;
+        orbit.apply_to(&mut transform, RotationMode::Turntable);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_ignores_unknown_and_invalid_values() {
+        let params = UrlParams::parse("?rho=99.96&method=rk4&dt=-1&sigma=abc&foo=1&beta");
+        assert_eq!(params.rho, Some(99.96));
+        assert_eq!(params.method, Some(IntegrationMethod::RungeKutta4));
+        assert_eq!(params.dt, None);
+        assert_eq!(params.sigma, None);
+        assert_eq!(params.camera, None);
+
+        let mut config = SimulationConfig::default();
+        params.apply(&mut config);
+        assert_eq!(config.rho, 99.96);
+        assert_eq!(config.sigma, 10.0);
+    }
+
+    #[test]
+    fn test_query_round_trips() {
+        let config = SimulationConfig {
+            rho: 28.5,
+            dt: 0.002,
+            method: IntegrationMethod::Euler,
+            initial_x: -3.25,
+            ..default()
+        };
+        let orbit = OrbitCamera {
+            focus: Vec3::new(1.5, 20.0, -0.25),
+            theta: 0.5,
+            phi: -1.25,
+            radius: 80.0,
+            ..default()
+        };
+        let params = UrlParams::current(&config, &orbit);
+        let parsed = UrlParams::parse(&params.to_query());
+        assert_eq!(parsed, params);
+    }
+
+    #[test]
+    fn test_param_sets_round_trip_through_json() {
+        let config = SimulationConfig {
+            beta: 8.0 / 3.0,
+            method: IntegrationMethod::Euler,
+            initial_z: -0.125,
+            ..default()
+        };
+        let json = ParamSet::current(&config).to_json();
+        assert!(json.contains(r#""method":"euler""#), "{json}");
+
+        let mut pasted = SimulationConfig {
+            rho: 99.96,
+            ..default()
+        };
+        ParamSet::parse(&format!("  {json}\n")).unwrap().apply(&mut pasted);
+        assert!(pasted == config);
+
+        assert!(ParamSet::parse(&json.replace("lorenz", "rossler")).is_err());
+        assert!(ParamSet::parse(&json.replace("euler", "verlet")).is_err());
+        assert!(ParamSet::parse("rho=28").is_err());
+    }
+}
\ No newline at end of file
@@ -0,0 +1,98 @@
+use crate::config::{BifurcationSettings, IntegrationMethod};
+use super::lorenz::{euler_step, rk4_step, LorenzParams, LorenzState};
+
+pub fn rho_values(settings: &BifurcationSettings) -> impl Iterator<Item = f64> + '_ {
+    let samples = settings.samples.max(2);
+    (0..samples).map(move |i| {
+        let f = i as f64 / (samples - 1) as f64;
+        settings.rho_min + f * (settings.rho_max - settings.rho_min)
+    })
+}
+
+/// Local maxima of z after the transient, or the settled z when the orbit
+/// converges to a fixed point and has no maxima left to record.
+pub fn z_maxima(
+    initial: &LorenzState,
+    params: &LorenzParams,
+    method: IntegrationMethod,
+    dt: f64,
+    settings: &BifurcationSettings,
+) -> Vec<f64> {
+    let step = |state: &LorenzState| match method {
+        IntegrationMethod::Euler => euler_step(state, params, dt),
+        IntegrationMethod::RungeKutta4 => rk4_step(state, params, dt),
+    };
+
+    let mut state = initial.clone();
+    for _ in 0..settings.transient_steps {
+        state = step(&state);
+    }
+
+    let mut maxima = Vec::new();
+    let (mut before, mut previous) = (state.z, state.z);
+    for _ in 0..settings.record_steps {
+        state = step(&state);
+        if !state.z.is_finite() {
+            return maxima;
+        }
+        if previous > before && previous >= state.z {
+            maxima.push(previous);
+        }
+        (before, previous) = (previous, state.z);
+    }
+    if maxima.is_empty() {
+        maxima.push(state.z);
+    }
+    maxima
+}
+
+pub fn scan(
+    settings: &BifurcationSettings,
+    initial: &LorenzState,
+    sigma: f64,
+    beta: f64,
+    method: IntegrationMethod,
+    dt: f64,
+) -> Vec<(f64, f64)> {
+    rho_values(settings)
+        .flat_map(|rho| {
+            let params = LorenzParams { sigma, rho, beta };
+            z_maxima(initial, &params, method, dt, settings)
+                .into_iter()
+                .map(move |z| (rho, z))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stable_and_chaotic_branches() {
+        let settings = BifurcationSettings {
+            rho_min: 10.0,
+            rho_max: 28.0,
+            samples: 2,
+            transient_steps: 4000,
+            record_steps: 4000,
+            ..Default::default()
+        };
+        let points = scan(
+            &settings,
+            &LorenzState::default(),
+            10.0,
+            8.0 / 3.0,
+            IntegrationMethod::RungeKutta4,
+            0.005,
+        );
+
+        let stable: Vec<f64> = points.iter().filter(|p| p.0 == 10.0).map(|p| p.1).collect();
+        assert!(!stable.is_empty());
+        assert!(stable.iter().all(|&z| (z - 9.0).abs() < 1e-3));
+
+        let chaotic: Vec<f64> = points.iter().filter(|p| p.0 == 28.0).map(|p| p.1).collect();
+        assert!(chaotic.len() > 10);
+        assert!(chaotic.iter().all(|&z| z > 27.0 && z < 50.0));
+    }
+}
\ No newline at end of file
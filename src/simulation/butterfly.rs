@@ -0,0 +1,113 @@
+use bevy::prelude::*;
+
+use crate::config::{ButterflyDemoEvent, ResetEvent, SimulationConfig, SimulationStats};
+use crate::i18n::tr;
+use crate::ui::layout::{PanelLayout, PanelSection};
+use super::integrator::TrailBuffer;
+use super::lorenz::LorenzState;
+use super::trajectories::{ExtraTrajectory, Trajectories};
+
+/// Initial distance between the two demo trajectories.
+pub const BUTTERFLY_OFFSET: f64 = 1e-8;
+const MAX_SAMPLES: usize = 50_000;
+const RED: Color = Color::srgb(1.0, 0.3, 0.25);
+const BLUE: Color = Color::srgb(0.3, 0.55, 1.0);
+
+/// The pair of trajectories whose distance is plotted, and that distance
+/// over time as `[t, log₁₀ |Δ|]`.
+#[derive(Resource, Default)]
+pub struct Separation {
+    pub pair: Option<(Entity, Entity)>,
+    pub samples: Vec<[f64; 2]>,
+}
+
+pub fn log_distance(a: &LorenzState, b: &LorenzState) -> f64 {
+    let (dx, dy, dz) = (a.x - b.x, a.y - b.y, a.z - b.z);
+    (dx * dx + dy * dy + dz * dz).sqrt().log10()
+}
+
+/// Resets, then starts a red and a blue trajectory `BUTTERFLY_OFFSET` apart
+/// at x₀, y₀, z₀ and opens the separation plot. A previous demo pair is replaced.
+#[allow(clippy::too_many_arguments)]
+pub fn butterfly_demo_system(
+    mut commands: Commands,
+    mut events: EventReader<ButterflyDemoEvent>,
+    mut config: ResMut<SimulationConfig>,
+    mut trajectories: ResMut<Trajectories>,
+    mut separation: ResMut<Separation>,
+    mut layout: ResMut<PanelLayout>,
+    mut reset_events: EventWriter<ResetEvent>,
+    extras: Query<(), With<ExtraTrajectory>>,
+) {
+    if events.read().count() == 0 {
+        return;
+    }
+
+    if let Some((a, b)) = separation.pair.take() {
+        for entity in [a, b].into_iter().filter(|e| extras.contains(*e)) {
+            commands.entity(entity).despawn();
+        }
+    }
+    let (x, y, z) = (config.initial_x, config.initial_y, config.initial_z);
+    let mut spawn = |name: &str, color, initial: [f64; 3]| {
+        let extra = ExtraTrajectory {
+            name: name.to_string(),
+            visible: true,
+            color,
+            initial,
+            params: None,
+            dt: None,
+        };
+        let state = LorenzState::new(initial[0], initial[1], initial[2]);
+        commands
+            .spawn((extra, state, TrailBuffer::default(), SimulationStats::default()))
+            .id()
+    };
+    let red = spawn(tr("Red"), RED, [x, y, z]);
+    let blue = spawn(tr("Blue"), BLUE, [x + BUTTERFLY_OFFSET, y, z]);
+
+    separation.pair = Some((red, blue));
+    separation.samples.clear();
+    trajectories.main_visible = false;
+    trajectories.active = Some(red);
+    config.paused = false;
+    if !layout.is_open(PanelSection::Separation) {
+        layout.open.push(PanelSection::Separation);
+    }
+    reset_events.send(ResetEvent);
+}
+
+pub fn separation_system(
+    mut separation: ResMut<Separation>,
+    states: Query<&LorenzState, With<ExtraTrajectory>>,
+) {
+    let Some((a, b)) = separation.pair else {
+        return;
+    };
+    let (Ok(a), Ok(b)) = (states.get(a), states.get(b)) else {
+        separation.pair = None;
+        return;
+    };
+    if separation.samples.last().is_some_and(|[t, _]| a.t < *t) {
+        separation.samples.clear();
+    }
+    if separation.samples.last().is_some_and(|[t, _]| a.t == *t) {
+        return;
+    }
+    if separation.samples.len() >= MAX_SAMPLES {
+        separation.samples.drain(..MAX_SAMPLES / 2);
+    }
+    separation.samples.push([a.t, log_distance(a, b)]);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_log_distance_of_demo_offset() {
+        let a = LorenzState::new(1.0, 1.0, 1.0);
+        let b = LorenzState::new(1.0 + BUTTERFLY_OFFSET, 1.0, 1.0);
+        assert!((log_distance(&a, &b) + 8.0).abs() < 1e-6);
+    }
+}
\ No newline at end of file
@@ -0,0 +1,43 @@
+use bevy::prelude::*;
+
+use crate::config::{ComparisonConfig, ResetEvent, SimulationConfig};
+use super::integrator::{advance_trajectory, CustomSystem, TrailBuffer};
+use super::lorenz::LorenzState;
+
+#[derive(Component)]
+pub struct ComparisonTrajectory;
+
+pub fn comparison_simulation_system(
+    config: Res<SimulationConfig>,
+    comparison: Res<ComparisonConfig>,
+    mut query: Query<(&mut LorenzState, &mut TrailBuffer), With<ComparisonTrajectory>>,
+    system: Res<CustomSystem>,
+    mut reset_events: EventReader<ResetEvent>,
+    mut was_enabled: Local<bool>,
+) {
+    let Ok((mut state, mut trail)) = query.get_single_mut() else {
+        return;
+    };
+
+    let just_enabled = comparison.enabled && !*was_enabled;
+    *was_enabled = comparison.enabled;
+
+    if !reset_events.is_empty() || just_enabled {
+        reset_events.clear();
+        trail.points.clear();
+        *state = LorenzState::new(
+            comparison.initial_x,
+            comparison.initial_y,
+            comparison.initial_z,
+        );
+        return;
+    }
+
+    if !comparison.enabled || config.paused {
+        return;
+    }
+
+    trail.max_points = config.max_trail_points;
+    let params = comparison.params();
+    advance_trajectory(&mut state, &mut trail, &params, &config, system.0.as_ref());
+}
\ No newline at end of file
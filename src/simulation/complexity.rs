@@ -0,0 +1,168 @@
+//! Sample entropy (Richman & Moorman) and approximate entropy (Pincus) of
+//! one coordinate over a sliding window: how unpredictable the next value
+//! is from the last few. Chaos keeps them well above zero; a periodic orbit
+//! drives them toward it.
+
+use std::collections::VecDeque;
+
+use bevy::prelude::*;
+
+use crate::config::{ComplexityConfig, EntropyMeasure, SimulationConfig};
+use crate::export::coordinates;
+use super::integrator::TrailBuffer;
+
+/// Model time between evaluations; each one compares every pair of templates.
+const UPDATE_INTERVAL: f64 = 0.5;
+pub const COMPLEXITY_HISTORY: usize = 300;
+
+/// Whether the templates of length `m` starting at `i` and `j` stay within
+/// `r` of each other at every position.
+fn matches(series: &[f64], m: usize, r: f64, i: usize, j: usize) -> bool {
+    (0..m).all(|k| (series[i + k] - series[j + k]).abs() <= r)
+}
+
+/// −ln(A / B), where B counts pairs of distinct templates of length `m`
+/// within `r`, and A those that still match when extended by one sample.
+/// `None` when either count is zero.
+pub fn sample_entropy(series: &[f64], m: usize, r: f64) -> Option<f64> {
+    let templates = series.len().checked_sub(m + 1)? + 1;
+    let (mut a, mut b) = (0u64, 0u64);
+    for i in 0..templates {
+        for j in i + 1..templates {
+            if matches(series, m, r, i, j) {
+                b += 1;
+                if (series[i + m] - series[j + m]).abs() <= r {
+                    a += 1;
+                }
+            }
+        }
+    }
+    (a > 0 && b > 0).then(|| -(a as f64 / b as f64).ln())
+}
+
+/// Φₘ − Φₘ₊₁, where Φₘ averages the log fraction of templates of length `m`
+/// within `r` of each one, counting itself.
+pub fn approximate_entropy(series: &[f64], m: usize, r: f64) -> Option<f64> {
+    if series.len() <= m + 1 {
+        return None;
+    }
+    let phi = |m: usize| {
+        let count = series.len() - m + 1;
+        let total: f64 = (0..count)
+            .map(|i| {
+                let close = (0..count).filter(|&j| matches(series, m, r, i, j)).count();
+                (close as f64 / count as f64).ln()
+            })
+            .sum();
+        total / count as f64
+    };
+    Some(phi(m) - phi(m + 1))
+}
+
+fn std_dev(series: &[f64]) -> f64 {
+    let mean = series.iter().sum::<f64>() / series.len().max(1) as f64;
+    let variance =
+        series.iter().map(|v| (v - mean) * (v - mean)).sum::<f64>() / series.len().max(1) as f64;
+    variance.sqrt()
+}
+
+/// `measure` of `series` with templates of length `m` and a tolerance of
+/// `tolerance` standard deviations.
+pub fn entropy(measure: EntropyMeasure, series: &[f64], m: usize, tolerance: f64) -> Option<f64> {
+    let r = tolerance * std_dev(series);
+    match measure {
+        EntropyMeasure::Sample => sample_entropy(series, m, r),
+        EntropyMeasure::Approximate => approximate_entropy(series, m, r),
+    }
+}
+
+#[derive(Resource, Default)]
+pub struct Complexity {
+    /// Entropy of the latest full window; `None` before the trail covers one,
+    /// or when no templates match.
+    pub latest: Option<f64>,
+    /// Whether the trail held a full window at the last evaluation.
+    pub window_full: bool,
+    /// `(t, entropy)` of recent evaluations, oldest first.
+    pub history: VecDeque<[f64; 2]>,
+    last_time: f64,
+    measured_for: Option<ComplexityConfig>,
+}
+
+/// The chosen coordinate every `stride` trail points back from the head,
+/// oldest first, at most `window` of them.
+fn window_series(trail: &TrailBuffer, coordinate: usize, stride: usize, window: usize) -> Vec<f64> {
+    let mut series: Vec<f64> = trail
+        .points
+        .iter()
+        .rev()
+        .step_by(stride)
+        .take(window)
+        .map(|point| {
+            let (x, y, z) = coordinates(point);
+            f64::from([x, y, z][coordinate.min(2)])
+        })
+        .collect();
+    series.reverse();
+    series
+}
+
+/// Re-evaluates the entropy every `UPDATE_INTERVAL` of model time. New
+/// settings, a reset or a timeline scrub start the history over.
+pub fn complexity_system(
+    config: Res<ComplexityConfig>,
+    simulation: Res<SimulationConfig>,
+    trail: Res<TrailBuffer>,
+    mut complexity: ResMut<Complexity>,
+) {
+    let Some(head) = trail.points.back() else {
+        return;
+    };
+    let rewound = head.time < complexity.last_time;
+    if rewound || complexity.measured_for.as_ref() != Some(&*config) {
+        *complexity = Complexity {
+            measured_for: Some(config.clone()),
+            last_time: f64::NEG_INFINITY,
+            ..default()
+        };
+    }
+    if head.time - complexity.last_time < UPDATE_INTERVAL {
+        return;
+    }
+    complexity.last_time = head.time;
+
+    let stride = ((config.spacing / simulation.dt).round() as usize).max(1);
+    let series = window_series(&trail, config.coordinate, stride, config.window);
+    complexity.window_full = series.len() >= config.window;
+    complexity.latest = if complexity.window_full {
+        entropy(config.measure, &series, config.template, config.tolerance)
+    } else {
+        None
+    };
+    if let Some(value) = complexity.latest {
+        complexity.history.push_back([head.time, value]);
+        if complexity.history.len() > COMPLEXITY_HISTORY {
+            complexity.history.pop_front();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_noise_is_more_complex_than_a_sine() {
+        let sine: Vec<f64> = (0..300).map(|i| (i as f64 * 0.3).sin()).collect();
+        let mut rng = fastrand::Rng::with_seed(1);
+        let noise: Vec<f64> = (0..300).map(|_| rng.f64()).collect();
+
+        for measure in [EntropyMeasure::Sample, EntropyMeasure::Approximate] {
+            let regular = entropy(measure, &sine, 2, 0.2).unwrap();
+            let random = entropy(measure, &noise, 2, 0.2).unwrap();
+            assert!(regular < 0.5, "{measure:?} {regular}");
+            assert!(random > 1.0, "{measure:?} {random}");
+        }
+        assert_eq!(sample_entropy(&[1.0, 2.0], 2, 0.1), None);
+    }
+}
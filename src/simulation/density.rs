@@ -0,0 +1,173 @@
+use bevy::prelude::*;
+
+use crate::config::{DensityConfig, PhasePair, SimulationStats};
+use crate::export::coordinates;
+use super::integrator::{TrailBuffer, TrailPoint};
+
+/// Room left on each side when the grid grows to take in a new extreme, as a
+/// fraction of the extent, so that it does not regrow on every excursion.
+const GROW_MARGIN: f64 = 0.25;
+
+/// Two-dimensional histogram of every accepted step of the main trajectory
+/// projected onto a plane: an estimate of the attractor's invariant density,
+/// which unlike the trail is not limited to the most recent points.
+#[derive(Resource, Default)]
+pub struct DensityMap {
+    /// Row-major counts, `resolution` cells per side, row 0 at the lowest
+    /// vertical value.
+    pub counts: Vec<u32>,
+    pub resolution: usize,
+    /// Lower and upper corners of the grid; `None` until the first point.
+    pub bounds: Option<([f64; 2], [f64; 2])>,
+    pub total: u64,
+    /// Bumped on every change, so views can skip redrawing an unchanged map.
+    pub revision: u64,
+    built_for: Option<(PhasePair, usize)>,
+    steps_seen: u64,
+}
+
+impl DensityMap {
+    pub fn new(resolution: usize) -> Self {
+        Self {
+            counts: vec![0; resolution * resolution],
+            resolution,
+            ..default()
+        }
+    }
+
+    /// Forgets every count; binning carries on from the next step.
+    pub fn clear(&mut self) {
+        self.counts.fill(0);
+        self.bounds = None;
+        self.total = 0;
+        self.revision += 1;
+    }
+
+    pub fn max_count(&self) -> u32 {
+        self.counts.iter().copied().max().unwrap_or(0)
+    }
+
+    fn cell(&self, point: [f64; 2]) -> usize {
+        let (min, max) = self.bounds.expect("bounds are set before binning");
+        let n = self.resolution;
+        let index = |axis: usize| {
+            let t = (point[axis] - min[axis]) / (max[axis] - min[axis]);
+            ((t * n as f64) as usize).min(n - 1)
+        };
+        index(1) * n + index(0)
+    }
+
+    pub fn add(&mut self, point: [f64; 2]) {
+        if !(point[0].is_finite() && point[1].is_finite()) || self.resolution == 0 {
+            return;
+        }
+        match self.bounds {
+            None => self.bounds = Some(padded(point, point)),
+            Some((min, max)) => {
+                let inside = (0..2).all(|i| (min[i]..=max[i]).contains(&point[i]));
+                if !inside {
+                    let lower = [min[0].min(point[0]), min[1].min(point[1])];
+                    let upper = [max[0].max(point[0]), max[1].max(point[1])];
+                    self.regrid(padded(lower, upper));
+                }
+            }
+        }
+        let cell = self.cell(point);
+        self.counts[cell] += 1;
+        self.total += 1;
+        self.revision += 1;
+    }
+
+    /// Moves every count to the cell of the new grid that holds its old
+    /// cell's centre; the map blurs slightly but keeps its total.
+    fn regrid(&mut self, bounds: ([f64; 2], [f64; 2])) {
+        let Some((min, max)) = self.bounds.replace(bounds) else {
+            return;
+        };
+        let n = self.resolution;
+        let old = std::mem::replace(&mut self.counts, vec![0; n * n]);
+        let width = [(max[0] - min[0]) / n as f64, (max[1] - min[1]) / n as f64];
+        for (index, count) in old.into_iter().enumerate().filter(|(_, count)| *count > 0) {
+            let centre = [
+                min[0] + ((index % n) as f64 + 0.5) * width[0],
+                min[1] + ((index / n) as f64 + 0.5) * width[1],
+            ];
+            let cell = self.cell(centre);
+            self.counts[cell] += count;
+        }
+    }
+
+    fn add_points<'a>(&mut self, points: impl Iterator<Item = &'a TrailPoint>, plane: PhasePair) {
+        let (h, v) = plane.axes();
+        for point in points {
+            let (x, y, z) = coordinates(point);
+            let values = [x, y, z];
+            self.add([f64::from(values[h]), f64::from(values[v])]);
+        }
+    }
+}
+
+/// The box from `lower` to `upper` with `GROW_MARGIN` of room on every side,
+/// and at least a unit across so a single point has somewhere to go.
+fn padded(lower: [f64; 2], upper: [f64; 2]) -> ([f64; 2], [f64; 2]) {
+    let mut min = [0.0; 2];
+    let mut max = [0.0; 2];
+    for i in 0..2 {
+        let margin = ((upper[i] - lower[i]) * GROW_MARGIN).max(0.5);
+        min[i] = lower[i] - margin;
+        max[i] = upper[i] + margin;
+    }
+    (min, max)
+}
+
+/// Bins the steps the main trajectory took since the last frame. Changing the
+/// plane or resolution starts over from the current trail, and so does a
+/// reset of the simulation.
+pub fn density_system(
+    config: Res<DensityConfig>,
+    stats: Res<SimulationStats>,
+    trail: Res<TrailBuffer>,
+    mut density: ResMut<DensityMap>,
+) {
+    let layout = (config.plane, config.resolution);
+    let restarted = stats.total_steps < density.steps_seen;
+    let new_steps = stats.total_steps - density.steps_seen.min(stats.total_steps);
+    density.steps_seen = stats.total_steps;
+    if !config.enabled {
+        return;
+    }
+
+    if restarted || density.built_for != Some(layout) {
+        *density = DensityMap {
+            built_for: Some(layout),
+            steps_seen: stats.total_steps,
+            revision: density.revision + 1,
+            ..DensityMap::new(config.resolution)
+        };
+        density.add_points(trail.points.iter(), config.plane);
+        return;
+    }
+
+    let new_points = (new_steps as usize).min(trail.points.len());
+    let start = trail.points.len() - new_points;
+    density.add_points(trail.points.range(start..), config.plane);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_regrid_keeps_every_count() {
+        let mut density = DensityMap::new(32);
+        density.add([0.0, 0.0]);
+        density.add([0.1, 0.2]);
+        let (_, before) = density.bounds.unwrap();
+        density.add([40.0, -25.0]);
+        let (min, max) = density.bounds.unwrap();
+        assert!(max[0] > before[0] && max[0] >= 40.0 && min[1] <= -25.0);
+        assert_eq!(density.total, 3);
+        assert_eq!(density.counts.iter().sum::<u32>(), 3);
+        assert_eq!(density.max_count(), 2);
+    }
+}
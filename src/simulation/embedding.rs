@@ -0,0 +1,88 @@
+//! Choosing the delay τ for reconstructing the attractor from x(t) alone,
+//! as (x(t), x(t − τ)), by the first minimum of the average mutual
+//! information between the series and its delayed copy (Fraser & Swinney).
+
+/// Most delays evaluated for one curve; longer ranges are strided.
+pub const MAX_DELAYS: usize = 100;
+
+/// Mutual information in bits between `samples[i]` and `samples[i + lag]`,
+/// from a `bins` × `bins` histogram over the range of the whole series.
+pub fn mutual_information(samples: &[f64], lag: usize, bins: usize) -> f64 {
+    let pairs = samples.len().saturating_sub(lag);
+    if pairs == 0 || bins == 0 {
+        return 0.0;
+    }
+    let min = samples.iter().copied().fold(f64::INFINITY, f64::min);
+    let max = samples.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+    let width = if max > min { (max - min) / bins as f64 } else { 1.0 };
+    let bin = |value: f64| (((value - min) / width) as usize).min(bins - 1);
+
+    let mut leading = vec![0u32; bins];
+    let mut delayed = vec![0u32; bins];
+    let mut joint = vec![0u32; bins * bins];
+    for (&a, &b) in samples.iter().zip(&samples[lag..]) {
+        let (i, j) = (bin(a), bin(b));
+        leading[i] += 1;
+        delayed[j] += 1;
+        joint[i * bins + j] += 1;
+    }
+
+    let n = pairs as f64;
+    joint
+        .iter()
+        .enumerate()
+        .filter(|(_, count)| **count > 0)
+        .map(|(cell, &count)| {
+            let p = f64::from(count) / n;
+            let marginals = f64::from(leading[cell / bins]) * f64::from(delayed[cell % bins]);
+            p * (p * n * n / marginals).log2()
+        })
+        .sum()
+}
+
+/// `(lag, AMI)` for lags from 0 to `max_lag` in steps of samples, strided so
+/// that at most about `MAX_DELAYS` lags are evaluated.
+pub fn ami_curve(samples: &[f64], max_lag: usize, bins: usize) -> Vec<(usize, f64)> {
+    let stride = max_lag.div_ceil(MAX_DELAYS).max(1);
+    (0..=max_lag)
+        .step_by(stride)
+        .map(|lag| (lag, mutual_information(samples, lag, bins)))
+        .collect()
+}
+
+/// Lag of the first local minimum of `curve`, if it has one before its end.
+pub fn first_minimum(curve: &[(usize, f64)]) -> Option<usize> {
+    curve
+        .windows(3)
+        .find(|w| w[1].1 < w[0].1 && w[1].1 <= w[2].1)
+        .map(|w| w[1].0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::simulation::lorenz::{rk4_step, LorenzParams, LorenzState};
+
+    #[test]
+    fn test_lorenz_first_minimum_is_near_a_sixth_of_a_time_unit() {
+        let params = LorenzParams::default();
+        let dt = 0.005;
+        let mut state = LorenzState::new(1.0, 1.0, 1.0);
+        for _ in 0..4000 {
+            state = rk4_step(&state, &params, dt);
+        }
+        let samples: Vec<f64> = (0..4096)
+            .map(|_| {
+                state = rk4_step(&state, &params, dt);
+                state.x
+            })
+            .collect();
+
+        let curve = ami_curve(&samples, 200, 32);
+        assert_eq!(curve.len(), 101);
+        // Identical series share all their information.
+        assert!(curve[0].1 > curve[1].1);
+        let tau = first_minimum(&curve).unwrap() as f64 * dt;
+        assert!((0.12..0.22).contains(&tau), "{tau}");
+    }
+}
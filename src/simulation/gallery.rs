@@ -0,0 +1,98 @@
+use bevy::prelude::*;
+
+use crate::config::{GalleryConfig, ResetEvent, SimulationConfig};
+use super::integrator::{advance_trajectory, CustomSystem, TrailBuffer};
+use super::lorenz::{LorenzParams, LorenzState};
+
+pub struct GalleryTile {
+    pub rho: f64,
+    pub state: LorenzState,
+    pub trail: TrailBuffer,
+}
+
+#[derive(Resource, Default)]
+pub struct Gallery {
+    pub tiles: Vec<GalleryTile>,
+    built_for: Option<(f64, f64, usize)>,
+}
+
+pub fn rho_sweep(rho_min: f64, rho_max: f64, count: usize) -> Vec<f64> {
+    match count {
+        0 => Vec::new(),
+        1 => vec![rho_min],
+        _ => (0..count)
+            .map(|i| rho_min + (rho_max - rho_min) * i as f64 / (count - 1) as f64)
+            .collect(),
+    }
+}
+
+impl Gallery {
+    fn rebuild(&mut self, gallery: &GalleryConfig, config: &SimulationConfig) {
+        let count = gallery.columns * gallery.rows;
+        self.tiles = rho_sweep(gallery.rho_min, gallery.rho_max, count)
+            .into_iter()
+            .map(|rho| GalleryTile {
+                rho,
+                state: LorenzState::new(config.initial_x, config.initial_y, config.initial_z),
+                trail: TrailBuffer {
+                    points: Default::default(),
+                    max_points: gallery.trail_points,
+                },
+            })
+            .collect();
+        self.built_for = Some((gallery.rho_min, gallery.rho_max, count));
+    }
+}
+
+pub fn gallery_simulation_system(
+    config: Res<SimulationConfig>,
+    gallery_config: Res<GalleryConfig>,
+    mut gallery: ResMut<Gallery>,
+    system: Res<CustomSystem>,
+    mut reset_events: EventReader<ResetEvent>,
+) {
+    let reset = !reset_events.is_empty();
+    reset_events.clear();
+
+    if !gallery_config.enabled {
+        return;
+    }
+
+    let layout = (
+        gallery_config.rho_min,
+        gallery_config.rho_max,
+        gallery_config.columns * gallery_config.rows,
+    );
+    if reset || gallery.built_for != Some(layout) {
+        gallery.rebuild(&gallery_config, &config);
+        return;
+    }
+
+    if config.paused {
+        return;
+    }
+
+    for tile in gallery.tiles.iter_mut() {
+        let params = LorenzParams {
+            sigma: config.sigma,
+            rho: tile.rho,
+            beta: config.beta,
+        };
+        tile.trail.max_points = gallery_config.trail_points;
+        advance_trajectory(&mut tile.state, &mut tile.trail, &params, &config, system.0.as_ref());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rho_sweep_spans_range() {
+        let values = rho_sweep(0.5, 30.5, 16);
+        assert_eq!(values.len(), 16);
+        assert!((values[0] - 0.5).abs() < 1e-12);
+        assert!((values[15] - 30.5).abs() < 1e-12);
+        assert!(values.windows(2).all(|w| w[1] > w[0]));
+    }
+}
\ No newline at end of file
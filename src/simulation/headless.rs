@@ -0,0 +1,181 @@
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::PathBuf;
+use std::str::FromStr;
+
+use crate::config::{IntegrationMethod, SimulationConfig};
+use super::lorenz::{euler_step, rk4_step, LorenzParams, LorenzState};
+
+pub const HEADLESS_SIM_USAGE: &str = "\
+Usage: lorenz-attractor --headless-sim [options]
+
+Integrates the Lorenz system without any window or GPU and writes t,x,y,z as CSV.
+
+Options:
+  --sigma S            σ (default 10)
+  --rho R              ρ (default 28)
+  --beta B             β (default 8/3)
+  --x0 X --y0 Y --z0 Z Initial state (default 1, 1, 1)
+  --method rk4|euler   Integration method (default rk4)
+  --dt DT              Time step (default 0.005)
+  --duration T         Simulated time to integrate (default 100)
+  --every N            Write every N-th step (default 1)
+  --output PATH        Write to a file instead of stdout";
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct HeadlessSim {
+    pub params: LorenzParams,
+    pub initial: (f64, f64, f64),
+    pub method: IntegrationMethod,
+    pub dt: f64,
+    pub duration: f64,
+    pub every: u64,
+    pub output: Option<PathBuf>,
+}
+
+impl Default for HeadlessSim {
+    fn default() -> Self {
+        let config = SimulationConfig::default();
+        Self {
+            params: config.params(),
+            initial: (config.initial_x, config.initial_y, config.initial_z),
+            method: config.method,
+            dt: config.dt,
+            duration: 100.0,
+            every: 1,
+            output: None,
+        }
+    }
+}
+
+fn parse<T: FromStr>(flag: &str, value: &str) -> Result<T, String> {
+    value
+        .parse()
+        .map_err(|_| format!("invalid value '{value}' for {flag}"))
+}
+
+impl HeadlessSim {
+    pub fn from_args(args: &[String]) -> Result<Option<Self>, String> {
+        if !args.iter().any(|arg| arg == "--headless-sim") {
+            return Ok(None);
+        }
+
+        let mut sim = Self::default();
+        let mut args = args.iter();
+        while let Some(arg) = args.next() {
+            if arg == "--headless-sim" {
+                continue;
+            }
+            let value = args
+                .next()
+                .ok_or_else(|| format!("missing value for {arg}"))?;
+            match arg.as_str() {
+                "--sigma" => sim.params.sigma = parse(arg, value)?,
+                "--rho" => sim.params.rho = parse(arg, value)?,
+                "--beta" => sim.params.beta = parse(arg, value)?,
+                "--x0" => sim.initial.0 = parse(arg, value)?,
+                "--y0" => sim.initial.1 = parse(arg, value)?,
+                "--z0" => sim.initial.2 = parse(arg, value)?,
+                "--method" => {
+                    sim.method = IntegrationMethod::from_short_name(value)
+                        .ok_or_else(|| format!("unknown method '{value}' (rk4 or euler)"))?
+                }
+                "--dt" => sim.dt = parse(arg, value)?,
+                "--duration" => sim.duration = parse(arg, value)?,
+                "--every" => sim.every = parse(arg, value)?,
+                "--output" => sim.output = Some(PathBuf::from(value)),
+                other => return Err(format!("unknown option '{other}'")),
+            }
+        }
+
+        let valid_dt = sim.dt.is_finite() && sim.dt > 0.0;
+        if !valid_dt || !sim.duration.is_finite() || sim.duration < 0.0 {
+            return Err("--dt must be positive and --duration non-negative".into());
+        }
+        if sim.every == 0 {
+            return Err("--every must be at least 1".into());
+        }
+        Ok(Some(sim))
+    }
+
+    pub fn steps(&self) -> u64 {
+        (self.duration / self.dt).round() as u64
+    }
+
+    pub fn write_csv<W: Write>(&self, mut out: W) -> io::Result<u64> {
+        let mut state = LorenzState::new(self.initial.0, self.initial.1, self.initial.2);
+        let mut rows = 1;
+        writeln!(out, "t,x,y,z")?;
+        writeln!(out, "0,{},{},{}", state.x, state.y, state.z)?;
+
+        for step in 1..=self.steps() {
+            state = match self.method {
+                IntegrationMethod::Euler => euler_step(&state, &self.params, self.dt),
+                IntegrationMethod::RungeKutta4 => rk4_step(&state, &self.params, self.dt),
+            };
+            if !(state.x.is_finite() && state.y.is_finite() && state.z.is_finite()) {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("trajectory diverged at t = {}", step as f64 * self.dt),
+                ));
+            }
+            if step % self.every == 0 {
+                let t = step as f64 * self.dt;
+                writeln!(out, "{t},{},{},{}", state.x, state.y, state.z)?;
+                rows += 1;
+            }
+        }
+
+        out.flush()?;
+        Ok(rows)
+    }
+
+    pub fn run(&self) -> io::Result<u64> {
+        match &self.output {
+            Some(path) => self.write_csv(BufWriter::new(File::create(path)?)),
+            None => self.write_csv(BufWriter::new(io::stdout().lock())),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(list: &[&str]) -> Vec<String> {
+        list.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn test_headless_sim_streams_rows() {
+        let sim = HeadlessSim::from_args(&args(&[
+            "--headless-sim",
+            "--rho",
+            "24.5",
+            "--method",
+            "euler",
+            "--dt",
+            "0.01",
+            "--duration",
+            "1",
+            "--every",
+            "10",
+        ]))
+        .unwrap()
+        .unwrap();
+        assert_eq!(sim.params.rho, 24.5);
+        assert_eq!(sim.method, IntegrationMethod::Euler);
+
+        let mut out = Vec::new();
+        assert_eq!(sim.write_csv(&mut out).unwrap(), 11);
+        let text = String::from_utf8(out).unwrap();
+        let lines: Vec<&str> = text.lines().collect();
+        assert_eq!(lines[0], "t,x,y,z");
+        assert_eq!(lines[1], "0,1,1,1");
+        assert!(lines[11].starts_with("1,"));
+
+        assert_eq!(HeadlessSim::from_args(&args(&["--batch"])), Ok(None));
+        assert!(HeadlessSim::from_args(&args(&["--headless-sim", "--dt", "0"])).is_err());
+        assert!(HeadlessSim::from_args(&args(&["--headless-sim", "--method", "rk2"])).is_err());
+    }
+}
\ No newline at end of file
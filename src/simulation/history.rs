@@ -0,0 +1,274 @@
+use std::collections::VecDeque;
+
+use bevy::prelude::*;
+
+use crate::config::{SimulationConfig, SimulationStats};
+use super::integrator::{velocity_to_color, TrailBuffer, TrailPoint};
+
+const QUANTUM: f32 = 1e-3;
+const SPEED_SCALE: f32 = 100.0;
+const CHUNK_POINTS: usize = 65_536;
+pub const DEFAULT_HISTORY_LIMIT: usize = 5_000_000;
+
+/// Positions are delta-encoded as i16 steps of `QUANTUM` against the
+/// reconstructed previous point, so the error never accumulates past half a
+/// quantum. A step too large for i16 starts a new chunk.
+struct Chunk {
+    origin: Vec3,
+    start_time: f64,
+    last: Vec3,
+    deltas: Vec<[i16; 3]>,
+    time_offsets: Vec<f32>,
+    speeds: Vec<u16>,
+}
+
+impl Chunk {
+    fn new(point: &TrailPoint) -> Self {
+        let mut chunk = Self {
+            origin: point.position,
+            start_time: point.time,
+            last: point.position,
+            deltas: Vec::new(),
+            time_offsets: Vec::new(),
+            speeds: Vec::new(),
+        };
+        chunk.append([0; 3], point);
+        chunk
+    }
+
+    fn len(&self) -> usize {
+        self.deltas.len()
+    }
+
+    fn try_push(&mut self, point: &TrailPoint) -> bool {
+        if self.len() >= CHUNK_POINTS {
+            return false;
+        }
+        let steps = ((point.position - self.last) / QUANTUM).round();
+        if steps.abs().max_element() > i16::MAX as f32 {
+            return false;
+        }
+        let delta = [steps.x as i16, steps.y as i16, steps.z as i16];
+        self.last += Vec3::new(delta[0] as f32, delta[1] as f32, delta[2] as f32) * QUANTUM;
+        self.append(delta, point);
+        true
+    }
+
+    fn append(&mut self, delta: [i16; 3], point: &TrailPoint) {
+        self.deltas.push(delta);
+        self.time_offsets.push((point.time - self.start_time) as f32);
+        self.speeds
+            .push((point.speed * SPEED_SCALE).round().clamp(0.0, u16::MAX as f32) as u16);
+    }
+
+    fn decode(&self, range: std::ops::Range<usize>, out: &mut impl Extend<TrailPoint>) {
+        let mut position = self.origin;
+        for (i, delta) in self.deltas[..range.end].iter().enumerate() {
+            position += Vec3::new(delta[0] as f32, delta[1] as f32, delta[2] as f32) * QUANTUM;
+            if i < range.start {
+                continue;
+            }
+            let speed = self.speeds[i] as f32 / SPEED_SCALE;
+            out.extend(Some(TrailPoint {
+                position,
+                color: velocity_to_color(speed as f64),
+                time: self.start_time + self.time_offsets[i] as f64,
+                speed,
+            }));
+        }
+    }
+
+    fn bytes(&self) -> usize {
+        self.len() * (std::mem::size_of::<[i16; 3]>() + 4 + 2)
+    }
+}
+
+#[derive(Resource)]
+pub struct History {
+    chunks: VecDeque<Chunk>,
+    len: usize,
+    recorded_steps: u64,
+    shown: Option<usize>,
+    pub limit: usize,
+    pub cursor: Option<usize>,
+}
+
+impl Default for History {
+    fn default() -> Self {
+        Self {
+            chunks: VecDeque::new(),
+            len: 0,
+            recorded_steps: 0,
+            shown: None,
+            limit: DEFAULT_HISTORY_LIMIT,
+            cursor: None,
+        }
+    }
+}
+
+impl History {
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn memory_bytes(&self) -> usize {
+        self.chunks.iter().map(Chunk::bytes).sum()
+    }
+
+    pub fn clear(&mut self) {
+        self.chunks.clear();
+        self.len = 0;
+        self.recorded_steps = 0;
+        self.cursor = None;
+        self.shown = None;
+    }
+
+    pub fn rebase(&mut self, trail: &TrailBuffer, total_steps: u64) {
+        self.clear();
+        trail.points.iter().for_each(|point| self.push(point));
+        self.recorded_steps = total_steps;
+    }
+
+    pub fn push(&mut self, point: &TrailPoint) {
+        if !self.chunks.back_mut().is_some_and(|chunk| chunk.try_push(point)) {
+            self.chunks.push_back(Chunk::new(point));
+        }
+        self.len += 1;
+
+        while self.len > self.limit && self.chunks.len() > 1 {
+            let dropped = self.chunks.pop_front().map_or(0, |chunk| chunk.len());
+            self.len -= dropped;
+            self.cursor = self.cursor.map(|c| c.saturating_sub(dropped));
+        }
+    }
+
+    pub fn time_at(&self, index: usize) -> Option<f64> {
+        let mut out = Vec::new();
+        self.decode(index..index + 1, &mut out);
+        out.first().map(|p| p.time)
+    }
+
+    pub fn decode(&self, range: std::ops::Range<usize>, out: &mut impl Extend<TrailPoint>) {
+        let mut base = 0;
+        for chunk in &self.chunks {
+            let end = base + chunk.len();
+            if range.start < end && range.end > base {
+                let local_start = range.start.saturating_sub(base);
+                let local_end = range.end.min(end) - base;
+                chunk.decode(local_start..local_end, out);
+            }
+            if end >= range.end {
+                break;
+            }
+            base = end;
+        }
+    }
+
+    fn show_window(&mut self, end: usize, trail: &mut TrailBuffer) {
+        trail.points.clear();
+        let end = (end + 1).min(self.len);
+        self.decode(end.saturating_sub(trail.max_points)..end, &mut trail.points);
+    }
+}
+
+pub fn history_record_system(
+    stats: Res<SimulationStats>,
+    trail: Res<TrailBuffer>,
+    mut history: ResMut<History>,
+) {
+    if stats.total_steps < history.recorded_steps {
+        history.clear();
+    }
+    let new = (stats.total_steps - history.recorded_steps) as usize;
+    if new == 0 {
+        return;
+    }
+    let skip = trail.points.len().saturating_sub(new);
+    for point in trail.points.iter().skip(skip) {
+        history.push(point);
+    }
+    history.recorded_steps = stats.total_steps;
+}
+
+pub fn history_scrub_system(
+    config: Res<SimulationConfig>,
+    mut history: ResMut<History>,
+    mut trail: ResMut<TrailBuffer>,
+    mut stats: ResMut<SimulationStats>,
+) {
+    let history = &mut *history;
+    match (config.paused, history.cursor) {
+        (true, Some(cursor)) if history.shown != Some(cursor) => {
+            history.show_window(cursor, &mut trail);
+            history.shown = Some(cursor);
+        }
+        (false, Some(_)) => {
+            history.cursor = None;
+            if history.shown.take().is_some() {
+                history.show_window(history.len.saturating_sub(1), &mut trail);
+            }
+        }
+        _ => return,
+    }
+    stats.point_count = trail.points.len();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn point(i: usize) -> TrailPoint {
+        let t = i as f32 * 0.01;
+        TrailPoint {
+            position: Vec3::new(20.0 * t.sin(), t, 25.0 + 10.0 * t.cos()),
+            color: Color::WHITE,
+            time: t as f64,
+            speed: 12.5,
+        }
+    }
+
+    #[test]
+    fn test_history_round_trip_within_quantum() {
+        let mut history = History::default();
+        for i in 0..70_000 {
+            history.push(&point(i));
+        }
+        history.push(&TrailPoint {
+            position: Vec3::splat(1000.0),
+            ..point(70_000)
+        });
+        assert_eq!(history.len(), 70_001);
+        assert_eq!(history.chunks.len(), 3);
+        assert!(history.memory_bytes() < 70_001 * 14);
+
+        let mut out = Vec::new();
+        history.decode(65_530..70_001, &mut out);
+        assert_eq!(out.len(), 4471);
+        for (offset, decoded) in out.iter().take(4470).enumerate() {
+            let original = point(65_530 + offset);
+            assert!((decoded.position - original.position).abs().max_element() <= QUANTUM);
+            assert!((decoded.time - original.time).abs() < 1e-4);
+            assert_eq!(decoded.speed, 12.5);
+        }
+        assert_eq!(out[4470].position, Vec3::splat(1000.0));
+    }
+
+    #[test]
+    fn test_history_limit_drops_oldest_chunks() {
+        let mut history = History {
+            limit: CHUNK_POINTS + 10,
+            cursor: Some(CHUNK_POINTS + 5),
+            ..Default::default()
+        };
+        for i in 0..CHUNK_POINTS + 20 {
+            history.push(&point(i));
+        }
+        assert_eq!(history.len(), 20);
+        assert_eq!(history.cursor, Some(5));
+        assert!((history.time_at(0).unwrap() - point(CHUNK_POINTS).time).abs() < 1e-6);
+    }
+}
\ No newline at end of file
@@ -1,20 +1,30 @@
 use std::collections::VecDeque;
+use std::sync::Arc;
 use std::time::Instant;
 
 use bevy::prelude::*;
 
-use crate::config::{IntegrationMethod, ResetEvent, SimulationConfig, SimulationStats};
+use crate::config::{
+    IntegrationMethod, ResetEvent, SimulationConfig, SimulationStats, StepEvent,
+};
 use super::lorenz::{
-    divergence, lorenz_derivatives, system_energy, velocity_magnitude, LorenzParams, LorenzState,
+    divergence, euler_step_with, lorenz_derivatives, rk4_step_with, system_energy,
+    velocity_magnitude, LorenzParams, LorenzState,
 };
+use super::trajectories::MainTrajectory;
+use crate::export::coordinates;
 
 #[derive(Clone, Debug)]
 pub struct TrailPoint {
+    /// Model coordinates of the state, `(x, y, z)`; `ViewScale` decides where
+    /// it is drawn.
     pub position: Vec3,
     pub color: Color,
+    pub time: f64,
+    pub speed: f32,
 }
 
-#[derive(Resource)]
+#[derive(Resource, Component, Clone)]
 pub struct TrailBuffer {
     pub points: VecDeque<TrailPoint>,
     pub max_points: usize,
@@ -29,50 +39,41 @@ impl Default for TrailBuffer {
     }
 }
 
-#[inline]
-pub fn euler_step(state: &LorenzState, params: &LorenzParams, dt: f64) -> LorenzState {
-    let (dx, dy, dz) = lorenz_derivatives(state, params);
-    LorenzState::new(
-        state.x + dt * dx,
-        state.y + dt * dy,
-        state.z + dt * dz,
-    )
-}
+impl TrailBuffer {
+    /// Lower and upper corners of the box around every point.
+    pub fn bounding_box(&self) -> Option<(Vec3, Vec3)> {
+        let first = self.points.front()?.position;
+        Some(
+            self.points
+                .iter()
+                .fold((first, first), |(min, max), p| (min.min(p.position), max.max(p.position))),
+        )
+    }
+
+    pub fn bounding_sphere(&self) -> Option<(Vec3, f32)> {
+        self.bounding_sphere_mapped(|position| position)
+    }
 
-#[inline]
-pub fn rk4_step(state: &LorenzState, params: &LorenzParams, dt: f64) -> LorenzState {
-    let (k1x, k1y, k1z) = lorenz_derivatives(state, params);
-
-    let s2 = LorenzState::new(
-        state.x + 0.5 * dt * k1x,
-        state.y + 0.5 * dt * k1y,
-        state.z + 0.5 * dt * k1z,
-    );
-    let (k2x, k2y, k2z) = lorenz_derivatives(&s2, params);
-
-    let s3 = LorenzState::new(
-        state.x + 0.5 * dt * k2x,
-        state.y + 0.5 * dt * k2y,
-        state.z + 0.5 * dt * k2z,
-    );
-    let (k3x, k3y, k3z) = lorenz_derivatives(&s3, params);
-
-    let s4 = LorenzState::new(
-        state.x + dt * k3x,
-        state.y + dt * k3y,
-        state.z + dt * k3z,
-    );
-    let (k4x, k4y, k4z) = lorenz_derivatives(&s4, params);
-
-    let sixth_dt = dt / 6.0;
-    LorenzState::new(
-        state.x + sixth_dt * (k1x + 2.0 * k2x + 2.0 * k3x + k4x),
-        state.y + sixth_dt * (k1y + 2.0 * k2y + 2.0 * k3y + k4y),
-        state.z + sixth_dt * (k1z + 2.0 * k2z + 2.0 * k3z + k4z),
-    )
+    /// Bounding sphere of the points after `map`, such as their place in the
+    /// scene under a `ViewScale`.
+    pub fn bounding_sphere_mapped(&self, map: impl Fn(Vec3) -> Vec3) -> Option<(Vec3, f32)> {
+        let first = map(self.points.front()?.position);
+        let (min, max) = self
+            .points
+            .iter()
+            .map(|p| map(p.position))
+            .fold((first, first), |(min, max), p| (min.min(p), max.max(p)));
+        let center = (min + max) * 0.5;
+        let radius = self
+            .points
+            .iter()
+            .map(|p| map(p.position).distance(center))
+            .fold(0.0, f32::max);
+        Some((center, radius))
+    }
 }
 
-fn velocity_to_color(velocity: f64) -> Color {
+pub fn velocity_to_color(velocity: f64) -> Color {
     const MAX_VELOCITY: f64 = 55.0;
     let t = (velocity / MAX_VELOCITY).clamp(0.0, 1.0);
     let hue: f32 = (240.0 * (1.0 - t)) as f32;
@@ -81,139 +82,210 @@ fn velocity_to_color(velocity: f64) -> Color {
     Color::hsl(hue, saturation, lightness)
 }
 
+pub fn trail_point(state: &LorenzState, params: &LorenzParams) -> TrailPoint {
+    let vel = velocity_magnitude(state, params);
+    TrailPoint {
+        position: state.to_vec3(),
+        color: velocity_to_color(vel),
+        time: state.t,
+        speed: vel as f32,
+    }
+}
+
+/// A vector field that stands in for the Lorenz equations.
+pub type Derivatives =
+    Arc<dyn Fn(&LorenzState, &LorenzParams) -> (f64, f64, f64) + Send + Sync>;
+
+/// The vector field the trajectories follow instead of the Lorenz system,
+/// if any; set while a script defines one.
+#[derive(Resource, Clone, Default)]
+pub struct CustomSystem(pub Option<Derivatives>);
+
+/// One step of `config.method`, along `system` or else the Lorenz equations.
+pub fn step(
+    state: &LorenzState,
+    params: &LorenzParams,
+    config: &SimulationConfig,
+    system: Option<&Derivatives>,
+) -> LorenzState {
+    let derivatives = |s: &LorenzState| match system {
+        Some(system) => system(s, params),
+        None => lorenz_derivatives(s, params),
+    };
+    match config.method {
+        IntegrationMethod::Euler => euler_step_with(state, config.dt, derivatives),
+        IntegrationMethod::RungeKutta4 => rk4_step_with(state, config.dt, derivatives),
+    }
+}
+
+pub fn advance_trajectory(
+    state: &mut LorenzState,
+    trail: &mut TrailBuffer,
+    params: &LorenzParams,
+    config: &SimulationConfig,
+    system: Option<&Derivatives>,
+) -> u32 {
+    let mut accepted = 0;
+    for _ in 0..config.steps_per_frame {
+        let new_state = step(state, params, config, system);
+
+        if new_state.x.is_nan()
+            || new_state.y.is_nan()
+            || new_state.z.is_nan()
+            || new_state.x.abs() > 1e6
+        {
+            continue;
+        }
+
+        trail.points.push_back(trail_point(&new_state, params));
+
+        while trail.points.len() > trail.max_points {
+            trail.points.pop_front();
+        }
+
+        *state = new_state;
+        accepted += 1;
+    }
+    accepted
+}
+
+fn record_statistics(stats: &mut SimulationStats, trail: &TrailBuffer, accepted: u32) {
+    stats.total_steps += u64::from(accepted);
+    let start = trail.points.len() - (accepted as usize).min(trail.points.len());
+    let mut previous = start.checked_sub(1).map(|i| &trail.points[i]);
+    for point in trail.points.range(start..) {
+        if let Some(previous) = previous {
+            stats.arc_length += f64::from(point.position.distance(previous.position));
+            stats.travel_time += point.time - previous.time;
+        }
+        stats.running.record(point);
+        stats.lobes.record(coordinates(point).0, point.time);
+        previous = Some(point);
+    }
+}
+
+fn advance_lyapunov(
+    stats: &mut SimulationStats,
+    state: &LorenzState,
+    params: &LorenzParams,
+    config: &SimulationConfig,
+    system: Option<&Derivatives>,
+    accepted: u32,
+) {
+    stats.lyapunov.advance(state, config.dt, accepted, |shadow| {
+        step(shadow, params, config, system)
+    });
+}
+
+/// Advances one trajectory by a frame, or by a single requested step while
+/// paused, and brings its statistics and readouts up to date.
+pub fn run_frame(
+    state: &mut LorenzState,
+    trail: &mut TrailBuffer,
+    stats: &mut SimulationStats,
+    params: &LorenzParams,
+    config: &SimulationConfig,
+    system: Option<&Derivatives>,
+    single_step: bool,
+) {
+    trail.max_points = config.max_trail_points;
+    // A cap lowered while paused would otherwise only bite on the next step.
+    while trail.points.len() > trail.max_points {
+        trail.points.pop_front();
+    }
+    let timer = Instant::now();
+    if !config.paused {
+        let accepted = advance_trajectory(state, trail, params, config, system);
+        record_statistics(stats, trail, accepted);
+        advance_lyapunov(stats, state, params, config, system, accepted);
+    } else if single_step {
+        let single = SimulationConfig {
+            steps_per_frame: 1,
+            ..config.clone()
+        };
+        let accepted = advance_trajectory(state, trail, params, &single, system);
+        record_statistics(stats, trail, accepted);
+        advance_lyapunov(stats, state, params, &single, system, accepted);
+    }
+    stats.integration_time_us = if config.paused {
+        0.0
+    } else {
+        timer.elapsed().as_secs_f64() * 1_000_000.0
+    };
+
+    stats.current_energy = system_energy(state);
+    stats.current_velocity = velocity_magnitude(state, params);
+    stats.divergence = divergence(params);
+    stats.point_count = trail.points.len();
+}
+
 pub fn simulation_system(
     config: Res<SimulationConfig>,
-    mut state_query: Query<&mut LorenzState>,
+    mut state_query: Query<&mut LorenzState, MainTrajectory>,
     mut trail: ResMut<TrailBuffer>,
     mut stats: ResMut<SimulationStats>,
+    system: Res<CustomSystem>,
     mut reset_events: EventReader<ResetEvent>,
+    mut step_events: EventReader<StepEvent>,
 ) {
-    for _ in reset_events.read() {
+    if !reset_events.is_empty() {
+        reset_events.clear();
         trail.points.clear();
         for mut state in state_query.iter_mut() {
-            state.x = config.initial_x;
-            state.y = config.initial_y;
-            state.z = config.initial_z;
+            *state = LorenzState::new(config.initial_x, config.initial_y, config.initial_z);
         }
+        *stats = SimulationStats::default();
         return;
     }
 
-    trail.max_points = config.max_trail_points;
-
-    if config.paused {
-        if let Ok(state) = state_query.get_single() {
-            let params = LorenzParams {
-                sigma: config.sigma,
-                rho: config.rho,
-                beta: config.beta,
-            };
-            stats.current_energy = system_energy(&state);
-            stats.current_velocity = velocity_magnitude(&state, &params);
-            stats.divergence = divergence(&params);
-            stats.point_count = trail.points.len();
-            stats.integration_time_us = 0.0;
-        }
-        return;
-    }
-
-    let params = LorenzParams {
-        sigma: config.sigma,
-        rho: config.rho,
-        beta: config.beta,
-    };
-
-    let timer = Instant::now();
-
-    for mut state in state_query.iter_mut() {
-        for _ in 0..config.steps_per_frame {
-            let new_state = match config.method {
-                IntegrationMethod::Euler => euler_step(&state, &params, config.dt),
-                IntegrationMethod::RungeKutta4 => rk4_step(&state, &params, config.dt),
-            };
-
-            if new_state.x.is_nan()
-                || new_state.y.is_nan()
-                || new_state.z.is_nan()
-                || new_state.x.abs() > 1e6
-            {
-                continue;
-            }
-
-            let vel = velocity_magnitude(&new_state, &params);
-
-            let point = TrailPoint {
-                position: new_state.to_vec3(),
-                color: velocity_to_color(vel),
-            };
-            trail.points.push_back(point);
-
-            while trail.points.len() > trail.max_points {
-                trail.points.pop_front();
-            }
-
-            state.x = new_state.x;
-            state.y = new_state.y;
-            state.z = new_state.z;
-        }
-
-        stats.current_energy = system_energy(&state);
-        stats.current_velocity = velocity_magnitude(&state, &params);
-        stats.divergence = divergence(&params);
-        stats.point_count = trail.points.len();
+    let step = step_events.read().count() > 0;
+    if let Ok(mut state) = state_query.get_single_mut() {
+        let params = config.params();
+        run_frame(&mut state, &mut trail, &mut stats, &params, &config, system.0.as_ref(), step);
     }
-
-    stats.integration_time_us = timer.elapsed().as_secs_f64() * 1_000_000.0;
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    fn std_params() -> LorenzParams {
-        LorenzParams {
-            sigma: 10.0,
-            rho: 28.0,
-            beta: 8.0 / 3.0,
-        }
-    }
-
     #[test]
-    fn test_euler_advances_state() {
-        let state = LorenzState::new(1.0, 1.0, 1.0);
-        let next = euler_step(&state, &std_params(), 0.01);
-        assert!((next.x - state.x).abs() > 1e-10);
-    }
+    fn test_bounding_sphere_contains_all_points() {
+        let mut trail = TrailBuffer::default();
+        assert!(trail.bounding_sphere().is_none());
+
+        for p in [Vec3::new(-4.0, 0.0, 1.0), Vec3::new(6.0, 2.0, -3.0), Vec3::ZERO] {
+            trail.points.push_back(TrailPoint {
+                position: p,
+                color: Color::WHITE,
+                time: 0.0,
+                speed: 0.0,
+            });
+        }
 
-    #[test]
-    fn test_rk4_advances_state() {
-        let state = LorenzState::new(1.0, 1.0, 1.0);
-        let next = rk4_step(&state, &std_params(), 0.01);
-        assert!((next.x - state.x).abs() > 1e-10);
+        let (center, radius) = trail.bounding_sphere().unwrap();
+        for p in &trail.points {
+            assert!(p.position.distance(center) <= radius + 1e-5);
+        }
     }
 
     #[test]
-    fn test_rk4_more_accurate_than_euler() {
-        let state = LorenzState::new(1.0, 1.0, 1.0);
-        let params = std_params();
-        let dt_coarse = 0.1;
-        let dt_fine = 0.0001;
-        let steps_fine = (dt_coarse / dt_fine).round() as usize;
-
-        let mut ref_state = state.clone();
-        for _ in 0..steps_fine {
-            ref_state = rk4_step(&ref_state, &params, dt_fine);
+    fn test_arc_length_accumulates_across_frames() {
+        let mut trail = TrailBuffer::default();
+        let mut stats = SimulationStats::default();
+        for (i, x) in [0.0, 3.0, 3.0, 6.0].into_iter().enumerate() {
+            let y = if i == 2 { 4.0 } else { 0.0 };
+            trail.points.push_back(TrailPoint {
+                position: Vec3::new(x, y, 0.0),
+                color: Color::WHITE,
+                time: i as f64 * 0.5,
+                speed: 0.0,
+            });
+            record_statistics(&mut stats, &trail, 1);
         }
-
-        let euler_result = euler_step(&state, &params, dt_coarse);
-        let euler_err = (euler_result.x - ref_state.x).powi(2)
-            + (euler_result.y - ref_state.y).powi(2)
-            + (euler_result.z - ref_state.z).powi(2);
-
-        let rk4_result = rk4_step(&state, &params, dt_coarse);
-        let rk4_err = (rk4_result.x - ref_state.x).powi(2)
-            + (rk4_result.y - ref_state.y).powi(2)
-            + (rk4_result.z - ref_state.z).powi(2);
-
-        assert!(rk4_err < euler_err);
+        // 3 along x, 4 up to (3, 4), then 5 back down to (6, 0).
+        assert!((stats.arc_length - 12.0).abs() < 1e-6);
+        assert!((stats.average_speed() - 8.0).abs() < 1e-6);
+        assert_eq!(stats.total_steps, 4);
     }
 }
\ No newline at end of file
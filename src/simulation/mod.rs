@@ -1,2 +1,16 @@
 pub mod lorenz;
-pub mod integrator;
\ No newline at end of file
+pub mod integrator;
+pub mod comparison;
+pub mod complexity;
+pub mod density;
+pub mod embedding;
+pub mod gallery;
+pub mod headless;
+pub mod bifurcation;
+pub mod replay;
+pub mod history;
+pub mod randomize;
+pub mod spectrum;
+pub mod statistics;
+pub mod trajectories;
+pub mod butterfly;
\ No newline at end of file
@@ -0,0 +1,125 @@
+use bevy::prelude::*;
+
+use crate::config::{RandomizeEvent, RandomizeSettings, ResetEvent, SimulationConfig};
+use crate::i18n::{tr, trf};
+use crate::presets::Presets;
+use crate::ui::toast::Toasts;
+use super::lorenz::{rk4_step, velocity_magnitude, LorenzParams, LorenzState};
+
+const SETTLE_DT: f64 = 0.005;
+const SETTLE_STEPS: usize = 4000;
+const CHECK_STEPS: usize = 1000;
+const SETTLED_SPEED: f64 = 0.5;
+const MAX_ATTEMPTS: usize = 100;
+
+/// Whether the orbit from `initial` has all but stopped after a short
+/// transient, i.e. it is converging on the origin or C±. Orbits that blow up
+/// count as collapsed too.
+pub fn collapses(params: &LorenzParams, initial: &LorenzState) -> bool {
+    let mut state = initial.clone();
+    for _ in 0..SETTLE_STEPS {
+        state = rk4_step(&state, params, SETTLE_DT);
+    }
+    let mut max_speed: f64 = 0.0;
+    for _ in 0..CHECK_STEPS {
+        state = rk4_step(&state, params, SETTLE_DT);
+        max_speed = max_speed.max(velocity_magnitude(&state, params));
+    }
+    !max_speed.is_finite() || max_speed < SETTLED_SPEED
+}
+
+fn sample(rng: &mut fastrand::Rng, (low, high): (f64, f64)) -> f64 {
+    low + (high - low) * rng.f64()
+}
+
+pub fn surprise(
+    settings: &RandomizeSettings,
+    current: &LorenzState,
+    rng: &mut fastrand::Rng,
+) -> Option<(LorenzParams, LorenzState)> {
+    let extent = (-settings.initial_extent, settings.initial_extent);
+    (0..MAX_ATTEMPTS).find_map(|_| {
+        let params = LorenzParams {
+            sigma: sample(rng, settings.sigma),
+            rho: sample(rng, settings.rho),
+            beta: sample(rng, settings.beta),
+        };
+        let initial = if settings.initial_conditions {
+            LorenzState::new(sample(rng, extent), sample(rng, extent), sample(rng, extent))
+        } else {
+            current.clone()
+        };
+        (!collapses(&params, &initial)).then_some((params, initial))
+    })
+}
+
+pub fn randomize_system(
+    mut events: EventReader<RandomizeEvent>,
+    settings: Res<RandomizeSettings>,
+    mut config: ResMut<SimulationConfig>,
+    mut presets: ResMut<Presets>,
+    mut reset_events: EventWriter<ResetEvent>,
+    mut toasts: ResMut<Toasts>,
+) {
+    if events.read().count() == 0 {
+        return;
+    }
+
+    let current = LorenzState::new(config.initial_x, config.initial_y, config.initial_z);
+    let Some((params, initial)) = surprise(&settings, &current, &mut fastrand::Rng::new()) else {
+        toasts.push(tr("🎲 Every sample collapsed to a fixed point; try wider ranges"));
+        return;
+    };
+
+    presets.morph = None;
+    presets.active = None;
+    config.sigma = params.sigma;
+    config.rho = params.rho;
+    config.beta = params.beta;
+    if settings.initial_conditions {
+        config.initial_x = initial.x;
+        config.initial_y = initial.y;
+        config.initial_z = initial.z;
+        reset_events.send(ResetEvent);
+    }
+    toasts.push(trf(
+        "🎲 σ = {sigma}, ρ = {rho}, β = {beta}",
+        &[
+            ("sigma", &format!("{:.3}", params.sigma)),
+            ("rho", &format!("{:.3}", params.rho)),
+            ("beta", &format!("{:.3}", params.beta)),
+        ],
+    ));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_collapses_detects_fixed_points() {
+        let initial = LorenzState::new(1.0, 1.0, 1.0);
+        let params = |rho| LorenzParams {
+            sigma: 10.0,
+            rho,
+            beta: 8.0 / 3.0,
+        };
+        assert!(collapses(&params(0.5), &initial));
+        assert!(collapses(&params(10.0), &initial));
+        assert!(!collapses(&params(28.0), &initial));
+    }
+
+    #[test]
+    fn test_surprise_respects_ranges() {
+        let settings = RandomizeSettings {
+            rho: (26.0, 30.0),
+            ..Default::default()
+        };
+        let current = LorenzState::new(1.0, 1.0, 1.0);
+        let (params, initial) =
+            surprise(&settings, &current, &mut fastrand::Rng::with_seed(7)).unwrap();
+        assert!((26.0..=30.0).contains(&params.rho));
+        assert!((5.0..=20.0).contains(&params.sigma));
+        assert_eq!((initial.x, initial.y, initial.z), (1.0, 1.0, 1.0));
+    }
+}
\ No newline at end of file
@@ -0,0 +1,280 @@
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use bevy::prelude::*;
+use serde_json::Value;
+
+use crate::config::{LoadReplayEvent, ResetEvent, SimulationConfig, SimulationStats};
+use crate::i18n::trf;
+use crate::ui::toast::Toasts;
+use super::integrator::{velocity_to_color, TrailBuffer, TrailPoint};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ReplaySample {
+    pub t: f64,
+    pub x: f64,
+    pub y: f64,
+    pub z: f64,
+    pub speed: f64,
+}
+
+impl ReplaySample {
+    fn trail_point(&self) -> TrailPoint {
+        TrailPoint {
+            position: Vec3::new(self.x as f32, self.y as f32, self.z as f32),
+            color: velocity_to_color(self.speed),
+            time: self.t,
+            speed: self.speed as f32,
+        }
+    }
+}
+
+type RawSample = (Option<f64>, f64, f64, f64, Option<f64>);
+
+fn invalid(message: impl Into<String>) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message.into())
+}
+
+fn finish(rows: Vec<RawSample>) -> io::Result<Vec<ReplaySample>> {
+    if rows.is_empty() {
+        return Err(invalid("no samples found"));
+    }
+
+    let mut samples: Vec<ReplaySample> = rows
+        .iter()
+        .enumerate()
+        .map(|(i, &(t, x, y, z, speed))| ReplaySample {
+            t: t.unwrap_or(i as f64),
+            x,
+            y,
+            z,
+            speed: speed.unwrap_or(f64::NAN),
+        })
+        .collect();
+    if !samples[0].t.is_finite()
+        || samples.windows(2).any(|w| !w[1].t.is_finite() || w[1].t < w[0].t)
+    {
+        return Err(invalid("time column must be finite and non-decreasing"));
+    }
+
+    for i in 0..samples.len() {
+        if !samples[i].speed.is_nan() {
+            continue;
+        }
+        let (a, b) = if i > 0 { (i - 1, i) } else { (0, 1.min(samples.len() - 1)) };
+        let (p, q) = (samples[a], samples[b]);
+        let dt = q.t - p.t;
+        let distance = ((q.x - p.x).powi(2) + (q.y - p.y).powi(2) + (q.z - p.z).powi(2)).sqrt();
+        samples[i].speed = if dt > 0.0 { distance / dt } else { 0.0 };
+    }
+    Ok(samples)
+}
+
+pub fn parse_csv(text: &str) -> io::Result<Vec<ReplaySample>> {
+    let mut lines = text.lines().enumerate().filter(|(_, line)| !line.trim().is_empty());
+    let (_, header) = lines.next().ok_or_else(|| invalid("empty file"))?;
+    let columns: Vec<&str> = header.split(',').map(str::trim).collect();
+    let column = |name: &str| columns.iter().position(|c| c.eq_ignore_ascii_case(name));
+    let (t, speed) = (column("t"), column("speed"));
+    let (Some(x), Some(y), Some(z)) = (column("x"), column("y"), column("z")) else {
+        return Err(invalid("CSV header needs x, y, and z columns"));
+    };
+
+    let mut rows = Vec::new();
+    for (index, line) in lines {
+        let fields: Vec<&str> = line.split(',').map(str::trim).collect();
+        let field = |i: usize| -> io::Result<f64> {
+            fields.get(i).and_then(|f| f.parse().ok()).ok_or_else(|| {
+                invalid(format!("line {}: expected a number in column {}", index + 1, i + 1))
+            })
+        };
+        rows.push((
+            t.map(field).transpose()?,
+            field(x)?,
+            field(y)?,
+            field(z)?,
+            speed.map(field).transpose()?,
+        ));
+    }
+    finish(rows)
+}
+
+pub fn parse_json(text: &str) -> io::Result<Vec<ReplaySample>> {
+    let value: Value = serde_json::from_str(text)?;
+    let records = value
+        .as_array()
+        .ok_or_else(|| invalid("expected an array of {t, x, y, z} records"))?;
+
+    let mut rows = Vec::with_capacity(records.len());
+    for (index, record) in records.iter().enumerate() {
+        let number = |name: &str| record.get(name).and_then(Value::as_f64);
+        let coordinate = |name: &str| {
+            number(name).ok_or_else(|| invalid(format!("record {index}: missing \"{name}\"")))
+        };
+        rows.push((
+            number("t"),
+            coordinate("x")?,
+            coordinate("y")?,
+            coordinate("z")?,
+            number("speed"),
+        ));
+    }
+    finish(rows)
+}
+
+pub fn load(path: &Path) -> io::Result<Vec<ReplaySample>> {
+    let text = fs::read_to_string(path)?;
+    match path.extension().and_then(|e| e.to_str()) {
+        Some(ext) if ext.eq_ignore_ascii_case("json") => parse_json(&text),
+        _ => parse_csv(&text),
+    }
+}
+
+#[derive(Resource)]
+pub struct Replay {
+    pub path: String,
+    pub samples: Vec<ReplaySample>,
+    pub cursor: f64,
+    pub playing: bool,
+    pub rate: f64,
+    shown: usize,
+}
+
+impl Default for Replay {
+    fn default() -> Self {
+        Self {
+            path: String::new(),
+            samples: Vec::new(),
+            cursor: 0.0,
+            playing: false,
+            rate: 1.0,
+            shown: 0,
+        }
+    }
+}
+
+impl Replay {
+    pub fn is_active(&self) -> bool {
+        !self.samples.is_empty()
+    }
+
+    pub fn time_range(&self) -> (f64, f64) {
+        match (self.samples.first(), self.samples.last()) {
+            (Some(first), Some(last)) => (first.t, last.t),
+            _ => (0.0, 0.0),
+        }
+    }
+
+    pub fn stop(&mut self) {
+        self.samples.clear();
+        self.playing = false;
+        self.shown = 0;
+    }
+}
+
+pub fn is_replaying(replay: Res<Replay>) -> bool {
+    replay.is_active()
+}
+
+pub fn load_replay_system(
+    mut events: EventReader<LoadReplayEvent>,
+    mut replay: ResMut<Replay>,
+    mut trail: ResMut<TrailBuffer>,
+    mut toasts: ResMut<Toasts>,
+) {
+    if events.read().count() == 0 {
+        return;
+    }
+
+    let path = replay.path.trim().to_string();
+    match load(Path::new(&path)) {
+        Ok(samples) => {
+            toasts.push(trf(
+                "⏯ Replaying {count} samples from {path}",
+                &[("count", &samples.len()), ("path", &path)],
+            ));
+            replay.samples = samples;
+            replay.cursor = replay.time_range().0;
+            replay.playing = true;
+            replay.shown = 0;
+            trail.points.clear();
+        }
+        Err(err) => toasts.push(trf("Replay failed: {err}", &[("err", &err)])),
+    }
+}
+
+pub fn replay_system(
+    time: Res<Time>,
+    config: Res<SimulationConfig>,
+    mut replay: ResMut<Replay>,
+    mut trail: ResMut<TrailBuffer>,
+    mut stats: ResMut<SimulationStats>,
+    mut reset_events: EventWriter<ResetEvent>,
+    mut was_active: Local<bool>,
+) {
+    let active = replay.is_active();
+    if *was_active && !active {
+        reset_events.send(ResetEvent);
+    }
+    *was_active = active;
+    if !active {
+        return;
+    }
+
+    let replay = &mut *replay;
+    let (start, end) = replay.time_range();
+    if replay.playing {
+        replay.cursor += time.delta_seconds_f64() * replay.rate;
+        if replay.cursor >= end {
+            replay.playing = false;
+        }
+    }
+    replay.cursor = replay.cursor.clamp(start, end);
+
+    let target = replay.samples.partition_point(|s| s.t <= replay.cursor);
+    trail.max_points = config.max_trail_points;
+    if target < replay.shown {
+        trail.points.clear();
+        replay.shown = target.saturating_sub(trail.max_points);
+    }
+    trail
+        .points
+        .extend(replay.samples[replay.shown..target].iter().map(ReplaySample::trail_point));
+    replay.shown = target;
+    while trail.points.len() > trail.max_points {
+        trail.points.pop_front();
+    }
+    stats.point_count = trail.points.len();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_exported_csv_and_json() {
+        let csv = parse_csv("t,x,y,z,speed\n0,1,2,3,4\n0.5,1,2,4,5\n").unwrap();
+        assert_eq!(csv.len(), 2);
+        assert_eq!(csv[1], ReplaySample { t: 0.5, x: 1.0, y: 2.0, z: 4.0, speed: 5.0 });
+
+        let json = parse_json(r#"[{"t":0,"x":0,"y":0,"z":0},{"t":2,"x":3,"y":4,"z":0}]"#).unwrap();
+        assert_eq!(json[0].speed, 2.5);
+        assert_eq!(json[1].speed, 2.5);
+
+        let untimed = parse_csv("x,y,z\n0,0,0\n0,0,1\n").unwrap();
+        assert_eq!((untimed[1].t, untimed[1].speed), (1.0, 1.0));
+    }
+
+    #[test]
+    fn test_parse_rejects_bad_input() {
+        assert!(parse_csv("t,x,y\n0,1,2\n").is_err());
+        assert!(parse_csv("t,x,y,z\n0,1,2,oops\n").is_err());
+        assert!(parse_csv("t,x,y,z\n1,0,0,0\n0,0,0,0\n").is_err());
+        assert!(parse_csv("t,x,y,z\n0,0,0,0\nNaN,0,0,0\n1,0,0,0\n").is_err());
+        assert!(parse_csv("t,x,y,z\nNaN,0,0,0\n1,0,0,0\n").is_err());
+        assert!(parse_csv("t,x,y,z\n0,0,0,0\ninf,0,0,0\n").is_err());
+        assert!(parse_json(r#"{"x":1}"#).is_err());
+        assert!(parse_json("[]").is_err());
+    }
+}
\ No newline at end of file
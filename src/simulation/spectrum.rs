@@ -0,0 +1,154 @@
+use std::collections::VecDeque;
+use std::f64::consts::TAU;
+
+use bevy::prelude::*;
+
+use crate::config::{SimulationConfig, SpectrumWindow, SPECTRUM_LENGTHS};
+use crate::export::coordinates;
+use super::integrator::TrailBuffer;
+
+const MAX_SAMPLES: usize = SPECTRUM_LENGTHS[SPECTRUM_LENGTHS.len() - 1];
+
+/// x(t) at every integration step, kept separately from the trail so the
+/// spectrum does not depend on the trail length.
+#[derive(Resource, Default)]
+pub struct SpectrumSampler {
+    samples: VecDeque<f64>,
+    dt: f64,
+    last_time: f64,
+}
+
+impl SpectrumSampler {
+    pub fn len(&self) -> usize {
+        self.samples.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.samples.is_empty()
+    }
+
+    pub fn dt(&self) -> f64 {
+        self.dt
+    }
+
+    /// The most recent `count` samples, oldest first.
+    pub fn latest(&self, count: usize) -> Option<Vec<f64>> {
+        let start = self.samples.len().checked_sub(count)?;
+        Some(self.samples.range(start..).copied().collect())
+    }
+}
+
+pub fn spectrum_sampler_system(
+    config: Res<SimulationConfig>,
+    trail: Res<TrailBuffer>,
+    mut sampler: ResMut<SpectrumSampler>,
+) {
+    // A new dt or a rewound trail (reset, timeline scrub) would make the
+    // spacing uneven, so the series starts over.
+    let rewound = trail.points.back().is_none_or(|p| p.time < sampler.last_time);
+    if sampler.dt != config.dt || rewound {
+        sampler.samples.clear();
+        sampler.dt = config.dt;
+        sampler.last_time = f64::NEG_INFINITY;
+    }
+
+    let last_time = sampler.last_time;
+    let fresh = trail.points.iter().rev().take_while(|p| p.time > last_time).count();
+    for point in trail.points.range(trail.points.len() - fresh..) {
+        sampler.samples.push_back(f64::from(coordinates(point).0));
+        sampler.last_time = point.time;
+    }
+    let excess = sampler.samples.len().saturating_sub(MAX_SAMPLES);
+    sampler.samples.drain(..excess);
+}
+
+/// In-place radix-2 Cooley–Tukey transform. `re.len()` must be a power of two.
+fn fft(re: &mut [f64], im: &mut [f64]) {
+    let n = re.len();
+    let mut j = 0;
+    for i in 1..n {
+        let mut bit = n >> 1;
+        while j & bit != 0 {
+            j ^= bit;
+            bit >>= 1;
+        }
+        j |= bit;
+        if i < j {
+            re.swap(i, j);
+            im.swap(i, j);
+        }
+    }
+
+    let mut len = 2;
+    while len <= n {
+        let angle = -TAU / len as f64;
+        for start in (0..n).step_by(len) {
+            for k in 0..len / 2 {
+                let (sin, cos) = (angle * k as f64).sin_cos();
+                let (a, b) = (start + k, start + k + len / 2);
+                let tr = re[b] * cos - im[b] * sin;
+                let ti = re[b] * sin + im[b] * cos;
+                re[b] = re[a] - tr;
+                im[b] = im[a] - ti;
+                re[a] += tr;
+                im[a] += ti;
+            }
+        }
+        len <<= 1;
+    }
+}
+
+/// One-sided power spectral density of `samples` taken every `dt`, as
+/// `(frequency, power)` pairs from the lowest nonzero bin up to Nyquist.
+/// The mean is removed first so the DC bin does not swamp the plot.
+pub fn power_spectrum(samples: &[f64], dt: f64, window: SpectrumWindow) -> Vec<[f64; 2]> {
+    let n = samples.len();
+    debug_assert!(n.is_power_of_two());
+    let weight = |i: usize| match window {
+        SpectrumWindow::Hann => 0.5 - 0.5 * (TAU * i as f64 / n as f64).cos(),
+        SpectrumWindow::Flat => 1.0,
+    };
+    let mean = samples.iter().sum::<f64>() / n as f64;
+    let mut re: Vec<f64> = samples
+        .iter()
+        .enumerate()
+        .map(|(i, s)| (s - mean) * weight(i))
+        .collect();
+    let mut im = vec![0.0; n];
+    fft(&mut re, &mut im);
+
+    let scale = dt / (0..n).map(|i| weight(i).powi(2)).sum::<f64>();
+    (1..=n / 2)
+        .map(|k| {
+            let one_sided = if k == n / 2 { 1.0 } else { 2.0 };
+            let power = (re[k].powi(2) + im[k].powi(2)) * scale * one_sided;
+            [k as f64 / (n as f64 * dt), power]
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sine_peaks_at_its_frequency() {
+        let dt = 0.01;
+        let n = 1024;
+        // Exactly 64 cycles in the window.
+        let frequency = 64.0 / (n as f64 * dt);
+        let samples: Vec<f64> = (0..n).map(|i| (TAU * frequency * i as f64 * dt).sin()).collect();
+
+        for window in [SpectrumWindow::Hann, SpectrumWindow::Flat] {
+            let spectrum = power_spectrum(&samples, dt, window);
+            assert_eq!(spectrum.len(), n / 2);
+            let peak = spectrum.iter().max_by(|a, b| a[1].total_cmp(&b[1])).unwrap();
+            assert!((peak[0] - frequency).abs() < 1e-9, "{window:?}");
+        }
+
+        // Parseval: a flat-windowed unit sine carries power ½.
+        let spectrum = power_spectrum(&samples, dt, SpectrumWindow::Flat);
+        let total: f64 = spectrum.iter().map(|[_, p]| p).sum::<f64>() / (n as f64 * dt);
+        assert!((total - 0.5).abs() < 1e-9, "{total}");
+    }
+}
\ No newline at end of file
@@ -0,0 +1,274 @@
+use std::collections::{HashMap, VecDeque};
+
+use bevy::prelude::*;
+
+use super::integrator::{TrailBuffer, TrailPoint};
+use crate::config::SimulationStats;
+use crate::export::coordinates;
+use crate::i18n::tr_noop;
+
+pub use super::lorenz::LyapunovEstimate;
+
+/// |x| the trajectory must reach on the far side before a switch counts, so
+/// jitter around the x = 0 plane is not mistaken for a lobe change.
+pub const LOBE_HYSTERESIS: f32 = 2.0;
+/// Completed residence durations kept for the histogram.
+pub const MAX_RESIDENCES: usize = 10_000;
+/// Block entropies are estimated over at most this many of the latest symbols.
+pub const ENTROPY_WINDOW: usize = 20_000;
+/// Model time between rows of the statistics history.
+pub const HISTORY_INTERVAL: f64 = 0.1;
+pub const MAX_HISTORY_ROWS: usize = 100_000;
+
+/// Streaming min / max / mean / standard deviation (Welford's algorithm).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RunningStats {
+    pub count: u64,
+    pub min: f64,
+    pub max: f64,
+    pub mean: f64,
+    m2: f64,
+}
+
+impl Default for RunningStats {
+    fn default() -> Self {
+        Self {
+            count: 0,
+            min: f64::INFINITY,
+            max: f64::NEG_INFINITY,
+            mean: 0.0,
+            m2: 0.0,
+        }
+    }
+}
+
+impl RunningStats {
+    pub fn push(&mut self, value: f64) {
+        self.count += 1;
+        self.min = self.min.min(value);
+        self.max = self.max.max(value);
+        let delta = value - self.mean;
+        self.mean += delta / self.count as f64;
+        self.m2 += delta * (value - self.mean);
+    }
+
+    /// Population standard deviation; zero until there are two samples.
+    pub fn std_dev(&self) -> f64 {
+        if self.count < 2 {
+            0.0
+        } else {
+            (self.m2 / self.count as f64).sqrt()
+        }
+    }
+}
+
+/// Running statistics of x, y, z and speed since the last reset.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TrajectoryStats {
+    pub coordinates: [RunningStats; 3],
+    pub speed: RunningStats,
+}
+
+impl TrajectoryStats {
+    pub fn record(&mut self, point: &TrailPoint) {
+        let (x, y, z) = coordinates(point);
+        for (stats, value) in self.coordinates.iter_mut().zip([x, y, z]) {
+            stats.push(f64::from(value));
+        }
+        self.speed.push(f64::from(point.speed));
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Lobe {
+    Left,
+    Right,
+}
+
+impl Lobe {
+    pub const ALL: [Lobe; 2] = [Lobe::Left, Lobe::Right];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Lobe::Left => tr_noop("Left wing (x < 0)"),
+            Lobe::Right => tr_noop("Right wing (x > 0)"),
+        }
+    }
+
+    pub fn symbol(&self) -> char {
+        match self {
+            Lobe::Left => 'L',
+            Lobe::Right => 'R',
+        }
+    }
+
+    fn classify(x: f32) -> Option<Lobe> {
+        if x < -LOBE_HYSTERESIS {
+            Some(Lobe::Left)
+        } else if x > LOBE_HYSTERESIS {
+            Some(Lobe::Right)
+        } else {
+            None
+        }
+    }
+}
+
+/// Wing transitions and how long the trajectory stays in each wing.
+#[derive(Debug, Clone, Default)]
+pub struct LobeStats {
+    pub current: Option<Lobe>,
+    /// `None` while in the first wing, whose visit began at the initial
+    /// condition rather than at a switch and so is not a full residence.
+    entered_at: Option<f64>,
+    pub switches: u64,
+    /// Indexed by `Lobe as usize`.
+    pub residence: [RunningStats; 2],
+    pub durations: VecDeque<f64>,
+    /// Every wing visited, in order, as `L`/`R`; starts with the first wing.
+    pub symbols: String,
+}
+
+impl LobeStats {
+    pub fn record(&mut self, x: f32, time: f64) {
+        let Some(lobe) = Lobe::classify(x) else {
+            return;
+        };
+        match self.current {
+            None => {
+                self.current = Some(lobe);
+                self.symbols.push(lobe.symbol());
+            }
+            Some(current) if current != lobe => {
+                if let Some(start) = self.entered_at {
+                    let duration = time - start;
+                    self.residence[current as usize].push(duration);
+                    self.durations.push_back(duration);
+                    if self.durations.len() > MAX_RESIDENCES {
+                        self.durations.pop_front();
+                    }
+                }
+                self.switches += 1;
+                self.current = Some(lobe);
+                self.symbols.push(lobe.symbol());
+                self.entered_at = Some(time);
+            }
+            Some(_) => {}
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct StatsSample {
+    pub time: f64,
+    pub energy: f64,
+    pub speed: f64,
+    pub average_speed: f64,
+    pub arc_length: f64,
+    pub lyapunov: Option<f64>,
+    pub lobe_switches: u64,
+}
+
+/// Diagnostics sampled every `HISTORY_INTERVAL` of model time for export.
+#[derive(Resource, Default)]
+pub struct StatsHistory {
+    pub samples: VecDeque<StatsSample>,
+}
+
+pub fn stats_history_system(
+    trail: Res<TrailBuffer>,
+    stats: Res<SimulationStats>,
+    mut history: ResMut<StatsHistory>,
+) {
+    let Some(time) = trail.points.back().map(|p| p.time) else {
+        history.samples.clear();
+        return;
+    };
+    let last = history.samples.back().map(|s| s.time);
+    if last.is_some_and(|last| time < last) {
+        // Reset or timeline scrub.
+        history.samples.clear();
+    } else if last.is_some_and(|last| time - last < HISTORY_INTERVAL) {
+        return;
+    }
+    history.samples.push_back(StatsSample {
+        time,
+        energy: stats.current_energy,
+        speed: stats.current_velocity,
+        average_speed: stats.average_speed(),
+        arc_length: stats.arc_length,
+        lyapunov: stats.lyapunov.exponent(),
+        lobe_switches: stats.lobes.switches,
+    });
+    if history.samples.len() > MAX_HISTORY_ROWS {
+        history.samples.pop_front();
+    }
+}
+
+/// Shannon entropy in bits of the length-`n` words of `symbols`, estimated
+/// from their overlapping occurrence frequencies. `None` when there are
+/// fewer than `n` symbols.
+pub fn block_entropy(symbols: &[u8], n: usize) -> Option<f64> {
+    if n == 0 || symbols.len() < n {
+        return None;
+    }
+    let mut counts: HashMap<&[u8], usize> = HashMap::new();
+    for word in symbols.windows(n) {
+        *counts.entry(word).or_default() += 1;
+    }
+    let total = (symbols.len() - n + 1) as f64;
+    Some(
+        counts
+            .values()
+            .map(|&count| {
+                let p = count as f64 / total;
+                -p * p.log2()
+            })
+            .sum(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_running_stats_match_batch_values() {
+        let values = [2.0, 4.0, 4.0, 4.0, 5.0, 5.0, 7.0, 9.0];
+        let mut stats = RunningStats::default();
+        for value in values {
+            stats.push(value);
+        }
+        assert_eq!(stats.count, 8);
+        assert_eq!((stats.min, stats.max), (2.0, 9.0));
+        assert!((stats.mean - 5.0).abs() < 1e-12);
+        assert!((stats.std_dev() - 2.0).abs() < 1e-12);
+        assert_eq!(RunningStats::default().std_dev(), 0.0);
+    }
+
+    #[test]
+    fn test_lobe_switches_ignore_jitter_and_first_visit() {
+        let mut lobes = LobeStats::default();
+        let xs = [5.0, 1.0, -1.0, 1.0, -5.0, -8.0, 0.5, -0.5, 6.0, 4.0, -3.0];
+        for (i, x) in xs.into_iter().enumerate() {
+            lobes.record(x, i as f64);
+        }
+        assert_eq!(lobes.switches, 3);
+        assert_eq!(lobes.current, Some(Lobe::Left));
+        // Left from t=4 to t=8, right from t=8 to t=10; the first right visit is partial.
+        assert_eq!(lobes.durations, [4.0, 2.0]);
+        assert_eq!(lobes.residence[Lobe::Left as usize].mean, 4.0);
+        assert_eq!(lobes.residence[Lobe::Right as usize].count, 1);
+        assert_eq!(lobes.symbols, "RLRL");
+    }
+
+    #[test]
+    fn test_block_entropy() {
+        let alternating = "LR".repeat(50);
+        let entropy = |n| block_entropy(alternating.as_bytes(), n).unwrap();
+        assert!((entropy(1) - 1.0).abs() < 1e-12);
+        // Only "LR" and "RL" occur, so longer blocks add no information.
+        assert!((entropy(4) - 1.0).abs() < 1e-3);
+        assert_eq!(block_entropy(b"LLLL", 2), Some(0.0));
+        assert_eq!(block_entropy(b"L", 2), None);
+    }
+}
\ No newline at end of file
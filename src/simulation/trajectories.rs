@@ -0,0 +1,168 @@
+use bevy::prelude::*;
+
+use crate::config::{ResetEvent, SimulationConfig, SimulationStats, StepEvent, TrajectoryEvent};
+use crate::i18n::{tr, trf};
+use super::comparison::ComparisonTrajectory;
+use super::integrator::{run_frame, CustomSystem, TrailBuffer};
+use super::lorenz::{LorenzParams, LorenzState};
+
+/// Query filter for the main trajectory, whose trail is the `TrailBuffer`
+/// resource rather than a component.
+pub type MainTrajectory = (Without<ComparisonTrajectory>, Without<ExtraTrajectory>);
+
+/// Offset along x between the initial conditions of added trajectories.
+const ADD_OFFSET: f64 = 1e-3;
+const PALETTE: [Color; 6] = [
+    Color::srgb(1.0, 0.6, 0.2),
+    Color::srgb(0.3, 0.9, 0.9),
+    Color::srgb(0.9, 0.4, 0.9),
+    Color::srgb(0.6, 1.0, 0.3),
+    Color::srgb(1.0, 0.9, 0.3),
+    Color::srgb(0.5, 0.6, 1.0),
+];
+
+/// A user-added trajectory, integrated alongside the main one with its own
+/// `LorenzState`, `TrailBuffer` and `SimulationStats` components.
+#[derive(Component, Clone)]
+pub struct ExtraTrajectory {
+    pub name: String,
+    pub visible: bool,
+    pub color: Color,
+    /// Where Reset puts it back.
+    pub initial: [f64; 3],
+    /// Own σ, ρ, β; `None` follows the main parameters.
+    pub params: Option<LorenzParams>,
+    /// Own time step; `None` follows the main dt.
+    pub dt: Option<f64>,
+}
+
+#[derive(Resource)]
+pub struct Trajectories {
+    pub main_visible: bool,
+    /// Trajectory the Diagnostics and Lobe Switches panels show; `None` is the main one.
+    pub active: Option<Entity>,
+    added: usize,
+}
+
+impl Default for Trajectories {
+    fn default() -> Self {
+        Self {
+            main_visible: true,
+            active: None,
+            added: 0,
+        }
+    }
+}
+
+impl Trajectories {
+    fn next_color(&mut self) -> (usize, Color) {
+        self.added += 1;
+        (self.added, PALETTE[(self.added - 1) % PALETTE.len()])
+    }
+}
+
+pub fn manage_trajectories_system(
+    mut commands: Commands,
+    mut events: EventReader<TrajectoryEvent>,
+    config: Res<SimulationConfig>,
+    mut trajectories: ResMut<Trajectories>,
+    main_trail: Res<TrailBuffer>,
+    main_state: Query<&LorenzState, MainTrajectory>,
+    extras: Query<(&ExtraTrajectory, &LorenzState, &TrailBuffer)>,
+) {
+    for event in events.read() {
+        match *event {
+            TrajectoryEvent::Add(at) => {
+                let (n, color) = trajectories.next_color();
+                let initial = at.unwrap_or([
+                    config.initial_x + ADD_OFFSET * n as f64,
+                    config.initial_y,
+                    config.initial_z,
+                ]);
+                let extra = ExtraTrajectory {
+                    name: trf("Trajectory {n}", &[("n", &n)]),
+                    visible: true,
+                    color,
+                    initial,
+                    params: None,
+                    dt: None,
+                };
+                let state = LorenzState::new(initial[0], initial[1], initial[2]);
+                commands.spawn((extra, state, TrailBuffer::default(), SimulationStats::default()));
+            }
+            TrajectoryEvent::Clone(source) => {
+                let (_, color) = trajectories.next_color();
+                let copy = match source {
+                    Some(entity) => extras.get(entity).ok().map(|(extra, state, trail)| {
+                        (extra.clone(), state.clone(), trail.clone())
+                    }),
+                    None => main_state.get_single().ok().map(|state| {
+                        let main = ExtraTrajectory {
+                            name: tr("Main").to_string(),
+                            visible: true,
+                            color,
+                            initial: [config.initial_x, config.initial_y, config.initial_z],
+                            params: None,
+                            dt: None,
+                        };
+                        (main, state.clone(), main_trail.clone())
+                    }),
+                };
+                if let Some((source, state, trail)) = copy {
+                    let extra = ExtraTrajectory {
+                        name: trf("{name} copy", &[("name", &source.name)]),
+                        visible: true,
+                        color,
+                        ..source
+                    };
+                    commands.spawn((extra, state, trail, SimulationStats::default()));
+                }
+            }
+            TrajectoryEvent::Remove(entity) => {
+                if extras.contains(entity) {
+                    commands.entity(entity).despawn();
+                }
+                if trajectories.active == Some(entity) {
+                    trajectories.active = None;
+                }
+            }
+        }
+    }
+}
+
+pub fn trajectories_simulation_system(
+    config: Res<SimulationConfig>,
+    mut query: Query<(
+        &ExtraTrajectory,
+        &mut LorenzState,
+        &mut TrailBuffer,
+        &mut SimulationStats,
+    )>,
+    system: Res<CustomSystem>,
+    mut reset_events: EventReader<ResetEvent>,
+    mut step_events: EventReader<StepEvent>,
+) {
+    if !reset_events.is_empty() {
+        reset_events.clear();
+        for (extra, mut state, mut trail, mut stats) in query.iter_mut() {
+            let [x, y, z] = extra.initial;
+            *state = LorenzState::new(x, y, z);
+            trail.points.clear();
+            *stats = SimulationStats::default();
+        }
+        return;
+    }
+
+    let step = step_events.read().count() > 0;
+    let system = system.0.as_ref();
+    for (extra, mut state, mut trail, mut stats) in query.iter_mut() {
+        let params = extra.params.unwrap_or_else(|| config.params());
+        match extra.dt {
+            Some(dt) => {
+                let config = SimulationConfig { dt, ..config.clone() };
+                run_frame(&mut state, &mut trail, &mut stats, &params, &config, system, step);
+            }
+            None => run_frame(&mut state, &mut trail, &mut stats, &params, &config, system, step),
+        }
+    }
+}
\ No newline at end of file
@@ -0,0 +1,198 @@
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use bevy::ecs::system::SystemParam;
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::config::{
+    LoadSnapshotEvent, SaveSnapshotEvent, SimulationConfig, SimulationStats, SnapshotSettings,
+};
+use crate::export::resolve_path;
+use crate::i18n::trf;
+use crate::rendering::camera_controller::OrbitCamera;
+use crate::session::CameraPose;
+use crate::simulation::history::History;
+use crate::simulation::integrator::{velocity_to_color, TrailBuffer, TrailPoint};
+use crate::simulation::lorenz::LorenzState;
+use crate::simulation::trajectories::MainTrajectory;
+use crate::ui::toast::Toasts;
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct SnapshotPoint(pub [f32; 3], pub f64, pub f32);
+
+#[derive(Serialize, Deserialize)]
+pub struct Snapshot {
+    pub version: String,
+    pub config: SimulationConfig,
+    pub state: LorenzState,
+    pub total_steps: u64,
+    pub camera: Option<CameraPose>,
+    pub trail: Option<Vec<SnapshotPoint>>,
+}
+
+impl Snapshot {
+    pub fn trail_points(&self) -> impl Iterator<Item = TrailPoint> + '_ {
+        self.trail.iter().flatten().map(|SnapshotPoint(position, time, speed)| TrailPoint {
+            position: Vec3::from_array(*position),
+            color: velocity_to_color(*speed as f64),
+            time: *time,
+            speed: *speed,
+        })
+    }
+}
+
+pub fn encode(snapshot: &Snapshot) -> io::Result<String> {
+    ron::ser::to_string_pretty(snapshot, ron::ser::PrettyConfig::default().compact_arrays(true))
+        .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+}
+
+pub fn decode(text: &str) -> io::Result<Snapshot> {
+    ron::from_str(text).map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+}
+
+fn load_snapshot(path: &Path) -> io::Result<Snapshot> {
+    decode(&fs::read_to_string(path)?)
+}
+
+/// Everything a snapshot captures from, and restores into, the running app.
+#[derive(SystemParam)]
+pub struct SnapshotTarget<'w, 's> {
+    config: ResMut<'w, SimulationConfig>,
+    stats: ResMut<'w, SimulationStats>,
+    trail: ResMut<'w, TrailBuffer>,
+    history: ResMut<'w, History>,
+    states: Query<'w, 's, &'static mut LorenzState, MainTrajectory>,
+    cameras: Query<'w, 's, (&'static mut OrbitCamera, &'static mut Transform)>,
+}
+
+impl SnapshotTarget<'_, '_> {
+    /// Captures at most `trail_points` of the most recent trail points.
+    pub fn capture(&self, trail_points: usize) -> Option<Snapshot> {
+        let state = self.states.get_single().ok()?;
+        let skip = self.trail.points.len().saturating_sub(trail_points);
+        Some(Snapshot {
+            version: env!("CARGO_PKG_VERSION").to_string(),
+            config: self.config.clone(),
+            state: state.clone(),
+            total_steps: self.stats.total_steps,
+            camera: self.cameras.get_single().ok().map(|(orbit, _)| CameraPose::capture(orbit)),
+            trail: (trail_points > 0).then(|| {
+                self.trail
+                    .points
+                    .iter()
+                    .skip(skip)
+                    .map(|p| SnapshotPoint(p.position.to_array(), p.time, p.speed))
+                    .collect()
+            }),
+        })
+    }
+
+    pub fn restore(&mut self, snapshot: &Snapshot) {
+        *self.config = snapshot.config.clone();
+        for mut state in self.states.iter_mut() {
+            *state = snapshot.state.clone();
+        }
+        if let (Some(pose), Ok((mut orbit, mut transform))) =
+            (&snapshot.camera, self.cameras.get_single_mut())
+        {
+            pose.apply(&mut orbit, &mut transform);
+        }
+        let trail = &mut *self.trail;
+        trail.max_points = self.config.max_trail_points;
+        trail.points = snapshot.trail_points().collect();
+        while trail.points.len() > trail.max_points {
+            trail.points.pop_front();
+        }
+        self.stats.total_steps = snapshot.total_steps;
+        self.stats.point_count = trail.points.len();
+        self.history.rebase(trail, snapshot.total_steps);
+    }
+}
+
+pub fn snapshot_system(
+    mut save_events: EventReader<SaveSnapshotEvent>,
+    mut load_events: EventReader<LoadSnapshotEvent>,
+    mut settings: ResMut<SnapshotSettings>,
+    mut target: SnapshotTarget,
+    mut toasts: ResMut<Toasts>,
+) {
+    if save_events.read().count() > 0 {
+        let trail_points = if settings.include_trail { usize::MAX } else { 0 };
+        let Some(snapshot) = target.capture(trail_points) else {
+            return;
+        };
+        let result = resolve_path(&settings.path, "snapshot", "ron").and_then(|path| {
+            fs::write(&path, encode(&snapshot)?)?;
+            Ok(path)
+        });
+        match result {
+            Ok(path) => {
+                toasts.push(trf("📌 Saved snapshot to {path}", &[("path", &path.display())]));
+                settings.path = path.display().to_string();
+            }
+            Err(err) => toasts.push(trf("Snapshot save failed: {err}", &[("err", &err)])),
+        }
+    }
+
+    if load_events.read().count() > 0 {
+        let snapshot = match load_snapshot(Path::new(settings.path.trim())) {
+            Ok(snapshot) => snapshot,
+            Err(err) => {
+                toasts.push(trf("Snapshot load failed: {err}", &[("err", &err)]));
+                return;
+            }
+        };
+        target.restore(&snapshot);
+        toasts.push(trf(
+            "📌 Restored t = {t} from {path}",
+            &[
+                ("t", &format!("{:.2}", snapshot.state.t)),
+                ("path", &settings.path.trim()),
+            ],
+        ));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_snapshot_round_trip() {
+        let config = SimulationConfig {
+            rho: 99.96,
+            paused: true,
+            ..Default::default()
+        };
+        let mut state = LorenzState::new(-3.5, 1.25, 40.0);
+        state.t = 12.5;
+        let snapshot = Snapshot {
+            version: "1.0.0".to_string(),
+            config,
+            state,
+            total_steps: 2500,
+            camera: Some(CameraPose::capture(&OrbitCamera::default())),
+            trail: Some(vec![SnapshotPoint([1.0, 2.0, 3.0], 12.5, 7.0)]),
+        };
+
+        let loaded = decode(&encode(&snapshot).unwrap()).unwrap();
+        assert_eq!(loaded.config.rho, 99.96);
+        assert!(loaded.config.paused);
+        assert_eq!((loaded.state.x, loaded.state.z, loaded.state.t), (-3.5, 40.0, 12.5));
+        assert_eq!(loaded.total_steps, 2500);
+        assert_eq!(loaded.camera, snapshot.camera);
+        let trail: Vec<TrailPoint> = loaded.trail_points().collect();
+        assert_eq!(trail[0].position, Vec3::new(1.0, 2.0, 3.0));
+        assert_eq!(trail[0].speed, 7.0);
+
+        let partial = decode(concat!(
+            "(version: \"1.0.0\", config: (rho: 15.0), state: (x: 1.0, y: 1.0, z: 1.0, t: 0.0),",
+            " total_steps: 0, camera: None, trail: None)",
+        ))
+        .unwrap();
+        assert_eq!(partial.config.rho, 15.0);
+        assert_eq!(partial.config.sigma, 10.0);
+    }
+}
\ No newline at end of file
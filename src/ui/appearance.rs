@@ -0,0 +1,54 @@
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContexts, EguiSettings};
+
+use crate::config::{Appearance, UiTheme};
+use crate::i18n::set_language;
+
+pub fn visuals_for(appearance: &Appearance) -> egui::Visuals {
+    let mut visuals = match appearance.theme {
+        UiTheme::Dark => egui::Visuals::dark(),
+        UiTheme::Light => egui::Visuals::light(),
+    };
+    let [r, g, b] = appearance.accent;
+    let accent = egui::Color32::from_rgb(r, g, b);
+    visuals.selection.bg_fill = accent;
+    visuals.hyperlink_color = accent;
+    visuals.slider_trailing_fill = true;
+
+    let opacity = appearance.panel_opacity.clamp(0.0, 1.0);
+    visuals.panel_fill = visuals.panel_fill.gamma_multiply(opacity);
+    visuals.window_fill = visuals.window_fill.gamma_multiply(opacity);
+    visuals
+}
+
+pub fn appearance_system(
+    mut contexts: EguiContexts,
+    appearance: Res<Appearance>,
+    mut egui_settings: ResMut<EguiSettings>,
+) {
+    if !appearance.is_changed() {
+        return;
+    }
+    contexts.ctx_mut().set_visuals(visuals_for(&appearance));
+    egui_settings.scale_factor = appearance.ui_scale.clamp(0.5, 3.0);
+    set_language(appearance.language);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_visuals_follow_theme_and_opacity() {
+        let appearance = Appearance {
+            theme: UiTheme::Light,
+            accent: [200, 40, 40],
+            panel_opacity: 0.5,
+            ..default()
+        };
+        let visuals = visuals_for(&appearance);
+        assert!(!visuals.dark_mode);
+        assert_eq!(visuals.selection.bg_fill, egui::Color32::from_rgb(200, 40, 40));
+        assert!(visuals.panel_fill.a() < 200);
+    }
+}
\ No newline at end of file
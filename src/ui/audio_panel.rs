@@ -0,0 +1,104 @@
+use bevy_egui::egui;
+
+use crate::audio::{AudioBand, AudioInputState, AudioSettings, AudioTarget};
+use crate::config::SliderRanges;
+use crate::i18n::{tr, trf};
+
+pub fn audio_panel_ui(
+    ui: &mut egui::Ui,
+    settings: &mut AudioSettings,
+    audio: &mut AudioInputState,
+    ranges: &SliderRanges,
+) {
+    ui.checkbox(&mut settings.enabled, tr("React to audio input"));
+    ui.horizontal(|ui| {
+        ui.label(tr("Device:"));
+        let selected = settings
+            .device
+            .clone()
+            .unwrap_or_else(|| tr("System default").to_string());
+        egui::ComboBox::from_id_source("audio_device")
+            .selected_text(selected)
+            .width(150.0)
+            .show_ui(ui, |ui| {
+                ui.selectable_value(&mut settings.device, None, tr("System default"));
+                for device in &audio.devices {
+                    ui.selectable_value(&mut settings.device, Some(device.clone()), device);
+                }
+            });
+        if ui.button("🔄").on_hover_text(tr("Rescan devices")).clicked() {
+            audio.refresh = true;
+        }
+    });
+
+    if let Some(error) = &audio.error {
+        ui.colored_label(egui::Color32::LIGHT_RED, format!("⚠ {error}"));
+    } else if audio.devices.is_empty() {
+        ui.label(tr("No audio inputs found"));
+    } else if audio.is_listening() {
+        let device = settings
+            .device
+            .clone()
+            .unwrap_or_else(|| tr("System default").to_string());
+        ui.colored_label(
+            egui::Color32::LIGHT_GREEN,
+            trf("Listening to {device}", &[("device", &device)]),
+        );
+    }
+
+    ui.add(
+        egui::Slider::new(&mut settings.sensitivity, 1.0..=200.0)
+            .logarithmic(true)
+            .text(tr("Sensitivity")),
+    );
+    ui.add(egui::Slider::new(&mut settings.smoothing, 0.0..=0.99).text(tr("Smoothing")));
+
+    ui.add_space(4.0);
+    for band in AudioBand::ALL {
+        let level = audio.levels[band as usize];
+        ui.horizontal(|ui| {
+            ui.add_sized([50.0, 14.0], egui::Label::new(tr(band.label())));
+            ui.add(egui::ProgressBar::new(level).desired_width(150.0));
+        });
+    }
+
+    ui.add_space(4.0);
+    egui::Grid::new("audio_routes").num_columns(3).show(ui, |ui| {
+        for target in AudioTarget::ALL {
+            ui.label(tr(target.label()));
+            let mut band = settings.route(target).map(|route| route.band);
+            egui::ComboBox::from_id_source(("audio_band", target as usize))
+                .selected_text(band.map_or_else(|| tr("Off"), |band| tr(band.label())))
+                .width(70.0)
+                .show_ui(ui, |ui| {
+                    ui.selectable_value(&mut band, None, tr("Off"));
+                    for option in AudioBand::ALL {
+                        ui.selectable_value(&mut band, Some(option), tr(option.label()));
+                    }
+                });
+            if band != settings.route(target).map(|route| route.band) {
+                settings.set_band(target, band, ranges);
+            }
+            match settings.routes.iter_mut().find(|route| route.target == target) {
+                Some(route) => {
+                    ui.horizontal(|ui| {
+                        ui.add(egui::DragValue::new(&mut route.range.0).speed(0.1));
+                        ui.label(tr("to"));
+                        ui.add(egui::DragValue::new(&mut route.range.1).speed(0.1));
+                    });
+                }
+                None => {
+                    ui.label("—");
+                }
+            }
+            ui.end_row();
+        }
+    });
+    ui.label(
+        egui::RichText::new(tr(
+            "Each parameter moves from the first value in silence to the second at full level.",
+        ))
+        .small()
+        .color(egui::Color32::GRAY),
+    );
+}
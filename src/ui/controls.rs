@@ -1,98 +1,891 @@
-use bevy::diagnostic::{DiagnosticsStore, FrameTimeDiagnosticsPlugin};
+use bevy::ecs::system::SystemParam;
+use bevy::input::gamepad::Gamepads;
 use bevy::prelude::*;
-use bevy_egui::{egui, EguiContexts};
+use bevy_egui::{egui, EguiClipboard, EguiContexts};
 
-use crate::config::{IntegrationMethod, ResetEvent, SimulationConfig, SimulationStats};
-use crate::rendering::camera_controller::EguiWantsPointer;
+#[cfg(feature = "audio")]
+use crate::audio::{AudioInputState, AudioSettings};
+#[cfg(feature = "export")]
+use crate::capture::frame_sequence::FrameRecorder;
+#[cfg(feature = "export")]
+use crate::capture::gif::GIF_PALETTE_SIZES;
+#[cfg(feature = "export")]
+use crate::capture::still::{StillRenderer, MAX_STILL_DIMENSION, STILL_PRESETS};
+#[cfg(feature = "export")]
+use crate::capture::video::{ClipKind, VideoRecorder};
+use crate::config::{
+    AnaglyphConfig, Appearance, ButterflyDemoEvent, CameraSettings, ComparisonConfig, FitViewEvent,
+    GalleryConfig, GamepadSettings, IntegrationMethod, KeyBindings, LoadReplayEvent,
+    LoadSettingsEvent, LoadSnapshotEvent, OrthoViewsConfig, PadButton, PlaySessionEvent,
+    RandomizeEvent, RandomizeSettings, RecordSessionEvent, ResetEvent, ResetViewEvent, RotationMode,
+    SaveSettingsEvent, SaveSnapshotEvent, ShortcutAction, SimulationConfig, SimulationStats,
+    SliderRanges, SnapshotSettings, TrajectoryEvent, UiTheme, UiVisibility,
+};
+#[cfg(feature = "export")]
+use crate::config::{
+    BifurcationSettings, CaptureSettings, ExportBifurcationEvent, ExportManifestEvent,
+    ExportPoincareEvent, ExportSettings, ExportStatsEvent, ExportSymbolsEvent,
+    ExportTrajectoryEvent, LogFormat, LogSettings, RecordEvent, RecordGifEvent, RecordVideoEvent,
+    RenderStillEvent, ScreenshotEvent, TrajectoryFormat, VideoFormat, VideoResolution,
+};
+#[cfg(feature = "analysis")]
+use crate::config::{
+    ComplexityConfig, DensityConfig, EmbeddingConfig, HistogramConfig, PhasePortraitConfig,
+    SpectrumConfig, TimeSeriesConfig,
+};
+use crate::display::{DisplayMode, DisplaySettings, SyncMode, FPS_CAPS, RESOLUTION_PRESETS};
+#[cfg(feature = "export")]
+use crate::export::bifurcation::BifurcationExport;
+#[cfg(feature = "export")]
+use crate::export::logger::StateLog;
+use crate::i18n::{tr, trf, Language};
+use crate::invariants::InvariantChecks;
+#[cfg(feature = "midi")]
+use crate::midi::{MidiInputState, MidiMappings};
+use crate::osc::{OscOutput, OscSettings, MAX_RATE, MIN_RATE};
+use crate::presets::{
+    ApplyPresetEvent, Preset, Presets, UserPresets, NOTABLE_BETA, NOTABLE_RHO, NOTABLE_SIGMA,
+};
+use crate::rendering::annotations::{Annotation, Annotations};
+use crate::rendering::camera_animation::CameraPath;
+use crate::rendering::camera_controller::{EguiWantsKeyboard, EguiWantsPointer, OrbitCamera};
+use crate::rendering::placement::{Placement, PlacementPlane, PlacementTarget};
+use crate::rendering::view_scale::ViewScale;
+#[cfg(feature = "rerun")]
+use crate::rerun_log::{RerunLog, RerunSettings, RerunSink};
+#[cfg(feature = "scripting")]
+use crate::scripting::{ScriptEvent, ScriptHost, ScriptSettings};
+use crate::session::Session;
+use crate::share::{page_base, ParamSet, UrlParams};
+#[cfg(feature = "analysis")]
+use crate::simulation::butterfly::Separation;
+#[cfg(feature = "analysis")]
+use crate::simulation::complexity::Complexity;
+#[cfg(feature = "analysis")]
+use crate::simulation::density::DensityMap;
+use crate::simulation::history::History;
+#[cfg(feature = "analysis")]
+use crate::simulation::integrator::TrailBuffer;
+use crate::simulation::lorenz::{fixed_points, LorenzParams, LorenzState};
+use crate::simulation::replay::Replay;
+#[cfg(feature = "analysis")]
+use crate::simulation::spectrum::SpectrumSampler;
+#[cfg(feature = "export")]
+use crate::simulation::statistics::StatsHistory;
+use crate::simulation::statistics::TrajectoryStats;
+use crate::simulation::trajectories::{ExtraTrajectory, MainTrajectory, Trajectories};
+#[cfg(feature = "audio")]
+use crate::ui::audio_panel::audio_panel_ui;
+use crate::ui::file_dialog::{
+    self, browse_button, DialogPurpose, DialogRequest, FileDialogSettings, FileDialogs,
+};
+use crate::ui::help::{HelpTopic, HoverHelp};
+use crate::ui::layout::{panel_section, PanelLayout, PanelSection, PanelSide};
+#[cfg(feature = "midi")]
+use crate::ui::midi_learn::midi_learn_ui;
+use crate::ui::performance::{invariants_ui, performance_ui, PerformanceHistory};
+#[cfg(feature = "analysis")]
+use crate::ui::plots::{
+    complexity_ui, density_ui, embedding_ui, histogram_ui, lobe_switches_ui, phase_portrait_ui,
+    separation_ui, spectrum_ui, time_series_ui, DensityTexture,
+};
+#[cfg(feature = "scripting")]
+use crate::ui::script_editor::script_editor_ui;
+use crate::ui::shortcuts::{key_label, KeyRebind};
+use crate::ui::toast::Toasts;
+use crate::ui::tutorial::Tutorial;
+use crate::ui::widgets::{expand_to_fit, parse_number, ParamSlider};
+use crate::undo::EditHistory;
+use crate::view_sync::{self, SyncRole, ViewSync, ViewSyncSettings};
+#[cfg(feature = "websocket")]
+use crate::websocket::{self, WebSocketServer, WebSocketSettings};
 
+const UI_SCALE_STEP: f32 = 0.1;
+const COLLAPSED_PANEL_WIDTH: f32 = 28.0;
+/// The web build can copy to the clipboard but not read it back.
+const CAN_PASTE: bool = cfg!(not(target_arch = "wasm32"));
+
+#[derive(SystemParam)]
+pub struct UiEvents<'w> {
+    reset: EventWriter<'w, ResetEvent>,
+    fit_view: EventWriter<'w, FitViewEvent>,
+    reset_view: EventWriter<'w, ResetViewEvent>,
+    load_replay: EventWriter<'w, LoadReplayEvent>,
+    record_session: EventWriter<'w, RecordSessionEvent>,
+    play_session: EventWriter<'w, PlaySessionEvent>,
+    save_snapshot: EventWriter<'w, SaveSnapshotEvent>,
+    load_snapshot: EventWriter<'w, LoadSnapshotEvent>,
+    save_settings: EventWriter<'w, SaveSettingsEvent>,
+    load_settings: EventWriter<'w, LoadSettingsEvent>,
+    randomize: EventWriter<'w, RandomizeEvent>,
+    apply_preset: EventWriter<'w, ApplyPresetEvent>,
+    trajectory: EventWriter<'w, TrajectoryEvent>,
+    butterfly: EventWriter<'w, ButterflyDemoEvent>,
+}
+
+#[cfg(feature = "export")]
+#[derive(SystemParam)]
+pub struct UiCaptureEvents<'w> {
+    screenshot: EventWriter<'w, ScreenshotEvent>,
+    record: EventWriter<'w, RecordEvent>,
+    record_video: EventWriter<'w, RecordVideoEvent>,
+    record_gif: EventWriter<'w, RecordGifEvent>,
+    render_still: EventWriter<'w, RenderStillEvent>,
+    export_trajectory: EventWriter<'w, ExportTrajectoryEvent>,
+    export_poincare: EventWriter<'w, ExportPoincareEvent>,
+    export_symbols: EventWriter<'w, ExportSymbolsEvent>,
+    export_stats: EventWriter<'w, ExportStatsEvent>,
+    export_bifurcation: EventWriter<'w, ExportBifurcationEvent>,
+    export_manifest: EventWriter<'w, ExportManifestEvent>,
+}
+
+#[cfg(feature = "export")]
+#[derive(SystemParam)]
+pub struct UiCapture<'w> {
+    settings: ResMut<'w, CaptureSettings>,
+    recorder: Res<'w, FrameRecorder>,
+    video: Res<'w, VideoRecorder>,
+    still: Res<'w, StillRenderer>,
+    export: ResMut<'w, ExportSettings>,
+    bifurcation: ResMut<'w, BifurcationSettings>,
+    bifurcation_export: Res<'w, BifurcationExport>,
+    log: ResMut<'w, LogSettings>,
+    state_log: Res<'w, StateLog>,
+    stats_history: Res<'w, StatsHistory>,
+    events: UiCaptureEvents<'w>,
+}
+
+#[derive(SystemParam)]
+pub struct UiPlayback<'w> {
+    replay: ResMut<'w, Replay>,
+    session: ResMut<'w, Session>,
+    history: ResMut<'w, History>,
+    snapshot: ResMut<'w, SnapshotSettings>,
+    visibility: ResMut<'w, UiVisibility>,
+    dialogs: ResMut<'w, FileDialogs>,
+    dialog_settings: ResMut<'w, FileDialogSettings>,
+}
+
+#[derive(SystemParam)]
+pub struct UiFocus<'w> {
+    pointer: ResMut<'w, EguiWantsPointer>,
+    keyboard: ResMut<'w, EguiWantsKeyboard>,
+}
+
+#[derive(SystemParam)]
+pub struct UiOutputs<'w> {
+    osc: ResMut<'w, OscSettings>,
+    osc_output: Res<'w, OscOutput>,
+    sync: ResMut<'w, ViewSyncSettings>,
+    view_sync: Res<'w, ViewSync>,
+}
+
+#[derive(SystemParam)]
+pub struct UiInputSettings<'w> {
+    camera: ResMut<'w, CameraSettings>,
+    gamepad: ResMut<'w, GamepadSettings>,
+    gamepads: Res<'w, Gamepads>,
+    bindings: ResMut<'w, KeyBindings>,
+    rebind: ResMut<'w, KeyRebind>,
+}
+
+#[derive(SystemParam)]
+pub struct UiParameterTools<'w> {
+    library: ResMut<'w, Presets>,
+    user: ResMut<'w, UserPresets>,
+    randomize: ResMut<'w, RandomizeSettings>,
+    edits: ResMut<'w, EditHistory>,
+    ranges: ResMut<'w, SliderRanges>,
+    clipboard: ResMut<'w, EguiClipboard>,
+    toasts: ResMut<'w, Toasts>,
+}
+
+#[derive(SystemParam)]
+pub struct UiLayout<'w> {
+    appearance: ResMut<'w, Appearance>,
+    layout: ResMut<'w, PanelLayout>,
+    display: ResMut<'w, DisplaySettings>,
+    tutorial: ResMut<'w, Tutorial>,
+    performance: Res<'w, PerformanceHistory>,
+    invariants: ResMut<'w, InvariantChecks>,
+    focus: UiFocus<'w>,
+}
+
+#[derive(SystemParam)]
+pub struct UiViewModes<'w> {
+    comparison: ResMut<'w, ComparisonConfig>,
+    ortho_views: ResMut<'w, OrthoViewsConfig>,
+    gallery: ResMut<'w, GalleryConfig>,
+    anaglyph: ResMut<'w, AnaglyphConfig>,
+    view_scale: ResMut<'w, ViewScale>,
+}
+
+#[derive(SystemParam)]
+pub struct UiScene<'w, 's> {
+    orbit: Query<'w, 's, &'static OrbitCamera>,
+    state: Query<'w, 's, &'static LorenzState, MainTrajectory>,
+    extras: Query<'w, 's, (Entity, &'static mut ExtraTrajectory, &'static mut SimulationStats)>,
+    trajectories: ResMut<'w, Trajectories>,
+    placement: ResMut<'w, Placement>,
+    annotations: ResMut<'w, Annotations>,
+    camera_path: ResMut<'w, CameraPath>,
+    stats: ResMut<'w, SimulationStats>,
+    view_modes: UiViewModes<'w>,
+}
+
+#[cfg(feature = "scripting")]
+#[derive(SystemParam)]
+pub struct UiScript<'w> {
+    settings: ResMut<'w, ScriptSettings>,
+    host: Res<'w, ScriptHost>,
+    events: EventWriter<'w, ScriptEvent>,
+}
+
+#[cfg(feature = "midi")]
+#[derive(SystemParam)]
+pub struct UiMidi<'w> {
+    mappings: ResMut<'w, MidiMappings>,
+    input: ResMut<'w, MidiInputState>,
+}
+
+#[cfg(feature = "audio")]
+#[derive(SystemParam)]
+pub struct UiAudio<'w> {
+    settings: ResMut<'w, AudioSettings>,
+    input: ResMut<'w, AudioInputState>,
+}
+
+#[cfg(feature = "websocket")]
+#[derive(SystemParam)]
+pub struct UiWebSocket<'w> {
+    settings: ResMut<'w, WebSocketSettings>,
+    server: Res<'w, WebSocketServer>,
+}
+
+#[cfg(feature = "rerun")]
+#[derive(SystemParam)]
+pub struct UiRerun<'w> {
+    settings: ResMut<'w, RerunSettings>,
+    log: Res<'w, RerunLog>,
+}
+
+#[cfg(feature = "analysis")]
+#[derive(SystemParam)]
+pub struct UiPlots<'w> {
+    trail: Res<'w, TrailBuffer>,
+    time_series: ResMut<'w, TimeSeriesConfig>,
+    phase_portrait: ResMut<'w, PhasePortraitConfig>,
+    spectrum_sampler: Res<'w, SpectrumSampler>,
+    spectrum: ResMut<'w, SpectrumConfig>,
+    embedding: ResMut<'w, EmbeddingConfig>,
+    complexity: Res<'w, Complexity>,
+    complexity_config: ResMut<'w, ComplexityConfig>,
+    histograms: ResMut<'w, HistogramConfig>,
+    density: ResMut<'w, DensityMap>,
+    density_config: ResMut<'w, DensityConfig>,
+    density_texture: ResMut<'w, DensityTexture>,
+    separation: Res<'w, Separation>,
+}
+
+#[allow(clippy::too_many_arguments)]
 pub fn ui_system(
     mut contexts: EguiContexts,
     mut config: ResMut<SimulationConfig>,
-    stats: Res<SimulationStats>,
-    diagnostics: Res<DiagnosticsStore>,
-    mut reset_events: EventWriter<ResetEvent>,
-    mut egui_wants: ResMut<EguiWantsPointer>,
+    input_settings: UiInputSettings,
+    mut events: UiEvents,
+    #[cfg(feature = "export")]
+    capture: UiCapture,
+    playback: UiPlayback,
+    outputs: UiOutputs,
+    parameter_tools: UiParameterTools,
+    ui_layout: UiLayout,
+    #[cfg(feature = "analysis")]
+    plots: UiPlots,
+    scene: UiScene,
+    #[cfg(feature = "scripting")]
+    script: UiScript,
+    #[cfg(feature = "midi")]
+    midi: UiMidi,
+    #[cfg(feature = "audio")]
+    audio: UiAudio,
+    #[cfg(feature = "websocket")]
+    websocket: UiWebSocket,
+    #[cfg(feature = "rerun")]
+    rerun: UiRerun,
 ) {
+    #[cfg(feature = "export")]
+    let UiCapture {
+        settings: mut capture_settings,
+        recorder,
+        video,
+        still,
+        mut export,
+        mut bifurcation,
+        bifurcation_export,
+        mut log,
+        state_log,
+        stats_history,
+        events: mut capture_events,
+    } = capture;
+    let UiPlayback {
+        mut replay,
+        mut session,
+        mut history,
+        mut snapshot,
+        visibility: mut ui_visibility,
+        mut dialogs,
+        mut dialog_settings,
+    } = playback;
+    let UiOutputs {
+        mut osc,
+        osc_output,
+        mut sync,
+        view_sync,
+    } = outputs;
+    let UiInputSettings {
+        camera: mut camera_settings,
+        gamepad: mut gamepad_settings,
+        gamepads,
+        mut bindings,
+        mut rebind,
+    } = input_settings;
+    let UiParameterTools {
+        library: mut presets,
+        user: mut user_presets,
+        mut randomize,
+        mut edits,
+        ranges: mut slider_ranges,
+        mut clipboard,
+        mut toasts,
+    } = parameter_tools;
+    let UiLayout {
+        mut appearance,
+        mut layout,
+        mut display,
+        mut tutorial,
+        performance,
+        mut invariants,
+        focus,
+    } = ui_layout;
+    #[cfg(feature = "analysis")]
+    let UiPlots {
+        trail,
+        mut time_series,
+        mut phase_portrait,
+        spectrum_sampler,
+        mut spectrum,
+        mut embedding,
+        complexity,
+        mut complexity_config,
+        mut histograms,
+        mut density,
+        mut density_config,
+        mut density_texture,
+        separation,
+    } = plots;
+    let UiScene {
+        orbit: orbit_query,
+        state: state_query,
+        extras: mut extra_query,
+        mut trajectories,
+        mut placement,
+        mut annotations,
+        mut camera_path,
+        mut stats,
+        view_modes,
+    } = scene;
+    let UiViewModes {
+        mut comparison,
+        mut ortho_views,
+        mut gallery,
+        mut anaglyph,
+        mut view_scale,
+    } = view_modes;
+    #[cfg(feature = "scripting")]
+    let UiScript {
+        settings: mut script_settings,
+        host: script_host,
+        events: mut script_events,
+    } = script;
+    #[cfg(feature = "midi")]
+    let UiMidi {
+        mappings: mut midi_mappings,
+        input: mut midi_input,
+    } = midi;
+    #[cfg(feature = "audio")]
+    let UiAudio {
+        settings: mut audio_settings,
+        input: mut audio_input,
+    } = audio;
+    #[cfg(feature = "websocket")]
+    let UiWebSocket {
+        settings: mut websocket_settings,
+        server: websocket_server,
+    } = websocket;
+    #[cfg(feature = "rerun")]
+    let UiRerun {
+        settings: mut rerun_settings,
+        log: rerun_log,
+    } = rerun;
+    if let Some((purpose, path)) = dialogs.poll() {
+        dialog_settings.remember(&path);
+        let chosen = path.display().to_string();
+        match purpose {
+            #[cfg(feature = "export")]
+            DialogPurpose::ExportTrail => {
+                export.path = chosen;
+                capture_events.export_trajectory.send(ExportTrajectoryEvent);
+            }
+            #[cfg(feature = "export")]
+            DialogPurpose::StateLog => log.path = chosen,
+            #[cfg(feature = "rerun")]
+            DialogPurpose::RerunRecording => rerun_settings.path = chosen,
+            DialogPurpose::Replay => {
+                replay.path = chosen;
+                events.load_replay.send(LoadReplayEvent);
+            }
+            DialogPurpose::Session => {
+                session.path = chosen;
+                events.play_session.send(PlaySessionEvent);
+            }
+            DialogPurpose::SaveSnapshot => {
+                snapshot.path = chosen;
+                events.save_snapshot.send(SaveSnapshotEvent);
+            }
+            DialogPurpose::LoadSnapshot => {
+                snapshot.path = chosen;
+                events.load_snapshot.send(LoadSnapshotEvent);
+            }
+            #[cfg(feature = "export")]
+            DialogPurpose::Screenshot => {
+                capture_events.screenshot.send(ScreenshotEvent { path: Some(path) });
+            }
+            #[cfg(feature = "export")]
+            DialogPurpose::RenderStill => {
+                capture_events.render_still.send(RenderStillEvent { path: Some(path) });
+            }
+            #[cfg(feature = "export")]
+            DialogPurpose::ExportStats => {
+                capture_events.export_stats.send(ExportStatsEvent { path: Some(path) });
+            }
+            #[cfg(feature = "export")]
+            DialogPurpose::ExportManifest => {
+                capture_events.export_manifest.send(ExportManifestEvent { path: Some(path) });
+            }
+            #[cfg(feature = "export")]
+            DialogPurpose::ExportPoincare => {
+                capture_events.export_poincare.send(ExportPoincareEvent { path: Some(path) });
+            }
+            #[cfg(feature = "export")]
+            DialogPurpose::ExportSymbols => {
+                capture_events.export_symbols.send(ExportSymbolsEvent { path: Some(path) });
+            }
+            #[cfg(feature = "export")]
+            DialogPurpose::ExportBifurcation => {
+                let event = ExportBifurcationEvent { path: Some(path) };
+                capture_events.export_bifurcation.send(event);
+            }
+            DialogPurpose::RecordSession => {
+                events.record_session.send(RecordSessionEvent { path: Some(path) });
+            }
+        }
+    }
+
     let ctx = contexts.ctx_mut();
 
-    egui_wants.0 = ctx.is_pointer_over_area();
+    let UiFocus {
+        mut pointer,
+        mut keyboard,
+    } = focus;
+    pointer.0 = ctx.is_pointer_over_area();
+    keyboard.0 = ctx.wants_keyboard_input();
+
+    if ui_visibility.hidden || ui_visibility.presentation {
+        return;
+    }
 
     let mut style = (*ctx.style()).clone();
     style.spacing.slider_width = 180.0;
     ctx.set_style(style);
 
-    egui::SidePanel::left("control_panel")
+    if layout.collapsed {
+        egui::SidePanel::new(layout.side.egui_side(), "control_strip")
+            .exact_width(COLLAPSED_PANEL_WIDTH)
+            .resizable(false)
+            .show(ctx, |ui| {
+                if ui.button("☰").on_hover_text(tr("Show control panel")).clicked() {
+                    layout.collapsed = false;
+                }
+            });
+        return;
+    }
+
+    let mut reset_layout = false;
+    egui::SidePanel::new(layout.side.egui_side(), "control_panel")
         .default_width(300.0)
         .resizable(true)
         .show(ctx, |ui| {
-            ui.heading("🦋 Lorenz Attractor");
+            ui.horizontal(|ui| {
+                ui.heading(tr("🦋 Lorenz Attractor"));
+                ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                    if ui.button("☰").on_hover_text(tr("Collapse to the edge")).clicked() {
+                        layout.collapsed = true;
+                    }
+                    if ui.button("🎓").on_hover_text(tr("Start the guided tour")).clicked() {
+                        tutorial.start();
+                    }
+                    let hint = trf(
+                        "Presentation mode: hide all UI ({key})",
+                        &[("key", &key_label(bindings.toggle_ui))],
+                    );
+                    if ui.button("⛶").on_hover_text(hint).clicked() {
+                        ui_visibility.presentation = true;
+                    }
+                    let (arrow, hint) = match layout.side {
+                        PanelSide::Left => ("➡", tr("Move panel to the right")),
+                        PanelSide::Right => ("⬅", tr("Move panel to the left")),
+                    };
+                    if ui.button(arrow).on_hover_text(hint).clicked() {
+                        layout.side = layout.side.flipped();
+                    }
+                });
+            });
             ui.separator();
 
-            ui.collapsing("🔬 Lorenz Parameters", |ui| {
-                ui.label("Canonical values: σ=10, ρ=28, β≈2.667");
+            if let Ok(state) = state_query.get_single() {
+                state_inspector(ui, state, &mut config);
+                ui.separator();
+            }
+
+            let demo =
+                egui::Button::new(egui::RichText::new(tr("⚡ Butterfly effect demo")).strong());
+            if ui
+                .add_sized([ui.available_width(), 28.0], demo)
+                .on_hover_text(tr(
+                    "Reset and start two trajectories 1e−8 apart, then plot how fast they separate",
+                ))
+                .clicked()
+            {
+                events.butterfly.send(ButterflyDemoEvent);
+            }
+            ui.add_space(4.0);
+
+            panel_section(ui, &mut layout, PanelSection::Parameters, |ui| {
+                ui.label(tr("Canonical values: σ=10, ρ=28, β=8/3"));
                 ui.add_space(4.0);
 
+                ab_tabs(ui, &mut comparison);
+                let (sigma, rho, beta) = if comparison.enabled && comparison.editing_b {
+                    let b = &mut *comparison;
+                    (&mut b.sigma, &mut b.rho, &mut b.beta)
+                } else {
+                    let a = &mut *config;
+                    (&mut a.sigma, &mut a.rho, &mut a.beta)
+                };
+
+                let ranges = &mut *slider_ranges;
+                if ranges.auto_expand {
+                    expand_to_fit(&mut ranges.sigma, *sigma);
+                    expand_to_fit(&mut ranges.rho, *rho);
+                    expand_to_fit(&mut ranges.beta, *beta);
+                }
+                let (s, r, b) = (ranges.sigma, ranges.rho, ranges.beta);
+                let notable = |values: &'static [f64]| {
+                    if ranges.snap_to_notable {
+                        values
+                    } else {
+                        &[]
+                    }
+                };
                 ui.add(
-                    egui::Slider::new(&mut config.sigma, 0.1..=30.0)
-                        .text("σ (sigma)")
-                        .clamp_to_range(true),
-                );
+                    ParamSlider::new(sigma, s.0..=s.1, tr("σ (sigma)"))
+                        .positive()
+                        .snap_to(notable(&NOTABLE_SIGMA)),
+                )
+                .on_hover_help(HelpTopic::Sigma);
                 ui.add(
-                    egui::Slider::new(&mut config.rho, 0.1..=50.0)
-                        .text("ρ (rho)")
-                        .clamp_to_range(true),
-                );
+                    ParamSlider::new(rho, r.0..=r.1, tr("ρ (rho)"))
+                        .positive()
+                        .snap_to(notable(&NOTABLE_RHO)),
+                )
+                .on_hover_help(HelpTopic::Rho);
                 ui.add(
-                    egui::Slider::new(&mut config.beta, 0.1..=10.0)
-                        .text("β (beta)")
-                        .clamp_to_range(true),
-                );
+                    ParamSlider::new(beta, b.0..=b.1, tr("β (beta)"))
+                        .positive()
+                        .snap_to(notable(&NOTABLE_BETA)),
+                )
+                .on_hover_help(HelpTopic::Beta);
+
+                ui.horizontal(|ui| {
+                    if ui.button(tr("Reset to canonical")).clicked() {
+                        *sigma = 10.0;
+                        *rho = 28.0;
+                        *beta = 8.0 / 3.0;
+                    }
+                    if ui
+                        .button(tr("🎲 Surprise me"))
+                        .on_hover_text(tr("Random σ, ρ, β that don't settle onto a fixed point"))
+                        .clicked()
+                    {
+                        events.randomize.send(RandomizeEvent);
+                    }
+                });
+                ui.horizontal(|ui| {
+                    if ui
+                        .button(tr("📋 Copy params"))
+                        .on_hover_text(tr(
+                            "σ, ρ, β, dt, method, and x₀, y₀, z₀ as one line of JSON, \
+                             to share in a chat or an issue",
+                        ))
+                        .clicked()
+                    {
+                        let json = ParamSet::current(&config).to_json();
+                        ui.output_mut(|o| o.copied_text = json);
+                    }
+                    if CAN_PASTE
+                        && ui
+                            .button(tr("📥 Paste params"))
+                            .on_hover_text(tr(
+                                "Apply copied parameters and restart from their initial condition",
+                            ))
+                            .clicked()
+                    {
+                        let pasted = clipboard_text(&mut clipboard)
+                            .ok_or_else(|| tr("The clipboard is empty").to_string())
+                            .and_then(|text| ParamSet::parse(&text));
+                        match pasted {
+                            Ok(set) => {
+                                set.apply(&mut config);
+                                events.reset.send(ResetEvent);
+                                toasts.push(tr("📥 Pasted parameters"));
+                            }
+                            Err(error) => toasts.push(format!("⚠ {error}")),
+                        }
+                    }
+                });
+                ui.collapsing(tr("Slider ranges"), |ui| {
+                    slider_ranges_editor(ui, &mut slider_ranges);
+                });
+                ui.collapsing(tr("Random ranges"), |ui| {
+                    let ranges = &mut *randomize;
+                    for (label, range, max) in [
+                        ("σ", &mut ranges.sigma, 50.0),
+                        ("ρ", &mut ranges.rho, 400.0),
+                        ("β", &mut ranges.beta, 10.0),
+                    ] {
+                        ui.horizontal(|ui| {
+                            ui.label(label);
+                            let (low, high) = *range;
+                            ui.add(
+                                egui::DragValue::new(&mut range.0)
+                                    .speed(0.1)
+                                    .range(0.01..=high),
+                            );
+                            ui.label(tr("to"));
+                            ui.add(egui::DragValue::new(&mut range.1).speed(0.1).range(low..=max));
+                        });
+                    }
+                    ui.checkbox(
+                        &mut randomize.initial_conditions,
+                        tr("Also randomize x₀, y₀, z₀"),
+                    );
+                    if randomize.initial_conditions {
+                        ui.add(
+                            egui::Slider::new(&mut randomize.initial_extent, 1.0..=50.0)
+                                .text(tr("Within ±")),
+                        );
+                    }
+                });
+
+                ui.add_space(4.0);
+                let active = presets.active.as_ref().map(|p| p.name.as_str());
+                egui::ComboBox::from_label(tr("Preset"))
+                    .selected_text(active.map_or(tr("Choose…"), tr))
+                    .width(200.0)
+                    .show_ui(ui, |ui| {
+                        for preset in presets.builtin.iter().chain(&user_presets.presets) {
+                            let selected = active == Some(preset.name.as_str());
+                            if ui
+                                .selectable_label(selected, tr(&preset.name))
+                                .on_hover_text(tr(&preset.description))
+                                .clicked()
+                            {
+                                events.apply_preset.send(ApplyPresetEvent(preset.clone()));
+                            }
+                        }
+                    });
+                ui.add(
+                    egui::Slider::new(&mut presets.morph_seconds, 0.0..=30.0)
+                        .text(tr("Morph time"))
+                        .suffix(" s"),
+                )
+                .on_hover_text(tr("Glide σ, ρ, β, dt, and the camera to the preset; 0 = instant"));
+                if let Some(morph) = presets.morph.as_ref() {
+                    let progress = morph.progress();
+                    let label = format!("→ {}", tr(&morph.to.name));
+                    ui.horizontal(|ui| {
+                        ui.add(egui::ProgressBar::new(progress).text(label).desired_width(200.0));
+                        if ui.small_button("⏹").on_hover_text(tr("Stop morphing")).clicked() {
+                            presets.morph = None;
+                        }
+                    });
+                } else if let Some(preset) = presets.active.as_ref() {
+                    ui.label(
+                        egui::RichText::new(format!(
+                            "{} (σ = {}, ρ = {}, β = {:.4}, dt = {})",
+                            tr(&preset.description),
+                            preset.sigma,
+                            preset.rho,
+                            preset.beta,
+                            preset.dt
+                        ))
+                        .small()
+                        .color(egui::Color32::GRAY),
+                    );
+                }
+
+                ui.collapsing(tr("My presets"), |ui| {
+                    ui.horizontal(|ui| {
+                        ui.label(tr("Name:"));
+                        ui.text_edit_singleline(&mut presets.new_name);
+                    });
+                    ui.horizontal(|ui| {
+                        ui.label(tr("Notes:"));
+                        ui.text_edit_singleline(&mut presets.new_description);
+                    });
+                    let name = presets.new_name.trim().to_string();
+                    let save = ui.add_enabled(
+                        !name.is_empty(),
+                        egui::Button::new(tr("💾 Save current parameters and view")),
+                    );
+                    if let (true, Ok(orbit)) = (save.clicked(), orbit_query.get_single()) {
+                        let description = presets.new_description.trim().to_string();
+                        user_presets.presets.retain(|p| p.name != name);
+                        user_presets
+                            .presets
+                            .push(Preset::capture(&name, &description, &config, orbit));
+                        presets.new_name.clear();
+                        presets.new_description.clear();
+                    }
+
+                    let mut removed = None;
+                    for (index, preset) in user_presets.presets.iter().enumerate() {
+                        ui.horizontal(|ui| {
+                            ui.label(preset.name.as_str());
+                            if ui.small_button("🗑").on_hover_text(tr("Delete preset")).clicked() {
+                                removed = Some(index);
+                            }
+                        });
+                    }
+                    if let Some(index) = removed {
+                        user_presets.presets.remove(index);
+                    }
+                });
 
-                if ui.button("Reset to canonical").clicked() {
-                    config.sigma = 10.0;
-                    config.rho = 28.0;
-                    config.beta = 8.0 / 3.0;
+                if let (Some(base), Ok(orbit)) = (page_base(), orbit_query.get_single()) {
+                    let share = ui
+                        .button(tr("🔗 Copy shareable link"))
+                        .on_hover_text(tr(
+                            "A link that opens this page with the current parameters, method, \
+                             dt, and camera view",
+                        ));
+                    if share.clicked() {
+                        let query = UrlParams::current(&config, orbit).to_query();
+                        ui.output_mut(|o| o.copied_text = format!("{base}?{query}"));
+                    }
+                }
+            });
+
+            ui.add_space(8.0);
+
+            panel_section(ui, &mut layout, PanelSection::EditHistory, |ui| {
+                ui.horizontal(|ui| {
+                    if ui
+                        .add_enabled(edits.can_undo(), egui::Button::new(tr("↩ Undo")))
+                        .on_hover_text("Ctrl+Z")
+                        .clicked()
+                    {
+                        edits.undo(&mut config);
+                    }
+                    if ui
+                        .add_enabled(edits.can_redo(), egui::Button::new(tr("↪ Redo")))
+                        .on_hover_text("Ctrl+Y / Ctrl+Shift+Z")
+                        .clicked()
+                    {
+                        edits.redo(&mut config);
+                    }
+                });
+                let mut undo_count = 0;
+                egui::ScrollArea::vertical()
+                    .max_height(140.0)
+                    .show(ui, |ui| {
+                        for (index, edit) in edits.edits().enumerate() {
+                            if ui
+                                .selectable_label(false, edit.label.as_str())
+                                .on_hover_text(tr("Go back to before this edit"))
+                                .clicked()
+                            {
+                                undo_count = index + 1;
+                            }
+                        }
+                    });
+                for _ in 0..undo_count {
+                    edits.undo(&mut config);
+                }
+                if !edits.can_undo() {
+                    ui.label(
+                        egui::RichText::new(tr("No parameter edits yet"))
+                            .small()
+                            .color(egui::Color32::GRAY),
+                    );
                 }
             });
 
             ui.add_space(8.0);
 
-            ui.collapsing("⚙️ Integration", |ui| {
+            panel_section(ui, &mut layout, PanelSection::Integration, |ui| {
+                if slider_ranges.auto_expand {
+                    expand_to_fit(&mut slider_ranges.dt, config.dt);
+                }
+                let dt_range = slider_ranges.dt.0..=slider_ranges.dt.1;
                 ui.add(
-                    egui::Slider::new(&mut config.dt, 0.0001..=0.05)
-                        .text("dt (time step)")
+                    ParamSlider::new(&mut config.dt, dt_range, tr("dt (time step)"))
                         .logarithmic(true)
-                        .clamp_to_range(true),
-                );
+                        .positive(),
+                )
+                .on_hover_help(HelpTopic::TimeStep);
 
                 ui.add(
                     egui::Slider::new(&mut config.steps_per_frame, 1..=50)
-                        .text("Steps / frame")
+                        .text(tr("Steps / frame"))
                         .clamp_to_range(true),
-                );
+                )
+                .on_hover_help(HelpTopic::StepsPerFrame);
 
                 ui.add_space(4.0);
-                ui.label("Integration method:");
+                ui.label(tr("Integration method:"));
 
                 ui.radio_value(
                     &mut config.method,
                     IntegrationMethod::RungeKutta4,
-                    IntegrationMethod::RungeKutta4.label(),
-                );
+                    tr(IntegrationMethod::RungeKutta4.label()),
+                )
+                .on_hover_help(HelpTopic::RungeKutta4);
                 ui.radio_value(
                     &mut config.method,
                     IntegrationMethod::Euler,
-                    IntegrationMethod::Euler.label(),
-                );
+                    tr(IntegrationMethod::Euler.label()),
+                )
+                .on_hover_help(HelpTopic::Euler);
 
                 ui.add_space(4.0);
                 ui.label(
                     egui::RichText::new(match config.method {
                         IntegrationMethod::Euler => {
-                            "⚠ Euler: O(dt) error. Expect drift at large dt."
+                            tr("⚠ Euler: O(dt) error. Expect drift at large dt.")
                         }
                         IntegrationMethod::RungeKutta4 => {
-                            "✓ RK4: O(dt⁴) error. Recommended for accuracy."
+                            tr("✓ RK4: O(dt⁴) error. Recommended for accuracy.")
                         }
                     })
                     .small()
@@ -105,78 +898,224 @@ pub fn ui_system(
 
             ui.add_space(8.0);
 
-            ui.collapsing("🎨 Trail", |ui| {
+            panel_section(ui, &mut layout, PanelSection::Trail, |ui| {
                 let mut max_k = config.max_trail_points as f64 / 1000.0;
                 ui.add(
                     egui::Slider::new(&mut max_k, 1.0..=100.0)
-                        .text("Max points (×1000)")
+                        .text(tr("Max points (×1000)"))
                         .clamp_to_range(true),
                 );
                 config.max_trail_points = (max_k * 1000.0) as usize;
 
-                ui.label(format!("Active points: {}", stats.point_count));
-                ui.label(format!(
-                    "Memory: ~{:.1} KB",
-                    stats.point_count as f64 * 32.0 / 1024.0
-                ));
+                ui.label(trf("Active points: {count}", &[("count", &stats.point_count)]));
+                let kilobytes = format!("{:.1}", stats.point_count as f64 * 32.0 / 1024.0);
+                ui.label(trf("Memory: ~{kb} KB", &[("kb", &kilobytes)]));
             });
 
             ui.add_space(8.0);
 
             ui.horizontal(|ui| {
                 if ui
-                    .button(if config.paused { "▶ Play" } else { "⏸ Pause" })
+                    .button(if config.paused { tr("▶ Play") } else { tr("⏸ Pause") })
                     .clicked()
                 {
                     config.paused = !config.paused;
                 }
-                if ui.button("🔄 Reset").clicked() {
-                    reset_events.send(ResetEvent);
+                if ui.button(tr("🔄 Reset")).clicked() {
+                    events.reset.send(ResetEvent);
                 }
             });
 
+            if config.paused && !history.is_empty() && !replay.is_active() {
+                let last = history.len() - 1;
+                let mut index = history.cursor.unwrap_or(last);
+                let t = history.time_at(index).unwrap_or_default();
+                let slider = egui::Slider::new(&mut index, 0..=last)
+                    .show_value(false)
+                    .text(format!("t = {t:.2}"));
+                if ui.add(slider).changed() {
+                    history.cursor = Some(index);
+                }
+                let megabytes = format!("{:.1}", history.memory_bytes() as f64 / (1024.0 * 1024.0));
+                ui.label(
+                    egui::RichText::new(trf(
+                        "Timeline: {count} states (~{mb} MB)",
+                        &[("count", &history.len()), ("mb", &megabytes)],
+                    ))
+                    .small()
+                    .color(egui::Color32::GRAY),
+                );
+            }
+
             ui.add_space(8.0);
 
-            ui.collapsing("📍 Initial Conditions", |ui| {
+            panel_section(ui, &mut layout, PanelSection::Comparison, |ui| {
+                if ui
+                    .checkbox(&mut comparison.enabled, tr("Split-screen comparison"))
+                    .changed()
+                    && comparison.enabled
+                {
+                    ortho_views.enabled = false;
+                }
+                ui.label(
+                    egui::RichText::new(
+                        tr("B (right) has its own σ/ρ/β and initial conditions; \
+                         dt, method, and trail length are shared."),
+                    )
+                    .small()
+                    .color(egui::Color32::GRAY),
+                );
+                if ui.button(tr("Copy A → B")).clicked() {
+                    comparison.copy_from(&config);
+                    events.reset.send(ResetEvent);
+                }
+            });
+
+            ui.add_space(8.0);
+
+            panel_section(ui, &mut layout, PanelSection::Trajectories, |ui| {
+                trajectories_ui(
+                    ui,
+                    &mut trajectories,
+                    &mut extra_query,
+                    &mut events.trajectory,
+                    &config,
+                    &slider_ranges,
+                );
+            });
+
+            ui.add_space(8.0);
+
+            #[cfg(feature = "analysis")]
+            panel_section(ui, &mut layout, PanelSection::Separation, |ui| {
+                separation_ui(ui, &separation, stats.lyapunov.exponent());
+            });
+
+            #[cfg(feature = "analysis")]
+            ui.add_space(8.0);
+
+            panel_section(ui, &mut layout, PanelSection::Gallery, |ui| {
+                ui.checkbox(&mut gallery.enabled, tr("Small-multiples gallery"));
+                ui.label(
+                    egui::RichText::new(
+                        tr("Tiles one short simulation per ρ value across the range; \
+                         σ, β, dt, and method follow the main panel."),
+                    )
+                    .small()
+                    .color(egui::Color32::GRAY),
+                );
+                let rho_max = gallery.rho_max;
                 ui.add(
-                    egui::Slider::new(&mut config.initial_x, -20.0..=20.0)
-                        .text("x₀"),
+                    egui::Slider::new(&mut gallery.rho_min, 0.1..=rho_max)
+                        .text(tr("ρ min"))
+                        .clamp_to_range(true),
                 );
+                let rho_min = gallery.rho_min;
                 ui.add(
-                    egui::Slider::new(&mut config.initial_y, -20.0..=20.0)
-                        .text("y₀"),
+                    egui::Slider::new(&mut gallery.rho_max, rho_min..=200.0)
+                        .text(tr("ρ max"))
+                        .clamp_to_range(true),
                 );
+                ui.add(egui::Slider::new(&mut gallery.columns, 1..=8).text(tr("Columns")));
+                ui.add(egui::Slider::new(&mut gallery.rows, 1..=8).text(tr("Rows")));
                 ui.add(
-                    egui::Slider::new(&mut config.initial_z, -20.0..=20.0)
-                        .text("z₀"),
+                    egui::Slider::new(&mut gallery.trail_points, 200..=5_000)
+                        .text(tr("Trail points per tile")),
                 );
+            });
+
+            ui.add_space(8.0);
+
+            panel_section(ui, &mut layout, PanelSection::InitialConditions, |ui| {
+                ab_tabs(ui, &mut comparison);
+                let (x0, y0, z0) = if comparison.enabled && comparison.editing_b {
+                    let b = &mut *comparison;
+                    (&mut b.initial_x, &mut b.initial_y, &mut b.initial_z)
+                } else {
+                    let a = &mut *config;
+                    (&mut a.initial_x, &mut a.initial_y, &mut a.initial_z)
+                };
+
+                let ranges = &mut *slider_ranges;
+                if ranges.auto_expand {
+                    for value in [*x0, *y0, *z0] {
+                        expand_to_fit(&mut ranges.initial, value);
+                    }
+                }
+                let (low, high) = ranges.initial;
+                for (value, label) in [(x0, "x₀"), (y0, "y₀"), (z0, "z₀")] {
+                    ui.add(ParamSlider::new(value, low..=high, label))
+                        .on_hover_help(HelpTopic::InitialConditions);
+                }
+                placement_ui(ui, &mut placement);
                 ui.label(
-                    egui::RichText::new("Changes apply on Reset")
+                    egui::RichText::new(tr("Changes apply on Reset"))
                         .small()
                         .italics(),
                 );
+                ui.label(egui::RichText::new(tr("ℹ Sensitivity to initial conditions")).small())
+                    .on_hover_help(HelpTopic::Lyapunov);
             });
 
             ui.add_space(8.0);
 
-            ui.collapsing("📊 Diagnostics", |ui| {
-                ui.checkbox(&mut config.show_energy, "Show energy");
-                ui.checkbox(&mut config.show_velocity, "Show velocity");
-                ui.checkbox(&mut config.show_divergence, "Show divergence");
+            panel_section(ui, &mut layout, PanelSection::Annotations, |ui| {
+                let head = state_query.get_single().ok();
+                let focus = orbit_query
+                    .get_single()
+                    .ok()
+                    .map(|orbit| view_scale.from_world(orbit.focus));
+                annotations_ui(ui, &mut annotations, head, focus, &config.params());
+            });
+
+            ui.add_space(8.0);
+
+            let active = trajectories.active.and_then(|entity| extra_query.get_mut(entity).ok());
+            let (shown_name, shown) = match active {
+                Some((_, extra, extra_stats)) => {
+                    (Some(extra.name.clone()), extra_stats.into_inner())
+                }
+                None => (None, &mut *stats),
+            };
+
+            panel_section(ui, &mut layout, PanelSection::Diagnostics, |ui| {
+                if let Some(name) = &shown_name {
+                    ui.label(trf("Showing: {name}", &[("name", name)]));
+                }
+                ui.checkbox(&mut config.show_energy, tr("Show energy"))
+                    .on_hover_help(HelpTopic::Energy);
+                ui.checkbox(&mut config.show_velocity, tr("Show velocity"))
+                    .on_hover_help(HelpTopic::Velocity);
+                ui.checkbox(&mut config.show_divergence, tr("Show divergence"))
+                    .on_hover_help(HelpTopic::Divergence);
 
                 ui.add_space(4.0);
                 ui.separator();
 
                 if config.show_energy {
-                    ui.label(format!("Energy (½|X|²): {:.2}", stats.current_energy));
+                    ui.label(format!("{} (½|X|²): {:.2}", tr("Energy"), shown.current_energy))
+                        .on_hover_help(HelpTopic::Energy);
                 }
                 if config.show_velocity {
-                    ui.label(format!("Velocity |dX/dt|: {:.2}", stats.current_velocity));
+                    ui.label(format!("{} |dX/dt|: {:.2}", tr("Velocity"), shown.current_velocity))
+                        .on_hover_help(HelpTopic::Velocity);
+                    ui.label(format!(
+                        "{}: {:.2}",
+                        tr("Average speed"),
+                        shown.average_speed()
+                    ));
+                    ui.label(format!("{}: {:.1}", tr("Arc length"), shown.arc_length))
+                        .on_hover_text(tr("Path length travelled since the last reset"));
+                }
+                if let Some(exponent) = shown.lyapunov.exponent() {
+                    ui.label(format!("{} λ₁ ≈ {:.3}", tr("Lyapunov exponent"), exponent))
+                        .on_hover_help(HelpTopic::Lyapunov);
                 }
                 if config.show_divergence {
-                    ui.label(format!("Divergence ∇·F: {:.4}", stats.divergence));
+                    ui.label(format!("{} ∇·F: {:.4}", tr("Divergence"), shown.divergence))
+                        .on_hover_help(HelpTopic::Divergence);
                     ui.label(
-                        egui::RichText::new("(Constant — system is uniformly dissipative)")
+                        egui::RichText::new(tr("(Constant — system is uniformly dissipative)"))
                             .small()
                             .color(egui::Color32::GRAY),
                     );
@@ -185,26 +1124,1647 @@ pub fn ui_system(
                 ui.add_space(4.0);
                 ui.separator();
 
-                if let Some(fps) = diagnostics
-                    .get(&FrameTimeDiagnosticsPlugin::FPS)
-                    .and_then(|d| d.smoothed())
+                performance_ui(ui, &performance);
+                invariants_ui(ui, &mut invariants);
+
+                ui.add_space(4.0);
+                ui.separator();
+
+                running_stats_ui(ui, &mut shown.running);
+            });
+
+            ui.add_space(8.0);
+
+            #[cfg(feature = "analysis")]
+            panel_section(ui, &mut layout, PanelSection::TimeSeries, |ui| {
+                time_series_ui(ui, &trail, &mut time_series);
+            });
+
+            #[cfg(feature = "analysis")]
+            ui.add_space(8.0);
+
+            #[cfg(feature = "analysis")]
+            panel_section(ui, &mut layout, PanelSection::PhasePortrait, |ui| {
+                phase_portrait_ui(ui, &trail, &mut phase_portrait);
+            });
+
+            #[cfg(feature = "analysis")]
+            ui.add_space(8.0);
+
+            #[cfg(feature = "analysis")]
+            panel_section(ui, &mut layout, PanelSection::Spectrum, |ui| {
+                spectrum_ui(ui, &spectrum_sampler, &mut spectrum);
+            });
+
+            #[cfg(feature = "analysis")]
+            ui.add_space(8.0);
+
+            #[cfg(feature = "analysis")]
+            panel_section(ui, &mut layout, PanelSection::Embedding, |ui| {
+                embedding_ui(ui, &spectrum_sampler, &mut embedding);
+            });
+
+            #[cfg(feature = "analysis")]
+            ui.add_space(8.0);
+
+            #[cfg(feature = "analysis")]
+            panel_section(ui, &mut layout, PanelSection::Complexity, |ui| {
+                complexity_ui(ui, &complexity, &mut complexity_config);
+            });
+
+            #[cfg(feature = "analysis")]
+            ui.add_space(8.0);
+
+            #[cfg(feature = "analysis")]
+            panel_section(ui, &mut layout, PanelSection::Histograms, |ui| {
+                histogram_ui(ui, &trail, &mut histograms);
+            });
+
+            #[cfg(feature = "analysis")]
+            ui.add_space(8.0);
+
+            #[cfg(feature = "analysis")]
+            panel_section(ui, &mut layout, PanelSection::Density, |ui| {
+                density_ui(ui, &mut density, &mut density_config, &mut density_texture);
+            });
+
+            #[cfg(feature = "analysis")]
+            ui.add_space(8.0);
+
+            #[cfg(feature = "analysis")]
+            panel_section(ui, &mut layout, PanelSection::LobeSwitches, |ui| {
+                if let Some(name) = &shown_name {
+                    ui.label(trf("Showing: {name}", &[("name", name)]));
+                }
+                lobe_switches_ui(ui, &mut shown.lobes);
+            });
+
+            #[cfg(feature = "analysis")]
+            ui.add_space(8.0);
+
+            #[cfg(feature = "scripting")]
+            panel_section(ui, &mut layout, PanelSection::Script, |ui| {
+                script_editor_ui(ui, &mut script_settings, &script_host, &mut script_events);
+            });
+
+            #[cfg(feature = "scripting")]
+            ui.add_space(8.0);
+
+            panel_section(ui, &mut layout, PanelSection::Camera, |ui| {
+                ui.horizontal(|ui| {
+                    if ui.button(tr("⛶ Frame attractor (F)")).clicked() {
+                        events.fit_view.send(FitViewEvent);
+                    }
+                    if ui.button(tr("🏠 Reset view (Home)")).clicked() {
+                        events.reset_view.send(ResetViewEvent);
+                    }
+                });
+
+                if ui
+                    .checkbox(&mut ortho_views.enabled, tr("Four-pane layout (xy / xz / yz)"))
+                    .changed()
+                    && ortho_views.enabled
                 {
-                    ui.label(format!("FPS: {:.0}", fps));
+                    comparison.enabled = false;
+                }
+
+                ui.checkbox(&mut anaglyph.enabled, tr("Anaglyph stereo (red–cyan glasses)"));
+                if anaglyph.enabled {
+                    ui.add(
+                        egui::Slider::new(&mut anaglyph.eye_separation, 0.0..=10.0)
+                            .text(tr("Eye separation"))
+                            .clamp_to_range(true),
+                    );
+                }
+
+                ui.add_space(4.0);
+                let mapping = view_scale.mapping;
+                ui.horizontal(|ui| {
+                    ui.label(tr("Up axis:"));
+                    for (axis, name) in ["x", "y", "z"].into_iter().enumerate() {
+                        ui.radio_value(&mut view_scale.mapping.up, axis, name);
+                    }
+                });
+                ui.checkbox(&mut view_scale.mapping.swap_horizontal, tr("Swap horizontal axes"));
+                if view_scale.mapping != mapping {
+                    events.fit_view.send(FitViewEvent);
                 }
+                ui.checkbox(&mut view_scale.normalize, tr("Normalize to a cube"))
+                    .on_hover_text(tr(
+                        "Stretch the trail's bounding box to fill the home view, whatever the \
+                         size or shape of the system",
+                    ));
+                ui.collapsing(tr("Axis scale"), |ui| {
+                    for (axis, name) in ["x", "y", "z"].into_iter().enumerate() {
+                        ui.add(
+                            egui::Slider::new(&mut view_scale.axes[axis], 0.1..=10.0)
+                                .text(name)
+                                .logarithmic(true)
+                                .clamp_to_range(true),
+                        );
+                    }
+                    let reset = egui::Button::new(tr("Reset scale"));
+                    if ui.add_enabled(!view_scale.is_identity(), reset).clicked() {
+                        view_scale.axes = [1.0; 3];
+                        view_scale.normalize = false;
+                    }
+                });
+
+                ui.add_space(4.0);
+                ui.label(tr("Rotation mode:"));
+                ui.radio_value(
+                    &mut camera_settings.rotation_mode,
+                    RotationMode::Turntable,
+                    tr(RotationMode::Turntable.label()),
+                );
+                ui.radio_value(
+                    &mut camera_settings.rotation_mode,
+                    RotationMode::Trackball,
+                    tr(RotationMode::Trackball.label()),
+                );
+
+                ui.add_space(4.0);
+                ui.add(
+                    egui::Slider::new(&mut camera_settings.fov_degrees, 20.0..=110.0)
+                        .text(tr("Field of view (°)"))
+                        .clamp_to_range(true),
+                );
+
+                ui.add_space(4.0);
+                ui.collapsing(tr("Sensitivity"), |ui| {
+                    ui.add(
+                        egui::Slider::new(&mut camera_settings.rotate_sensitivity, 0.001..=0.02)
+                            .text(tr("Rotate"))
+                            .logarithmic(true)
+                            .clamp_to_range(true),
+                    );
+                    ui.add(
+                        egui::Slider::new(&mut camera_settings.pan_sensitivity, 0.01..=0.5)
+                            .text(tr("Pan"))
+                            .logarithmic(true)
+                            .clamp_to_range(true),
+                    );
+                    ui.add(
+                        egui::Slider::new(&mut camera_settings.zoom_sensitivity, 0.5..=10.0)
+                            .text(tr("Zoom"))
+                            .clamp_to_range(true),
+                    );
+                    ui.checkbox(&mut camera_settings.invert_scroll, tr("Invert scroll zoom"));
+                    if ui.button(tr("Restore defaults")).clicked() {
+                        let defaults = CameraSettings::default();
+                        camera_settings.rotate_sensitivity = defaults.rotate_sensitivity;
+                        camera_settings.pan_sensitivity = defaults.pan_sensitivity;
+                        camera_settings.zoom_sensitivity = defaults.zoom_sensitivity;
+                        camera_settings.invert_scroll = defaults.invert_scroll;
+                    }
+                });
+
+                ui.add_space(4.0);
+                ui.checkbox(&mut camera_settings.inertia, tr("Inertia (glide to a stop)"));
+                ui.add_enabled(
+                    camera_settings.inertia,
+                    egui::Slider::new(&mut camera_settings.damping, 1.0..=20.0)
+                        .text(tr("Damping (1/s)"))
+                        .clamp_to_range(true),
+                );
+
+                ui.add_space(4.0);
+                ui.checkbox(&mut camera_settings.follow_head, tr("Follow trajectory head"));
+                ui.add_enabled(
+                    camera_settings.follow_head,
+                    egui::Slider::new(&mut camera_settings.follow_smoothing, 0.0..=2.0)
+                        .text(tr("Follow lag (s)"))
+                        .clamp_to_range(true),
+                );
+                ui.add_enabled(
+                    camera_settings.follow_head,
+                    egui::Slider::new(&mut camera_settings.follow_distance, 5.0..=200.0)
+                        .text(tr("Follow distance"))
+                        .clamp_to_range(true),
+                );
+
+                ui.add_space(4.0);
+                ui.checkbox(&mut camera_settings.auto_orbit, tr("Auto-orbit (turntable)"));
+                ui.add_enabled(
+                    camera_settings.auto_orbit,
+                    egui::Slider::new(&mut camera_settings.auto_orbit_speed, -90.0..=90.0)
+                        .text(tr("Speed (°/s)"))
+                        .clamp_to_range(true),
+                );
+            });
+
+            ui.add_space(8.0);
+
+            panel_section(ui, &mut layout, PanelSection::CameraPath, |ui| {
+                ui.add(
+                    egui::Slider::new(&mut camera_path.segment_duration, 0.5..=20.0)
+                        .text(tr("Segment duration (s)"))
+                        .clamp_to_range(true),
+                );
+
+                if ui.button(tr("➕ Add keyframe")).clicked() {
+                    if let Ok(orbit) = orbit_query.get_single() {
+                        camera_path.record(orbit);
+                    }
+                }
+
+                let mut remove = None;
+                for (i, key) in camera_path.keyframes.iter().enumerate() {
+                    ui.horizontal(|ui| {
+                        ui.label(format!(
+                            "#{}  t = {:.1} s  r = {:.0}",
+                            i + 1,
+                            key.time,
+                            key.radius
+                        ));
+                        if ui.small_button("🗑").clicked() {
+                            remove = Some(i);
+                        }
+                    });
+                }
+                if let Some(i) = remove {
+                    camera_path.remove(i);
+                }
+
+                ui.horizontal(|ui| {
+                    let can_play = camera_path.keyframes.len() >= 2;
+                    if camera_path.playing {
+                        if ui.button(tr("⏹ Stop")).clicked() {
+                            camera_path.playing = false;
+                        }
+                    } else if ui
+                        .add_enabled(can_play, egui::Button::new(tr("▶ Play path")))
+                        .clicked()
+                    {
+                        camera_path.play();
+                    }
+                    ui.checkbox(&mut camera_path.looping, tr("Loop"));
+                    if ui.button(tr("Clear")).clicked() {
+                        *camera_path = CameraPath {
+                            segment_duration: camera_path.segment_duration,
+                            ..default()
+                        };
+                    }
+                });
+
+                if camera_path.playing {
+                    ui.label(format!(
+                        "{}: {:.1} / {:.1} s",
+                        tr("Playing"),
+                        camera_path.playhead,
+                        camera_path.duration()
+                    ));
+                }
+            });
+
+            ui.add_space(8.0);
+
+            #[cfg(feature = "export")]
+            panel_section(ui, &mut layout, PanelSection::Capture, |ui| {
+                if ui.button(tr("📸 Screenshot (F12)")).clicked() {
+                    let request = DialogRequest::new(
+                        DialogPurpose::Screenshot,
+                        "PNG",
+                        &["png"],
+                        "screenshot.png",
+                    );
+                    if !dialogs.save_as(request, &dialog_settings) {
+                        capture_events.screenshot.send(ScreenshotEvent::default());
+                    }
+                }
+                ui.checkbox(&mut capture_settings.include_ui, tr("Include control panel"));
+
+                ui.add_space(4.0);
+                ui.label(tr("Frame sequence:"));
+                let record_label = match &recorder.active {
+                    Some(recording) => {
+                        trf("⏹ Stop recording ({n} frames)", &[("n", &recording.frame)])
+                    }
+                    None => tr("⏺ Record frames (F10)").to_string(),
+                };
+                if ui.button(record_label).clicked() {
+                    capture_events.record.send(RecordEvent);
+                }
+                ui.add_enabled_ui(recorder.active.is_none(), |ui| {
+                    ui.add(
+                        egui::Slider::new(&mut capture_settings.fps, 10..=120)
+                            .text(tr("Frame rate")),
+                    );
+                    ui.add(
+                        egui::Slider::new(&mut capture_settings.max_frames, 0..=3_600)
+                            .text(tr("Stop after frames (0 = manual)")),
+                    );
+                });
+                ui.label(
+                    egui::RichText::new(trf(
+                        "Each frame advances {steps} steps × dt = {units} time units",
+                        &[
+                            ("steps", &config.steps_per_frame),
+                            ("units", &format!("{:.4}", config.steps_per_frame as f64 * config.dt)),
+                        ],
+                    ))
+                    .small()
+                    .color(egui::Color32::GRAY),
+                );
+
+                ui.add_space(4.0);
+                ui.label(tr("Video (requires ffmpeg on PATH):"));
+                let video_label = match video.progress() {
+                    Some((ClipKind::Video, sent, total)) => {
+                        trf("⏹ Stop video ({sent}/{total})", &[("sent", &sent), ("total", &total)])
+                    }
+                    _ => tr("🎞 Record video (F9)").to_string(),
+                };
+                if ui.button(video_label).clicked() {
+                    capture_events.record_video.send(RecordVideoEvent);
+                }
+                if video.is_encoding() {
+                    ui.label(tr("Encoding…"));
+                }
+                ui.add_enabled_ui(!video.is_recording(), |ui| {
+                    ui.horizontal(|ui| {
+                        for format in [VideoFormat::Mp4, VideoFormat::WebM] {
+                            ui.radio_value(
+                                &mut capture_settings.video_format,
+                                format,
+                                format.label(),
+                            );
+                        }
+                    });
+                    egui::ComboBox::from_label(tr("Resolution"))
+                        .selected_text(tr(capture_settings.video_resolution.label()))
+                        .show_ui(ui, |ui| {
+                            for resolution in VideoResolution::ALL {
+                                ui.selectable_value(
+                                    &mut capture_settings.video_resolution,
+                                    resolution,
+                                    tr(resolution.label()),
+                                );
+                            }
+                        });
+                    ui.add(
+                        egui::Slider::new(&mut capture_settings.video_seconds, 1.0..=120.0)
+                            .text(tr("Duration (s)")),
+                    );
+                    ui.add(
+                        egui::Slider::new(&mut capture_settings.video_bitrate_mbps, 1.0..=50.0)
+                            .text(tr("Bitrate (Mbit/s)")),
+                    );
+                });
+
+                ui.add_space(4.0);
+                ui.label(tr("Animated GIF:"));
+                let gif_label = match video.progress() {
+                    Some((ClipKind::Gif, sent, total)) => {
+                        trf("⏹ Stop GIF ({sent}/{total})", &[("sent", &sent), ("total", &total)])
+                    }
+                    _ => tr("🖼 Record GIF (F8)").to_string(),
+                };
+                if ui.button(gif_label).clicked() {
+                    capture_events.record_gif.send(RecordGifEvent);
+                }
+                ui.add_enabled_ui(!video.is_recording(), |ui| {
+                    ui.add(
+                        egui::Slider::new(&mut capture_settings.gif_width, 160..=960)
+                            .text(tr("Max width (px)")),
+                    );
+                    ui.add(
+                        egui::Slider::new(&mut capture_settings.gif_fps, 5..=30)
+                            .text(tr("Frame rate")),
+                    );
+                    ui.add(
+                        egui::Slider::new(&mut capture_settings.gif_seconds, 1.0..=15.0)
+                            .text(tr("Duration (s)")),
+                    );
+                    ui.horizontal(|ui| {
+                        ui.label(tr("Colors:"));
+                        for colors in GIF_PALETTE_SIZES {
+                            ui.selectable_value(
+                                &mut capture_settings.gif_colors,
+                                colors,
+                                colors.to_string(),
+                            );
+                        }
+                    });
+                });
 
-                ui.label(format!(
-                    "Integration time: {:.1} μs",
-                    stats.integration_time_us
+                ui.add_space(4.0);
+                ui.label(tr("High-resolution still:"));
+                ui.add_enabled_ui(!still.is_busy(), |ui| {
+                    if ui.button(tr("🖼 Render still (F7)")).clicked() {
+                        let request = DialogRequest::new(
+                            DialogPurpose::RenderStill,
+                            "PNG",
+                            &["png"],
+                            "still.png",
+                        );
+                        if !dialogs.save_as(request, &dialog_settings) {
+                            capture_events.render_still.send(RenderStillEvent::default());
+                        }
+                    }
+                    ui.horizontal_wrapped(|ui| {
+                        for (label, width, height) in STILL_PRESETS {
+                            let selected = capture_settings.still_width == width
+                                && capture_settings.still_height == height;
+                            if ui.selectable_label(selected, label).clicked() {
+                                capture_settings.still_width = width;
+                                capture_settings.still_height = height;
+                            }
+                        }
+                    });
+                    ui.horizontal(|ui| {
+                        ui.add(
+                            egui::DragValue::new(&mut capture_settings.still_width)
+                                .range(16..=MAX_STILL_DIMENSION)
+                                .suffix(" px"),
+                        );
+                        ui.label("×");
+                        ui.add(
+                            egui::DragValue::new(&mut capture_settings.still_height)
+                                .range(16..=MAX_STILL_DIMENSION)
+                                .suffix(" px"),
+                        );
+                    });
+                });
+                if still.is_busy() {
+                    ui.label(tr("Rendering…"));
+                }
+
+                ui.label(
+                    egui::RichText::new(trf(
+                        "Saved to ./{dir}/",
+                        &[("dir", &crate::capture::CAPTURE_DIR)],
+                    ))
+                        .small()
+                        .color(egui::Color32::GRAY),
+                );
+            });
+
+            #[cfg(feature = "export")]
+            ui.add_space(8.0);
+
+            #[cfg(feature = "export")]
+            panel_section(ui, &mut layout, PanelSection::Export, |ui| {
+                ui.horizontal(|ui| {
+                    ui.label(tr("Trail format:"));
+                    for &format in TrajectoryFormat::ALL {
+                        ui.radio_value(&mut export.trajectory_format, format, format.label());
+                    }
+                });
+                ui.horizontal(|ui| {
+                    ui.label(tr("File:"));
+                    ui.add(
+                        egui::TextEdit::singleline(&mut export.path)
+                            .hint_text(format!(
+                                "{}/trajectory-<timestamp>",
+                                crate::capture::CAPTURE_DIR
+                            ))
+                            .desired_width(180.0),
+                    );
+                    if browse_button(ui, &dialogs) {
+                        let format = export.trajectory_format;
+                        let extension = format.extension();
+                        dialogs.browse(
+                            DialogRequest::new(
+                                DialogPurpose::ExportTrail,
+                                format.label(),
+                                &[extension],
+                                format!("trajectory.{extension}"),
+                            ),
+                            &export.path,
+                            &dialog_settings,
+                        );
+                    }
+                });
+                if ui
+                    .button(trf("📤 Export trail ({n} points)", &[("n", &stats.point_count)]))
+                    .clicked()
+                {
+                    capture_events.export_trajectory.send(ExportTrajectoryEvent);
+                }
+                let hint = match export.trajectory_format {
+                    TrajectoryFormat::Csv | TrajectoryFormat::Json => {
+                        tr("Columns: t, x, y, z, speed")
+                    }
+                    TrajectoryFormat::Ply => tr("Colored vertices + edges (MeshLab, Blender)"),
+                    TrajectoryFormat::Obj => tr("Vertex colors + polyline (Blender)"),
+                    TrajectoryFormat::Glb => {
+                        tr("Tube mesh + parameters in extras (three.js, engines)")
+                    }
+                    TrajectoryFormat::Npy => tr("N × 5 float64 array: t, x, y, z, speed"),
+                    TrajectoryFormat::Npz => tr("trajectory + σ, ρ, β, dt, method, initial_state"),
+                    #[cfg(feature = "hdf5")]
+                    TrajectoryFormat::Hdf5 => {
+                        tr("time, state (N × 3), speed + parameter attributes")
+                    }
+                };
+                if export.trajectory_format == TrajectoryFormat::Glb {
+                    ui.add(
+                        egui::Slider::new(&mut export.tube_radius, 0.02..=1.0)
+                            .text(tr("Tube radius")),
+                    );
+                    ui.add(
+                        egui::Slider::new(&mut export.tube_sides, 3..=24).text(tr("Tube sides")),
+                    );
+                }
+                ui.label(egui::RichText::new(hint).small().color(egui::Color32::GRAY));
+                if ui
+                    .button(trf(
+                        "📊 Export statistics history ({n} samples)",
+                        &[("n", &stats_history.samples.len())],
+                    ))
+                    .on_hover_text(tr(
+                        "Every 0.1 time units: t, energy, speed, average_speed, arc_length, \
+                         lyapunov, lobe_switches",
+                    ))
+                    .clicked()
+                {
+                    let request = DialogRequest::new(
+                        DialogPurpose::ExportStats,
+                        "CSV",
+                        &["csv"],
+                        "stats.csv",
+                    );
+                    if !dialogs.save_as(request, &dialog_settings) {
+                        capture_events.export_stats.send(ExportStatsEvent::default());
+                    }
+                }
+
+                ui.separator();
+                if ui
+                    .button(tr("🧾 Export run manifest"))
+                    .on_hover_text(tr(
+                        "Parameters, integrator, initial state, version, and step count",
+                    ))
+                    .clicked()
+                {
+                    let request = DialogRequest::new(
+                        DialogPurpose::ExportManifest,
+                        "JSON",
+                        &["json"],
+                        "manifest.json",
+                    );
+                    if !dialogs.save_as(request, &dialog_settings) {
+                        capture_events.export_manifest.send(ExportManifestEvent::default());
+                    }
+                }
+
+                ui.separator();
+                if ui
+                    .button(trf(
+                        "✂ Export Poincaré section (z = {z})",
+                        &[("z", &format!("{:.2}", config.rho - 1.0))],
+                    ))
+                    .on_hover_text(tr(
+                        "Upward crossings of the plane through C±: n, t, x, y, return_time",
+                    ))
+                    .clicked()
+                {
+                    let request = DialogRequest::new(
+                        DialogPurpose::ExportPoincare,
+                        "CSV",
+                        &["csv"],
+                        "poincare.csv",
+                    );
+                    if !dialogs.save_as(request, &dialog_settings) {
+                        capture_events.export_poincare.send(ExportPoincareEvent::default());
+                    }
+                }
+                if ui
+                    .button(trf(
+                        "🔤 Export L/R symbols ({n})",
+                        &[("n", &stats.lobes.symbols.len())],
+                    ))
+                    .on_hover_text(tr("The sequence of wings visited since the last reset"))
+                    .clicked()
+                {
+                    let request = DialogRequest::new(
+                        DialogPurpose::ExportSymbols,
+                        "Text",
+                        &["txt"],
+                        "symbols.txt",
+                    );
+                    if !dialogs.save_as(request, &dialog_settings) {
+                        capture_events.export_symbols.send(ExportSymbolsEvent::default());
+                    }
+                }
+
+                ui.separator();
+                ui.label(tr("Bifurcation diagram (z maxima vs ρ)"));
+                let (rho_min, rho_max) = (bifurcation.rho_min, bifurcation.rho_max);
+                ui.horizontal(|ui| {
+                    ui.label(tr("ρ from"));
+                    ui.add(
+                        egui::DragValue::new(&mut bifurcation.rho_min)
+                            .speed(0.5)
+                            .range(0.0..=rho_max),
+                    );
+                    ui.label(tr("to"));
+                    ui.add(
+                        egui::DragValue::new(&mut bifurcation.rho_max)
+                            .speed(0.5)
+                            .range(rho_min..=500.0),
+                    );
+                });
+                ui.add(
+                    egui::Slider::new(&mut bifurcation.samples, 10..=2000).text(tr("ρ samples")),
+                );
+                ui.add(
+                    egui::Slider::new(&mut bifurcation.transient_steps, 0..=20000)
+                        .text(tr("Transient steps")),
+                );
+                ui.add(
+                    egui::Slider::new(&mut bifurcation.record_steps, 1000..=50000)
+                        .text(tr("Recorded steps")),
+                );
+                ui.add_enabled_ui(!bifurcation_export.is_running(), |ui| {
+                    if ui
+                        .button(tr("📈 Scan & export bifurcation (PNG + CSV + JSON)"))
+                        .clicked()
+                    {
+                        // The CSV and JSON are written beside the chosen PNG.
+                        let request = DialogRequest::new(
+                            DialogPurpose::ExportBifurcation,
+                            "PNG",
+                            &["png"],
+                            "bifurcation.png",
+                        );
+                        if !dialogs.save_as(request, &dialog_settings) {
+                            let event = ExportBifurcationEvent::default();
+                            capture_events.export_bifurcation.send(event);
+                        }
+                    }
+                });
+                if bifurcation_export.is_running() {
+                    ui.label(tr("Scanning…"));
+                }
+            });
+
+            #[cfg(feature = "export")]
+            ui.add_space(8.0);
+
+            #[cfg(feature = "export")]
+            panel_section(ui, &mut layout, PanelSection::StateLog, |ui| {
+                ui.add_enabled_ui(!state_log.is_open(), |ui| {
+                    ui.horizontal(|ui| {
+                        ui.label(tr("Format:"));
+                        for format in LogFormat::ALL {
+                            ui.radio_value(&mut log.format, format, format.label());
+                        }
+                    });
+                    ui.horizontal(|ui| {
+                        ui.label(tr("File:"));
+                        ui.add(
+                            egui::TextEdit::singleline(&mut log.path)
+                                .hint_text(format!(
+                                    "{}/log-<timestamp>",
+                                    crate::capture::CAPTURE_DIR
+                                ))
+                                .desired_width(180.0),
+                        );
+                        if browse_button(ui, &dialogs) {
+                            let extension = log.format.extension();
+                            dialogs.browse(
+                                DialogRequest::new(
+                                    DialogPurpose::StateLog,
+                                    log.format.label(),
+                                    &[extension],
+                                    format!("log.{extension}"),
+                                ),
+                                &log.path,
+                                &dialog_settings,
+                            );
+                        }
+                    });
+                });
+                ui.add(
+                    egui::Slider::new(&mut log.every_n_steps, 1..=1000)
+                        .logarithmic(true)
+                        .text(tr("Every N steps")),
+                );
+                ui.add(
+                    egui::Slider::new(&mut log.max_megabytes, 1..=1024)
+                        .logarithmic(true)
+                        .text(tr("Rotate at MB")),
+                );
+                ui.checkbox(&mut log.enabled, tr("Append (t, x, y, z) while running"));
+                if state_log.is_open() {
+                    ui.label(trf(
+                        "{rows} rows → {path}",
+                        &[
+                            ("rows", &state_log.rows),
+                            ("path", &state_log.current_path().display()),
+                        ],
+                    ));
+                }
+            });
+
+            #[cfg(feature = "export")]
+            ui.add_space(8.0);
+
+            #[cfg(feature = "rerun")]
+            panel_section(ui, &mut layout, PanelSection::Rerun, |ui| {
+                ui.add_enabled_ui(!rerun_log.is_open(), |ui| {
+                    ui.horizontal(|ui| {
+                        ui.label(tr("Send to:"));
+                        for sink in RerunSink::ALL {
+                            ui.radio_value(&mut rerun_settings.sink, sink, tr(sink.label()));
+                        }
+                    });
+                    if rerun_settings.sink == RerunSink::File {
+                        ui.horizontal(|ui| {
+                            ui.label(tr("File:"));
+                            ui.add(
+                                egui::TextEdit::singleline(&mut rerun_settings.path)
+                                    .hint_text(format!(
+                                        "{}/lorenz-<timestamp>.rrd",
+                                        crate::capture::CAPTURE_DIR
+                                    ))
+                                    .desired_width(180.0),
+                            );
+                            if browse_button(ui, &dialogs) {
+                                dialogs.browse(
+                                    DialogRequest::new(
+                                        DialogPurpose::RerunRecording,
+                                        "Rerun",
+                                        &["rrd"],
+                                        "lorenz.rrd",
+                                    ),
+                                    &rerun_settings.path,
+                                    &dialog_settings,
+                                );
+                            }
+                        });
+                    }
+                });
+                ui.add(
+                    egui::Slider::new(&mut rerun_settings.every_n_steps, 1..=1000)
+                        .logarithmic(true)
+                        .text(tr("Every N steps")),
+                );
+                ui.checkbox(&mut rerun_settings.enabled, tr("Log to Rerun while running"));
+                if let Some(error) = &rerun_log.error {
+                    ui.colored_label(egui::Color32::LIGHT_RED, format!("⚠ {error}"));
+                } else if let Some(destination) = &rerun_log.destination {
+                    ui.colored_label(
+                        egui::Color32::LIGHT_GREEN,
+                        trf("📼 Logging to {destination}", &[("destination", destination)]),
+                    );
+                }
+                ui.label(
+                    egui::RichText::new(tr(
+                        "The trajectory, σ ρ β dt, and the diagnostics on the step and \
+                         sim_time timelines",
+                    ))
+                    .small()
+                    .color(egui::Color32::GRAY),
+                );
+            });
+
+            #[cfg(feature = "rerun")]
+            ui.add_space(8.0);
+
+            panel_section(ui, &mut layout, PanelSection::Replay, |ui| {
+                ui.horizontal(|ui| {
+                    ui.label(tr("File:"));
+                    ui.add(
+                        egui::TextEdit::singleline(&mut replay.path)
+                            .hint_text("captures/trajectory-….csv")
+                            .desired_width(160.0),
+                    );
+                    if browse_button(ui, &dialogs) {
+                        dialogs.browse(
+                            DialogRequest::new(
+                                DialogPurpose::Replay,
+                                "CSV / JSON",
+                                &["csv", "json"],
+                                "",
+                            ),
+                            &replay.path,
+                            &dialog_settings,
+                        );
+                    }
+                    if ui.button(tr("📂 Load")).clicked() {
+                        events.load_replay.send(LoadReplayEvent);
+                    }
+                });
+
+                if replay.is_active() {
+                    let (start, end) = replay.time_range();
+                    ui.horizontal(|ui| {
+                        let label = if replay.playing { tr("⏸ Pause") } else { tr("▶ Play") };
+                        if ui.button(label).clicked() {
+                            if !replay.playing && replay.cursor >= end {
+                                replay.cursor = start;
+                            }
+                            replay.playing = !replay.playing;
+                        }
+                        if ui.button(tr("⏹ Stop replay")).clicked() {
+                            replay.stop();
+                        }
+                    });
+                    if ui
+                        .add(egui::Slider::new(&mut replay.cursor, start..=end).text("t"))
+                        .dragged()
+                    {
+                        replay.playing = false;
+                    }
+                    ui.add(
+                        egui::Slider::new(&mut replay.rate, 0.05..=20.0)
+                            .logarithmic(true)
+                            .text(tr("Time units / s")),
+                    );
+                    ui.label(format!(
+                        "{}, t ∈ [{start:.2}, {end:.2}]",
+                        trf("{n} samples", &[("n", &replay.samples.len())])
+                    ));
+                }
+
+                ui.label(
+                    egui::RichText::new(
+                        tr("CSV or JSON with t, x, y, z columns; integration pauses during replay"),
+                    )
+                    .small()
+                    .color(egui::Color32::GRAY),
+                );
+            });
+
+            ui.add_space(8.0);
+
+            panel_section(ui, &mut layout, PanelSection::Session, |ui| {
+                let record_label = if session.is_recording() {
+                    trf("⏹ Stop & save ({n} actions)", &[("n", &session.recorded_entries())])
+                } else {
+                    tr("⏺ Record session").to_string()
+                };
+                if ui.button(record_label).clicked() {
+                    // Only stopping writes a file.
+                    let request = DialogRequest::new(
+                        DialogPurpose::RecordSession,
+                        "JSON",
+                        &["json"],
+                        "session.json",
+                    );
+                    if !(session.is_recording() && dialogs.save_as(request, &dialog_settings)) {
+                        events.record_session.send(RecordSessionEvent::default());
+                    }
+                }
+
+                ui.horizontal(|ui| {
+                    ui.label(tr("File:"));
+                    ui.add(
+                        egui::TextEdit::singleline(&mut session.path)
+                            .hint_text("captures/session-….json")
+                            .desired_width(160.0),
+                    );
+                    if browse_button(ui, &dialogs) {
+                        dialogs.browse(
+                            DialogRequest::new(DialogPurpose::Session, "JSON", &["json"], ""),
+                            &session.path,
+                            &dialog_settings,
+                        );
+                    }
+                });
+                if let Some((done, total)) = session.playback_progress() {
+                    ui.horizontal(|ui| {
+                        ui.label(format!("{} {done}/{total}", tr("Playing")));
+                        if ui.button(tr("⏹ Stop playback")).clicked() {
+                            session.stop_playback();
+                        }
+                    });
+                } else {
+                    ui.add_enabled_ui(!session.is_recording(), |ui| {
+                        if ui.button(tr("▶ Play session")).clicked() {
+                            events.play_session.send(PlaySessionEvent);
+                        }
+                    });
+                }
+
+                ui.label(
+                    egui::RichText::new(tr(
+                        "Records parameter changes, resets, and camera moves per frame",
+                    ))
+                    .small()
+                    .color(egui::Color32::GRAY),
+                );
+            });
+
+            ui.add_space(8.0);
+
+            panel_section(ui, &mut layout, PanelSection::Snapshot, |ui| {
+                ui.horizontal(|ui| {
+                    ui.label(tr("File:"));
+                    ui.add(
+                        egui::TextEdit::singleline(&mut snapshot.path)
+                            .hint_text("captures/snapshot-….ron")
+                            .desired_width(160.0),
+                    );
+                });
+                ui.checkbox(&mut snapshot.include_trail, tr("Include trail"));
+                ui.horizontal(|ui| {
+                    if ui.button(tr("💾 Save state")).clicked() {
+                        events.save_snapshot.send(SaveSnapshotEvent);
+                    }
+                    if ui.button(tr("📂 Load state")).clicked() {
+                        events.load_snapshot.send(LoadSnapshotEvent);
+                    }
+                });
+                if file_dialog::AVAILABLE {
+                    ui.add_enabled_ui(!dialogs.is_open(), |ui| {
+                        ui.horizontal(|ui| {
+                            for (label, purpose) in [
+                                (tr("💾 Save as…"), DialogPurpose::SaveSnapshot),
+                                (tr("📂 Open…"), DialogPurpose::LoadSnapshot),
+                            ] {
+                                if ui.button(label).clicked() {
+                                    dialogs.browse(
+                                        DialogRequest::new(
+                                            purpose,
+                                            "RON",
+                                            &["ron"],
+                                            "snapshot.ron",
+                                        ),
+                                        &snapshot.path,
+                                        &dialog_settings,
+                                    );
+                                }
+                            }
+                        });
+                    });
+                }
+                ui.label(
+                    egui::RichText::new(tr("State, parameters, camera, and trail as RON"))
+                        .small()
+                        .color(egui::Color32::GRAY),
+                );
+            });
+
+            ui.add_space(8.0);
+
+            panel_section(ui, &mut layout, PanelSection::Osc, |ui| {
+                ui.checkbox(&mut osc.enabled, tr("Stream state over OSC"));
+                ui.horizontal(|ui| {
+                    ui.label(tr("Host:"));
+                    ui.add(egui::TextEdit::singleline(&mut osc.host).desired_width(110.0));
+                    ui.label(tr("Port:"));
+                    ui.add(egui::DragValue::new(&mut osc.port).range(1..=u16::MAX));
+                });
+                ui.add(
+                    egui::Slider::new(&mut osc.rate, MIN_RATE..=MAX_RATE)
+                        .logarithmic(true)
+                        .text(tr("Rate (Hz)")),
+                );
+                if let Some(error) = &osc_output.error {
+                    ui.colored_label(egui::Color32::LIGHT_RED, format!("⚠ {error}"));
+                } else if osc_output.is_connected() {
+                    let target = format!("{}:{}", osc.host, osc.port);
+                    ui.colored_label(
+                        egui::Color32::LIGHT_GREEN,
+                        trf("Sending to {target}", &[("target", &target)]),
+                    );
+                }
+                ui.label(
+                    egui::RichText::new(tr(
+                        "/lorenz/state x y z t, /lorenz/speed, /lorenz/params σ ρ β, and \
+                         /lorenz/switch on every wing change",
+                    ))
+                    .small()
+                    .color(egui::Color32::GRAY),
+                );
+            });
+
+            ui.add_space(8.0);
+
+            #[cfg(feature = "websocket")]
+            panel_section(ui, &mut layout, PanelSection::WebSocket, |ui| {
+                ui.checkbox(&mut websocket_settings.enabled, tr("Serve state over WebSocket"));
+                ui.horizontal(|ui| {
+                    ui.label(tr("Bind:"));
+                    ui.add(
+                        egui::TextEdit::singleline(&mut websocket_settings.bind)
+                            .desired_width(110.0),
+                    )
+                    .on_hover_text(tr("0.0.0.0 accepts clients from other devices on the network"));
+                    ui.label(tr("Port:"));
+                    ui.add(egui::DragValue::new(&mut websocket_settings.port).range(1..=u16::MAX));
+                });
+                ui.add(
+                    egui::Slider::new(
+                        &mut websocket_settings.rate,
+                        websocket::MIN_RATE..=websocket::MAX_RATE,
+                    )
+                    .logarithmic(true)
+                    .text(tr("Rate (Hz)")),
+                );
+                if let Some(error) = &websocket_server.error {
+                    ui.colored_label(egui::Color32::LIGHT_RED, format!("⚠ {error}"));
+                } else if let Some(addr) = websocket_server.local_addr() {
+                    ui.colored_label(
+                        egui::Color32::LIGHT_GREEN,
+                        trf(
+                            "Serving ws://{addr} to {n} clients",
+                            &[("addr", &addr), ("n", &websocket_server.client_count())],
+                        ),
+                    );
+                }
+                ui.label(
+                    egui::RichText::new(tr(
+                        "Clients receive JSON state frames and can send \
+                         {\"command\": \"set\", \"rho\": 35}, pause, resume, or reset.",
+                    ))
+                    .small()
+                    .color(egui::Color32::GRAY),
+                );
+            });
+
+            #[cfg(feature = "websocket")]
+            ui.add_space(8.0);
+
+            panel_section(ui, &mut layout, PanelSection::ViewSync, |ui| {
+                ui.horizontal(|ui| {
+                    for role in SyncRole::ALL {
+                        ui.selectable_value(&mut sync.role, role, tr(role.label()));
+                    }
+                });
+                ui.horizontal(|ui| {
+                    if sync.role == SyncRole::Follow {
+                        ui.label(tr("Host:"));
+                        ui.add(egui::TextEdit::singleline(&mut sync.host).desired_width(110.0));
+                    }
+                    ui.label(tr("Port:"));
+                    ui.add(egui::DragValue::new(&mut sync.port).range(1..=u16::MAX));
+                });
+                if sync.role == SyncRole::Host {
+                    ui.add(
+                        egui::Slider::new(
+                            &mut sync.rate,
+                            view_sync::MIN_RATE..=view_sync::MAX_RATE,
+                        )
+                        .logarithmic(true)
+                        .text(tr("Rate (Hz)")),
+                    );
+                }
+                if let Some(error) = &view_sync.error {
+                    ui.colored_label(egui::Color32::LIGHT_RED, format!("⚠ {error}"));
+                } else if let Some(addr) = view_sync.local_addr() {
+                    ui.colored_label(
+                        egui::Color32::LIGHT_GREEN,
+                        trf(
+                            "Hosting on port {port} for {n} followers",
+                            &[("port", &addr.port()), ("n", &view_sync.follower_count())],
+                        ),
+                    );
+                } else if view_sync.connected {
+                    ui.colored_label(
+                        egui::Color32::LIGHT_GREEN,
+                        trf("🔗 Following {host}", &[("host", &sync.host)]),
+                    );
+                }
+                ui.label(
+                    egui::RichText::new(tr(
+                        "A host sends its parameters, resets, and camera; followers copy them, \
+                         overriding local changes.",
+                    ))
+                    .small()
+                    .color(egui::Color32::GRAY),
+                );
+            });
+
+            ui.add_space(8.0);
+
+            #[cfg(feature = "midi")]
+            panel_section(ui, &mut layout, PanelSection::Midi, |ui| {
+                midi_learn_ui(ui, &mut midi_mappings, &mut midi_input);
+            });
+
+            #[cfg(feature = "midi")]
+            ui.add_space(8.0);
+
+            #[cfg(feature = "audio")]
+            panel_section(ui, &mut layout, PanelSection::Audio, |ui| {
+                audio_panel_ui(ui, &mut audio_settings, &mut audio_input, &slider_ranges);
+            });
+
+            #[cfg(feature = "audio")]
+            ui.add_space(8.0);
+
+            panel_section(ui, &mut layout, PanelSection::Gamepad, |ui| {
+                ui.checkbox(&mut gamepad_settings.enabled, tr("Enable gamepad control"));
+                ui.label(trf("Connected: {n}", &[("n", &gamepads.iter().count())]));
+
+                ui.add_space(4.0);
+                ui.checkbox(&mut gamepad_settings.swap_sticks, tr("Swap sticks (left = pan)"));
+                ui.checkbox(&mut gamepad_settings.invert_y, tr("Invert stick Y"));
+                ui.add(
+                    egui::Slider::new(&mut gamepad_settings.stick_speed, 100.0..=2000.0)
+                        .text(tr("Stick speed"))
+                        .clamp_to_range(true),
+                );
+                ui.add(
+                    egui::Slider::new(&mut gamepad_settings.deadzone, 0.0..=0.5)
+                        .text(tr("Deadzone"))
+                        .clamp_to_range(true),
+                );
+
+                ui.add_space(4.0);
+                let pad = &mut *gamepad_settings;
+                for (label, button) in [
+                    (tr("Pause / resume"), &mut pad.pause_button),
+                    (tr("Reset"), &mut pad.reset_button),
+                ] {
+                    egui::ComboBox::from_label(label)
+                        .selected_text(tr(button.label()))
+                        .show_ui(ui, |ui| {
+                            for option in PadButton::ALL {
+                                ui.selectable_value(button, option, tr(option.label()));
+                            }
+                        });
+                }
+
+                ui.add_space(4.0);
+                ui.label(trf(
+                    "D-pad ↑/↓ nudges {param}, ←/→ selects parameter",
+                    &[("param", &gamepad_settings.nudge_target.label())],
                 ));
+                ui.label(
+                    egui::RichText::new(tr("Left stick orbits, right stick pans, triggers zoom"))
+                        .small()
+                        .color(egui::Color32::GRAY),
+                );
+            });
+
+            ui.add_space(8.0);
+
+            panel_section(ui, &mut layout, PanelSection::Shortcuts, |ui| {
+                egui::Grid::new("shortcuts").num_columns(2).show(ui, |ui| {
+                    for action in ShortcutAction::ALL {
+                        ui.label(tr(action.label()));
+                        let text = if rebind.waiting == Some(action) {
+                            tr("Press a key…").to_string()
+                        } else {
+                            key_label(bindings.key(action))
+                        };
+                        if ui.button(text).clicked() {
+                            rebind.waiting = Some(action);
+                        }
+                        ui.end_row();
+                    }
+                });
+                if ui.button(tr("Restore defaults")).clicked() {
+                    *bindings = KeyBindings::default();
+                }
+                ui.label(
+                    egui::RichText::new(tr("Click a key to rebind it; Esc cancels"))
+                        .small()
+                        .color(egui::Color32::GRAY),
+                );
+            });
+
+            ui.add_space(8.0);
+
+            panel_section(ui, &mut layout, PanelSection::Appearance, |ui| {
+                ui.horizontal(|ui| {
+                    for theme in UiTheme::ALL {
+                        ui.radio_value(&mut appearance.theme, theme, tr(theme.label()));
+                    }
+                });
+                ui.horizontal(|ui| {
+                    ui.color_edit_button_srgb(&mut appearance.accent);
+                    ui.label(tr("Accent color"));
+                });
+                egui::ComboBox::from_label(tr("Language"))
+                    .selected_text(appearance.language.native_name())
+                    .show_ui(ui, |ui| {
+                        for language in Language::ALL {
+                            ui.selectable_value(
+                                &mut appearance.language,
+                                language,
+                                language.native_name(),
+                            );
+                        }
+                    });
+                // Buttons rather than a slider: rescaling moves the widgets
+                // under the pointer, which would fight a drag.
+                ui.horizontal(|ui| {
+                    ui.label(tr("UI scale"));
+                    if ui.small_button("−").clicked() {
+                        appearance.ui_scale = (appearance.ui_scale - UI_SCALE_STEP).max(0.5);
+                    }
+                    ui.label(format!("{:.0}%", appearance.ui_scale * 100.0));
+                    if ui.small_button("+").clicked() {
+                        appearance.ui_scale = (appearance.ui_scale + UI_SCALE_STEP).min(3.0);
+                    }
+                });
+                ui.add(
+                    egui::Slider::new(&mut appearance.panel_opacity, 0.2..=1.0)
+                        .text(tr("Panel opacity")),
+                );
+                ui.horizontal(|ui| {
+                    if ui.button(tr("Reset appearance")).clicked() {
+                        *appearance = Appearance::default();
+                    }
+                    reset_layout = ui.button(tr("Reset panel layout")).clicked();
+                });
+            });
+
+            ui.add_space(8.0);
+
+            panel_section(ui, &mut layout, PanelSection::Display, |ui| {
+                for mode in DisplayMode::ALL {
+                    ui.radio_value(&mut display.mode, mode, tr(mode.label()));
+                }
+                ui.add_enabled_ui(display.mode == DisplayMode::Windowed, |ui| {
+                    let (width, height) = display.resolution;
+                    egui::ComboBox::from_label(tr("Window size"))
+                        .selected_text(format!("{width} × {height}"))
+                        .show_ui(ui, |ui| {
+                            for (w, h) in RESOLUTION_PRESETS {
+                                ui.selectable_value(
+                                    &mut display.resolution,
+                                    (w, h),
+                                    format!("{w} × {h}"),
+                                );
+                            }
+                        });
+                });
+                ui.horizontal(|ui| {
+                    for sync in SyncMode::ALL {
+                        ui.radio_value(&mut display.sync, sync, tr(sync.label()));
+                    }
+                })
+                .response
+                .on_hover_text(tr(
+                    "Strict VSync never tears; No VSync lets the frame rate exceed the display \
+                     refresh rate",
+                ));
+                let cap_label = |cap: Option<u32>| match cap {
+                    Some(fps) => format!("{fps} FPS"),
+                    None => tr("Unlimited").to_string(),
+                };
+                egui::ComboBox::from_label(tr("Frame rate cap"))
+                    .selected_text(cap_label(display.fps_cap))
+                    .show_ui(ui, |ui| {
+                        for cap in FPS_CAPS.map(Some).into_iter().chain([None]) {
+                            ui.selectable_value(&mut display.fps_cap, cap, cap_label(cap));
+                        }
+                    })
+                    .response
+                    .on_hover_text(tr(
+                        "A lower cap saves battery; the simulation keeps the same steps per frame",
+                    ));
+                ui.checkbox(&mut display.always_on_top, tr("Always on top"));
+                ui.checkbox(&mut display.low_power_idle, tr("Low-power idle"))
+                    .on_hover_text(tr(
+                        "While paused with a still camera, redraw only on input instead of every \
+                         frame",
+                    ));
+                ui.checkbox(&mut display.overlay, tr("Transparent overlay"))
+                    .on_hover_text(tr(
+                        "Borderless window with a see-through background, for the desktop or an \
+                         OBS window capture with transparency allowed",
+                    ));
+                ui.add_enabled_ui(display.overlay, |ui| {
+                    ui.checkbox(&mut display.click_through, tr("Click-through"))
+                        .on_hover_text(trf(
+                            "Clicks reach the windows underneath; focus this window and press \
+                             {key} to take the mouse back",
+                            &[("key", &key_label(bindings.toggle_click_through))],
+                        ));
+                });
+                if display.overlay {
+                    ui.label(
+                        egui::RichText::new(trf(
+                            "A window opened without transparency stays opaque until the next \
+                             launch. Press {key} to hide the panels.",
+                            &[("key", &key_label(bindings.toggle_ui))],
+                        ))
+                        .small()
+                        .color(egui::Color32::GRAY),
+                    );
+                }
+            });
+
+            ui.add_space(8.0);
+
+            panel_section(ui, &mut layout, PanelSection::SettingsFiles, |ui| {
+                ui.horizontal(|ui| {
+                    if ui.button(tr("💾 Save settings")).clicked() {
+                        events.save_settings.send(SaveSettingsEvent);
+                    }
+                    if ui.button(tr("📂 Load settings")).clicked() {
+                        events.load_settings.send(LoadSettingsEvent);
+                    }
+                });
+                if let Some(dir) = crate::persistence::config_path("") {
+                    ui.label(
+                        egui::RichText::new(trf(
+                            "Simulation settings also save on exit to {dir}",
+                            &[("dir", &dir.display())],
+                        ))
+                        .small()
+                        .color(egui::Color32::GRAY),
+                    );
+                }
             });
 
             ui.add_space(16.0);
             ui.separator();
 
-            ui.collapsing("❓ Camera Controls", |ui| {
-                ui.label("🖱 Left drag: Orbit");
-                ui.label("🖱 Right drag: Pan");
-                ui.label("🖱 Scroll: Zoom");
+            panel_section(ui, &mut layout, PanelSection::CameraControls, |ui| {
+                ui.label(tr("🖱 Left drag: Orbit"));
+                ui.label(tr("🖱 Right drag: Pan"));
+                ui.label(tr("🖱 Scroll: Zoom"));
+                ui.label(tr("🖱 Double-click: Re-center on head"));
+                ui.label(tr("👆 Touch: 1 finger orbit, 2 fingers pan, pinch zoom"));
+                ui.label(tr("⌨ WASD: Pan"));
+                ui.label(tr("⌨ Arrow keys: Orbit"));
+                ui.label(tr("⌨ Q/E or −/+: Zoom"));
+                ui.label(tr("⌨ Home: Reset view"));
+            });
+        });
+    if reset_layout {
+        *layout = PanelLayout::default();
+    }
+}
+
+fn ab_tabs(ui: &mut egui::Ui, comparison: &mut ComparisonConfig) {
+    if !comparison.enabled {
+        return;
+    }
+    ui.horizontal(|ui| {
+        ui.selectable_value(&mut comparison.editing_b, false, tr("A (left)"));
+        ui.selectable_value(&mut comparison.editing_b, true, tr("B (right)"));
+    });
+    ui.add_space(4.0);
+}
+
+fn trajectories_ui(
+    ui: &mut egui::Ui,
+    trajectories: &mut Trajectories,
+    extras: &mut Query<(Entity, &mut ExtraTrajectory, &mut SimulationStats)>,
+    events: &mut EventWriter<TrajectoryEvent>,
+    config: &SimulationConfig,
+    ranges: &SliderRanges,
+) {
+    let show_hint = tr("Show in Diagnostics and Lobe Switches");
+    egui::Grid::new("trajectories").num_columns(4).show(ui, |ui| {
+        ui.radio_value(&mut trajectories.active, None, "").on_hover_text(show_hint);
+        ui.checkbox(&mut trajectories.main_visible, "");
+        ui.label(tr("Main"));
+        if ui.small_button("⎘").on_hover_text(tr("Clone")).clicked() {
+            events.send(TrajectoryEvent::Clone(None));
+        }
+        ui.end_row();
+
+        for (entity, mut extra, _) in extras.iter_mut() {
+            ui.radio_value(&mut trajectories.active, Some(entity), "").on_hover_text(show_hint);
+            ui.checkbox(&mut extra.visible, "");
+            ui.horizontal(|ui| {
+                let [r, g, b, _] = extra.color.to_srgba().to_u8_array();
+                ui.colored_label(egui::Color32::from_rgb(r, g, b), "⏺");
+                ui.add(egui::TextEdit::singleline(&mut extra.name).desired_width(110.0));
             });
+            ui.horizontal(|ui| {
+                if ui.small_button("⎘").on_hover_text(tr("Clone")).clicked() {
+                    events.send(TrajectoryEvent::Clone(Some(entity)));
+                }
+                if ui.small_button("🗑").on_hover_text(tr("Remove")).clicked() {
+                    events.send(TrajectoryEvent::Remove(entity));
+                }
+            });
+            ui.end_row();
+        }
+    });
+    if let Some((_, mut extra, _)) = trajectories.active.and_then(|e| extras.get_mut(e).ok()) {
+        ui.separator();
+        ui.label(trf("Parameters of {name}", &[("name", &extra.name)]));
+        let mut own_params = extra.params.is_some();
+        if ui.checkbox(&mut own_params, tr("Own σ, ρ, β")).changed() {
+            extra.params = own_params.then(|| config.params());
+        }
+        if let Some(params) = &mut extra.params {
+            for (value, range, label) in [
+                (&mut params.sigma, ranges.sigma, tr("σ (sigma)")),
+                (&mut params.rho, ranges.rho, tr("ρ (rho)")),
+                (&mut params.beta, ranges.beta, tr("β (beta)")),
+            ] {
+                ui.add(ParamSlider::new(value, range.0..=range.1, label).positive());
+            }
+        }
+        let mut own_dt = extra.dt.is_some();
+        if ui.checkbox(&mut own_dt, tr("Own dt")).changed() {
+            extra.dt = own_dt.then_some(config.dt);
+        }
+        if let Some(dt) = &mut extra.dt {
+            let range = ranges.dt.0..=ranges.dt.1;
+            ui.add(
+                ParamSlider::new(dt, range, tr("dt (time step)"))
+                    .logarithmic(true)
+                    .positive(),
+            );
+        }
+    }
+    if ui.button(tr("➕ Add trajectory")).clicked() {
+        events.send(TrajectoryEvent::Add(None));
+    }
+    ui.label(
+        egui::RichText::new(tr(
+            "Added trajectories start just beside x₀, y₀, z₀ and follow the main parameters \
+             unless given their own; Reset returns each to its own start.",
+        ))
+        .small()
+        .color(egui::Color32::GRAY),
+    );
+}
+
+fn annotations_ui(
+    ui: &mut egui::Ui,
+    annotations: &mut Annotations,
+    head: Option<&LorenzState>,
+    focus: Option<Vec3>,
+    params: &LorenzParams,
+) {
+    ui.checkbox(&mut annotations.visible, tr("Show annotations"));
+    let mut remove = None;
+    egui::Grid::new("annotations").num_columns(5).show(ui, |ui| {
+        for (i, annotation) in annotations.items.iter_mut().enumerate() {
+            ui.add(egui::TextEdit::singleline(&mut annotation.label).desired_width(120.0));
+            for value in &mut annotation.position {
+                ui.add(egui::DragValue::new(value).speed(0.1).max_decimals(2));
+            }
+            if ui.small_button("🗑").on_hover_text(tr("Remove")).clicked() {
+                remove = Some(i);
+            }
+            ui.end_row();
+        }
+    });
+    if let Some(i) = remove {
+        annotations.items.remove(i);
+    }
+
+    let next = annotations.items.len() + 1;
+    let label = trf("Note {n}", &[("n", &next)]);
+    ui.horizontal_wrapped(|ui| {
+        if let (true, Some(head)) = (ui.button(tr("➕ At the head")).clicked(), head) {
+            annotations.items.push(Annotation::new(label.clone(), [head.x, head.y, head.z]));
+        }
+        if let (true, Some(focus)) = (ui.button(tr("➕ At the camera focus")).clicked(), focus) {
+            let position = focus.to_array().map(f64::from);
+            annotations.items.push(Annotation::new(label, position));
+        }
+        let equilibria = fixed_points(params);
+        let button =
+            ui.add_enabled(equilibria.is_some(), egui::Button::new(tr("➕ C± fixed points")));
+        if let (true, Some([plus, minus])) = (button.clicked(), equilibria) {
+            annotations.items.push(Annotation::new("C+", plus));
+            annotations.items.push(Annotation::new("C−", minus));
+        }
+    });
+    ui.label(
+        egui::RichText::new(tr(
+            "🎯 Place by clicking can also drop annotations in the view. \
+             They are saved with recorded sessions.",
+        ))
+        .small()
+        .color(egui::Color32::GRAY),
+    );
+}
+
+fn placement_ui(ui: &mut egui::Ui, placement: &mut Placement) {
+    ui.toggle_value(&mut placement.active, tr("🎯 Place by clicking")).on_hover_text(tr(
+        "Click in the 3D view to pick a starting point; dragging still orbits, Esc leaves",
+    ));
+    if !placement.active {
+        return;
+    }
+    egui::ComboBox::from_label(tr("Plane"))
+        .selected_text(tr(placement.plane.label()))
+        .show_ui(ui, |ui| {
+            for plane in PlacementPlane::ALL {
+                ui.selectable_value(&mut placement.plane, plane, tr(plane.label()));
+            }
         });
+    for target in PlacementTarget::ALL {
+        ui.radio_value(&mut placement.target, target, tr(target.label()));
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn clipboard_text(clipboard: &mut EguiClipboard) -> Option<String> {
+    clipboard.get_contents()
+}
+
+#[cfg(target_arch = "wasm32")]
+fn clipboard_text(_clipboard: &mut EguiClipboard) -> Option<String> {
+    None
+}
+
+/// The current state at full (round-trip) precision, with copy buttons.
+fn state_inspector(ui: &mut egui::Ui, state: &LorenzState, config: &mut SimulationConfig) {
+    egui::Grid::new("state_inspector").num_columns(3).show(ui, |ui| {
+        for (label, value) in [("x", state.x), ("y", state.y), ("z", state.z), ("t", state.t)] {
+            let text = value.to_string();
+            ui.label(label);
+            ui.monospace(&text);
+            if ui.small_button("📋").on_hover_text(tr("Copy")).clicked() {
+                ui.output_mut(|o| o.copied_text = text);
+            }
+            ui.end_row();
+        }
+    });
+    ui.horizontal(|ui| {
+        if ui.button(tr("📋 Copy all")).clicked() {
+            let text = format!("{}, {}, {}, {}", state.x, state.y, state.z, state.t);
+            ui.output_mut(|o| o.copied_text = text);
+        }
+        if ui
+            .button(tr("📍 Use as initial condition"))
+            .on_hover_text(tr("Sets x₀, y₀, z₀ to the current state; takes effect on Reset"))
+            .clicked()
+        {
+            config.initial_x = state.x;
+            config.initial_y = state.y;
+            config.initial_z = state.z;
+        }
+    });
+}
+
+fn running_stats_ui(ui: &mut egui::Ui, running: &mut TrajectoryStats) {
+    let rows = [
+        ("x", &running.coordinates[0]),
+        ("y", &running.coordinates[1]),
+        ("z", &running.coordinates[2]),
+        ("|v|", &running.speed),
+    ];
+    egui::Grid::new("running_stats")
+        .num_columns(5)
+        .striped(true)
+        .show(ui, |ui| {
+            ui.label("");
+            for heading in [tr("min"), tr("max"), tr("mean"), "σ"] {
+                ui.label(egui::RichText::new(heading).strong());
+            }
+            ui.end_row();
+            for (label, stats) in rows {
+                ui.label(label);
+                if stats.count == 0 {
+                    for _ in 0..4 {
+                        ui.label("—");
+                    }
+                } else {
+                    for value in [stats.min, stats.max, stats.mean, stats.std_dev()] {
+                        ui.monospace(format!("{value:.2}"));
+                    }
+                }
+                ui.end_row();
+            }
+        });
+    ui.horizontal(|ui| {
+        ui.label(
+            egui::RichText::new(trf("{count} steps", &[("count", &running.speed.count)]))
+                .small()
+                .color(egui::Color32::GRAY),
+        );
+        if ui.small_button(tr("Reset statistics")).clicked() {
+            *running = TrajectoryStats::default();
+        }
+    });
+}
+
+fn slider_ranges_editor(ui: &mut egui::Ui, ranges: &mut SliderRanges) {
+    egui::Grid::new("slider_ranges").num_columns(4).show(ui, |ui| {
+        for (label, range, positive) in [
+            ("σ", &mut ranges.sigma, true),
+            ("ρ", &mut ranges.rho, true),
+            ("β", &mut ranges.beta, true),
+            ("dt", &mut ranges.dt, true),
+            ("x₀ y₀ z₀", &mut ranges.initial, false),
+        ] {
+            let (low, high) = *range;
+            let floor = if positive { 1e-6 } else { f64::NEG_INFINITY };
+            let speed = (high - low) / 200.0;
+            ui.label(label);
+            ui.add(
+                egui::DragValue::new(&mut range.0)
+                    .speed(speed)
+                    .range(floor..=high)
+                    .custom_parser(parse_number),
+            );
+            ui.label(tr("to"));
+            ui.add(
+                egui::DragValue::new(&mut range.1)
+                    .speed(speed)
+                    .range(low..=f64::INFINITY)
+                    .custom_parser(parse_number),
+            );
+            ui.end_row();
+        }
+    });
+    ui.checkbox(&mut ranges.auto_expand, tr("Grow to fit typed values"))
+        .on_hover_text(tr("Widens a slider when its value is typed in beyond the current span"));
+    ui.checkbox(&mut ranges.snap_to_notable, tr("Snap to notable values"))
+        .on_hover_text(tr("σ=10, ρ=28, 24.74, 99.96, β=8/3, … are marked and pull the handle"));
+    if ui.button(tr("Reset ranges")).clicked() {
+        *ranges = SliderRanges::default();
+    }
 }
\ No newline at end of file
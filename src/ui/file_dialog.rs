@@ -0,0 +1,290 @@
+//! Native open / save dialogs for the panel's file fields. Each dialog runs on
+//! its own thread so the window keeps drawing, and the folder of the last
+//! chosen file is remembered for the next one. Web builds and builds without
+//! the `dialogs` feature keep the typed paths only.
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{Receiver, TryRecvError};
+use std::sync::Mutex;
+
+use bevy::prelude::*;
+use bevy_egui::egui;
+use serde::{Deserialize, Serialize};
+
+use crate::capture::CAPTURE_DIR;
+use crate::i18n::{tr, tr_noop};
+
+/// Whether this build can show native dialogs at all.
+pub const AVAILABLE: bool = cfg!(all(feature = "dialogs", not(target_arch = "wasm32")));
+
+/// What the chosen file is for; decides the dialog kind and what happens next.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DialogPurpose {
+    #[cfg(feature = "export")]
+    ExportTrail,
+    #[cfg(feature = "export")]
+    StateLog,
+    #[cfg(feature = "rerun")]
+    RerunRecording,
+    Replay,
+    Session,
+    SaveSnapshot,
+    LoadSnapshot,
+    #[cfg(feature = "export")]
+    Screenshot,
+    #[cfg(feature = "export")]
+    RenderStill,
+    #[cfg(feature = "export")]
+    ExportStats,
+    #[cfg(feature = "export")]
+    ExportManifest,
+    #[cfg(feature = "export")]
+    ExportPoincare,
+    #[cfg(feature = "export")]
+    ExportSymbols,
+    #[cfg(feature = "export")]
+    ExportBifurcation,
+    RecordSession,
+}
+
+impl DialogPurpose {
+    pub fn is_save(&self) -> bool {
+        match self {
+            #[cfg(feature = "export")]
+            Self::ExportTrail
+            | Self::StateLog
+            | Self::Screenshot
+            | Self::RenderStill
+            | Self::ExportStats
+            | Self::ExportManifest
+            | Self::ExportPoincare
+            | Self::ExportSymbols
+            | Self::ExportBifurcation => true,
+            #[cfg(feature = "rerun")]
+            Self::RerunRecording => true,
+            Self::SaveSnapshot | Self::RecordSession => true,
+            Self::Replay | Self::Session | Self::LoadSnapshot => false,
+        }
+    }
+
+    pub fn title(&self) -> &'static str {
+        match self {
+            #[cfg(feature = "export")]
+            Self::ExportTrail => tr_noop("Export trail"),
+            #[cfg(feature = "export")]
+            Self::StateLog => tr_noop("State log file"),
+            #[cfg(feature = "rerun")]
+            Self::RerunRecording => tr_noop("Rerun recording"),
+            Self::Replay => tr_noop("Replay a trajectory"),
+            Self::Session => tr_noop("Play a session"),
+            Self::SaveSnapshot => tr_noop("Save snapshot"),
+            Self::LoadSnapshot => tr_noop("Load snapshot"),
+            #[cfg(feature = "export")]
+            Self::Screenshot => tr_noop("Save screenshot"),
+            #[cfg(feature = "export")]
+            Self::RenderStill => tr_noop("Save still"),
+            #[cfg(feature = "export")]
+            Self::ExportStats => tr_noop("Export statistics history"),
+            #[cfg(feature = "export")]
+            Self::ExportManifest => tr_noop("Export run manifest"),
+            #[cfg(feature = "export")]
+            Self::ExportPoincare => tr_noop("Export Poincaré section"),
+            #[cfg(feature = "export")]
+            Self::ExportSymbols => tr_noop("Export L/R symbols"),
+            #[cfg(feature = "export")]
+            Self::ExportBifurcation => tr_noop("Export bifurcation diagram"),
+            Self::RecordSession => tr_noop("Save session"),
+        }
+    }
+}
+
+/// One dialog to show: its purpose, title, file filter and suggested name.
+pub struct DialogRequest {
+    pub purpose: DialogPurpose,
+    pub title: String,
+    pub filter: String,
+    pub extensions: Vec<&'static str>,
+    /// The file name a save dialog starts with.
+    pub file_name: String,
+}
+
+impl DialogRequest {
+    pub fn new(
+        purpose: DialogPurpose,
+        filter: &str,
+        extensions: &[&'static str],
+        file_name: impl Into<String>,
+    ) -> Self {
+        Self {
+            purpose,
+            title: tr(purpose.title()).to_string(),
+            filter: filter.to_string(),
+            extensions: extensions.to_vec(),
+            file_name: file_name.into(),
+        }
+    }
+}
+
+#[derive(Resource, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct FileDialogSettings {
+    pub last_dir: Option<PathBuf>,
+}
+
+impl FileDialogSettings {
+    pub fn remember(&mut self, chosen: &Path) {
+        if let Some(dir) = chosen.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+            self.last_dir = Some(dir.to_path_buf());
+        }
+    }
+}
+
+/// Where a dialog opens: the folder of the path already typed in the field,
+/// then the last folder used, then captures/ once it exists.
+pub fn starting_directory(field: &str, last_dir: Option<&Path>) -> Option<PathBuf> {
+    let typed = Path::new(field.trim())
+        .parent()
+        .filter(|dir| !dir.as_os_str().is_empty());
+    typed
+        .or(last_dir)
+        .map(Path::to_path_buf)
+        .or_else(|| Some(PathBuf::from(CAPTURE_DIR)))
+        .filter(|dir| dir.is_dir())
+}
+
+type DialogResult = Receiver<Option<PathBuf>>;
+
+struct PendingDialog {
+    purpose: DialogPurpose,
+    result: Mutex<DialogResult>,
+}
+
+#[derive(Resource, Default)]
+pub struct FileDialogs {
+    pending: Option<PendingDialog>,
+}
+
+impl FileDialogs {
+    /// Only one dialog is shown at a time.
+    pub fn is_open(&self) -> bool {
+        self.pending.is_some()
+    }
+
+    /// Opens a dialog in the folder of `field`, or the last folder used.
+    pub fn browse(&mut self, request: DialogRequest, field: &str, settings: &FileDialogSettings) {
+        self.open(
+            request,
+            starting_directory(field, settings.last_dir.as_deref()),
+        );
+    }
+
+    /// Asks where to save a file that would otherwise get a timestamped name
+    /// in captures/. Returns false where there are no dialogs, so the caller
+    /// writes it there straight away.
+    pub fn save_as(&mut self, request: DialogRequest, settings: &FileDialogSettings) -> bool {
+        if AVAILABLE {
+            self.browse(request, "", settings);
+        }
+        AVAILABLE
+    }
+
+    pub fn open(&mut self, request: DialogRequest, directory: Option<PathBuf>) {
+        if self.is_open() {
+            return;
+        }
+        let purpose = request.purpose;
+        if let Some(result) = spawn_dialog(request, directory) {
+            self.pending = Some(PendingDialog {
+                purpose,
+                result: Mutex::new(result),
+            });
+        }
+    }
+
+    /// The chosen file once the dialog closes; cancelling yields nothing.
+    pub fn poll(&mut self) -> Option<(DialogPurpose, PathBuf)> {
+        let pending = self.pending.as_ref()?;
+        let received = pending.result.lock().ok()?.try_recv();
+        match received {
+            Ok(chosen) => {
+                let purpose = pending.purpose;
+                self.pending = None;
+                chosen.map(|path| (purpose, path))
+            }
+            Err(TryRecvError::Empty) => None,
+            Err(TryRecvError::Disconnected) => {
+                self.pending = None;
+                None
+            }
+        }
+    }
+}
+
+/// The "…" button beside a file field; absent where dialogs are unavailable.
+pub fn browse_button(ui: &mut egui::Ui, dialogs: &FileDialogs) -> bool {
+    AVAILABLE
+        && ui
+            .add_enabled(!dialogs.is_open(), egui::Button::new("…"))
+            .on_hover_text(tr("Choose a file"))
+            .clicked()
+}
+
+#[cfg(all(feature = "dialogs", not(target_arch = "wasm32")))]
+fn spawn_dialog(request: DialogRequest, directory: Option<PathBuf>) -> Option<DialogResult> {
+    let (sender, receiver) = std::sync::mpsc::channel();
+    let spawned = std::thread::Builder::new()
+        .name("file-dialog".into())
+        .spawn(move || {
+            // The async dialog hands itself to the main thread where the
+            // platform requires it (macOS), so it can be awaited from here.
+            let mut dialog = rfd::AsyncFileDialog::new()
+                .set_title(request.title)
+                .add_filter(request.filter, request.extensions.as_slice());
+            if let Some(directory) = directory {
+                dialog = dialog.set_directory(directory);
+            }
+            let chosen = if request.purpose.is_save() {
+                bevy::tasks::block_on(dialog.set_file_name(request.file_name).save_file())
+            } else {
+                bevy::tasks::block_on(dialog.pick_file())
+            };
+            let _ = sender.send(chosen.map(|file| file.path().to_path_buf()));
+        });
+    match spawned {
+        Ok(_) => Some(receiver),
+        Err(error) => {
+            warn!("Could not open a file dialog: {error}");
+            None
+        }
+    }
+}
+
+#[cfg(not(all(feature = "dialogs", not(target_arch = "wasm32"))))]
+fn spawn_dialog(_request: DialogRequest, _directory: Option<PathBuf>) -> Option<DialogResult> {
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dialogs_start_in_the_typed_or_last_folder() {
+        let here = std::env::current_dir().unwrap();
+        let typed = here.join("missing.csv");
+        assert_eq!(
+            starting_directory(&typed.display().to_string(), None),
+            Some(here.clone())
+        );
+        assert_eq!(starting_directory("", Some(&here)), Some(here.clone()));
+        assert_eq!(
+            starting_directory("", Some(Path::new("no/such/folder"))),
+            None
+        );
+
+        let mut settings = FileDialogSettings::default();
+        settings.remember(Path::new("run.csv"));
+        assert_eq!(settings.last_dir, None);
+        settings.remember(&typed);
+        assert_eq!(settings.last_dir, Some(here));
+    }
+}
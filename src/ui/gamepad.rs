@@ -0,0 +1,99 @@
+use bevy::input::gamepad::{
+    GamepadAxis, GamepadAxisType, GamepadButton, GamepadButtonType, Gamepads,
+};
+use bevy::prelude::*;
+
+use crate::config::{GamepadSettings, NudgeParameter, ResetEvent, SimulationConfig};
+use crate::rendering::camera_input::CameraInput;
+
+const TRIGGER_ZOOM_SPEED: f32 = 12.0;
+
+fn nudge(config: &mut SimulationConfig, target: NudgeParameter, direction: f64) {
+    match target {
+        NudgeParameter::Sigma => {
+            config.sigma = (config.sigma + 0.5 * direction).clamp(0.1, 30.0);
+        }
+        NudgeParameter::Rho => {
+            config.rho = (config.rho + 0.5 * direction).clamp(0.1, 50.0);
+        }
+        NudgeParameter::Beta => {
+            config.beta = (config.beta + 0.05 * direction).clamp(0.1, 10.0);
+        }
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn gamepad_system(
+    gamepads: Res<Gamepads>,
+    buttons: Res<ButtonInput<GamepadButton>>,
+    axes: Res<Axis<GamepadAxis>>,
+    button_axes: Res<Axis<GamepadButton>>,
+    mut settings: ResMut<GamepadSettings>,
+    mut config: ResMut<SimulationConfig>,
+    mut input: ResMut<CameraInput>,
+    mut reset_events: EventWriter<ResetEvent>,
+    time: Res<Time>,
+) {
+    if !settings.enabled {
+        return;
+    }
+
+    let dt = time.delta_seconds();
+    let deadzone = settings.deadzone;
+    let y_sign = if settings.invert_y { 1.0 } else { -1.0 };
+
+    for gamepad in gamepads.iter() {
+        let stick = |x: GamepadAxisType, y: GamepadAxisType| -> Vec2 {
+            let value = Vec2::new(
+                axes.get(GamepadAxis::new(gamepad, x)).unwrap_or(0.0),
+                axes.get(GamepadAxis::new(gamepad, y)).unwrap_or(0.0),
+            );
+            if value.length() < deadzone {
+                Vec2::ZERO
+            } else {
+                Vec2::new(value.x, value.y * y_sign)
+            }
+        };
+
+        let mut orbit = stick(GamepadAxisType::LeftStickX, GamepadAxisType::LeftStickY);
+        let mut pan = stick(GamepadAxisType::RightStickX, GamepadAxisType::RightStickY);
+        if settings.swap_sticks {
+            std::mem::swap(&mut orbit, &mut pan);
+        }
+        input.orbit += orbit * settings.stick_speed * dt;
+        input.pan += pan * settings.stick_speed * dt;
+
+        let trigger = |button: GamepadButtonType| -> f32 {
+            button_axes
+                .get(GamepadButton::new(gamepad, button))
+                .unwrap_or(0.0)
+        };
+        let zoom =
+            trigger(GamepadButtonType::RightTrigger2) - trigger(GamepadButtonType::LeftTrigger2);
+        input.zoom += zoom * TRIGGER_ZOOM_SPEED * dt;
+
+        let pressed = |button: GamepadButtonType| -> bool {
+            buttons.just_pressed(GamepadButton::new(gamepad, button))
+        };
+
+        if pressed(settings.pause_button.button_type()) {
+            config.paused = !config.paused;
+        }
+        if pressed(settings.reset_button.button_type()) {
+            reset_events.send(ResetEvent);
+        }
+
+        if pressed(GamepadButtonType::DPadRight) {
+            settings.nudge_target = settings.nudge_target.next();
+        }
+        if pressed(GamepadButtonType::DPadLeft) {
+            settings.nudge_target = settings.nudge_target.previous();
+        }
+        if pressed(GamepadButtonType::DPadUp) {
+            nudge(&mut config, settings.nudge_target, 1.0);
+        }
+        if pressed(GamepadButtonType::DPadDown) {
+            nudge(&mut config, settings.nudge_target, -1.0);
+        }
+    }
+}
\ No newline at end of file
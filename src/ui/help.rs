@@ -0,0 +1,262 @@
+use bevy_egui::egui;
+
+use crate::i18n::{tr, tr_noop};
+
+/// Tooltip content for one control or readout.
+pub struct Help {
+    pub title: &'static str,
+    pub equation: Option<&'static str>,
+    pub body: &'static [&'static str],
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HelpTopic {
+    Sigma,
+    Rho,
+    Beta,
+    TimeStep,
+    StepsPerFrame,
+    Euler,
+    RungeKutta4,
+    InitialConditions,
+    Lyapunov,
+    Energy,
+    Velocity,
+    Divergence,
+    IntegrationTime,
+}
+
+impl HelpTopic {
+    #[cfg(test)]
+    pub const ALL: [HelpTopic; 13] = [
+        Self::Sigma,
+        Self::Rho,
+        Self::Beta,
+        Self::TimeStep,
+        Self::StepsPerFrame,
+        Self::Euler,
+        Self::RungeKutta4,
+        Self::InitialConditions,
+        Self::Lyapunov,
+        Self::Energy,
+        Self::Velocity,
+        Self::Divergence,
+        Self::IntegrationTime,
+    ];
+
+    pub fn help(&self) -> Help {
+        match self {
+            Self::Sigma => Help {
+                title: tr_noop("σ — Prandtl number"),
+                equation: Some("dx/dt = σ(y − x)"),
+                body: const {
+                    &[
+                        tr_noop("Ratio of the fluid's viscosity to its thermal diffusivity."),
+                        tr_noop(
+                            "It sets how quickly the convection speed x catches up with the \
+                             temperature difference y. Lorenz used σ = 10.",
+                        ),
+                    ]
+                },
+            },
+            Self::Rho => Help {
+                title: tr_noop("ρ — Rayleigh number (relative to onset)"),
+                equation: Some("dy/dt = x(ρ − z) − y"),
+                body: const {
+                    &[
+                        tr_noop("How strongly the fluid layer is heated from below."),
+                        tr_noop("ρ < 1: heat is conducted and the fluid stays at rest."),
+                        tr_noop(
+                            "1 < ρ < 24.74: steady convection rolls, the fixed points C±; past ρ \
+                             ≈ 13.926 orbits can wander chaotically before settling.",
+                        ),
+                        tr_noop(
+                            "ρ > 24.74: sustained chaos, interrupted by periodic windows (e.g. \
+                             near 99.96) and ending in a single limit cycle above ~313.",
+                        ),
+                    ]
+                },
+            },
+            Self::Beta => Help {
+                title: tr_noop("β — geometric factor"),
+                equation: Some("dz/dt = xy − βz"),
+                body: const {
+                    &[
+                        tr_noop("Set by the width-to-height ratio of the convection rolls."),
+                        tr_noop(
+                            "β = 8/3 is the roll shape that starts convecting first; larger β \
+                             damps the vertical temperature profile z faster.",
+                        ),
+                    ]
+                },
+            },
+            Self::TimeStep => Help {
+                title: tr_noop("dt — integration time step"),
+                equation: None,
+                body: const {
+                    &[
+                        tr_noop("Model time advanced by each integrator step."),
+                        tr_noop(
+                            "Smaller steps are more accurate but need more steps per frame for \
+                             the same speed. RK4 stays accurate up to about 0.01 at the classic \
+                             parameters; Euler needs far smaller steps.",
+                        ),
+                    ]
+                },
+            },
+            Self::StepsPerFrame => Help {
+                title: tr_noop("Steps per frame"),
+                equation: Some("model time per frame = steps × dt"),
+                body: const {
+                    &[
+                        tr_noop("Controls playback speed without changing accuracy."),
+                    ]
+                },
+            },
+            Self::Euler => Help {
+                title: tr_noop("Forward Euler"),
+                equation: Some("X(n+1) = X(n) + dt·F(X(n))"),
+                body: const {
+                    &[
+                        tr_noop(
+                            "One evaluation per step; the error shrinks only in proportion to dt.",
+                        ),
+                        tr_noop(
+                            "It systematically spirals outward on rotating motion, so orbits \
+                             visibly drift at large dt. Useful for seeing why the method matters.",
+                        ),
+                    ]
+                },
+            },
+            Self::RungeKutta4 => Help {
+                title: tr_noop("Runge–Kutta 4"),
+                equation: None,
+                body: const {
+                    &[
+                        tr_noop(
+                            "Four evaluations per step, blended so the error shrinks with dt⁴: \
+                             halving dt cuts it sixteenfold.",
+                        ),
+                    ]
+                },
+            },
+            Self::InitialConditions => Help {
+                title: tr_noop("Initial conditions"),
+                equation: None,
+                body: const {
+                    &[
+                        tr_noop(
+                            "Where the trajectory starts. Almost every starting point ends up on \
+                             the same attractor; only the route there differs.",
+                        ),
+                        tr_noop("The fixed points — the origin and C± — are the exceptions."),
+                    ]
+                },
+            },
+            Self::Lyapunov => Help {
+                title: tr_noop("Sensitivity — the Lyapunov exponent"),
+                equation: Some("|δ(t)| ≈ |δ(0)|·e^(λ₁t),  λ₁ ≈ 0.906"),
+                body: const {
+                    &[
+                        tr_noop(
+                            "At the classic parameters, nearby trajectories separate \
+                             exponentially. A difference of 1e-10 reaches the size of the \
+                             attractor after roughly 30 time units.",
+                        ),
+                        tr_noop(
+                            "A positive largest exponent is the signature of chaos. The full \
+                             spectrum is about (0.906, 0, −14.57); it sums to the divergence.",
+                        ),
+                    ]
+                },
+            },
+            Self::Energy => Help {
+                title: tr_noop("Energy ½|X|²"),
+                equation: None,
+                body: const {
+                    &[
+                        tr_noop(
+                            "Half the squared distance from the origin. It is not conserved, but \
+                             it stays bounded: every orbit enters and never leaves a fixed \
+                             ellipsoid around the attractor.",
+                        ),
+                    ]
+                },
+            },
+            Self::Velocity => Help {
+                title: tr_noop("Velocity |dX/dt|"),
+                equation: None,
+                body: const {
+                    &[
+                        tr_noop(
+                            "Speed along the trajectory. It drops near the centre of each lobe, \
+                             close to C±, and peaks on the wide outer swings.",
+                        ),
+                    ]
+                },
+            },
+            Self::Divergence => Help {
+                title: tr_noop("Divergence of the flow"),
+                equation: Some("div F = −(σ + 1 + β)"),
+                body: const {
+                    &[
+                        tr_noop(
+                            "The divergence does not depend on position, so any blob of starting \
+                             states shrinks in volume by e^(−(σ+1+β)t) everywhere.",
+                        ),
+                        tr_noop(
+                            "That is why the attractor has zero volume, with a fractal dimension \
+                             of about 2.06.",
+                        ),
+                    ]
+                },
+            },
+            Self::IntegrationTime => Help {
+                title: tr_noop("Integration time"),
+                equation: None,
+                body: const {
+                    &[
+                        tr_noop("Wall-clock time spent integrating during the last frame."),
+                    ]
+                },
+            },
+        }
+    }
+}
+
+pub fn help_ui(ui: &mut egui::Ui, topic: HelpTopic) {
+    let help = topic.help();
+    ui.set_max_width(320.0);
+    ui.label(egui::RichText::new(tr(help.title)).strong());
+    if let Some(equation) = help.equation {
+        ui.label(egui::RichText::new(equation).monospace());
+    }
+    for paragraph in help.body {
+        ui.label(tr(paragraph));
+    }
+}
+
+pub trait HoverHelp {
+    fn on_hover_help(self, topic: HelpTopic) -> Self;
+}
+
+impl HoverHelp for egui::Response {
+    fn on_hover_help(self, topic: HelpTopic) -> Self {
+        self.on_hover_ui(|ui| help_ui(ui, topic))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_every_topic_has_content() {
+        for topic in HelpTopic::ALL {
+            let help = topic.help();
+            assert!(!help.title.is_empty(), "{topic:?}");
+            assert!(help.body.iter().all(|p| !p.is_empty()), "{topic:?}");
+            assert!(!help.body.is_empty(), "{topic:?}");
+        }
+    }
+}
\ No newline at end of file
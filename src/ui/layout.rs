@@ -0,0 +1,261 @@
+use bevy::prelude::*;
+#[cfg(feature = "ui")]
+use bevy_egui::egui;
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "ui")]
+use crate::i18n::tr;
+use crate::i18n::tr_noop;
+
+#[cfg(feature = "ui")]
+const DETACH_OFFSET: f32 = 40.0;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum PanelSection {
+    Parameters,
+    EditHistory,
+    Integration,
+    Trail,
+    Comparison,
+    Trajectories,
+    Separation,
+    Gallery,
+    InitialConditions,
+    Annotations,
+    Diagnostics,
+    TimeSeries,
+    PhasePortrait,
+    Spectrum,
+    Embedding,
+    Complexity,
+    Histograms,
+    Density,
+    LobeSwitches,
+    Script,
+    Camera,
+    CameraPath,
+    Capture,
+    Export,
+    StateLog,
+    Rerun,
+    Replay,
+    Session,
+    Snapshot,
+    Osc,
+    WebSocket,
+    ViewSync,
+    Midi,
+    Audio,
+    Gamepad,
+    Shortcuts,
+    Appearance,
+    Display,
+    SettingsFiles,
+    CameraControls,
+}
+
+impl PanelSection {
+    pub fn title(&self) -> &'static str {
+        match self {
+            Self::Parameters => tr_noop("🔬 Lorenz Parameters"),
+            Self::EditHistory => tr_noop("🕘 Edit History"),
+            Self::Integration => tr_noop("⚙️ Integration"),
+            Self::Trail => tr_noop("🎨 Trail"),
+            Self::Comparison => tr_noop("🆚 A/B Comparison"),
+            Self::Trajectories => tr_noop("📚 Trajectories"),
+            Self::Separation => tr_noop("📏 Separation"),
+            Self::Gallery => tr_noop("🔲 ρ Gallery"),
+            Self::InitialConditions => tr_noop("📍 Initial Conditions"),
+            Self::Annotations => tr_noop("🏷 Annotations"),
+            Self::Diagnostics => tr_noop("📊 Diagnostics"),
+            Self::TimeSeries => tr_noop("📈 Time Series"),
+            Self::PhasePortrait => tr_noop("🌀 Phase Portrait"),
+            Self::Spectrum => tr_noop("〰 Power Spectrum"),
+            Self::Embedding => tr_noop("⏱ Delay Embedding"),
+            Self::Complexity => tr_noop("🧮 Complexity"),
+            Self::Histograms => tr_noop("📶 Histograms"),
+            Self::Density => tr_noop("🔥 Density Heatmap"),
+            Self::LobeSwitches => tr_noop("🔀 Lobe Switches"),
+            Self::Script => tr_noop("📜 Script"),
+            Self::Camera => tr_noop("🎥 Camera"),
+            Self::CameraPath => tr_noop("🎬 Camera Path"),
+            Self::Capture => tr_noop("📷 Capture"),
+            Self::Export => tr_noop("💾 Export"),
+            Self::StateLog => tr_noop("🗒 State Log"),
+            Self::Rerun => tr_noop("📼 Rerun"),
+            Self::Replay => tr_noop("⏯ Replay"),
+            Self::Session => tr_noop("🎬 Session"),
+            Self::Snapshot => tr_noop("📌 Snapshot"),
+            Self::Osc => tr_noop("📡 OSC Output"),
+            Self::WebSocket => tr_noop("🌐 WebSocket"),
+            Self::ViewSync => tr_noop("🔗 View Sync"),
+            Self::Midi => tr_noop("🎹 MIDI"),
+            Self::Audio => tr_noop("🎤 Audio Input"),
+            Self::Gamepad => tr_noop("🎮 Gamepad"),
+            Self::Shortcuts => tr_noop("⌨ Shortcuts"),
+            Self::Appearance => tr_noop("🖌 Appearance"),
+            Self::Display => tr_noop("🖥 Display"),
+            Self::SettingsFiles => tr_noop("🗂 Settings Files"),
+            Self::CameraControls => tr_noop("❓ Camera Controls"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum PanelSide {
+    #[default]
+    Left,
+    Right,
+}
+
+impl PanelSide {
+    #[cfg(feature = "ui")]
+    pub fn egui_side(&self) -> egui::panel::Side {
+        match self {
+            Self::Left => egui::panel::Side::Left,
+            Self::Right => egui::panel::Side::Right,
+        }
+    }
+
+    pub fn flipped(&self) -> Self {
+        match self {
+            Self::Left => Self::Right,
+            Self::Right => Self::Left,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DetachedPanel {
+    pub section: PanelSection,
+    pub pos: [f32; 2],
+}
+
+/// Where the control panel sits, which of its sections are expanded, and
+/// which have been torn off into floating windows.
+#[derive(Resource, Clone, PartialEq, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PanelLayout {
+    pub side: PanelSide,
+    /// Shrinks the panel to a thin strip holding only the ☰ toggle.
+    pub collapsed: bool,
+    pub open: Vec<PanelSection>,
+    pub detached: Vec<DetachedPanel>,
+}
+
+impl PanelLayout {
+    pub fn is_open(&self, section: PanelSection) -> bool {
+        self.open.contains(&section)
+    }
+
+    pub fn set_open(&mut self, section: PanelSection, open: bool) {
+        self.open.retain(|s| *s != section);
+        if open {
+            self.open.push(section);
+        }
+    }
+
+    #[cfg(feature = "ui")]
+    pub fn detached_pos(&self, section: PanelSection) -> Option<egui::Pos2> {
+        self.detached
+            .iter()
+            .find(|d| d.section == section)
+            .map(|d| egui::Pos2::from(d.pos))
+    }
+
+    #[cfg(feature = "ui")]
+    pub fn detach(&mut self, section: PanelSection, pos: egui::Pos2) {
+        self.dock(section);
+        self.detached.push(DetachedPanel {
+            section,
+            pos: pos.into(),
+        });
+    }
+
+    /// Returns a torn-off section to the side panel, expanded.
+    pub fn dock(&mut self, section: PanelSection) {
+        self.detached.retain(|d| d.section != section);
+        self.set_open(section, true);
+    }
+
+    #[cfg(feature = "ui")]
+    fn moved(&mut self, section: PanelSection, pos: egui::Pos2) {
+        if let Some(detached) = self.detached.iter_mut().find(|d| d.section == section) {
+            if egui::Pos2::from(detached.pos) != pos {
+                detached.pos = pos.into();
+            }
+        }
+    }
+}
+
+/// Shows `section` either as an expandable block inside the side panel or,
+/// once torn off, as its own window. Closing the window docks it again.
+#[cfg(feature = "ui")]
+pub fn panel_section(
+    ui: &mut egui::Ui,
+    layout: &mut PanelLayout,
+    section: PanelSection,
+    add_body: impl FnOnce(&mut egui::Ui),
+) {
+    if let Some(pos) = layout.detached_pos(section) {
+        let ctx = ui.ctx().clone();
+        let mut open = true;
+        let window = egui::Window::new(tr(section.title()))
+            .id(egui::Id::new(("panel_window", section)))
+            .default_pos(pos)
+            .open(&mut open)
+            .show(&ctx, add_body);
+        if let Some(window) = window {
+            layout.moved(section, window.response.rect.min);
+        }
+        if !open {
+            layout.dock(section);
+        }
+        return;
+    }
+
+    let open = layout.is_open(section);
+    ui.horizontal(|ui| {
+        let arrow = if open { "⏷" } else { "⏵" };
+        let header = ui.selectable_label(false, format!("{arrow} {}", tr(section.title())));
+        if header.clicked() {
+            layout.set_open(section, !open);
+        }
+        ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+            let tear_off = ui
+                .small_button("🗖")
+                .on_hover_text(tr("Open in its own window"));
+            if tear_off.clicked() {
+                let pos = header.rect.right_top() + egui::vec2(DETACH_OFFSET, 0.0);
+                layout.detach(section, pos);
+            }
+        });
+    });
+    if open {
+        ui.indent(("panel_section", section), add_body);
+    }
+}
+
+#[cfg(all(test, feature = "ui"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detach_and_dock_round_trip() {
+        let mut layout = PanelLayout::default();
+        layout.detach(PanelSection::Diagnostics, egui::pos2(400.0, 80.0));
+        layout.detach(PanelSection::Diagnostics, egui::pos2(500.0, 90.0));
+        assert_eq!(layout.detached.len(), 1);
+        assert_eq!(
+            layout.detached_pos(PanelSection::Diagnostics),
+            Some(egui::pos2(500.0, 90.0))
+        );
+
+        layout.dock(PanelSection::Diagnostics);
+        assert_eq!(layout.detached_pos(PanelSection::Diagnostics), None);
+
+        let text = toml::to_string_pretty(&layout).unwrap();
+        let loaded: PanelLayout = toml::from_str(&text).unwrap();
+        assert!(loaded == layout);
+    }
+}
\ No newline at end of file
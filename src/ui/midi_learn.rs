@@ -0,0 +1,72 @@
+use bevy_egui::egui;
+
+use crate::i18n::{tr, trf};
+use crate::midi::{MidiInputState, MidiMappings, MidiTarget};
+
+pub fn midi_learn_ui(ui: &mut egui::Ui, mappings: &mut MidiMappings, midi: &mut MidiInputState) {
+    ui.horizontal(|ui| {
+        ui.label(tr("Device:"));
+        let selected = mappings.device.clone().unwrap_or_else(|| tr("None").to_string());
+        egui::ComboBox::from_id_source("midi_device")
+            .selected_text(selected)
+            .width(150.0)
+            .show_ui(ui, |ui| {
+                ui.selectable_value(&mut mappings.device, None, tr("None"));
+                for port in &midi.ports {
+                    ui.selectable_value(&mut mappings.device, Some(port.clone()), port);
+                }
+            });
+        if ui.button("🔄").on_hover_text(tr("Rescan devices")).clicked() {
+            midi.refresh = true;
+        }
+    });
+
+    if let Some(error) = &midi.error {
+        ui.colored_label(egui::Color32::LIGHT_RED, format!("⚠ {error}"));
+    } else if midi.ports.is_empty() {
+        ui.label(tr("No MIDI inputs found"));
+    }
+
+    let Some(device) = mappings.device.clone() else {
+        return;
+    };
+    if midi.is_connected() {
+        ui.colored_label(
+            egui::Color32::LIGHT_GREEN,
+            trf("Listening to {device}", &[("device", &device)]),
+        );
+    }
+
+    egui::Grid::new("midi_bindings").num_columns(3).show(ui, |ui| {
+        for target in MidiTarget::ALL {
+            ui.label(tr(target.label()));
+            match mappings.binding(&device, target) {
+                Some(binding) => ui.label(trf(
+                    "CC {cc} · ch {channel}",
+                    &[("cc", &binding.controller), ("channel", &(binding.channel + 1))],
+                )),
+                None => ui.label("—"),
+            };
+            ui.horizontal(|ui| {
+                let learning = midi.learning == Some(target);
+                let text = if learning { tr("Move a control…") } else { tr("🎯 Learn") };
+                if ui.selectable_label(learning, text).clicked() {
+                    midi.learning = (!learning).then_some(target);
+                }
+                if mappings.binding(&device, target).is_some()
+                    && ui.small_button("🗑").on_hover_text(tr("Forget")).clicked()
+                {
+                    mappings.forget(&device, target);
+                }
+            });
+            ui.end_row();
+        }
+    });
+    ui.label(
+        egui::RichText::new(tr(
+            "Click Learn, then turn a knob or move a fader. Bindings are saved for each device.",
+        ))
+        .small()
+        .color(egui::Color32::GRAY),
+    );
+}
\ No newline at end of file
@@ -1 +1,27 @@
-pub mod controls;
\ No newline at end of file
+#[cfg(feature = "ui")]
+pub mod appearance;
+#[cfg(all(feature = "ui", feature = "audio"))]
+pub mod audio_panel;
+#[cfg(feature = "ui")]
+pub mod controls;
+#[cfg(feature = "ui")]
+pub mod file_dialog;
+pub mod gamepad;
+#[cfg(feature = "ui")]
+pub mod help;
+pub mod layout;
+#[cfg(all(feature = "ui", feature = "midi"))]
+pub mod midi_learn;
+#[cfg(feature = "ui")]
+pub mod performance;
+#[cfg(feature = "analysis")]
+pub mod plots;
+#[cfg(feature = "scripting")]
+pub mod script_editor;
+#[cfg(feature = "ui")]
+pub mod shortcuts;
+pub mod toast;
+#[cfg(feature = "ui")]
+pub mod tutorial;
+#[cfg(feature = "ui")]
+pub mod widgets;
\ No newline at end of file
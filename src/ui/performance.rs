@@ -0,0 +1,122 @@
+use std::collections::VecDeque;
+
+use bevy::prelude::*;
+use bevy_egui::egui;
+
+use crate::config::SimulationStats;
+use crate::i18n::{tr, trf};
+use crate::invariants::InvariantChecks;
+use crate::ui::help::{HelpTopic, HoverHelp};
+use crate::ui::widgets::sparkline;
+
+pub const PERFORMANCE_FRAMES: usize = 300;
+/// The FPS readout averages this many frames so it does not flicker.
+const FPS_AVERAGE_FRAMES: usize = 60;
+
+/// Per-frame wall-clock costs for the last `PERFORMANCE_FRAMES` frames.
+#[derive(Resource, Default)]
+pub struct PerformanceHistory {
+    pub frame_ms: VecDeque<f64>,
+    pub integration_us: VecDeque<f64>,
+}
+
+impl PerformanceHistory {
+    pub fn push(&mut self, frame_ms: f64, integration_us: f64) {
+        for (series, value) in [
+            (&mut self.frame_ms, frame_ms),
+            (&mut self.integration_us, integration_us),
+        ] {
+            series.push_back(value);
+            if series.len() > PERFORMANCE_FRAMES {
+                series.pop_front();
+            }
+        }
+    }
+}
+
+pub fn performance_history_system(
+    time: Res<Time<Real>>,
+    stats: Res<SimulationStats>,
+    mut history: ResMut<PerformanceHistory>,
+) {
+    history.push(time.delta_seconds_f64() * 1000.0, stats.integration_time_us);
+}
+
+pub fn performance_ui(ui: &mut egui::Ui, history: &PerformanceHistory) {
+    let frame_ms: Vec<f64> = history.frame_ms.iter().copied().collect();
+    let recent = &frame_ms[frame_ms.len().saturating_sub(FPS_AVERAGE_FRAMES)..];
+    let mean = recent.iter().sum::<f64>() / recent.len().max(1) as f64;
+    let fps = if mean > 0.0 { 1000.0 / mean } else { 0.0 };
+    ui.label(format!(
+        "{}: {:.1} ms ({fps:.0} FPS)",
+        tr("Frame time"),
+        frame_ms.last().copied().unwrap_or_default()
+    ));
+    series_sparkline(ui, &frame_ms, "ms", egui::Color32::from_rgb(120, 200, 255));
+
+    let integration_us: Vec<f64> = history.integration_us.iter().copied().collect();
+    ui.label(format!(
+        "{}: {:.1} μs",
+        tr("Integration time"),
+        integration_us.last().copied().unwrap_or_default()
+    ))
+    .on_hover_help(HelpTopic::IntegrationTime);
+    series_sparkline(ui, &integration_us, "μs", egui::Color32::from_rgb(255, 180, 90));
+}
+
+pub fn invariants_ui(ui: &mut egui::Ui, checks: &mut InvariantChecks) {
+    ui.checkbox(&mut checks.enabled, tr("Check invariants"))
+        .on_hover_text(tr(
+            "Every frame, check that trails stay within their cap, statistics are finite, time \
+             only moves forward and the camera distance is in bounds",
+        ));
+    if !checks.enabled {
+        return;
+    }
+    if checks.violations.is_empty() {
+        ui.colored_label(egui::Color32::LIGHT_GREEN, tr("✔ All invariants hold"));
+    }
+    for (invariant, detail) in &checks.violations {
+        ui.colored_label(
+            egui::Color32::LIGHT_RED,
+            format!("⚠ {}: {detail}", tr(invariant.label())),
+        );
+    }
+    if checks.failed_frames > 0 {
+        ui.label(
+            egui::RichText::new(trf(
+                "Violations on {frames} frames so far",
+                &[("frames", &checks.failed_frames)],
+            ))
+            .small()
+            .color(egui::Color32::GRAY),
+        );
+    }
+}
+
+fn series_sparkline(ui: &mut egui::Ui, values: &[f64], unit: &str, color: egui::Color32) {
+    let peak = values.iter().copied().fold(0.0, f64::max);
+    sparkline(ui, values, color).on_hover_text(trf(
+        "Peak over the last {frames} frames: {peak}",
+        &[
+            ("frames", &PERFORMANCE_FRAMES),
+            ("peak", &format!("{peak:.1} {unit}")),
+        ],
+    ));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_history_keeps_the_latest_frames() {
+        let mut history = PerformanceHistory::default();
+        for frame in 0..PERFORMANCE_FRAMES + 10 {
+            history.push(frame as f64, 2.0 * frame as f64);
+        }
+        assert_eq!(history.frame_ms.len(), PERFORMANCE_FRAMES);
+        assert_eq!(history.frame_ms.front(), Some(&10.0));
+        assert_eq!(history.integration_us.back(), Some(&(2.0 * 309.0)));
+    }
+}
\ No newline at end of file
@@ -0,0 +1,771 @@
+use bevy::prelude::Resource;
+use bevy_egui::egui;
+use egui_plot::{Bar, BarChart, Legend, Line, Plot, PlotImage, PlotPoint, PlotPoints, Points, VLine};
+
+use crate::config::{
+    Colormap, ComplexityConfig, DensityConfig, EmbeddingConfig, EntropyMeasure, HistogramConfig,
+    PhasePair, PhasePortraitConfig, SpectrumConfig, SpectrumWindow, TimeSeriesConfig,
+    DENSITY_RESOLUTIONS, SPECTRUM_LENGTHS,
+};
+use crate::export::coordinates;
+use crate::i18n::{tr, trf};
+use crate::simulation::butterfly::Separation;
+use crate::simulation::complexity::Complexity;
+use crate::simulation::density::DensityMap;
+use crate::simulation::embedding::{ami_curve, first_minimum};
+use crate::simulation::integrator::TrailBuffer;
+use crate::simulation::spectrum::{power_spectrum, SpectrumSampler};
+use crate::simulation::statistics::{block_entropy, Lobe, LobeStats, ENTROPY_WINDOW};
+
+/// Longer spans are strided down to about this many vertices per curve.
+pub const MAX_PLOT_POINTS: usize = 4000;
+/// Grid cells across the wider side of the phase portrait.
+const PORTRAIT_CELLS: f64 = 600.0;
+pub const MAX_PORTRAIT_POINTS: usize = 20_000;
+const RESIDENCE_BINS: usize = 30;
+const RECENT_SYMBOLS: usize = 48;
+const MAX_BLOCK_LENGTH: usize = 6;
+/// Latest x(t) samples the delay embedding is computed from.
+const EMBEDDING_SAMPLES: usize = 4096;
+
+const CHANNEL_NAMES: [&str; 3] = ["x", "y", "z"];
+// Matches the axis gizmos: Lorenz y is drawn along Bevy's z, and z along y.
+const CHANNEL_COLORS: [egui::Color32; 3] = [
+    egui::Color32::from_rgb(255, 77, 77),
+    egui::Color32::from_rgb(77, 77, 255),
+    egui::Color32::from_rgb(77, 255, 77),
+];
+
+/// Index of the first trail point within `window` time units of the head.
+fn window_start(trail: &TrailBuffer, window: f64) -> usize {
+    let Some(head) = trail.points.back() else {
+        return 0;
+    };
+    trail.points.partition_point(|p| p.time < head.time - window)
+}
+
+/// `(t, x)`, `(t, y)` and `(t, z)` over the last `window` time units.
+pub fn time_series(trail: &TrailBuffer, window: f64) -> [Vec<[f64; 2]>; 3] {
+    let mut series: [Vec<[f64; 2]>; 3] = Default::default();
+    let start = window_start(trail, window);
+    let stride = (trail.points.len() - start).div_ceil(MAX_PLOT_POINTS).max(1);
+    for point in trail.points.range(start..).step_by(stride) {
+        let (x, y, z) = coordinates(point);
+        for (curve, value) in series.iter_mut().zip([x, y, z]) {
+            curve.push([point.time, f64::from(value)]);
+        }
+    }
+    series
+}
+
+pub fn time_series_ui(ui: &mut egui::Ui, trail: &TrailBuffer, config: &mut TimeSeriesConfig) {
+    ui.horizontal(|ui| {
+        for (i, shown) in config.channels.iter_mut().enumerate() {
+            ui.checkbox(shown, egui::RichText::new(CHANNEL_NAMES[i]).color(CHANNEL_COLORS[i]));
+        }
+    });
+    ui.add(
+        egui::Slider::new(&mut config.window, 1.0..=200.0)
+            .logarithmic(true)
+            .text(tr("Window (time units)")),
+    );
+
+    let series = time_series(trail, config.window);
+    // Scrolling stays with the side panel; Ctrl+scroll or a right-drag box zooms.
+    Plot::new("time_series")
+        .height(180.0)
+        .legend(Legend::default())
+        .allow_scroll(false)
+        .x_axis_label("t")
+        .show(ui, |plot_ui| {
+            for (i, curve) in series.into_iter().enumerate() {
+                if config.channels[i] {
+                    let line = Line::new(PlotPoints::new(curve))
+                        .name(CHANNEL_NAMES[i])
+                        .color(CHANNEL_COLORS[i]);
+                    plot_ui.line(line);
+                }
+            }
+        });
+    ui.label(
+        egui::RichText::new(tr("Double-click the plot to follow the head again"))
+            .small()
+            .color(egui::Color32::GRAY),
+    );
+}
+
+/// The trail projected onto `pair` and resampled so that consecutive
+/// vertices are at least one grid cell apart. If that still leaves more than
+/// `MAX_PORTRAIT_POINTS`, the spacing grows until it fits.
+pub fn phase_portrait(trail: &TrailBuffer, pair: PhasePair) -> Vec<[f64; 2]> {
+    let (h, v) = pair.axes();
+    let projected: Vec<[f64; 2]> = trail
+        .points
+        .iter()
+        .map(|point| {
+            let (x, y, z) = coordinates(point);
+            let values = [x, y, z];
+            [f64::from(values[h]), f64::from(values[v])]
+        })
+        .collect();
+
+    let mut min = [f64::INFINITY; 2];
+    let mut max = [f64::NEG_INFINITY; 2];
+    for p in &projected {
+        for i in 0..2 {
+            min[i] = min[i].min(p[i]);
+            max[i] = max[i].max(p[i]);
+        }
+    }
+    let mut spacing = (max[0] - min[0]).max(max[1] - min[1]) / PORTRAIT_CELLS;
+    loop {
+        let curve = thin(&projected, spacing);
+        if curve.len() <= MAX_PORTRAIT_POINTS || spacing <= 0.0 {
+            return curve;
+        }
+        spacing *= curve.len() as f64 / MAX_PORTRAIT_POINTS as f64 * 1.05;
+    }
+}
+
+/// Keeps the first and last points, and every point at least `spacing` from
+/// the previously kept one.
+fn thin(points: &[[f64; 2]], spacing: f64) -> Vec<[f64; 2]> {
+    let mut kept: Vec<[f64; 2]> = Vec::new();
+    for &p in points {
+        let far = kept.last().is_none_or(|last| {
+            (p[0] - last[0]).abs() >= spacing || (p[1] - last[1]).abs() >= spacing
+        });
+        if far {
+            kept.push(p);
+        }
+    }
+    if let (Some(&head), Some(last)) = (points.last(), kept.last()) {
+        if *last != head {
+            kept.push(head);
+        }
+    }
+    kept
+}
+
+pub fn phase_portrait_ui(ui: &mut egui::Ui, trail: &TrailBuffer, config: &mut PhasePortraitConfig) {
+    ui.horizontal(|ui| {
+        for pair in PhasePair::ALL {
+            ui.radio_value(&mut config.pair, pair, pair.label());
+        }
+    });
+
+    let curve = phase_portrait(trail, config.pair);
+    let head = curve.last().copied();
+    let (h, v) = config.pair.axes();
+    // One plot memory per pair, so switching pairs autoscales afresh.
+    Plot::new(("phase_portrait", config.pair.label()))
+        .height(220.0)
+        .data_aspect(1.0)
+        .allow_scroll(false)
+        .x_axis_label(CHANNEL_NAMES[h])
+        .y_axis_label(CHANNEL_NAMES[v])
+        .show(ui, |plot_ui| {
+            plot_ui.line(Line::new(PlotPoints::new(curve)));
+            if let Some(head) = head {
+                plot_ui.points(Points::new(vec![head]).radius(3.0).color(egui::Color32::WHITE));
+            }
+        });
+}
+
+/// Counts of `values` in `bins` equal-width bins spanning their range, as
+/// the bin width and `(centre, count)` pairs.
+pub fn histogram(values: &[f64], bins: usize) -> (f64, Vec<[f64; 2]>) {
+    let min = values.iter().copied().fold(f64::INFINITY, f64::min);
+    let max = values.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+    if values.is_empty() || bins == 0 {
+        return (1.0, Vec::new());
+    }
+    let width = if max > min { (max - min) / bins as f64 } else { 1.0 };
+    let mut counts = vec![0usize; bins];
+    for value in values {
+        let bin = ((value - min) / width) as usize;
+        counts[bin.min(bins - 1)] += 1;
+    }
+    let centres = counts
+        .into_iter()
+        .enumerate()
+        .map(|(i, count)| [min + (i as f64 + 0.5) * width, count as f64])
+        .collect();
+    (width, centres)
+}
+
+pub fn histogram_ui(ui: &mut egui::Ui, trail: &TrailBuffer, config: &mut HistogramConfig) {
+    ui.add(egui::Slider::new(&mut config.bins, 5..=200).text(tr("Bins")));
+    ui.add(
+        egui::Slider::new(&mut config.window, 1.0..=500.0)
+            .logarithmic(true)
+            .text(tr("Window (time units)")),
+    );
+
+    let start = window_start(trail, config.window);
+    let mut values: [Vec<f64>; 3] = Default::default();
+    for point in trail.points.range(start..) {
+        let (x, y, z) = coordinates(point);
+        for (channel, value) in values.iter_mut().zip([x, y, z]) {
+            channel.push(f64::from(value));
+        }
+    }
+
+    for (i, channel) in values.iter().enumerate() {
+        let (width, counts) = histogram(channel, config.bins);
+        let bars = counts
+            .into_iter()
+            .map(|[centre, count]| Bar::new(centre, count).width(width))
+            .collect();
+        Plot::new(("histogram", i))
+            .height(90.0)
+            .allow_scroll(false)
+            .x_axis_label(CHANNEL_NAMES[i])
+            .show(ui, |plot_ui| {
+                plot_ui.bar_chart(BarChart::new(bars).color(CHANNEL_COLORS[i]));
+            });
+    }
+}
+
+/// Evenly spaced stops of each colormap, from empty to densest.
+fn colormap_stops(colormap: Colormap) -> [[u8; 3]; 5] {
+    match colormap {
+        Colormap::Inferno => [
+            [0, 0, 4],
+            [87, 16, 110],
+            [188, 55, 84],
+            [249, 142, 9],
+            [252, 255, 164],
+        ],
+        Colormap::Viridis => [
+            [68, 1, 84],
+            [59, 82, 139],
+            [33, 145, 140],
+            [94, 201, 98],
+            [253, 231, 37],
+        ],
+        Colormap::Magma => [
+            [0, 0, 4],
+            [81, 18, 124],
+            [183, 55, 121],
+            [252, 137, 97],
+            [252, 253, 191],
+        ],
+        Colormap::Grayscale => [
+            [0, 0, 0],
+            [64, 64, 64],
+            [128, 128, 128],
+            [191, 191, 191],
+            [255, 255, 255],
+        ],
+    }
+}
+
+/// `colormap` at `t` in `[0, 1]`, interpolated linearly between its stops.
+pub fn colormap_color(colormap: Colormap, t: f64) -> egui::Color32 {
+    let stops = colormap_stops(colormap);
+    let scaled = t.clamp(0.0, 1.0) * (stops.len() - 1) as f64;
+    let i = (scaled as usize).min(stops.len() - 2);
+    let f = scaled - i as f64;
+    let [r, g, b] = [0, 1, 2].map(|c| {
+        let (from, to) = (f64::from(stops[i][c]), f64::from(stops[i + 1][c]));
+        (from + (to - from) * f).round() as u8
+    });
+    egui::Color32::from_rgb(r, g, b)
+}
+
+/// The density map as an image, top row first, each cell coloured by its
+/// count relative to the densest one.
+pub fn density_image(
+    density: &DensityMap,
+    log_scale: bool,
+    colormap: Colormap,
+) -> egui::ColorImage {
+    let n = density.resolution;
+    let peak = f64::from(density.max_count().max(1));
+    let scale = |count: u32| {
+        if log_scale {
+            f64::from(count).ln_1p() / peak.ln_1p()
+        } else {
+            f64::from(count) / peak
+        }
+    };
+    let pixels = (0..n)
+        .rev()
+        .flat_map(|row| density.counts[row * n..(row + 1) * n].iter())
+        .map(|&count| colormap_color(colormap, scale(count)))
+        .collect();
+    egui::ColorImage {
+        size: [n, n],
+        pixels,
+    }
+}
+
+/// The heatmap texture and the map revision and styling it was drawn from,
+/// so it is only re-uploaded when one of them changes.
+#[derive(Resource, Default)]
+pub struct DensityTexture {
+    handle: Option<egui::TextureHandle>,
+    drawn: Option<(u64, bool, Colormap)>,
+}
+
+pub fn density_ui(
+    ui: &mut egui::Ui,
+    density: &mut DensityMap,
+    config: &mut DensityConfig,
+    texture: &mut DensityTexture,
+) {
+    ui.horizontal(|ui| {
+        ui.checkbox(&mut config.enabled, tr("Accumulate"));
+        if ui.button(tr("Clear")).clicked() {
+            density.clear();
+        }
+    });
+    ui.horizontal(|ui| {
+        for pair in PhasePair::ALL {
+            ui.radio_value(&mut config.plane, pair, pair.label());
+        }
+    });
+    ui.horizontal(|ui| {
+        egui::ComboBox::from_id_source("density_resolution")
+            .selected_text(format!("{0}×{0}", config.resolution))
+            .width(80.0)
+            .show_ui(ui, |ui| {
+                for resolution in DENSITY_RESOLUTIONS {
+                    ui.selectable_value(
+                        &mut config.resolution,
+                        resolution,
+                        format!("{resolution}×{resolution}"),
+                    );
+                }
+            });
+        egui::ComboBox::from_id_source("density_colormap")
+            .selected_text(tr(config.colormap.label()))
+            .width(80.0)
+            .show_ui(ui, |ui| {
+                for colormap in Colormap::ALL {
+                    ui.selectable_value(&mut config.colormap, colormap, tr(colormap.label()));
+                }
+            });
+        ui.checkbox(&mut config.log_scale, tr("Log scale"));
+    });
+
+    let Some((min, max)) = density.bounds else {
+        ui.label(tr("No steps binned yet"));
+        return;
+    };
+    let drawn = (density.revision, config.log_scale, config.colormap);
+    if texture.drawn != Some(drawn) || texture.handle.is_none() {
+        let image = density_image(density, config.log_scale, config.colormap);
+        let options = egui::TextureOptions::NEAREST;
+        match &mut texture.handle {
+            Some(handle) => handle.set(image, options),
+            None => texture.handle = Some(ui.ctx().load_texture("density_map", image, options)),
+        }
+        texture.drawn = Some(drawn);
+    }
+    let Some(handle) = &texture.handle else {
+        return;
+    };
+
+    let (h, v) = config.plane.axes();
+    let centre = PlotPoint::new((min[0] + max[0]) / 2.0, (min[1] + max[1]) / 2.0);
+    let size = egui::vec2((max[0] - min[0]) as f32, (max[1] - min[1]) as f32);
+    Plot::new(("density", config.plane.label()))
+        .height(220.0)
+        .data_aspect(1.0)
+        .allow_scroll(false)
+        .x_axis_label(CHANNEL_NAMES[h])
+        .y_axis_label(CHANNEL_NAMES[v])
+        .show(ui, |plot_ui| {
+            plot_ui.image(PlotImage::new(handle.id(), centre, size));
+        });
+    ui.label(
+        egui::RichText::new(trf(
+            "{steps} steps binned; the densest cell holds {peak}",
+            &[("steps", &density.total), ("peak", &density.max_count())],
+        ))
+        .small()
+        .color(egui::Color32::GRAY),
+    );
+}
+
+pub fn lobe_switches_ui(ui: &mut egui::Ui, lobes: &mut LobeStats) {
+    ui.label(trf("Switches: {count}", &[("count", &lobes.switches)]));
+    for lobe in Lobe::ALL {
+        let residence = &lobes.residence[lobe as usize];
+        let text = if residence.count == 0 {
+            format!("{}: —", tr(lobe.label()))
+        } else {
+            trf(
+                "{lobe}: mean {mean} over {count} visits",
+                &[
+                    ("lobe", &tr(lobe.label())),
+                    ("mean", &format!("{:.3}", residence.mean)),
+                    ("count", &residence.count),
+                ],
+            )
+        };
+        let mut text = egui::RichText::new(text);
+        if lobes.current == Some(lobe) {
+            text = text.strong();
+        }
+        ui.label(text);
+    }
+
+    let durations: Vec<f64> = lobes.durations.iter().copied().collect();
+    let (width, counts) = histogram(&durations, RESIDENCE_BINS);
+    let bars = counts
+        .into_iter()
+        .map(|[centre, count]| Bar::new(centre, count).width(width))
+        .collect();
+    Plot::new("residence_times")
+        .height(120.0)
+        .allow_scroll(false)
+        .x_axis_label(tr("residence time"))
+        .show(ui, |plot_ui| {
+            plot_ui.bar_chart(BarChart::new(bars));
+        });
+
+    ui.separator();
+    let symbols = lobes.symbols.as_bytes();
+    let recent = &lobes.symbols[symbols.len().saturating_sub(RECENT_SYMBOLS)..];
+    ui.label(tr("Symbol sequence (latest last):"));
+    ui.monospace(if recent.is_empty() { "—" } else { recent });
+    let window = &symbols[symbols.len().saturating_sub(ENTROPY_WINDOW)..];
+    egui::Grid::new("block_entropies").num_columns(3).striped(true).show(ui, |ui| {
+        for heading in ["n", "H(n)", "H(n) − H(n−1)"] {
+            ui.label(egui::RichText::new(heading).strong());
+        }
+        ui.end_row();
+        let mut previous = 0.0;
+        for n in 1..=MAX_BLOCK_LENGTH {
+            let Some(entropy) = block_entropy(window, n) else {
+                break;
+            };
+            ui.label(n.to_string());
+            ui.monospace(format!("{entropy:.3}"));
+            ui.monospace(format!("{:.3}", entropy - previous));
+            ui.end_row();
+            previous = entropy;
+        }
+    });
+    ui.label(
+        egui::RichText::new(tr("Block entropies in bits; the increments approach the entropy rate"))
+            .small()
+            .color(egui::Color32::GRAY),
+    );
+
+    if ui.small_button(tr("Reset counts")).clicked() {
+        *lobes = LobeStats::default();
+    }
+}
+
+pub fn spectrum_ui(ui: &mut egui::Ui, sampler: &SpectrumSampler, config: &mut SpectrumConfig) {
+    egui::ComboBox::from_label(tr("Window length"))
+        .selected_text(config.length.to_string())
+        .show_ui(ui, |ui| {
+            for length in SPECTRUM_LENGTHS {
+                ui.selectable_value(&mut config.length, length, length.to_string());
+            }
+        });
+    ui.horizontal(|ui| {
+        for window in [SpectrumWindow::Hann, SpectrumWindow::Flat] {
+            ui.radio_value(&mut config.window, window, tr(window.label()));
+        }
+    });
+
+    let Some(samples) = sampler.latest(config.length) else {
+        ui.add(
+            egui::ProgressBar::new(sampler.len() as f32 / config.length as f32).text(trf(
+                "Collecting x(t): {have}/{need} steps",
+                &[("have", &sampler.len()), ("need", &config.length)],
+            )),
+        );
+        return;
+    };
+    let spectrum: Vec<[f64; 2]> = power_spectrum(&samples, sampler.dt(), config.window)
+        .into_iter()
+        .filter(|[_, power]| *power > 0.0)
+        .map(|[frequency, power]| [frequency, power.log10()])
+        .collect();
+    Plot::new("spectrum")
+        .height(180.0)
+        .allow_scroll(false)
+        .x_axis_label(tr("frequency (1 / time unit)"))
+        .y_axis_label("log₁₀ PSD")
+        .show(ui, |plot_ui| {
+            plot_ui.line(Line::new(PlotPoints::new(spectrum)).color(CHANNEL_COLORS[0]));
+        });
+    ui.label(
+        egui::RichText::new(tr(
+            "Chaos spreads power over a broad band; periodic orbits show sharp peaks",
+        ))
+        .small()
+        .color(egui::Color32::GRAY),
+    );
+}
+
+/// `(x(t), x(t − lag))` over `samples`, strided down to about
+/// `MAX_PLOT_POINTS` vertices.
+pub fn delay_pairs(samples: &[f64], lag: usize) -> Vec<[f64; 2]> {
+    let pairs = samples.len().saturating_sub(lag);
+    let stride = pairs.div_ceil(MAX_PLOT_POINTS).max(1);
+    (lag..samples.len())
+        .step_by(stride)
+        .map(|i| [samples[i], samples[i - lag]])
+        .collect()
+}
+
+pub fn embedding_ui(ui: &mut egui::Ui, sampler: &SpectrumSampler, config: &mut EmbeddingConfig) {
+    ui.add(
+        egui::Slider::new(&mut config.max_delay, 0.2..=5.0)
+            .logarithmic(true)
+            .text(tr("Longest delay")),
+    );
+    ui.add(egui::Slider::new(&mut config.bins, 8..=64).text(tr("Bins")));
+
+    let Some(samples) = sampler.latest(EMBEDDING_SAMPLES) else {
+        ui.add(
+            egui::ProgressBar::new(sampler.len() as f32 / EMBEDDING_SAMPLES as f32).text(trf(
+                "Collecting x(t): {have}/{need} steps",
+                &[("have", &sampler.len()), ("need", &EMBEDDING_SAMPLES)],
+            )),
+        );
+        return;
+    };
+    let dt = sampler.dt();
+    let max_lag = ((config.max_delay / dt).round() as usize).clamp(2, samples.len() / 2);
+    let curve = ami_curve(&samples, max_lag, config.bins);
+    let suggested = first_minimum(&curve).map(|lag| lag as f64 * dt);
+
+    match suggested {
+        Some(tau) => ui.label(trf(
+            "First minimum: τ = {tau}",
+            &[("tau", &format!("{tau:.3}"))],
+        )),
+        None => ui.colored_label(
+            egui::Color32::LIGHT_RED,
+            tr("No minimum within the longest delay"),
+        ),
+    };
+    ui.checkbox(&mut config.auto_delay, tr("Use the first minimum"));
+    if let (true, Some(tau)) = (config.auto_delay, suggested) {
+        config.delay = tau;
+    }
+    ui.add_enabled(
+        !config.auto_delay,
+        egui::Slider::new(&mut config.delay, dt..=config.max_delay).text(tr("Delay τ")),
+    );
+
+    let ami: Vec<[f64; 2]> = curve.iter().map(|&(lag, bits)| [lag as f64 * dt, bits]).collect();
+    Plot::new("mutual_information")
+        .height(140.0)
+        .allow_scroll(false)
+        .x_axis_label("τ")
+        .y_axis_label(tr("AMI (bits)"))
+        .show(ui, |plot_ui| {
+            plot_ui.line(Line::new(PlotPoints::new(ami)).color(CHANNEL_COLORS[0]));
+            plot_ui.vline(VLine::new(config.delay).color(egui::Color32::WHITE));
+        });
+
+    let lag = ((config.delay / dt).round() as usize).clamp(1, samples.len() - 1);
+    Plot::new("delay_reconstruction")
+        .height(220.0)
+        .data_aspect(1.0)
+        .allow_scroll(false)
+        .x_axis_label("x(t)")
+        .y_axis_label("x(t − τ)")
+        .show(ui, |plot_ui| {
+            plot_ui.line(Line::new(PlotPoints::new(delay_pairs(&samples, lag))));
+        });
+    ui.label(
+        egui::RichText::new(tr(
+            "x(t) against its delayed copy unfolds both wings of the attractor from one \
+             coordinate",
+        ))
+        .small()
+        .color(egui::Color32::GRAY),
+    );
+}
+
+pub fn complexity_ui(ui: &mut egui::Ui, complexity: &Complexity, config: &mut ComplexityConfig) {
+    ui.horizontal(|ui| {
+        for measure in EntropyMeasure::ALL {
+            ui.radio_value(&mut config.measure, measure, tr(measure.label()));
+        }
+    });
+    ui.horizontal(|ui| {
+        ui.label(tr("Coordinate:"));
+        for (i, name) in CHANNEL_NAMES.iter().enumerate() {
+            ui.radio_value(&mut config.coordinate, i, *name);
+        }
+    });
+    ui.add(egui::Slider::new(&mut config.window, 100..=1000).text(tr("Window (samples)")));
+    ui.add(
+        egui::Slider::new(&mut config.spacing, 0.01..=0.5)
+            .logarithmic(true)
+            .text(tr("Sample spacing")),
+    );
+    ui.add(egui::Slider::new(&mut config.template, 1..=4).text(tr("Template length m")));
+    ui.add(egui::Slider::new(&mut config.tolerance, 0.05..=0.5).text(tr("Tolerance r (× σ)")));
+
+    let span = config.window as f64 * config.spacing;
+    match complexity.latest {
+        Some(value) => {
+            ui.label(
+                egui::RichText::new(format!("{}: {value:.3}", tr(config.measure.label())))
+                    .strong(),
+            );
+        }
+        None if !complexity.window_full => {
+            ui.label(trf(
+                "Waiting for {span} time units of trail",
+                &[("span", &format!("{span:.1}"))],
+            ));
+        }
+        None => {
+            ui.label(tr("No templates match; raise the tolerance"));
+        }
+    }
+
+    let history: Vec<[f64; 2]> = complexity.history.iter().copied().collect();
+    Plot::new("complexity")
+        .height(140.0)
+        .allow_scroll(false)
+        .include_y(0.0)
+        .x_axis_label("t")
+        .y_axis_label(tr(config.measure.label()))
+        .show(ui, |plot_ui| {
+            let color = CHANNEL_COLORS[config.coordinate.min(2)];
+            plot_ui.line(Line::new(PlotPoints::new(history)).color(color));
+        });
+    ui.label(
+        egui::RichText::new(tr(
+            "Chaos keeps the entropy well above zero; move ρ into a periodic window such as \
+             99.96 and it falls toward zero once the window holds only the new orbit",
+        ))
+        .small()
+        .color(egui::Color32::GRAY),
+    );
+}
+
+/// log₁₀ of the distance between the demo pair, with a reference line
+/// growing at `lyapunov` from the first sample.
+pub fn separation_ui(ui: &mut egui::Ui, separation: &Separation, lyapunov: Option<f64>) {
+    let (Some(&[t0, d0]), Some(&[t, d])) = (separation.samples.first(), separation.samples.last())
+    else {
+        ui.label(tr("Run the butterfly effect demo to track two nearby trajectories."));
+        return;
+    };
+    ui.label(format!("|Δ| = {:.3e}", 10f64.powf(d)));
+
+    let stride = separation.samples.len().div_ceil(MAX_PLOT_POINTS).max(1);
+    let measured: Vec<[f64; 2]> = separation.samples.iter().step_by(stride).copied().collect();
+    Plot::new("separation")
+        .height(180.0)
+        .legend(Legend::default())
+        .allow_scroll(false)
+        .x_axis_label("t")
+        .y_axis_label("log₁₀ |Δ|")
+        .show(ui, |plot_ui| {
+            plot_ui.line(
+                Line::new(PlotPoints::new(measured))
+                    .name("log₁₀ |Δ|")
+                    .color(egui::Color32::from_rgb(255, 191, 77)),
+            );
+            if let Some(lambda) = lyapunov.filter(|l| *l > 0.0) {
+                let end = d0 + lambda * (t - t0) / std::f64::consts::LN_10;
+                plot_ui.line(
+                    Line::new(PlotPoints::new(vec![[t0, d0], [t, end]]))
+                        .name(format!("λ₁ ≈ {lambda:.3}"))
+                        .color(egui::Color32::GRAY)
+                        .style(egui_plot::LineStyle::dashed_loose()),
+                );
+            }
+        });
+    ui.label(
+        egui::RichText::new(tr(
+            "The distance grows exponentially, a straight line on this log scale, \
+             until it saturates at the size of the attractor.",
+        ))
+        .small()
+        .color(egui::Color32::GRAY),
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::prelude::*;
+
+    use super::*;
+    use crate::simulation::integrator::TrailPoint;
+
+    #[test]
+    fn test_time_series_keeps_window_and_decimates() {
+        let mut trail = TrailBuffer::default();
+        for i in 0..20_000 {
+            trail.points.push_back(TrailPoint {
+                position: Vec3::new(1.0, 2.0, 3.0),
+                color: Color::WHITE,
+                time: i as f64 * 0.01,
+                speed: 0.0,
+            });
+        }
+
+        let [x, y, z] = time_series(&trail, 50.0);
+        assert!(x.len() <= MAX_PLOT_POINTS);
+        assert!(x.first().unwrap()[0] >= 199.99 - 50.0);
+        assert_eq!((x[0][1], y[0][1], z[0][1]), (1.0, 2.0, 3.0));
+        assert!(time_series(&TrailBuffer::default(), 50.0)[0].is_empty());
+    }
+
+    #[test]
+    fn test_histogram_counts_every_value() {
+        let values = [-2.0, -1.9, -1.8, 0.0, 1.8, 1.9, 2.0];
+        let (width, counts) = histogram(&values, 4);
+        assert_eq!(width, 1.0);
+        let heights: Vec<f64> = counts.iter().map(|[_, count]| *count).collect();
+        assert_eq!(heights, [3.0, 0.0, 1.0, 3.0]);
+        assert_eq!(counts[0][0], -1.5);
+        assert_eq!(histogram(&[5.0, 5.0], 10).1.iter().map(|[_, c]| c).sum::<f64>(), 2.0);
+    }
+
+    #[test]
+    fn test_phase_portrait_thins_dense_trails() {
+        let mut trail = TrailBuffer::default();
+        for i in 0..100_000 {
+            let angle = i as f32 * 0.001;
+            trail.points.push_back(TrailPoint {
+                position: Vec3::new(10.0 * angle.cos(), 0.0, 10.0 * angle.sin() + 25.0),
+                color: Color::WHITE,
+                time: f64::from(angle),
+                speed: 0.0,
+            });
+        }
+
+        let curve = phase_portrait(&trail, PhasePair::XZ);
+        assert!(curve.len() <= MAX_PORTRAIT_POINTS, "{}", curve.len());
+        assert!(curve.len() > MAX_PORTRAIT_POINTS / 2, "{}", curve.len());
+        let head = trail.points.back().unwrap().position;
+        assert_eq!(curve.last(), Some(&[f64::from(head.x), f64::from(head.z)]));
+        assert!(phase_portrait(&TrailBuffer::default(), PhasePair::XY).is_empty());
+    }
+
+    #[test]
+    fn test_density_image_puts_high_values_on_top() {
+        assert_eq!(colormap_color(Colormap::Grayscale, 0.0), egui::Color32::BLACK);
+        assert_eq!(colormap_color(Colormap::Grayscale, 1.0), egui::Color32::WHITE);
+
+        let mut density = DensityMap::new(2);
+        density.add([0.0, 0.0]);
+        density.add([-0.4, -0.4]);
+        density.add([0.4, 0.4]);
+        let image = density_image(&density, false, Colormap::Grayscale);
+        assert_eq!(image.size, [2, 2]);
+        // Row 0 of the image is the top, where the densest cell sits.
+        assert_eq!(image.pixels[1], egui::Color32::WHITE);
+        assert_eq!(image.pixels[2], colormap_color(Colormap::Grayscale, 0.5));
+        assert_eq!(image.pixels[0], egui::Color32::BLACK);
+    }
+}
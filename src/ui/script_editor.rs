@@ -0,0 +1,73 @@
+use bevy::prelude::*;
+use bevy_egui::egui;
+
+use crate::i18n::tr;
+use crate::scripting::{ScriptEvent, ScriptHost, ScriptSettings, EXAMPLE_SCRIPT};
+
+const EDITOR_ROWS: usize = 14;
+
+/// Signatures of the script API; the names are code and stay untranslated.
+const API: &str = "\
+derivatives(x, y, z, sigma, rho, beta) -> [dx, dy, dz]
+on_frame(t)
+get(name)          sigma rho beta dt steps x y z t speed
+set(name, value)   sigma rho beta dt steps
+pause()  resume()  reset()  print(text)
+export_file(kind)  screenshot trajectory poincare symbols stats manifest";
+
+pub fn script_editor_ui(
+    ui: &mut egui::Ui,
+    settings: &mut ScriptSettings,
+    host: &ScriptHost,
+    events: &mut EventWriter<ScriptEvent>,
+) {
+    ui.label(
+        egui::RichText::new(tr(
+            "Define derivatives to replace the Lorenz equations, and on_frame to change \
+             parameters or export over time. Scripts cannot touch files or the network.",
+        ))
+        .small()
+        .color(egui::Color32::GRAY),
+    );
+    ui.collapsing(tr("API"), |ui| {
+        ui.label(egui::RichText::new(API).monospace().small());
+    });
+    ui.add(
+        egui::TextEdit::multiline(&mut settings.source)
+            .code_editor()
+            .desired_rows(EDITOR_ROWS)
+            .desired_width(f32::INFINITY),
+    );
+
+    ui.horizontal(|ui| {
+        let run = if host.is_running() {
+            tr("🔄 Restart")
+        } else {
+            tr("▶ Run")
+        };
+        if ui.button(run).clicked() {
+            events.send(ScriptEvent::Run);
+        }
+        if ui
+            .add_enabled(host.is_running(), egui::Button::new(tr("⏹ Stop")))
+            .clicked()
+        {
+            events.send(ScriptEvent::Stop);
+        }
+        if ui
+            .button(tr("📝 Example"))
+            .on_hover_text(tr("Replace the editor text with the example script"))
+            .clicked()
+        {
+            settings.source = EXAMPLE_SCRIPT.to_string();
+        }
+    });
+
+    if let Some(error) = &host.error {
+        ui.colored_label(egui::Color32::LIGHT_RED, format!("⚠ {error}"));
+    } else if host.has_derivatives() {
+        ui.colored_label(egui::Color32::LIGHT_GREEN, tr("Running with the script's vector field"));
+    } else if host.is_running() {
+        ui.colored_label(egui::Color32::LIGHT_GREEN, tr("Running"));
+    }
+}
\ No newline at end of file
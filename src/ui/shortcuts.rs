@@ -0,0 +1,135 @@
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContexts};
+
+use crate::config::{
+    FitViewEvent, IntegrationMethod, KeyBindings, ResetEvent, ShortcutAction, SimulationConfig,
+    StepEvent, UiVisibility,
+};
+use crate::display::DisplaySettings;
+use crate::i18n::trf;
+use crate::rendering::camera_controller::EguiWantsKeyboard;
+
+const PRESENTATION_HINT_SECONDS: f32 = 4.0;
+
+/// Set by the Shortcuts panel; the next key pressed is bound to this action.
+#[derive(Resource, Default)]
+pub struct KeyRebind {
+    pub waiting: Option<ShortcutAction>,
+}
+
+pub fn key_label(key: KeyCode) -> String {
+    let name = format!("{key:?}");
+    name.strip_prefix("Key")
+        .or_else(|| name.strip_prefix("Digit"))
+        .unwrap_or(&name)
+        .to_string()
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn shortcut_system(
+    keys: Res<ButtonInput<KeyCode>>,
+    egui_keyboard: Res<EguiWantsKeyboard>,
+    mut bindings: ResMut<KeyBindings>,
+    mut rebind: ResMut<KeyRebind>,
+    mut config: ResMut<SimulationConfig>,
+    mut visibility: ResMut<UiVisibility>,
+    mut display: ResMut<DisplaySettings>,
+    mut reset_events: EventWriter<ResetEvent>,
+    mut fit_events: EventWriter<FitViewEvent>,
+    mut step_events: EventWriter<StepEvent>,
+) {
+    if let Some(action) = rebind.waiting {
+        if let Some(&key) = keys.get_just_pressed().next() {
+            if key != KeyCode::Escape {
+                bindings.assign(action, key);
+            }
+            rebind.waiting = None;
+        }
+        return;
+    }
+
+    if egui_keyboard.0 {
+        return;
+    }
+
+    for action in ShortcutAction::ALL {
+        if !keys.just_pressed(bindings.key(action)) {
+            continue;
+        }
+        match action {
+            ShortcutAction::TogglePause => config.paused = !config.paused,
+            ShortcutAction::Reset => {
+                reset_events.send(ResetEvent);
+            }
+            ShortcutAction::SingleStep => {
+                config.paused = true;
+                step_events.send(StepEvent);
+            }
+            ShortcutAction::FitView => {
+                fit_events.send(FitViewEvent);
+            }
+            ShortcutAction::ToggleUi => visibility.presentation = !visibility.presentation,
+            ShortcutAction::UseEuler => config.method = IntegrationMethod::Euler,
+            ShortcutAction::UseRungeKutta4 => config.method = IntegrationMethod::RungeKutta4,
+            ShortcutAction::ToggleClickThrough => display.click_through = !display.click_through,
+        }
+    }
+}
+
+/// Briefly shows which key leaves presentation mode, fading out so it is
+/// gone before any screenshot or recording is worth taking.
+pub fn presentation_hint_system(
+    mut contexts: EguiContexts,
+    visibility: Res<UiVisibility>,
+    bindings: Res<KeyBindings>,
+    time: Res<Time<Real>>,
+    mut was_presenting: Local<bool>,
+    mut shown_for: Local<f32>,
+) {
+    // Capture systems rewrite `UiVisibility` every frame, so compare the flag
+    // itself rather than relying on change detection.
+    if visibility.presentation != *was_presenting {
+        *was_presenting = visibility.presentation;
+        *shown_for = 0.0;
+    }
+    if !visibility.presentation || visibility.hidden || *shown_for > PRESENTATION_HINT_SECONDS {
+        return;
+    }
+    *shown_for += time.delta_seconds();
+
+    // Fade out over the last second.
+    let alpha = (PRESENTATION_HINT_SECONDS - *shown_for).clamp(0.0, 1.0);
+    egui::Area::new(egui::Id::new("presentation_hint"))
+        .anchor(egui::Align2::CENTER_BOTTOM, egui::vec2(0.0, -16.0))
+        .interactable(false)
+        .show(contexts.ctx_mut(), |ui| {
+            ui.label(
+                egui::RichText::new(trf(
+                    "Press {key} to show the UI",
+                    &[("key", &key_label(bindings.toggle_ui))],
+                ))
+                .small()
+                .color(egui::Color32::from_gray(160).gamma_multiply(alpha)),
+            );
+        });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_key_label_strips_prefixes() {
+        assert_eq!(key_label(KeyCode::KeyR), "R");
+        assert_eq!(key_label(KeyCode::Digit1), "1");
+        assert_eq!(key_label(KeyCode::Space), "Space");
+    }
+
+    #[test]
+    fn test_assign_swaps_conflicting_binding() {
+        let mut bindings = KeyBindings::default();
+        bindings.assign(ShortcutAction::Reset, KeyCode::Space);
+        assert_eq!(bindings.reset, KeyCode::Space);
+        assert_eq!(bindings.toggle_pause, KeyCode::KeyR);
+    }
+}
\ No newline at end of file
@@ -0,0 +1,58 @@
+use bevy::prelude::*;
+#[cfg(feature = "ui")]
+use bevy_egui::{egui, EguiContexts};
+
+#[cfg(feature = "ui")]
+use crate::config::UiVisibility;
+
+const TOAST_SECONDS: f32 = 3.0;
+const MAX_TOASTS: usize = 4;
+
+#[derive(Resource, Default)]
+pub struct Toasts {
+    active: Vec<(String, f32)>,
+}
+
+impl Toasts {
+    pub fn push(&mut self, message: impl Into<String>) {
+        let message = message.into();
+        info!("{message}");
+        self.active.push((message, TOAST_SECONDS));
+        if self.active.len() > MAX_TOASTS {
+            self.active.remove(0);
+        }
+    }
+}
+
+#[cfg(feature = "ui")]
+pub fn toast_system(
+    mut contexts: EguiContexts,
+    mut toasts: ResMut<Toasts>,
+    ui_visibility: Res<UiVisibility>,
+    time: Res<Time<Real>>,
+) {
+    if toasts.active.is_empty() || ui_visibility.hidden || ui_visibility.presentation {
+        return;
+    }
+
+    let dt = time.delta_seconds();
+    toasts.active.retain_mut(|(_, remaining)| {
+        *remaining -= dt;
+        *remaining > 0.0
+    });
+
+    let ctx = contexts.ctx_mut();
+    egui::Area::new(egui::Id::new("toasts"))
+        .anchor(egui::Align2::RIGHT_BOTTOM, egui::vec2(-12.0, -12.0))
+        .interactable(false)
+        .show(ctx, |ui| {
+            for (message, remaining) in &toasts.active {
+                let alpha = (remaining / 0.5).min(1.0);
+                egui::Frame::popup(ui.style())
+                    .multiply_with_opacity(alpha)
+                    .show(ui, |ui| {
+                        ui.label(message.as_str());
+                    });
+            }
+        });
+}
\ No newline at end of file
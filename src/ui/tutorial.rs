@@ -0,0 +1,273 @@
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContexts};
+use serde::{Deserialize, Serialize};
+
+use crate::config::{ResetEvent, UiVisibility};
+use crate::i18n::{tr, tr_noop, trf};
+use crate::presets::{ApplyPresetEvent, Preset, PresetCamera};
+
+pub struct TutorialStep {
+    pub title: &'static str,
+    pub body: &'static str,
+    /// Parameters and camera view the step moves to, if any.
+    pub scene: Option<Preset>,
+    pub reset: bool,
+}
+
+impl TutorialStep {
+    fn text(title: &'static str, body: &'static str) -> Self {
+        Self {
+            title,
+            body,
+            scene: None,
+            reset: false,
+        }
+    }
+
+    /// Moves to ρ with the classic σ and β, framed by the preset camera
+    /// unless `view` overrides its angles.
+    fn scene(title: &'static str, body: &'static str, rho: f64, view: Option<(f32, f32)>) -> Self {
+        let mut preset = Preset::classic(title, "", rho, 0.005);
+        if let Some((theta, phi)) = view {
+            preset.camera = PresetCamera {
+                theta,
+                phi,
+                ..PresetCamera::framing(rho.max(1.0))
+            };
+        }
+        Self {
+            scene: Some(preset),
+            ..Self::text(title, body)
+        }
+    }
+
+    fn with_reset(self) -> Self {
+        Self {
+            reset: true,
+            ..self
+        }
+    }
+}
+
+pub fn tutorial_steps() -> Vec<TutorialStep> {
+    vec![
+        TutorialStep::text(
+            tr_noop("Welcome"),
+            tr_noop(
+                "This tour walks through the controls and the science of the Lorenz system. \
+                 Some steps change the parameters or move the camera for you. Use Next and \
+                 Back to move between steps, and leave at any time.",
+            ),
+        ),
+        TutorialStep::text(
+            tr_noop("Moving around"),
+            tr_noop(
+                "Drag with the left mouse button to orbit, drag with the right button to pan, \
+                 and scroll to zoom. Double-click re-centers on the white head marker, and \
+                 Home returns to the default view. The side panel holds every setting; hover \
+                 over a control to see what it does.",
+            ),
+        ),
+        TutorialStep::scene(
+            tr_noop("The strange attractor"),
+            tr_noop(
+                "With Lorenz's original values σ = 10, ρ = 28, β = 8/3, the trajectory settles \
+                 onto a butterfly-shaped set that it never leaves, yet never repeats on. Color \
+                 shows speed: blue is slow, red is fast.",
+            ),
+            28.0,
+            None,
+        )
+        .with_reset(),
+        TutorialStep::scene(
+            tr_noop("Two lobes"),
+            tr_noop(
+                "Seen from above, the two wings wrap around the fixed points C+ and C−. The \
+                 trajectory spirals outward around one of them, then is thrown over to the \
+                 other. The order of these switches looks random; 🔀 Lobe Switches counts them.",
+            ),
+            28.0,
+            Some((0.15, -0.5)),
+        ),
+        TutorialStep::scene(
+            tr_noop("Trajectories never cross"),
+            tr_noop(
+                "The system is deterministic: through every point passes exactly one path. \
+                 Where the trail seems to cross itself, it is only overlapping in projection. \
+                 Orbit the camera to see the sheets pass over and under each other.",
+            ),
+            28.0,
+            Some((1.45, 0.0)),
+        ),
+        TutorialStep::scene(
+            tr_noop("ρ < 1: no convection"),
+            tr_noop(
+                "Below ρ = 1 the fluid is not heated enough to roll. The origin is the only \
+                 fixed point, and every trajectory decays into it.",
+            ),
+            0.5,
+            None,
+        )
+        .with_reset(),
+        TutorialStep::scene(
+            tr_noop("1 < ρ < 24.74: steady rolls"),
+            tr_noop(
+                "Above ρ = 1 the origin turns unstable and the fixed points C± appear: steady \
+                 convection rolls turning one way or the other. Trajectories spiral into one of \
+                 them. From ρ ≈ 13.93 they may wander chaotically for a while first.",
+            ),
+            15.0,
+            None,
+        )
+        .with_reset(),
+        TutorialStep::scene(
+            tr_noop("ρ ≈ 24.74: onset of chaos"),
+            tr_noop(
+                "At ρ ≈ 24.74 the fixed points C± lose their stability. Nothing stable is left \
+                 nearby, and the motion becomes permanently chaotic: the strange attractor \
+                 takes over.",
+            ),
+            25.0,
+            None,
+        )
+        .with_reset(),
+        TutorialStep::scene(
+            tr_noop("Sensitive dependence"),
+            tr_noop(
+                "Two starts a hair apart follow each other for a while and then separate \
+                 completely — the butterfly effect. Try ➕ Add trajectory in 📚 Trajectories, \
+                 or the 🆚 A/B Comparison, and watch them diverge.",
+            ),
+            28.0,
+            None,
+        )
+        .with_reset(),
+        TutorialStep::text(
+            tr_noop("That's the tour"),
+            tr_noop(
+                "Explore the presets, diagnostics, and plots from here. 🎓 at the top of the \
+                 panel starts this tour again.",
+            ),
+        ),
+    ]
+}
+
+/// Whether the tour has been seen, so it only opens by itself on first launch.
+#[derive(Resource, Clone, PartialEq, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TutorialProgress {
+    pub seen: bool,
+}
+
+#[derive(Resource)]
+pub struct Tutorial {
+    pub steps: Vec<TutorialStep>,
+    /// Current step while the tour is open.
+    pub step: Option<usize>,
+    applied: Option<usize>,
+}
+
+impl Default for Tutorial {
+    fn default() -> Self {
+        Self {
+            steps: tutorial_steps(),
+            step: None,
+            applied: None,
+        }
+    }
+}
+
+impl Tutorial {
+    pub fn start(&mut self) {
+        self.step = Some(0);
+        self.applied = None;
+    }
+}
+
+pub fn tutorial_system(
+    mut contexts: EguiContexts,
+    mut tutorial: ResMut<Tutorial>,
+    mut progress: ResMut<TutorialProgress>,
+    ui_visibility: Res<UiVisibility>,
+    mut preset_events: EventWriter<ApplyPresetEvent>,
+    mut reset_events: EventWriter<ResetEvent>,
+    mut started: Local<bool>,
+) {
+    if !*started {
+        *started = true;
+        if !progress.seen {
+            tutorial.start();
+        }
+    }
+    let Some(index) = tutorial.step else {
+        return;
+    };
+
+    if tutorial.applied != Some(index) {
+        tutorial.applied = Some(index);
+        let step = &tutorial.steps[index];
+        if let Some(preset) = &step.scene {
+            preset_events.send(ApplyPresetEvent(preset.clone()));
+        }
+        if step.reset {
+            reset_events.send(ResetEvent);
+        }
+    }
+    if ui_visibility.hidden || ui_visibility.presentation {
+        return;
+    }
+
+    let total = tutorial.steps.len();
+    let step = &tutorial.steps[index];
+    let mut next = Some(index);
+    egui::Window::new(tr("🎓 Guided tour"))
+        .collapsible(false)
+        .resizable(false)
+        .default_width(380.0)
+        .anchor(egui::Align2::CENTER_BOTTOM, egui::vec2(0.0, -24.0))
+        .show(contexts.ctx_mut(), |ui| {
+            ui.label(
+                egui::RichText::new(trf(
+                    "Step {n} of {total}",
+                    &[("n", &(index + 1)), ("total", &total)],
+                ))
+                .small()
+                .color(egui::Color32::GRAY),
+            );
+            ui.heading(tr(step.title));
+            ui.label(tr(step.body));
+            ui.add_space(6.0);
+            ui.horizontal(|ui| {
+                if ui.add_enabled(index > 0, egui::Button::new(tr("◀ Back"))).clicked() {
+                    next = Some(index - 1);
+                }
+                let last = index + 1 == total;
+                let label = if last { tr("Finish") } else { tr("Next ▶") };
+                if ui.button(label).clicked() {
+                    next = (!last).then_some(index + 1);
+                }
+                if !last && ui.button(tr("Skip tour")).clicked() {
+                    next = None;
+                }
+            });
+        });
+
+    tutorial.step = next;
+    if next.is_none() && !progress.seen {
+        progress.seen = true;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tour_starts_and_ends_without_scripted_changes() {
+        let steps = tutorial_steps();
+        assert!(steps.len() > 2);
+        for step in [&steps[0], &steps[steps.len() - 1]] {
+            assert!(step.scene.is_none() && !step.reset);
+        }
+    }
+}
\ No newline at end of file
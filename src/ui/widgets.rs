@@ -0,0 +1,219 @@
+use std::ops::RangeInclusive;
+
+use bevy_egui::egui;
+
+const ENTRY_DECIMALS: usize = 6;
+const SLIDER_WIDTH: f32 = 130.0;
+const EXPAND_HEADROOM: f64 = 0.25;
+/// Snap distance as a fraction of the slider's span.
+const SNAP_FRACTION: f64 = 0.015;
+const TICK_HEIGHT: f32 = 4.0;
+const SPARKLINE_HEIGHT: f32 = 28.0;
+
+/// Parses a plain number or a simple fraction such as `8/3` or `-1/2`.
+pub fn parse_number(text: &str) -> Option<f64> {
+    let text = text.trim();
+    let value = match text.split_once('/') {
+        Some((numerator, denominator)) => {
+            let numerator: f64 = numerator.trim().parse().ok()?;
+            let denominator: f64 = denominator.trim().parse().ok()?;
+            numerator / denominator
+        }
+        None => text.parse().ok()?,
+    };
+    value.is_finite().then_some(value)
+}
+
+/// Widens `range` so `value` lies inside it, leaving some headroom so the
+/// handle doesn't sit pinned at the end. Returns whether the range changed.
+pub fn expand_to_fit(range: &mut (f64, f64), value: f64) -> bool {
+    let margin = value.abs() * EXPAND_HEADROOM;
+    if value > range.1 {
+        range.1 = value + margin;
+        true
+    } else if value < range.0 {
+        range.0 = value - margin;
+        true
+    } else {
+        false
+    }
+}
+
+/// Returns the notable value nearest to `value` if it is within `tolerance`.
+pub fn snap_value(value: f64, notable: &[f64], tolerance: f64) -> f64 {
+    notable
+        .iter()
+        .copied()
+        .filter(|n| (n - value).abs() <= tolerance)
+        .min_by(|a, b| (a - value).abs().total_cmp(&(b - value).abs()))
+        .unwrap_or(value)
+}
+
+/// A slider with an exact-entry box beside it. Typed values may fall outside
+/// the slider's range, and fractions are accepted.
+pub struct ParamSlider<'a> {
+    value: &'a mut f64,
+    range: RangeInclusive<f64>,
+    text: &'a str,
+    logarithmic: bool,
+    positive: bool,
+    snap: Option<&'a [f64]>,
+}
+
+impl<'a> ParamSlider<'a> {
+    pub fn new(value: &'a mut f64, range: RangeInclusive<f64>, text: &'a str) -> Self {
+        Self {
+            value,
+            range,
+            text,
+            logarithmic: false,
+            positive: false,
+            snap: None,
+        }
+    }
+
+    pub fn logarithmic(mut self, logarithmic: bool) -> Self {
+        self.logarithmic = logarithmic;
+        self
+    }
+
+    /// Rejects typed values ≤ 0.
+    pub fn positive(mut self) -> Self {
+        self.positive = true;
+        self
+    }
+
+    /// Marks `notable` values on the rail and snaps dragged values onto them.
+    /// Only meaningful for linear sliders.
+    pub fn snap_to(mut self, notable: &'a [f64]) -> Self {
+        self.snap = Some(notable);
+        self
+    }
+}
+
+impl egui::Widget for ParamSlider<'_> {
+    fn ui(self, ui: &mut egui::Ui) -> egui::Response {
+        let Self {
+            value,
+            range,
+            text,
+            logarithmic,
+            positive,
+            snap,
+        } = self;
+        let speed = (range.end() - range.start()) / 1000.0;
+        let limits = if positive {
+            f64::MIN_POSITIVE..=f64::INFINITY
+        } else {
+            f64::NEG_INFINITY..=f64::INFINITY
+        };
+
+        ui.horizontal(|ui| {
+            ui.spacing_mut().slider_width = SLIDER_WIDTH;
+            let (low, high) = (*range.start(), *range.end());
+            let slider = ui.add(
+                egui::Slider::new(&mut *value, range)
+                    .show_value(false)
+                    .clamp_to_range(false)
+                    .logarithmic(logarithmic),
+            );
+            if let Some(notable) = snap {
+                if slider.dragged() {
+                    *value = snap_value(*value, notable, (high - low) * SNAP_FRACTION);
+                }
+                paint_ticks(ui, slider.rect, low..=high, notable);
+            }
+            let entry = ui.add(
+                egui::DragValue::new(value)
+                    .speed(speed)
+                    .range(limits)
+                    .max_decimals(ENTRY_DECIMALS)
+                    .custom_parser(parse_number),
+            );
+            ui.label(text);
+            slider | entry
+        })
+        .inner
+    }
+}
+
+/// A small line graph of `values` scaled from zero to their maximum, for
+/// trends where the exact numbers matter less than the shape.
+pub fn sparkline(ui: &mut egui::Ui, values: &[f64], color: egui::Color32) -> egui::Response {
+    let size = egui::vec2(ui.available_width(), SPARKLINE_HEIGHT);
+    let (rect, response) = ui.allocate_exact_size(size, egui::Sense::hover());
+    let painter = ui.painter_at(rect);
+    painter.rect_filled(rect, 2.0, ui.visuals().extreme_bg_color);
+
+    let max = values.iter().copied().fold(0.0, f64::max);
+    if values.len() < 2 || max <= 0.0 {
+        return response;
+    }
+    let last = (values.len() - 1) as f32;
+    let points = values
+        .iter()
+        .enumerate()
+        .map(|(i, &v)| {
+            let x = rect.left() + rect.width() * i as f32 / last;
+            let y = rect.bottom() - rect.height() * (v / max) as f32;
+            egui::pos2(x, y)
+        })
+        .collect();
+    painter.add(egui::Shape::line(points, egui::Stroke::new(1.0, color)));
+    response
+}
+
+/// Draws a short mark under the rail for each value inside the slider's span.
+fn paint_ticks(ui: &egui::Ui, rect: egui::Rect, range: RangeInclusive<f64>, values: &[f64]) {
+    // Matches egui's handle inset, so ticks line up with the handle position.
+    let inset = rect.height() / 2.5;
+    let rail = egui::Rangef::new(rect.left() + inset, rect.right() - inset);
+    let stroke = egui::Stroke::new(1.0, ui.visuals().weak_text_color());
+    let bottom = rect.bottom();
+    for &v in values.iter().filter(|v| range.contains(v)) {
+        let x = egui::emath::remap(v, range.clone(), rail.min as f64..=rail.max as f64) as f32;
+        ui.painter().line_segment(
+            [egui::pos2(x, bottom - TICK_HEIGHT), egui::pos2(x, bottom)],
+            stroke,
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_number_accepts_fractions() {
+        assert_eq!(parse_number("99.96"), Some(99.96));
+        assert_eq!(parse_number(" 8 / 3 "), Some(8.0 / 3.0));
+        assert_eq!(parse_number("-1/2"), Some(-0.5));
+        assert_eq!(parse_number("1e-3"), Some(0.001));
+    }
+
+    #[test]
+    fn test_parse_number_rejects_garbage() {
+        assert_eq!(parse_number("1/0"), None);
+        assert_eq!(parse_number("abc"), None);
+        assert_eq!(parse_number("1/2/3"), None);
+    }
+
+    #[test]
+    fn test_snap_value_picks_nearest_within_tolerance() {
+        let rho = [24.06, 24.74, 28.0];
+        assert_eq!(snap_value(24.5, &rho, 0.75), 24.74);
+        assert_eq!(snap_value(26.0, &rho, 0.75), 26.0);
+    }
+
+    #[test]
+    fn test_expand_to_fit_keeps_sign_of_lower_bound() {
+        let mut rho = (0.1, 50.0);
+        assert!(!expand_to_fit(&mut rho, 28.0));
+        assert!(expand_to_fit(&mut rho, 200.0));
+        assert_eq!(rho, (0.1, 250.0));
+
+        let mut dt = (0.0001, 0.05);
+        assert!(expand_to_fit(&mut dt, 0.00004));
+        assert!(dt.0 > 0.0 && dt.0 < 0.00004);
+    }
+}
\ No newline at end of file
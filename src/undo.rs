@@ -0,0 +1,226 @@
+use bevy::prelude::*;
+
+use crate::config::SimulationConfig;
+use crate::i18n::trf;
+use crate::rendering::camera_controller::EguiWantsKeyboard;
+use crate::ui::toast::Toasts;
+
+/// Changes closer together than this merge into one edit, so a slider drag
+/// is undone in one step.
+const MERGE_SECONDS: f32 = 0.75;
+const MAX_EDITS: usize = 100;
+
+pub struct Edit {
+    pub label: String,
+    before: SimulationConfig,
+    after: SimulationConfig,
+    at: f32,
+}
+
+/// Undo/redo stacks for `SimulationConfig`. Pausing and resuming are not
+/// treated as edits.
+#[derive(Resource, Default)]
+pub struct EditHistory {
+    undo: Vec<Edit>,
+    redo: Vec<Edit>,
+    last_seen: Option<SimulationConfig>,
+    merge_open: bool,
+}
+
+fn ignoring_pause(config: &SimulationConfig) -> SimulationConfig {
+    SimulationConfig {
+        paused: false,
+        ..config.clone()
+    }
+}
+
+fn short(value: f64) -> String {
+    let text = format!("{value:.4}");
+    text.trim_end_matches('0').trim_end_matches('.').to_string()
+}
+
+pub fn describe(before: &SimulationConfig, after: &SimulationConfig) -> String {
+    let mut changes = Vec::new();
+    for (name, a, b) in [
+        ("σ", before.sigma, after.sigma),
+        ("ρ", before.rho, after.rho),
+        ("β", before.beta, after.beta),
+        ("dt", before.dt, after.dt),
+        ("x₀", before.initial_x, after.initial_x),
+        ("y₀", before.initial_y, after.initial_y),
+        ("z₀", before.initial_z, after.initial_z),
+    ] {
+        if a != b {
+            changes.push(format!("{name} {} → {}", short(a), short(b)));
+        }
+    }
+    if before.method != after.method {
+        changes.push(format!("method → {}", after.method.short_name()));
+    }
+    if before.steps_per_frame != after.steps_per_frame {
+        changes.push(format!("steps/frame → {}", after.steps_per_frame));
+    }
+    if before.max_trail_points != after.max_trail_points {
+        changes.push(format!("trail → {}", after.max_trail_points));
+    }
+    if changes.is_empty() {
+        changes.push("display options".to_string());
+    }
+    changes.join(", ")
+}
+
+impl EditHistory {
+    pub fn can_undo(&self) -> bool {
+        !self.undo.is_empty()
+    }
+
+    pub fn can_redo(&self) -> bool {
+        !self.redo.is_empty()
+    }
+
+    /// Undoable edits, most recent first.
+    pub fn edits(&self) -> impl Iterator<Item = &Edit> {
+        self.undo.iter().rev()
+    }
+
+    pub fn record(&mut self, config: &SimulationConfig, now: f32) {
+        let Some(previous) = self.last_seen.replace(config.clone()) else {
+            return;
+        };
+        if ignoring_pause(&previous) == ignoring_pause(config) {
+            return;
+        }
+
+        self.redo.clear();
+        match self.undo.last_mut() {
+            Some(edit) if self.merge_open && now - edit.at < MERGE_SECONDS => {
+                edit.after = config.clone();
+                edit.at = now;
+                if ignoring_pause(&edit.before) == ignoring_pause(config) {
+                    self.undo.pop();
+                } else {
+                    edit.label = describe(&edit.before, config);
+                }
+            }
+            _ => {
+                self.undo.push(Edit {
+                    label: describe(&previous, config),
+                    before: previous,
+                    after: config.clone(),
+                    at: now,
+                });
+                if self.undo.len() > MAX_EDITS {
+                    self.undo.remove(0);
+                }
+            }
+        }
+        self.merge_open = true;
+    }
+
+    fn restore(&mut self, config: &mut SimulationConfig, target: &SimulationConfig) {
+        *config = SimulationConfig {
+            paused: config.paused,
+            ..target.clone()
+        };
+        self.last_seen = Some(config.clone());
+        self.merge_open = false;
+    }
+
+    pub fn undo(&mut self, config: &mut SimulationConfig) -> Option<&str> {
+        let edit = self.undo.pop()?;
+        self.restore(config, &edit.before);
+        self.redo.push(edit);
+        self.redo.last().map(|edit| edit.label.as_str())
+    }
+
+    pub fn redo(&mut self, config: &mut SimulationConfig) -> Option<&str> {
+        let edit = self.redo.pop()?;
+        self.restore(config, &edit.after);
+        self.undo.push(edit);
+        self.undo.last().map(|edit| edit.label.as_str())
+    }
+}
+
+pub fn edit_history_system(
+    config: Res<SimulationConfig>,
+    time: Res<Time<Real>>,
+    mut history: ResMut<EditHistory>,
+) {
+    if config.is_changed() {
+        history.record(&config, time.elapsed_seconds());
+    }
+}
+
+pub fn undo_shortcut_system(
+    keys: Res<ButtonInput<KeyCode>>,
+    egui_keyboard: Res<EguiWantsKeyboard>,
+    mut history: ResMut<EditHistory>,
+    mut config: ResMut<SimulationConfig>,
+    mut toasts: ResMut<Toasts>,
+) {
+    // egui text fields have their own Ctrl+Z.
+    if egui_keyboard.0 {
+        return;
+    }
+    let command = keys.any_pressed([
+        KeyCode::ControlLeft,
+        KeyCode::ControlRight,
+        KeyCode::SuperLeft,
+        KeyCode::SuperRight,
+    ]);
+    if !command {
+        return;
+    }
+    let shift = keys.any_pressed([KeyCode::ShiftLeft, KeyCode::ShiftRight]);
+
+    let label = if keys.just_pressed(KeyCode::KeyY) || shift && keys.just_pressed(KeyCode::KeyZ) {
+        history.redo(&mut config).map(|label| trf("↪ Redo {label}", &[("label", &label)]))
+    } else if keys.just_pressed(KeyCode::KeyZ) {
+        history.undo(&mut config).map(|label| trf("↩ Undo {label}", &[("label", &label)]))
+    } else {
+        return;
+    };
+    if let Some(label) = label {
+        toasts.push(label);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_drag_merges_into_one_undoable_edit() {
+        let mut config = SimulationConfig::default();
+        let mut history = EditHistory::default();
+        history.record(&config, 0.0);
+        for (i, rho) in [30.0, 35.0, 40.0].into_iter().enumerate() {
+            config.rho = rho;
+            history.record(&config, 1.0 + i as f32 * 0.1);
+        }
+        config.paused = true;
+        history.record(&config, 5.0);
+
+        assert_eq!(history.edits().count(), 1);
+        assert_eq!(history.undo(&mut config), Some("ρ 28 → 40"));
+        assert_eq!(config.rho, 28.0);
+        assert!(config.paused);
+        history.redo(&mut config);
+        assert_eq!(config.rho, 40.0);
+    }
+
+    #[test]
+    fn test_new_edit_after_undo_clears_redo() {
+        let mut config = SimulationConfig::default();
+        let mut history = EditHistory::default();
+        history.record(&config, 0.0);
+        config.sigma = 12.0;
+        history.record(&config, 1.0);
+        history.undo(&mut config);
+        config.beta = 2.0;
+        history.record(&config, 1.1);
+
+        assert!(!history.can_redo());
+        assert_eq!(history.edits().next().unwrap().label, "β 2.6667 → 2");
+    }
+}
\ No newline at end of file
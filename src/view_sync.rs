@@ -0,0 +1,450 @@
+//! Host / follow view sync: one instance hosts and sends its parameters,
+//! resets and camera to any number of followers over TCP, for a lecturer
+//! driving student machines or the screens of an installation.
+//!
+//! Each frame is one line of JSON. Resets are sent as a running count, so a
+//! follower that missed a frame still resets once:
+//!
+//! ```json
+//! {"params": {"sigma": 10.0, "rho": 28.0, ...}, "resets": 3, "camera": {"radius": 65.0, ...}}
+//! ```
+use std::io::{self, BufRead, BufReader, ErrorKind, Read, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream, ToSocketAddrs};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{channel, Receiver, Sender};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+use bevy::ecs::event::ManualEventReader;
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::config::{ResetEvent, SimulationConfig};
+use crate::i18n::{tr_noop, trf};
+use crate::rendering::camera_controller::OrbitCamera;
+use crate::session::{CameraPose, SessionParams};
+use crate::ui::toast::Toasts;
+
+pub const MIN_RATE: f32 = 1.0;
+pub const MAX_RATE: f32 = 60.0;
+/// How often a follower thread looks up from a quiet connection.
+const POLL: Duration = Duration::from_millis(100);
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(2);
+/// Pause between attempts to reach a host that went away.
+const RETRY: Duration = Duration::from_secs(2);
+/// Longest frame line a follower buffers; a host sending more is dropped
+/// rather than growing the line without end. Real frames are far shorter.
+const MAX_FRAME_BYTES: usize = 64 * 1024;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SyncRole {
+    #[default]
+    Off,
+    Host,
+    Follow,
+}
+
+impl SyncRole {
+    pub const ALL: [Self; 3] = [Self::Off, Self::Host, Self::Follow];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Off => tr_noop("Off"),
+            Self::Host => tr_noop("Host"),
+            Self::Follow => tr_noop("Follow"),
+        }
+    }
+}
+
+#[derive(Resource, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ViewSyncSettings {
+    pub role: SyncRole,
+    /// The machine to follow; a host listens on every interface.
+    pub host: String,
+    pub port: u16,
+    /// Frames a host sends per second.
+    pub rate: f32,
+}
+
+impl Default for ViewSyncSettings {
+    fn default() -> Self {
+        Self {
+            role: SyncRole::Off,
+            host: "127.0.0.1".into(),
+            port: 7878,
+            rate: 30.0,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct SyncFrame {
+    pub params: SessionParams,
+    /// Resets on the host since it started hosting.
+    pub resets: u64,
+    pub camera: Option<CameraPose>,
+}
+
+type Clients = Arc<Mutex<Vec<Sender<String>>>>;
+
+/// The listener thread and one writer thread per follower; they all wind
+/// down once dropped.
+struct Host {
+    addr: SocketAddr,
+    clients: Clients,
+    stop: Arc<AtomicBool>,
+}
+
+impl Host {
+    fn start(port: u16) -> io::Result<Self> {
+        let listener = TcpListener::bind(("0.0.0.0", port))?;
+        listener.set_nonblocking(true)?;
+        let addr = listener.local_addr()?;
+        let clients = Clients::default();
+        let stop = Arc::new(AtomicBool::new(false));
+        let (shared, stopped) = (clients.clone(), stop.clone());
+        thread::Builder::new()
+            .name("view-sync-host".into())
+            .spawn(move || {
+                while !stopped.load(Ordering::Relaxed) {
+                    match listener.accept() {
+                        Ok((stream, _)) => {
+                            let (sender, frames) = channel();
+                            if let Ok(mut clients) = shared.lock() {
+                                clients.push(sender);
+                            }
+                            let _ = thread::Builder::new()
+                                .name("view-sync-follower".into())
+                                .spawn(move || send_frames(stream, frames));
+                        }
+                        Err(error) if error.kind() == ErrorKind::WouldBlock => {
+                            thread::sleep(Duration::from_millis(50));
+                        }
+                        Err(error) => warn!("View sync accept failed: {error}"),
+                    }
+                }
+            })?;
+        Ok(Self {
+            addr,
+            clients,
+            stop,
+        })
+    }
+
+    fn follower_count(&self) -> usize {
+        self.clients.lock().map_or(0, |clients| clients.len())
+    }
+
+    fn broadcast(&self, frame: &str) {
+        if let Ok(mut clients) = self.clients.lock() {
+            clients.retain(|client| client.send(frame.to_string()).is_ok());
+        }
+    }
+}
+
+impl Drop for Host {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+    }
+}
+
+/// Writes frames to one follower until it disconnects or the host stops.
+fn send_frames(stream: TcpStream, frames: Receiver<String>) {
+    if stream.set_nonblocking(false).is_err() {
+        return;
+    }
+    let _ = stream.set_nodelay(true);
+    let mut stream = io::BufWriter::new(stream);
+    while let Ok(frame) = frames.recv() {
+        // Only the newest frame matters to a follower that fell behind.
+        let frame = frames.try_iter().last().unwrap_or(frame);
+        if writeln!(stream, "{frame}").and_then(|()| stream.flush()).is_err() {
+            break;
+        }
+    }
+}
+
+enum FollowEvent {
+    Connected,
+    Frame(Box<SyncFrame>),
+    Lost(String),
+}
+
+/// The reader thread of a follower; it reconnects until dropped.
+struct Follower {
+    events: Mutex<Receiver<FollowEvent>>,
+    stop: Arc<AtomicBool>,
+}
+
+impl Follower {
+    fn start(host: &str, port: u16) -> io::Result<Self> {
+        let host = host.trim().to_string();
+        let stop = Arc::new(AtomicBool::new(false));
+        let (report, events) = channel();
+        let stopped = stop.clone();
+        thread::Builder::new()
+            .name("view-sync-follow".into())
+            .spawn(move || {
+                while !stopped.load(Ordering::Relaxed) {
+                    let lost = match connect(&host, port) {
+                        Ok(stream) => {
+                            let _ = report.send(FollowEvent::Connected);
+                            read_frames(stream, &report, &stopped)
+                        }
+                        Err(error) => error,
+                    };
+                    if report.send(FollowEvent::Lost(lost.to_string())).is_err() {
+                        return;
+                    }
+                    let mut waited = Duration::ZERO;
+                    while waited < RETRY && !stopped.load(Ordering::Relaxed) {
+                        thread::sleep(POLL);
+                        waited += POLL;
+                    }
+                }
+            })?;
+        Ok(Self {
+            events: Mutex::new(events),
+            stop,
+        })
+    }
+
+    fn take_events(&self) -> Vec<FollowEvent> {
+        self.events
+            .lock()
+            .map(|events| events.try_iter().collect())
+            .unwrap_or_default()
+    }
+}
+
+impl Drop for Follower {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+    }
+}
+
+fn connect(host: &str, port: u16) -> io::Result<TcpStream> {
+    let addr = (host, port).to_socket_addrs()?.next().ok_or_else(|| {
+        io::Error::new(ErrorKind::NotFound, format!("{host} has no address"))
+    })?;
+    let stream = TcpStream::connect_timeout(&addr, CONNECT_TIMEOUT)?;
+    stream.set_read_timeout(Some(POLL))?;
+    Ok(stream)
+}
+
+/// Passes on every frame until the connection ends; returns why it did.
+fn read_frames(stream: TcpStream, report: &Sender<FollowEvent>, stop: &AtomicBool) -> io::Error {
+    let mut reader = BufReader::new(stream);
+    // Bytes of a line cut off by a read timeout stay here until the rest arrives.
+    let mut line = Vec::new();
+    while !stop.load(Ordering::Relaxed) {
+        let room = (MAX_FRAME_BYTES - line.len()) as u64;
+        match (&mut reader).take(room).read_until(b'\n', &mut line) {
+            Ok(0) => return io::Error::new(ErrorKind::UnexpectedEof, "the host disconnected"),
+            Ok(_) if line.len() >= MAX_FRAME_BYTES && line.last() != Some(&b'\n') => {
+                let message = format!("the host sent a frame over {MAX_FRAME_BYTES} bytes");
+                return io::Error::new(ErrorKind::InvalidData, message);
+            }
+            Ok(_) => {
+                match serde_json::from_slice::<SyncFrame>(&line) {
+                    Ok(frame) => {
+                        if report.send(FollowEvent::Frame(Box::new(frame))).is_err() {
+                            break;
+                        }
+                    }
+                    Err(error) => warn!("Ignoring a view sync frame: {error}"),
+                }
+                line.clear();
+            }
+            Err(error) if matches!(error.kind(), ErrorKind::WouldBlock | ErrorKind::TimedOut) => {}
+            Err(error) => return error,
+        }
+    }
+    io::Error::new(ErrorKind::Interrupted, "stopped")
+}
+
+#[derive(Resource, Default)]
+pub struct ViewSync {
+    host: Option<Host>,
+    follower: Option<Follower>,
+    /// The role and address last started, even if that failed.
+    target: Option<(SyncRole, String, u16)>,
+    since_send: f32,
+    resets: u64,
+    /// The host's reset count when the last frame arrived.
+    resets_seen: Option<u64>,
+    /// Whether a follower currently has its host.
+    pub connected: bool,
+    pub error: Option<String>,
+}
+
+impl ViewSync {
+    pub fn local_addr(&self) -> Option<SocketAddr> {
+        self.host.as_ref().map(|host| host.addr)
+    }
+
+    pub fn follower_count(&self) -> usize {
+        self.host.as_ref().map_or(0, Host::follower_count)
+    }
+
+    fn start(&mut self, role: SyncRole, host: &str, port: u16) -> io::Result<()> {
+        match role {
+            SyncRole::Off => {}
+            SyncRole::Host => self.host = Some(Host::start(port)?),
+            SyncRole::Follow => self.follower = Some(Follower::start(host, port)?),
+        }
+        Ok(())
+    }
+}
+
+/// Whether a follower needs to reset for this frame: on the first frame from
+/// a host, and whenever the host's count moves on.
+pub fn reset_due(seen: Option<u64>, resets: u64) -> bool {
+    seen != Some(resets)
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn view_sync_system(
+    settings: Res<ViewSyncSettings>,
+    mut sync: ResMut<ViewSync>,
+    time: Res<Time<Real>>,
+    mut config: ResMut<SimulationConfig>,
+    mut resets: ResMut<Events<ResetEvent>>,
+    mut reset_reader: Local<ManualEventReader<ResetEvent>>,
+    mut cameras: Query<(&mut OrbitCamera, &mut Transform)>,
+    mut toasts: ResMut<Toasts>,
+) {
+    let reset_count = reset_reader.read(&resets).count() as u64;
+    let wanted = (settings.role != SyncRole::Off)
+        .then(|| (settings.role, settings.host.clone(), settings.port));
+    if sync.target != wanted {
+        *sync = ViewSync::default();
+        if let Some((role, host, port)) = &wanted {
+            if let Err(error) = sync.start(*role, host, *port) {
+                toasts.push(trf("⚠ View sync failed: {error}", &[("error", &error)]));
+                sync.error = Some(error.to_string());
+            }
+        }
+        sync.target = wanted;
+    }
+
+    let sync = &mut *sync;
+    if let Some(host) = &sync.host {
+        sync.resets += reset_count;
+        sync.since_send += time.delta_seconds();
+        if sync.since_send < 1.0 / settings.rate.clamp(MIN_RATE, MAX_RATE) {
+            return;
+        }
+        sync.since_send = 0.0;
+        let frame = SyncFrame {
+            params: SessionParams::capture(&config),
+            resets: sync.resets,
+            camera: cameras
+                .get_single()
+                .ok()
+                .map(|(orbit, _)| CameraPose::capture(orbit)),
+        };
+        if let Ok(json) = serde_json::to_string(&frame) {
+            host.broadcast(&json);
+        }
+        return;
+    }
+
+    let Some(follower) = &sync.follower else {
+        return;
+    };
+    for event in follower.take_events() {
+        match event {
+            FollowEvent::Connected => {
+                sync.connected = true;
+                sync.error = None;
+                sync.resets_seen = None;
+                toasts.push(trf("🔗 Following {host}", &[("host", &settings.host)]));
+            }
+            FollowEvent::Lost(error) => {
+                // Retries fail the same way every few seconds; say so once.
+                if sync.connected || sync.error.is_none() {
+                    toasts.push(trf("⚠ View sync failed: {error}", &[("error", &error)]));
+                }
+                sync.connected = false;
+                sync.error = Some(error);
+            }
+            FollowEvent::Frame(frame) => {
+                if SessionParams::capture(&config) != frame.params {
+                    frame.params.apply(&mut config);
+                }
+                if reset_due(sync.resets_seen, frame.resets) {
+                    resets.send(ResetEvent);
+                    sync.resets_seen = Some(frame.resets);
+                }
+                if let (Some(pose), Ok((mut orbit, mut transform))) =
+                    (&frame.camera, cameras.get_single_mut())
+                {
+                    if CameraPose::capture(&orbit) != *pose {
+                        pose.apply(&mut orbit, &mut transform);
+                    }
+                }
+            }
+        }
+    }
+    reset_reader.read(&resets).for_each(|_| {});
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_frames_reach_a_follower() {
+        let host = Host::start(0).unwrap();
+        let follower = Follower::start("127.0.0.1", host.addr.port()).unwrap();
+        let frame = SyncFrame {
+            params: SessionParams::capture(&SimulationConfig {
+                rho: 99.96,
+                ..default()
+            }),
+            resets: 2,
+            camera: Some(CameraPose::capture(&OrbitCamera::default())),
+        };
+        let json = serde_json::to_string(&frame).unwrap();
+
+        let mut received = None;
+        for _ in 0..100 {
+            host.broadcast(&json);
+            received = follower.take_events().into_iter().find_map(|event| match event {
+                FollowEvent::Frame(frame) => Some(frame),
+                _ => None,
+            });
+            if received.is_some() {
+                break;
+            }
+            thread::sleep(Duration::from_millis(20));
+        }
+        assert_eq!(received.as_deref(), Some(&frame));
+        assert_eq!(host.follower_count(), 1);
+    }
+
+    #[test]
+    fn test_followers_drop_a_host_sending_an_endless_line() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let stream = TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+        stream.set_read_timeout(Some(POLL)).unwrap();
+        let (mut host, _) = listener.accept().unwrap();
+        let writer = thread::spawn(move || host.write_all(&vec![b'{'; MAX_FRAME_BYTES + 1]));
+
+        let (report, events) = channel();
+        let error = read_frames(stream, &report, &AtomicBool::new(false));
+        assert_eq!(error.kind(), ErrorKind::InvalidData);
+        assert!(events.try_recv().is_err());
+        let _ = writer.join();
+    }
+
+    #[test]
+    fn test_followers_reset_once_per_host_reset() {
+        assert!(reset_due(None, 0));
+        assert!(!reset_due(Some(3), 3));
+        assert!(reset_due(Some(3), 4));
+    }
+}
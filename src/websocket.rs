@@ -0,0 +1,395 @@
+//! Embedded WebSocket server: every connected client receives JSON state
+//! frames and may send commands that steer the simulation. Each client is
+//! served by its own thread; the main thread only queues frames and drains
+//! the received commands.
+//!
+//! ```json
+//! {"t": 12.5, "x": -3.1, "y": -4.0, "z": 21.7, "speed": 48.2, "sigma": 10.0, ...}
+//! {"command": "set", "rho": 35, "dt": 0.002}
+//! {"command": "pause"}
+//! ```
+use std::io::{self, ErrorKind};
+use std::net::{SocketAddr, TcpListener, TcpStream};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{channel, Receiver, Sender};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use tungstenite::{Message, WebSocket};
+
+use crate::config::{ResetEvent, SimulationConfig, SimulationStats};
+use crate::i18n::trf;
+use crate::simulation::lorenz::LorenzState;
+use crate::simulation::trajectories::MainTrajectory;
+use crate::ui::toast::Toasts;
+
+pub const MIN_RATE: f32 = 1.0;
+pub const MAX_RATE: f32 = 120.0;
+/// How long a client thread waits for a command before sending queued frames.
+const POLL: Duration = Duration::from_millis(10);
+/// Most steps per frame a client may ask for, the same cap as
+/// `--steps-per-frame`; more would stall the frame loop.
+const MAX_STEPS: u32 = 1000;
+
+#[derive(Resource, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct WebSocketSettings {
+    pub enabled: bool,
+    /// 127.0.0.1 serves this machine only; 0.0.0.0 lets a phone on the LAN in.
+    pub bind: String,
+    pub port: u16,
+    /// State frames per second.
+    pub rate: f32,
+}
+
+impl Default for WebSocketSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            bind: "127.0.0.1".into(),
+            port: 8765,
+            rate: 20.0,
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct StateFrame {
+    pub t: f64,
+    pub x: f64,
+    pub y: f64,
+    pub z: f64,
+    pub speed: f64,
+    pub sigma: f64,
+    pub rho: f64,
+    pub beta: f64,
+    pub dt: f64,
+    pub paused: bool,
+    pub switches: u64,
+    pub wing: Option<char>,
+}
+
+impl StateFrame {
+    pub fn new(state: &LorenzState, config: &SimulationConfig, stats: &SimulationStats) -> Self {
+        Self {
+            t: state.t,
+            x: state.x,
+            y: state.y,
+            z: state.z,
+            speed: stats.current_velocity,
+            sigma: config.sigma,
+            rho: config.rho,
+            beta: config.beta,
+            dt: config.dt,
+            paused: config.paused,
+            switches: stats.lobes.switches,
+            wing: stats.lobes.current.map(|wing| wing.symbol()),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+#[serde(tag = "command", rename_all = "snake_case", deny_unknown_fields)]
+pub enum ClientCommand {
+    Set {
+        sigma: Option<f64>,
+        rho: Option<f64>,
+        beta: Option<f64>,
+        dt: Option<f64>,
+        steps: Option<u32>,
+    },
+    Pause,
+    Resume,
+    Reset,
+}
+
+/// Parses one text message, rejecting values the integrator cannot use.
+pub fn parse_command(text: &str) -> Result<ClientCommand, String> {
+    let command: ClientCommand = serde_json::from_str(text).map_err(|error| error.to_string())?;
+    if let ClientCommand::Set {
+        sigma,
+        rho,
+        beta,
+        dt,
+        steps,
+    } = &command
+    {
+        for (name, value) in [("sigma", sigma), ("beta", beta), ("dt", dt)] {
+            if value.is_some_and(|v| !(v.is_finite() && v > 0.0)) {
+                return Err(format!("{name} must be a positive number"));
+            }
+        }
+        if rho.is_some_and(|v| !v.is_finite()) {
+            return Err("rho must be a finite number".into());
+        }
+        if steps.is_some_and(|n| !(1..=MAX_STEPS).contains(&n)) {
+            return Err(format!("steps must be between 1 and {MAX_STEPS}"));
+        }
+    }
+    Ok(command)
+}
+
+/// Applies everything except `reset`; returns whether a reset was asked for.
+pub fn apply_command(command: ClientCommand, config: &mut SimulationConfig) -> bool {
+    match command {
+        ClientCommand::Set {
+            sigma,
+            rho,
+            beta,
+            dt,
+            steps,
+        } => {
+            config.sigma = sigma.unwrap_or(config.sigma);
+            config.rho = rho.unwrap_or(config.rho);
+            config.beta = beta.unwrap_or(config.beta);
+            config.dt = dt.unwrap_or(config.dt);
+            config.steps_per_frame = steps.unwrap_or(config.steps_per_frame);
+        }
+        ClientCommand::Pause => config.paused = true,
+        ClientCommand::Resume => config.paused = false,
+        ClientCommand::Reset => return true,
+    }
+    false
+}
+
+type Clients = Arc<Mutex<Vec<Sender<String>>>>;
+
+/// The listener thread and its clients; they all wind down once dropped.
+struct Server {
+    addr: SocketAddr,
+    clients: Clients,
+    commands: Mutex<Receiver<ClientCommand>>,
+    stop: Arc<AtomicBool>,
+}
+
+impl Server {
+    fn start(bind: &str, port: u16) -> io::Result<Self> {
+        let listener = TcpListener::bind((bind, port))?;
+        listener.set_nonblocking(true)?;
+        let addr = listener.local_addr()?;
+        let clients = Clients::default();
+        let stop = Arc::new(AtomicBool::new(false));
+        let (report, commands) = channel();
+        let (shared, stopped) = (clients.clone(), stop.clone());
+        thread::Builder::new()
+            .name("websocket-server".into())
+            .spawn(move || {
+                while !stopped.load(Ordering::Relaxed) {
+                    match listener.accept() {
+                        Ok((stream, _)) => {
+                            let (clients, stop, report) =
+                                (shared.clone(), stopped.clone(), report.clone());
+                            let _ = thread::Builder::new()
+                                .name("websocket-client".into())
+                                .spawn(move || serve(stream, &clients, &stop, &report));
+                        }
+                        Err(error) if error.kind() == ErrorKind::WouldBlock => {
+                            thread::sleep(Duration::from_millis(50));
+                        }
+                        Err(error) => warn!("WebSocket accept failed: {error}"),
+                    }
+                }
+            })?;
+        Ok(Self {
+            addr,
+            clients,
+            commands: Mutex::new(commands),
+            stop,
+        })
+    }
+
+    fn client_count(&self) -> usize {
+        self.clients.lock().map_or(0, |clients| clients.len())
+    }
+
+    fn broadcast(&self, frame: &str) {
+        if let Ok(mut clients) = self.clients.lock() {
+            clients.retain(|client| client.send(frame.to_string()).is_ok());
+        }
+    }
+
+    fn take_commands(&self) -> Vec<ClientCommand> {
+        self.commands
+            .lock()
+            .map(|commands| commands.try_iter().collect())
+            .unwrap_or_default()
+    }
+}
+
+impl Drop for Server {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+    }
+}
+
+fn serve(stream: TcpStream, clients: &Clients, stop: &AtomicBool, report: &Sender<ClientCommand>) {
+    let Ok(()) = stream.set_nonblocking(false) else {
+        return;
+    };
+    let Ok(mut socket) = tungstenite::accept(stream) else {
+        return;
+    };
+    if socket.get_ref().set_read_timeout(Some(POLL)).is_err() {
+        return;
+    }
+    let (sender, frames) = channel();
+    if let Ok(mut clients) = clients.lock() {
+        clients.push(sender);
+    }
+    while !stop.load(Ordering::Relaxed) {
+        // Only the newest frame matters to a client that fell behind.
+        if let Some(frame) = frames.try_iter().last() {
+            if socket.send(Message::text(frame)).is_err() {
+                break;
+            }
+        }
+        match socket.read() {
+            Ok(Message::Text(text)) => match parse_command(text.as_str()) {
+                Ok(command) => {
+                    let _ = report.send(command);
+                }
+                Err(error) => {
+                    let reply = serde_json::json!({ "error": error }).to_string();
+                    if socket.send(Message::text(reply)).is_err() {
+                        break;
+                    }
+                }
+            },
+            Ok(Message::Close(_)) => break,
+            Ok(_) => {}
+            Err(tungstenite::Error::Io(error))
+                if matches!(error.kind(), ErrorKind::WouldBlock | ErrorKind::TimedOut) => {}
+            Err(_) => break,
+        }
+    }
+    close(&mut socket);
+}
+
+fn close(socket: &mut WebSocket<TcpStream>) {
+    let _ = socket.close(None);
+    let _ = socket.flush();
+}
+
+#[derive(Resource, Default)]
+pub struct WebSocketServer {
+    server: Option<Server>,
+    /// The address last bound, even if that failed.
+    target: Option<(String, u16)>,
+    since_send: f32,
+    pub error: Option<String>,
+}
+
+impl WebSocketServer {
+    pub fn local_addr(&self) -> Option<SocketAddr> {
+        self.server.as_ref().map(|server| server.addr)
+    }
+
+    pub fn client_count(&self) -> usize {
+        self.server.as_ref().map_or(0, Server::client_count)
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn websocket_system(
+    settings: Res<WebSocketSettings>,
+    mut server: ResMut<WebSocketServer>,
+    time: Res<Time>,
+    mut config: ResMut<SimulationConfig>,
+    stats: Res<SimulationStats>,
+    state: Query<&LorenzState, MainTrajectory>,
+    mut reset: EventWriter<ResetEvent>,
+    mut toasts: ResMut<Toasts>,
+) {
+    let wanted = settings
+        .enabled
+        .then(|| (settings.bind.clone(), settings.port));
+    if server.target != wanted {
+        server.server = None;
+        server.error = None;
+        if let Some((bind, port)) = &wanted {
+            match Server::start(bind, *port) {
+                Ok(started) => server.server = Some(started),
+                Err(error) => {
+                    toasts.push(trf(
+                        "⚠ WebSocket server failed: {error}",
+                        &[("error", &error)],
+                    ));
+                    server.error = Some(error.to_string());
+                }
+            }
+        }
+        server.target = wanted;
+    }
+
+    let server = &mut *server;
+    let Some(running) = &server.server else {
+        return;
+    };
+    for command in running.take_commands() {
+        if apply_command(command, &mut config) {
+            reset.send(ResetEvent);
+        }
+    }
+
+    server.since_send += time.delta_seconds();
+    if server.since_send < 1.0 / settings.rate.clamp(MIN_RATE, MAX_RATE) {
+        return;
+    }
+    server.since_send = 0.0;
+    if let Ok(state) = state.get_single() {
+        if let Ok(frame) = serde_json::to_string(&StateFrame::new(state, &config, &stats)) {
+            running.broadcast(&frame);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Instant;
+
+    #[test]
+    fn test_commands_are_parsed_and_validated() {
+        let command = parse_command(r#"{"command": "set", "rho": 35, "dt": 0.002}"#).unwrap();
+        let mut config = SimulationConfig::default();
+        assert!(!apply_command(command, &mut config));
+        assert_eq!((config.rho, config.dt, config.sigma), (35.0, 0.002, 10.0));
+        assert_eq!(
+            parse_command(r#"{"command": "reset"}"#),
+            Ok(ClientCommand::Reset)
+        );
+
+        assert!(parse_command(r#"{"command": "set", "dt": -1}"#).is_err());
+        assert!(parse_command(r#"{"command": "set", "steps": 0}"#).is_err());
+        assert!(parse_command(r#"{"command": "set", "steps": 4000000000}"#).is_err());
+        assert!(parse_command(r#"{"command": "set", "steps": 1000}"#).is_ok());
+        assert!(parse_command(r#"{"command": "set", "gamma": 1}"#).is_err());
+        assert!(parse_command(r#"{"command": "explode"}"#).is_err());
+    }
+
+    #[test]
+    fn test_server_broadcasts_frames_and_receives_commands() {
+        let server = Server::start("127.0.0.1", 0).unwrap();
+        let url = format!("ws://{}", server.addr);
+        let (mut client, _) = tungstenite::connect(url.as_str()).unwrap();
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while server.client_count() == 0 {
+            assert!(Instant::now() < deadline, "client never registered");
+            thread::sleep(Duration::from_millis(5));
+        }
+
+        server.broadcast(r#"{"t": 1.0}"#);
+        assert_eq!(client.read().unwrap(), Message::text(r#"{"t": 1.0}"#));
+
+        client
+            .send(Message::text(r#"{"command": "pause"}"#))
+            .unwrap();
+        while server.take_commands() != [ClientCommand::Pause] {
+            assert!(Instant::now() < deadline, "command never arrived");
+            thread::sleep(Duration::from_millis(5));
+        }
+    }
+}
\ No newline at end of file